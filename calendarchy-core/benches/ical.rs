@@ -0,0 +1,51 @@
+//! Parsing throughput for large calendars, e.g. a multi-year iCloud calendar or feed.
+//! Run with `cargo bench -p calendarchy-core`.
+
+use calendarchy_core::icloud::ICalEvent;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::fmt::Write;
+use std::hint::black_box;
+
+/// A VCALENDAR with `count` events shaped like real ones: zoned times, attendees,
+/// escaped text and a folded description
+fn calendar(count: usize) -> String {
+    let mut ical = String::from("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//calendarchy//bench//EN\r\n");
+    for i in 0..count {
+        let day = 1 + i % 28;
+        let month = 1 + (i / 28) % 12;
+        write!(
+            ical,
+            "BEGIN:VEVENT\r\n\
+UID:bench-{i}@example.com\r\n\
+SUMMARY:Planning\\, round {i}\r\n\
+DTSTART;TZID=Europe/Sofia:2026{month:02}{day:02}T100000\r\n\
+DTEND;TZID=Europe/Sofia:2026{month:02}{day:02}T110000\r\n\
+LOCATION:Room 4\\; second floor\r\n\
+DESCRIPTION:Agenda:\\n- review last week\\n- plan the next one. Join at https://z\r\n \
+oom.us/j/123456789 or dial in.\r\n\
+ORGANIZER;CN=Alice Example:mailto:alice@example.com\r\n\
+ATTENDEE;CN=Bob Example;PARTSTAT=ACCEPTED:mailto:bob@example.com\r\n\
+ATTENDEE;CN=\"Carol, Example\";PARTSTAT=NEEDS-ACTION:mailto:carol@example.com\r\n\
+TRANSP:OPAQUE\r\n\
+END:VEVENT\r\n"
+        )
+        .unwrap();
+    }
+    ical.push_str("END:VCALENDAR\r\n");
+    ical
+}
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_ical");
+    for count in [100, 1_000, 5_000] {
+        let ical = calendar(count);
+        group.throughput(Throughput::Bytes(ical.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(count), &ical, |b, ical| {
+            b.iter(|| ICalEvent::parse_ical(black_box(ical)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use crate::config::{ICloudTokens, StoredCalendar};
+use crate::google::TokenInfo;
+
+/// Trait for auth state display
+pub trait AuthDisplay {
+    fn is_authenticated(&self) -> bool;
+}
+
+/// Google authentication state
+#[derive(Debug, Clone)]
+pub enum GoogleAuthState {
+    NotConfigured,
+    NotAuthenticated,
+    AwaitingUserCode {
+        #[allow(dead_code)]
+        user_code: String,
+        #[allow(dead_code)]
+        verification_url: String,
+        device_code: String,
+        expires_at: DateTime<Utc>,
+    },
+    Authenticated(TokenInfo),
+    #[allow(dead_code)]
+    Error(String),
+}
+
+impl AuthDisplay for GoogleAuthState {
+    fn is_authenticated(&self) -> bool {
+        matches!(self, GoogleAuthState::Authenticated(_))
+    }
+}
+
+/// Calendar with URL, display name and color
+#[derive(Debug, Clone)]
+pub struct CalendarEntry {
+    pub url: String,
+    pub name: Option<String>,
+    pub color: Option<String>,
+    /// Unchecked in the calendar picker, so not fetched
+    pub hidden: bool,
+}
+
+impl CalendarEntry {
+    pub fn from_stored(stored: StoredCalendar) -> Self {
+        Self { url: stored.url, name: stored.name, color: stored.color, hidden: stored.hidden }
+    }
+
+    pub fn to_stored(&self) -> StoredCalendar {
+        StoredCalendar { url: self.url.clone(), name: self.name.clone(), color: self.color.clone(), hidden: self.hidden }
+    }
+
+    /// Calendars of saved discovery info, falling back to the legacy list of URLs
+    pub fn from_tokens(tokens: ICloudTokens) -> Vec<Self> {
+        if !tokens.calendars.is_empty() {
+            tokens.calendars.into_iter().map(Self::from_stored).collect()
+        } else {
+            tokens.calendar_urls.into_iter()
+                .map(|url| Self { url, name: None, color: None, hidden: false })
+                .collect()
+        }
+    }
+}
+
+/// iCloud authentication state
+#[derive(Debug, Clone)]
+pub enum ICloudAuthState {
+    NotConfigured,
+    NotAuthenticated,
+    /// Listing the account's calendars; `hidden` holds the URLs unchecked before, so they
+    /// stay unchecked
+    Discovering { hidden: Vec<String> },
+    Authenticated { calendars: Vec<CalendarEntry> },
+    #[allow(dead_code)]
+    Error(String),
+}
+
+impl ICloudAuthState {
+    /// URLs of the calendars unchecked in the picker, known or carried through a rediscovery
+    pub fn hidden_calendars(&self) -> Vec<String> {
+        match self {
+            ICloudAuthState::Authenticated { calendars } => {
+                calendars.iter().filter(|c| c.hidden).map(|c| c.url.clone()).collect()
+            }
+            ICloudAuthState::Discovering { hidden } => hidden.clone(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl AuthDisplay for ICloudAuthState {
+    fn is_authenticated(&self) -> bool {
+        matches!(self, ICloudAuthState::Authenticated { .. })
+    }
+}
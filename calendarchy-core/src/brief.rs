@@ -0,0 +1,152 @@
+//! Morning briefing: a plain-text summary of the day for notify-send, chat or the terminal
+
+use crate::cache::{AttendeeStatus, DisplayEvent, EventCache, EventId};
+use crate::review::{free_blocks, is_meeting};
+use crate::config::{TimeFormatConfig, WorkHoursConfig};
+use crate::utils::format_duration;
+use chrono::{Datelike, NaiveDate};
+
+/// Invitations you haven't answered yet; Google events you declined or tentatively
+/// accepted are also not accepted, but already have a response. Read-only events, such as
+/// those of someone else's shared calendar, can't be answered here.
+pub fn is_pending(event: &DisplayEvent) -> bool {
+    !event.accepted
+        && !matches!(event.id, EventId::Feed { .. })
+        && !event.attendees.iter().any(|a| a.is_self && matches!(a.status, AttendeeStatus::Declined | AttendeeStatus::Tentative))
+}
+
+/// Compose the briefing for `date`: agenda with links, first and last meeting, free blocks
+/// within working hours and invitations waiting for an answer from that day on
+pub fn compose(events: &EventCache, date: NaiveDate, work_hours: &WorkHoursConfig, format: &TimeFormatConfig) -> Vec<String> {
+    let mut agenda: Vec<&DisplayEvent> = events.day(date).collect();
+    agenda.sort_by_key(|e| e.start_time);
+    let meetings: Vec<&DisplayEvent> = agenda.iter().copied().filter(|e| is_meeting(e)).collect();
+
+    let mut lines = vec![date.format("%A, %B %-d").to_string(), String::new()];
+
+    let attending: Vec<&DisplayEvent> = agenda.iter().copied().filter(|e| e.accepted).collect();
+    if attending.is_empty() {
+        lines.push("Nothing scheduled".to_string());
+    }
+    for event in &attending {
+        let time = event.time_range_label(format);
+        let mut line = format!("{:<11} {}", time, event.title);
+        if let Some(url) = &event.meeting_url {
+            line.push_str(&format!("  {}", url));
+        }
+        lines.push(line);
+    }
+
+    if let (Some(first), Some(last)) = (meetings.first(), meetings.iter().max_by_key(|e| e.end_time)) {
+        let total: i64 = meetings.iter().filter_map(|e| e.duration_minutes()).sum();
+        lines.push(String::new());
+        lines.push(format!(
+            "{} meeting(s), {}: first at {}, last ends {}",
+            meetings.len(),
+            format_duration(total),
+            first.time_label(format),
+            last.end_time.map_or_else(|| last.time_label(format), |end| format.time(end))
+        ));
+    }
+
+    let blocks = work_hours.on(date.weekday()).map(|hours| free_blocks(&meetings, hours)).unwrap_or_default();
+    if !blocks.is_empty() {
+        lines.push(String::new());
+        lines.push("Free".to_string());
+        for (start, end) in blocks {
+            lines.push(format!(
+                "  {}-{} ({})",
+                format.time(start),
+                format.time(end),
+                format_duration((end - start).num_minutes())
+            ));
+        }
+    }
+
+    let mut pending: Vec<&DisplayEvent> = events.google.all_events()
+        .chain(events.icloud.all_events())
+        .filter(|e| e.date >= date && is_pending(e))
+        .collect();
+    pending.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.start_time.cmp(&b.start_time)));
+    if !pending.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("Pending invitations ({})", pending.len()));
+        for event in pending {
+            lines.push(format!("  {} {:<7} {}", event.date.format("%a %b %d"), event.time_label(format), event.title));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{DisplayAttendee, EventId, EventSource};
+    use chrono::{Duration, NaiveTime};
+
+    fn make_event(title: &str, date: NaiveDate, start: &str, end: &str) -> DisplayEvent {
+        DisplayEvent {
+            id: EventId::Google { calendar_id: "primary".to_string(), event_id: title.to_string(), calendar_name: None, account: None },
+            title: title.to_string(),
+            start_time: NaiveTime::parse_from_str(start, "%H:%M").ok(),
+            end_time: NaiveTime::parse_from_str(end, "%H:%M").ok(),
+            date,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_compose_brief() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 10).unwrap();
+        let mut standup = make_event("Standup", date, "09:00", "09:30");
+        standup.meeting_url = Some("https://meet.google.com/abc".to_string());
+        let mut invite = make_event("Design review", date + Duration::days(1), "11:00", "12:00");
+        invite.accepted = false;
+        let mut declined = make_event("Offsite", date + Duration::days(2), "10:00", "11:00");
+        declined.accepted = false;
+        declined.attendees = vec![DisplayAttendee {
+            name: None,
+            email: "me@example.com".to_string(),
+            status: AttendeeStatus::Declined,
+            is_self: true,
+        }];
+        let mut cache = EventCache::new();
+        cache.store(EventSource::Google, vec![
+            make_event("Retro", date, "14:00", "15:00"),
+            standup,
+            invite,
+            declined,
+        ], date);
+
+        assert_eq!(compose(&cache, date, &WorkHoursConfig::default(), &TimeFormatConfig::default()), vec![
+            "Tuesday, February 10",
+            "",
+            "09:00-09:30 Standup  https://meet.google.com/abc",
+            "14:00-15:00 Retro",
+            "",
+            "2 meeting(s), 1h30: first at 09:00, last ends 15:00",
+            "",
+            "Free",
+            "  09:30-14:00 (4h30)",
+            "  15:00-18:00 (3h)",
+            "",
+            "Pending invitations (1)",
+            "  Wed Feb 11 11:00   Design review",
+        ]);
+    }
+
+    #[test]
+    fn test_shared_calendar_events_are_not_pending() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 10).unwrap();
+        let mut shared = make_event("Their 1:1", date, "10:00", "10:30");
+        shared.accepted = false;
+        shared.id = EventId::Feed {
+            location: "colleague@example.com".to_string(),
+            event_uid: "their-1-1".to_string(),
+            calendar_name: Some("Colleague".to_string()),
+        };
+
+        assert!(!is_pending(&shared));
+        assert!(!is_meeting(&shared));
+    }
+}
@@ -0,0 +1,1266 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime};
+use crate::config::{DedupConfig, DedupPrefer, TimeFormatConfig};
+use crate::error::{CalendarchyError, Result};
+use crate::mute::MuteFilter;
+use crate::snooze::SnoozeStore;
+use crate::search::event_match_type;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Which event source/panel an event belongs to; "google" (Work) or "icloud" (Personal) in config
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventSource {
+    Google,
+    ICloud,
+}
+
+/// Attendee information for display
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplayAttendee {
+    pub name: Option<String>,  // Display name if available
+    pub email: String,
+    pub status: AttendeeStatus,
+    /// The account the calendar was fetched with
+    #[serde(default)]
+    pub is_self: bool,
+}
+
+impl DisplayAttendee {
+    /// Whether this is a meeting room or other resource rather than a person
+    pub fn is_resource(&self) -> bool {
+        self.email.ends_with("resource.calendar.google.com")
+    }
+}
+
+/// Attendee response status
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AttendeeStatus {
+    Accepted,
+    Declined,
+    Tentative,
+    NeedsAction,
+    Organizer,
+}
+
+impl AttendeeStatus {
+    /// Get the display icon for this status
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Accepted | Self::Organizer => "\u{2713}", // ✓
+            Self::Declined => "\u{2717}",                   // ✗
+            Self::Tentative | Self::NeedsAction => "?",
+        }
+    }
+}
+
+/// Event identifier for API actions (accept/decline/delete)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EventId {
+    /// Google Calendar event (calendar_id, event_id, calendar_name for display). Events of
+    /// the `google_account` providers name their account; the main account's have none.
+    Google {
+        calendar_id: String,
+        event_id: String,
+        calendar_name: Option<String>,
+        #[serde(default)]
+        account: Option<String>,
+    },
+    /// iCloud CalDAV event (calendar_url, event_uid, etag for updates, calendar_name for
+    /// display). Occurrences of a repeating event share the series' document and carry
+    /// their RECURRENCE-ID as an iCalendar value, e.g. "20260115T093000Z".
+    ICloud {
+        calendar_url: String,
+        event_uid: String,
+        etag: Option<String>,
+        calendar_name: Option<String>,
+        #[serde(default)]
+        occurrence: Option<String>,
+    },
+    /// Read-only event from an ICS feed, a local directory or a shared Google calendar
+    /// (feed URL, file path or calendar id, UID, feed name)
+    Feed { location: String, event_uid: String, calendar_name: Option<String> },
+}
+
+/// Kind of event; special Google event types aren't regular meetings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    #[default]
+    Default,
+    OutOfOffice,
+    FocusTime,
+    WorkingLocation,
+}
+
+impl EventKind {
+    /// Map a Google `eventType`; unknown types are treated as regular events
+    pub fn from_google(event_type: Option<&str>) -> Self {
+        match event_type {
+            Some("outOfOffice") => Self::OutOfOffice,
+            Some("focusTime") => Self::FocusTime,
+            Some("workingLocation") => Self::WorkingLocation,
+            _ => Self::Default,
+        }
+    }
+
+    /// Shown outside the meeting lists: out-of-office as a banner, working location as a badge
+    pub fn is_day_marker(self) -> bool {
+        matches!(self, Self::OutOfOffice | Self::WorkingLocation)
+    }
+}
+
+/// Unified event representation for display
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplayEvent {
+    pub id: EventId,
+    #[serde(default)]
+    pub ical_uid: Option<String>, // iCalendar UID, used to spot the same event in both sources
+    #[serde(default)]
+    pub color: Option<String>, // Provider color as "#rrggbb", if the event or its calendar has one
+    pub title: String,
+    /// Local start time; None for all-day events
+    #[serde(alias = "time_str", with = "clock_time")]
+    pub start_time: Option<NaiveTime>,
+    /// Local end time; None for all-day events and events without an end
+    #[serde(alias = "end_time_str", with = "clock_time", default)]
+    pub end_time: Option<NaiveTime>,
+    pub date: NaiveDate,
+    /// Last day of an event running over several days; None when it ends on `date`
+    #[serde(default)]
+    pub end_date: Option<NaiveDate>,
+    pub accepted: bool, // true if accepted or organizer, false if declined/tentative/needs-action
+    pub is_organizer: bool, // true if the user created/organizes this event
+    #[serde(default)] // backwards compat with old cache
+    pub is_free: bool, // true if event is marked as "free" (doesn't block time)
+    #[serde(default)]
+    pub kind: EventKind,
+    pub meeting_url: Option<String>, // Zoom, Meet, Teams link if available
+    #[serde(default)]
+    pub dial_in: Option<String>, // tel: URI with the PIN as DTMF, e.g. "tel:+15551234567,,123456#"
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub attendees: Vec<DisplayAttendee>,
+    /// Booked rooms and other resources, kept out of `attendees`
+    #[serde(default)]
+    pub rooms: Vec<String>,
+    /// Listed without attendee and room names; they arrive through
+    /// `EventCache::set_details` once the event is opened
+    #[serde(default)]
+    pub partial: bool,
+}
+
+/// An accepted, busy, all-day event of your own on 1970-01-01 with no content, for struct
+/// literals to fill in the rest from
+impl Default for DisplayEvent {
+    fn default() -> Self {
+        Self {
+            id: EventId::Google { calendar_id: String::new(), event_id: String::new(), calendar_name: None, account: None },
+            ical_uid: None,
+            color: None,
+            title: String::new(),
+            start_time: None,
+            end_time: None,
+            date: NaiveDate::default(),
+            end_date: None,
+            accepted: true,
+            is_organizer: false,
+            is_free: false,
+            kind: EventKind::Default,
+            meeting_url: None,
+            dial_in: None,
+            description: None,
+            location: None,
+            attendees: Vec::new(),
+            rooms: Vec::new(),
+            partial: false,
+        }
+    }
+}
+
+/// Event times stored as "HH:MM", the way caches written with the old string fields
+/// hold them; anything else, such as "All day", reads as no time
+mod clock_time {
+    use chrono::NaiveTime;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(time: &Option<NaiveTime>, serializer: S) -> Result<S::Ok, S::Error> {
+        match time {
+            Some(time) => serializer.serialize_str(&time.format("%H:%M").to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<NaiveTime>, D::Error> {
+        let text = Option::<String>::deserialize(deserializer)?;
+        Ok(text.and_then(|t| NaiveTime::parse_from_str(&t, "%H:%M").ok()))
+    }
+}
+
+/// What a full fetch adds to an event listed as `partial`
+#[derive(Debug, Clone)]
+pub struct EventDetails {
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub meeting_url: Option<String>,
+    pub dial_in: Option<String>,
+    pub attendees: Vec<DisplayAttendee>,
+    pub rooms: Vec<String>,
+}
+
+impl EventDetails {
+    /// The details of a fully fetched event
+    pub fn of(event: DisplayEvent) -> Self {
+        Self {
+            description: event.description,
+            location: event.location,
+            meeting_url: event.meeting_url,
+            dial_in: event.dial_in,
+            attendees: event.attendees,
+            rooms: event.rooms,
+        }
+    }
+
+    fn apply_to(&self, event: &mut DisplayEvent) {
+        event.description = self.description.clone();
+        event.location = self.location.clone();
+        event.meeting_url = self.meeting_url.clone();
+        event.dial_in = self.dial_in.clone();
+        event.attendees = self.attendees.clone();
+        event.rooms = self.rooms.clone();
+        event.partial = false;
+    }
+}
+
+impl DisplayEvent {
+    pub fn is_all_day(&self) -> bool {
+        self.start_time.is_none()
+    }
+
+    /// Start time as shown, e.g. "14:30", or "All day"
+    pub fn time_label(&self, format: &TimeFormatConfig) -> String {
+        match self.start_time {
+            Some(start) => format.time(start),
+            None => "All day".to_string(),
+        }
+    }
+
+    /// "14:30-15:00" for events with an end, otherwise the start as in `time_label`
+    pub fn time_range_label(&self, format: &TimeFormatConfig) -> String {
+        match (self.start_time, self.end_time) {
+            (Some(start), Some(end)) => format!("{}-{}", format.time(start), format.time(end)),
+            _ => self.time_label(format),
+        }
+    }
+
+    /// Listed in the all-day strip above the timed events, and left out of free/busy:
+    /// all-day events and timed ones lasting more than a day. A meeting past midnight
+    /// stays among the timed events.
+    pub fn in_all_day_strip(&self) -> bool {
+        let (Some(start), Some(end), Some(last)) = (self.start_time, self.end_time, self.end_date) else {
+            return self.start_time.is_none();
+        };
+        // An event ending at midnight covers up to the day before
+        let end_day = if end == NaiveTime::MIN { last + Duration::days(1) } else { last };
+        end_day.and_time(end) - self.date.and_time(start) > Duration::days(1)
+    }
+
+    /// Last day the event covers
+    pub fn last_date(&self) -> NaiveDate {
+        self.end_date.unwrap_or(self.date)
+    }
+
+    /// "Day 2/3" on the days of an event running over several days
+    pub fn day_of_span(&self, date: NaiveDate) -> Option<String> {
+        let total = (self.end_date? - self.date).num_days() + 1;
+        Some(format!("Day {}/{}", (date - self.date).num_days() + 1, total))
+    }
+
+    /// Reschedule to start on `date`, keeping the number of days the event spans
+    pub fn move_to(&mut self, date: NaiveDate) {
+        let shift = date - self.date;
+        self.date = date;
+        self.end_date = self.end_date.map(|end| end + shift);
+    }
+
+    /// Duration in minutes computed from start/end; events ending at or before
+    /// their start time are treated as running past midnight
+    pub fn duration_minutes(&self) -> Option<i64> {
+        let minutes = (self.end_time? - self.start_time?).num_minutes();
+        Some(if minutes <= 0 { minutes + 24 * 60 } else { minutes })
+    }
+
+    /// Whether most of the other attendees (ignoring you and meeting rooms) have declined
+    pub fn mostly_declined(&self) -> bool {
+        let others: Vec<&DisplayAttendee> = self.attendees.iter()
+            .filter(|a| !a.is_self && !a.is_resource())
+            .collect();
+        let declined = others.iter().filter(|a| a.status == AttendeeStatus::Declined).count();
+        others.len() >= 2 && declined * 2 > others.len()
+    }
+
+    /// Whether you turned the invitation down
+    pub fn declined(&self) -> bool {
+        self.attendees.iter().any(|a| a.is_self && a.status == AttendeeStatus::Declined)
+    }
+
+    /// How many invited people (meeting rooms aside) have accepted, not answered yet or
+    /// tentatively accepted, and declined; `None` unless someone besides you is invited
+    pub fn response_counts(&self) -> Option<(usize, usize, usize)> {
+        let people: Vec<&DisplayAttendee> = self.attendees.iter()
+            .filter(|a| !a.is_resource())
+            .collect();
+        if !people.iter().any(|a| !a.is_self) {
+            return None;
+        }
+        let count = |statuses: &[AttendeeStatus]| people.iter().filter(|a| statuses.contains(&a.status)).count();
+        Some((
+            count(&[AttendeeStatus::Accepted, AttendeeStatus::Organizer]),
+            count(&[AttendeeStatus::Tentative, AttendeeStatus::NeedsAction]),
+            count(&[AttendeeStatus::Declined]),
+        ))
+    }
+
+    /// Name of the calendar the event belongs to, or its source when unnamed
+    pub fn calendar_label(&self) -> &str {
+        match &self.id {
+            EventId::Google { calendar_name, .. } => calendar_name.as_deref().unwrap_or("Google"),
+            EventId::ICloud { calendar_name, .. } => calendar_name.as_deref().unwrap_or("iCloud"),
+            EventId::Feed { calendar_name, .. } => calendar_name.as_deref().unwrap_or("Feed"),
+        }
+    }
+
+    /// Stable identifier for locally stored data such as tags: the iCalendar UID
+    /// when known, otherwise the source's event id
+    pub fn uid(&self) -> &str {
+        match (&self.ical_uid, &self.id) {
+            (Some(uid), _) => uid,
+            (None, EventId::Google { event_id, .. }) => event_id,
+            (None, EventId::ICloud { event_uid, .. } | EventId::Feed { event_uid, .. }) => event_uid,
+        }
+    }
+
+    /// Same event on the same day; occurrences of a recurring event share their UID
+    pub fn is_same_occurrence(&self, other: &DisplayEvent) -> bool {
+        self.date == other.date && self.uid() == other.uid()
+    }
+
+    /// Email of the attendee organizing the event
+    pub fn organizer(&self) -> Option<&str> {
+        self.attendees.iter().find(|a| a.status == AttendeeStatus::Organizer).map(|a| a.email.as_str())
+    }
+
+    /// Whether two events on the same date are copies of one another: same iCalendar UID,
+    /// or same title and start time from the same organizer (the user, for events
+    /// without invitees)
+    pub fn is_mirror_of(&self, other: &DisplayEvent) -> bool {
+        if let (Some(a), Some(b)) = (&self.ical_uid, &other.ical_uid)
+            && a == b
+        {
+            return true;
+        }
+        let same_organizer = match (self.organizer(), other.organizer()) {
+            (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+            (None, None) => self.is_organizer && other.is_organizer,
+            _ => false,
+        };
+        same_organizer
+            && self.start_time == other.start_time
+            && self.title.trim().eq_ignore_ascii_case(other.title.trim())
+    }
+
+    /// Fill in details this event lacks from a mirrored copy
+    fn merge_from(&mut self, other: &DisplayEvent) {
+        if self.location.is_none() {
+            self.location = other.location.clone();
+        }
+        if self.meeting_url.is_none() {
+            self.meeting_url = other.meeting_url.clone();
+        }
+        if self.dial_in.is_none() {
+            self.dial_in = other.dial_in.clone();
+        }
+        if self.description.is_none() {
+            self.description = other.description.clone();
+        }
+    }
+}
+
+/// Serializable cache format for disk persistence
+#[derive(Deserialize)]
+struct DiskCache {
+    google: HashMap<NaiveDate, Vec<DisplayEvent>>,
+    icloud: HashMap<NaiveDate, Vec<DisplayEvent>>,
+    #[serde(default)]
+    holidays: HashMap<NaiveDate, Vec<String>>,
+}
+
+/// `DiskCache` borrowed from the live cache, so saving doesn't copy every event
+#[derive(Serialize)]
+struct DiskCacheRef<'a> {
+    google: &'a HashMap<NaiveDate, Vec<Arc<DisplayEvent>>>,
+    icloud: &'a HashMap<NaiveDate, Vec<Arc<DisplayEvent>>>,
+    holidays: &'a HashMap<NaiveDate, Vec<String>>,
+}
+
+/// Source-specific event cache. Events are shared between the stored and displayed maps,
+/// and only days whose display differs from the stored events get a list of their own.
+pub struct SourceCache {
+    by_date: HashMap<NaiveDate, Vec<Arc<DisplayEvent>>>,
+    /// Days displayed differently from `by_date`: without muted, banner or hidden-kind
+    /// events, or with mirrored duplicates removed or merged. Other days show `by_date`.
+    view: HashMap<NaiveDate, Vec<Arc<DisplayEvent>>>,
+    fetched_months: HashSet<(i32, u32)>,
+}
+
+impl SourceCache {
+    pub fn new() -> Self {
+        Self {
+            by_date: HashMap::new(),
+            view: HashMap::new(),
+            fetched_months: HashSet::new(),
+        }
+    }
+
+    fn reset_view(&mut self) {
+        self.view.clear();
+    }
+
+    /// Leave the events `keep` rejects out of the displayed days
+    fn filter_view(&mut self, keep: impl Fn(&NaiveDate, &DisplayEvent) -> bool) {
+        for (date, events) in &self.by_date {
+            if events.iter().all(|event| keep(date, event)) {
+                continue;
+            }
+            let shown = events.iter().filter(|event| keep(date, event)).cloned().collect();
+            self.view.insert(*date, shown);
+        }
+    }
+
+    pub fn has_month(&self, date: NaiveDate) -> bool {
+        self.fetched_months.contains(&(date.year(), date.month()))
+    }
+
+    pub fn store(&mut self, events: Vec<DisplayEvent>, month_date: NaiveDate) {
+        // Clear existing events for this month before storing fresh data
+        let year = month_date.year();
+        let month = month_date.month();
+        self.by_date.retain(|date, _| date.year() != year || date.month() != month);
+
+        // An event running over several days is listed under each of its days in this month
+        for event in events {
+            let event = Arc::new(event);
+            for date in event.date.iter_days().take_while(|date| *date <= event.last_date()) {
+                if date.year() == year && date.month() == month {
+                    self.by_date.entry(date).or_default().push(Arc::clone(&event));
+                }
+            }
+        }
+        for (_, events) in self.by_date.iter_mut().filter(|(date, _)| date.year() == year && date.month() == month) {
+            sort_day(events);
+        }
+        self.fetched_months.insert((year, month));
+        self.reset_view();
+    }
+
+    pub fn get(&self, date: NaiveDate) -> &[Arc<DisplayEvent>] {
+        self.view
+            .get(&date)
+            .or_else(|| self.by_date.get(&date))
+            .map(|v| v.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn has_events(&self, date: NaiveDate) -> bool {
+        !self.get(date).is_empty()
+    }
+
+    /// Displayed events, each once: an event running over several days is taken from
+    /// the first of its days held here
+    pub fn all_events(&self) -> impl Iterator<Item = &DisplayEvent> {
+        self.by_date.keys().flat_map(move |date| {
+            self.get(*date).iter().map(Arc::as_ref).filter(move |event| {
+                *date == event.date
+                    || !date.pred_opt().is_some_and(|prev| self.get(prev).iter().any(|e| e.is_same_occurrence(event)))
+            })
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.by_date.clear();
+        self.view.clear();
+        self.fetched_months.clear();
+    }
+
+    /// Forget which months were fetched so they are fetched again, keeping their events
+    /// on screen meanwhile
+    pub fn mark_stale(&mut self) {
+        self.fetched_months.clear();
+    }
+
+    /// Load from raw data (for cache restore)
+    pub fn load_from(&mut self, data: HashMap<NaiveDate, Vec<DisplayEvent>>) {
+        self.by_date = data
+            .into_iter()
+            .map(|(date, events)| {
+                let mut events: Vec<_> = events.into_iter().map(Arc::new).collect();
+                sort_day(&mut events);
+                (date, events)
+            })
+            .collect();
+        self.reset_view();
+        // Don't mark months as fetched - we want to refresh from network
+    }
+}
+
+/// The all-day strip first, then timed events by start
+fn sort_day(events: &mut [Arc<DisplayEvent>]) {
+    events.sort_by_key(|event| (!event.in_all_day_strip(), event.start_time));
+}
+
+impl Default for SourceCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Days the agenda lists unless `agenda_days` is configured
+pub const DEFAULT_AGENDA_DAYS: u32 = 14;
+
+/// An event in the agenda, with its place in its day's panel
+#[derive(Debug, Clone, Copy)]
+pub struct AgendaEntry<'a> {
+    pub source: EventSource,
+    pub index: usize,
+    pub event: &'a DisplayEvent,
+}
+
+/// Combined event cache for all sources
+pub struct EventCache {
+    pub google: SourceCache,
+    pub icloud: SourceCache,
+    pub dedup: DedupConfig,
+    pub mute: MuteFilter,
+    /// Events hidden for a day on this machine
+    pub snoozed: SnoozeStore,
+    /// Event kinds left out of the meeting lists
+    pub hidden_kinds: Vec<EventKind>,
+    /// Narrows one day's events to those whose title or attendees contain the (lowercase) text
+    pub day_filter: Option<(NaiveDate, String)>,
+    /// Public holiday names by date, shown as an overlay
+    holidays: HashMap<NaiveDate, Vec<String>>,
+    /// Details fetched for partially listed events, re-applied when their month is refetched
+    details: HashMap<EventId, EventDetails>,
+}
+
+impl EventCache {
+    pub fn new() -> Self {
+        Self {
+            google: SourceCache::new(),
+            icloud: SourceCache::new(),
+            dedup: DedupConfig::default(),
+            mute: MuteFilter::default(),
+            snoozed: SnoozeStore::default(),
+            hidden_kinds: Vec::new(),
+            day_filter: None,
+            holidays: HashMap::new(),
+            details: HashMap::new(),
+        }
+    }
+
+    pub fn set_holidays(&mut self, holidays: Vec<(NaiveDate, String)>) {
+        self.holidays.clear();
+        for (date, name) in holidays {
+            self.holidays.entry(date).or_default().push(name);
+        }
+    }
+
+    pub fn holidays(&self, date: NaiveDate) -> &[String] {
+        self.holidays.get(&date).map(|v| v.as_slice()).unwrap_or(&[])
+    }
+
+    /// Whether a month of a source has been fetched since the cache was loaded or cleared
+    pub fn has_month(&self, source: EventSource, date: NaiveDate) -> bool {
+        match source {
+            EventSource::Google => self.google.has_month(date),
+            EventSource::ICloud => self.icloud.has_month(date),
+        }
+    }
+
+    /// Store fetched events for a source and re-apply deduplication
+    pub fn store(&mut self, source: EventSource, mut events: Vec<DisplayEvent>, month_date: NaiveDate) {
+        for event in events.iter_mut().filter(|e| e.partial) {
+            if let Some(details) = self.details.get(&event.id) {
+                details.apply_to(event);
+            }
+        }
+        match source {
+            EventSource::Google => self.google.store(events, month_date),
+            EventSource::ICloud => self.icloud.store(events, month_date),
+        }
+        self.reconcile();
+    }
+
+    /// Fill in the details of a partially listed event and remember them for refetches
+    pub fn set_details(&mut self, id: EventId, details: EventDetails) {
+        for cache in [&mut self.google, &mut self.icloud] {
+            for event in cache.by_date.values_mut().flatten().filter(|e| e.partial && e.id == id) {
+                details.apply_to(Arc::make_mut(event));
+            }
+        }
+        self.details.insert(id, details);
+        self.reconcile();
+    }
+
+    /// Modify every stored event of both sources in place
+    pub fn update_events(&mut self, mut f: impl FnMut(&mut DisplayEvent)) {
+        for cache in [&mut self.google, &mut self.icloud] {
+            cache.by_date.values_mut().flatten().for_each(|event| f(Arc::make_mut(event)));
+        }
+        self.reconcile();
+    }
+
+    /// Stored events of a day of the given kind that aren't muted
+    fn events_of_kind(&self, date: NaiveDate, kind: EventKind) -> impl Iterator<Item = &DisplayEvent> {
+        [&self.google, &self.icloud]
+            .into_iter()
+            .filter_map(move |cache| cache.by_date.get(&date))
+            .flatten()
+            .map(Arc::as_ref)
+            .filter(move |event| event.kind == kind && !self.mute.is_muted(event))
+    }
+
+    /// Out-of-office events of a day, shown as banners
+    pub fn banners(&self, date: NaiveDate) -> Vec<&DisplayEvent> {
+        self.events_of_kind(date, EventKind::OutOfOffice).collect()
+    }
+
+    /// Where the day is worked from ("Office", "Home"), from a working-location event
+    pub fn working_location(&self, date: NaiveDate) -> Option<&str> {
+        self.events_of_kind(date, EventKind::WorkingLocation).next().map(|e| e.title.as_str())
+    }
+
+    /// Rebuild the displayed events: muted or snoozed events, day markers, hidden kinds and events not
+    /// matching the day filter are left out, and copies of an event
+    /// that appear in both sources are hidden from the non-preferred source, with
+    /// their missing details merged into the preferred copy
+    pub fn reconcile(&mut self) {
+        let (keep, drop) = match self.dedup.prefer {
+            DedupPrefer::Google => (&mut self.google, &mut self.icloud),
+            DedupPrefer::ICloud => (&mut self.icloud, &mut self.google),
+        };
+        for cache in [&mut *keep, &mut *drop] {
+            cache.reset_view();
+            cache.filter_view(|date, event| {
+                let filter = self.day_filter.as_ref().filter(|(filter_date, _)| filter_date == date);
+                !event.kind.is_day_marker()
+                    && !self.hidden_kinds.contains(&event.kind)
+                    && !self.mute.is_muted(event)
+                    && !self.snoozed.is_snoozed(event)
+                    && filter.is_none_or(|(_, query)| event_match_type(event, query).is_some())
+            });
+        }
+        if !self.dedup.enabled {
+            return;
+        }
+
+        let dates: Vec<NaiveDate> = drop.by_date.keys().copied().collect();
+        for date in dates {
+            let events = drop.get(date);
+            if events.is_empty() || keep.get(date).is_empty() {
+                continue;
+            }
+            let mut kept = keep.get(date).to_vec();
+            let shown: Vec<_> = events
+                .iter()
+                .filter(|event| match kept.iter_mut().find(|k| k.is_mirror_of(event)) {
+                    Some(original) => {
+                        // Copy-on-write: only merged events stop sharing with the stored map
+                        Arc::make_mut(original).merge_from(event);
+                        false
+                    }
+                    None => true,
+                })
+                .cloned()
+                .collect();
+            if shown.len() < events.len() {
+                drop.view.insert(date, shown);
+                keep.view.insert(date, kept);
+            }
+        }
+    }
+
+    /// Displayed events of a day, Google first
+    pub fn day(&self, date: NaiveDate) -> impl Iterator<Item = &DisplayEvent> {
+        self.google.get(date).iter().chain(self.icloud.get(date)).map(Arc::as_ref)
+    }
+
+    /// Displayed events of `days` days from `from` with both sources merged in time order;
+    /// the all-day strip leads its day
+    pub fn agenda(&self, from: NaiveDate, days: u32) -> Vec<AgendaEntry<'_>> {
+        let mut entries = Vec::new();
+        for date in from.iter_days().take(days as usize) {
+            let day_start = entries.len();
+            for (source, events) in [(EventSource::Google, self.google.get(date)), (EventSource::ICloud, self.icloud.get(date))] {
+                entries.extend(events.iter().enumerate().map(|(index, event)| AgendaEntry { source, index, event: event.as_ref() }));
+            }
+            entries[day_start..].sort_by_key(|entry| (!entry.event.in_all_day_strip(), entry.event.start_time));
+        }
+        entries
+    }
+
+    /// Check if any source has events on this date
+    pub fn has_events(&self, date: NaiveDate) -> bool {
+        self.google.has_events(date) || self.icloud.has_events(date)
+    }
+
+    /// Clear all caches
+    pub fn clear(&mut self) {
+        self.google.clear();
+        self.icloud.clear();
+        self.details.clear();
+    }
+
+    /// Get cache file path
+    fn cache_path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|p| p.join("calendarchy").join("events.json"))
+    }
+
+    /// Save cache to disk
+    pub fn save_to_disk(&self) {
+        let Some(path) = Self::cache_path() else { return };
+
+        // Create parent directory if needed
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+
+        let cache = DiskCacheRef {
+            google: &self.google.by_date,
+            icloud: &self.icloud.by_date,
+            holidays: &self.holidays,
+        };
+
+        if let Ok(json) = serde_json::to_vec(&cache) {
+            let _ = fs::write(&path, json);
+        }
+    }
+
+    /// Check the on-disk cache without loading it: Ok(None) if there is no cache file,
+    /// Ok(Some(count)) with the number of cached events, or Err if it fails to parse
+    pub fn inspect_disk() -> std::result::Result<Option<usize>, String> {
+        let Some(path) = Self::cache_path() else { return Ok(None) };
+        let Ok(json) = fs::read_to_string(&path) else { return Ok(None) };
+        let cache = serde_json::from_str::<DiskCache>(&json)
+            .map_err(|e| format!("{} is unreadable: {}", path.display(), e))?;
+        let count = cache.google.values().chain(cache.icloud.values()).map(Vec::len).sum();
+        Ok(Some(count))
+    }
+
+    /// Load cache from disk. A cache file that fails to parse is moved aside
+    /// and reported as an error; the cache stays empty in that case.
+    pub fn load_from_disk(&mut self) -> Result<bool> {
+        let Some(path) = Self::cache_path() else { return Ok(false) };
+
+        let Ok(json) = fs::read_to_string(&path) else { return Ok(false) };
+        let cache = serde_json::from_str::<DiskCache>(&json)
+            .map_err(|e| CalendarchyError::corrupted_file(&path, e))?;
+
+        self.google.load_from(cache.google);
+        self.icloud.load_from(cache.icloud);
+        self.holidays = cache.holidays;
+        self.reconcile();
+        Ok(true)
+    }
+}
+
+impl Default for EventCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_event(title: &str, date: NaiveDate, time: &str) -> DisplayEvent {
+        DisplayEvent {
+            id: EventId::Google { calendar_id: "test".to_string(), event_id: "test-id".to_string(), calendar_name: None, account: None },
+            title: title.to_string(),
+            start_time: NaiveTime::parse_from_str(time, "%H:%M").ok(),
+            date,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_duration_minutes() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let mut event = make_event("Meeting", date, "14:00");
+        assert_eq!(event.duration_minutes(), None);
+
+        event.end_time = NaiveTime::from_hms_opt(15, 30, 0);
+        assert_eq!(event.duration_minutes(), Some(90));
+
+        // Runs past midnight
+        event.start_time = NaiveTime::from_hms_opt(23, 0, 0);
+        event.end_time = NaiveTime::from_hms_opt(0, 30, 0);
+        assert_eq!(event.duration_minutes(), Some(90));
+
+        event.start_time = None;
+        assert_eq!(event.duration_minutes(), None);
+    }
+
+    #[test]
+    fn test_move_to_keeps_span() {
+        let day = |d| NaiveDate::from_ymd_opt(2026, 1, d).unwrap();
+        let mut trip = DisplayEvent { end_date: Some(day(3)), ..make_event("Trip", day(1), "All day") };
+        trip.move_to(day(10));
+        assert_eq!((trip.date, trip.end_date), (day(10), Some(day(12))));
+
+        let mut meeting = make_event("Meeting", day(10), "14:00");
+        meeting.move_to(day(5));
+        assert_eq!((meeting.date, meeting.end_date), (day(5), None));
+    }
+
+    #[test]
+    fn test_mostly_declined() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let attendee = |email: &str, status: AttendeeStatus| DisplayAttendee {
+            name: None,
+            email: email.to_string(),
+            status,
+            is_self: email == "me@example.com",
+        };
+        let mut event = make_event("Sync", date, "10:00");
+        event.attendees = vec![
+            attendee("me@example.com", AttendeeStatus::Accepted),
+            attendee("alice@example.com", AttendeeStatus::Declined),
+            attendee("bob@example.com", AttendeeStatus::Accepted),
+        ];
+        assert!(!event.mostly_declined());
+
+        event.attendees.push(attendee("carol@example.com", AttendeeStatus::Declined));
+        assert!(event.mostly_declined());
+
+        // A lone declined guest isn't a crowd
+        event.attendees = vec![attendee("alice@example.com", AttendeeStatus::Declined)];
+        assert!(!event.mostly_declined());
+    }
+
+    #[test]
+    fn test_response_counts() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let attendee = |email: &str, status: AttendeeStatus| DisplayAttendee {
+            name: None,
+            email: email.to_string(),
+            status,
+            is_self: email == "me@example.com",
+        };
+        let mut event = make_event("Sync", date, "10:00");
+        assert_eq!(event.response_counts(), None);
+        event.attendees = vec![attendee("me@example.com", AttendeeStatus::Accepted)];
+        assert_eq!(event.response_counts(), None);
+
+        event.attendees.extend([
+            attendee("alice@example.com", AttendeeStatus::Organizer),
+            attendee("bob@example.com", AttendeeStatus::Tentative),
+            attendee("carol@example.com", AttendeeStatus::Declined),
+            attendee("c_123@resource.calendar.google.com", AttendeeStatus::Accepted),
+        ]);
+        assert_eq!(event.response_counts(), Some((2, 1, 1)));
+    }
+
+    #[test]
+    fn test_is_same_occurrence() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let mut standup = make_event("Standup", date, "09:00");
+        standup.ical_uid = Some("series".to_string());
+        let mut next_day = make_event("Standup", date + chrono::Duration::days(1), "09:00");
+        next_day.ical_uid = Some("series".to_string());
+
+        assert!(standup.is_same_occurrence(&standup.clone()));
+        assert!(!standup.is_same_occurrence(&next_day));
+    }
+
+    #[test]
+    fn test_source_cache_store_and_get() {
+        let mut cache = SourceCache::new();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let month_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+
+        let events = vec![
+            make_event("Meeting 1", date, "10:00"),
+            make_event("Meeting 2", date, "14:00"),
+        ];
+
+        cache.store(events, month_date);
+
+        let retrieved = cache.get(date);
+        assert_eq!(retrieved.len(), 2);
+        assert_eq!(retrieved[0].title, "Meeting 1");
+        assert_eq!(retrieved[1].title, "Meeting 2");
+    }
+
+    #[test]
+    fn test_source_cache_has_month() {
+        let mut cache = SourceCache::new();
+        let month_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+
+        assert!(!cache.has_month(month_date));
+
+        cache.store(vec![], month_date);
+
+        assert!(cache.has_month(month_date));
+        assert!(!cache.has_month(NaiveDate::from_ymd_opt(2026, 2, 1).unwrap()));
+    }
+
+    #[test]
+    fn test_source_cache_store_replaces_month_data() {
+        let mut cache = SourceCache::new();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let month_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+
+        // Store first batch
+        cache.store(vec![make_event("Old Event", date, "09:00")], month_date);
+        assert_eq!(cache.get(date).len(), 1);
+        assert_eq!(cache.get(date)[0].title, "Old Event");
+
+        // Store second batch - should replace
+        cache.store(vec![make_event("New Event", date, "10:00")], month_date);
+        assert_eq!(cache.get(date).len(), 1);
+        assert_eq!(cache.get(date)[0].title, "New Event");
+    }
+
+    #[test]
+    fn test_source_cache_multi_day_event() {
+        let mut cache = SourceCache::new();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 30).unwrap();
+        let mut trip = make_event("Conference", date, "All day");
+        trip.end_date = NaiveDate::from_ymd_opt(2026, 2, 2);
+
+        // Each month's fetch lists the event under its own days
+        cache.store(vec![trip.clone()], NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        assert_eq!(cache.get(date.succ_opt().unwrap())[0].title, "Conference");
+        assert!(cache.get(NaiveDate::from_ymd_opt(2026, 2, 1).unwrap()).is_empty());
+        cache.store(vec![trip], NaiveDate::from_ymd_opt(2026, 2, 1).unwrap());
+        let last = NaiveDate::from_ymd_opt(2026, 2, 2).unwrap();
+        assert_eq!(cache.get(last).len(), 1);
+        assert!(cache.get(last.succ_opt().unwrap()).is_empty());
+        assert_eq!(cache.all_events().count(), 1);
+
+        let event = &cache.get(last)[0];
+        assert_eq!(event.day_of_span(last).as_deref(), Some("Day 4/4"));
+        assert_eq!(make_event("Lunch", date, "12:00").day_of_span(date), None);
+    }
+
+    #[test]
+    fn test_all_day_strip() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 30).unwrap();
+        let timed = |start: &str, end: &str, days| DisplayEvent {
+            end_time: NaiveTime::parse_from_str(end, "%H:%M").ok(),
+            end_date: Some(date + Duration::days(days)).filter(|_| days > 0),
+            ..make_event("Event", date, start)
+        };
+        assert!(make_event("Holiday", date, "All day").in_all_day_strip());
+        assert!(!timed("09:00", "10:00", 0).in_all_day_strip());
+
+        // A late meeting past midnight is a meeting like any other
+        let late = timed("23:00", "00:30", 0);
+        assert!(!late.in_all_day_strip());
+        assert_eq!(late.duration_minutes(), Some(90));
+        // Even when an older cache gave it the next day as its last
+        assert!(!timed("23:00", "00:30", 1).in_all_day_strip());
+        assert!(!timed("10:00", "00:00", 0).in_all_day_strip());
+
+        // More than a day goes to the strip
+        assert!(timed("10:00", "12:00", 1).in_all_day_strip());
+        assert!(timed("10:00", "00:00", 2).in_all_day_strip());
+    }
+
+    #[test]
+    fn test_source_cache_has_events() {
+        let mut cache = SourceCache::new();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let empty_date = NaiveDate::from_ymd_opt(2026, 1, 16).unwrap();
+        let month_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+
+        cache.store(vec![make_event("Event", date, "10:00")], month_date);
+
+        assert!(cache.has_events(date));
+        assert!(!cache.has_events(empty_date));
+    }
+
+    #[test]
+    fn test_source_cache_clear() {
+        let mut cache = SourceCache::new();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let month_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+
+        cache.store(vec![make_event("Event", date, "10:00")], month_date);
+        assert!(cache.has_month(month_date));
+        assert!(cache.has_events(date));
+
+        cache.clear();
+        assert!(!cache.has_month(month_date));
+        assert!(!cache.has_events(date));
+    }
+
+    #[test]
+    fn test_source_cache_mark_stale_keeps_events() {
+        let mut cache = SourceCache::new();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let month_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+
+        cache.store(vec![make_event("Event", date, "10:00")], month_date);
+        cache.mark_stale();
+        assert!(!cache.has_month(month_date));
+        assert!(cache.has_events(date));
+    }
+
+    #[test]
+    fn test_source_cache_load_from_does_not_mark_fetched() {
+        let mut cache = SourceCache::new();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let month_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+
+        let mut data = HashMap::new();
+        data.insert(date, vec![make_event("Cached Event", date, "10:00")]);
+
+        cache.load_from(data);
+
+        // Data should be there
+        assert_eq!(cache.get(date).len(), 1);
+        // But month should NOT be marked as fetched (allows refresh)
+        assert!(!cache.has_month(month_date));
+    }
+
+    #[test]
+    fn test_event_cache_has_events_either_source() {
+        let mut cache = EventCache::new();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let month_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+
+        assert!(!cache.has_events(date));
+
+        cache.google.store(vec![make_event("Google Event", date, "10:00")], month_date);
+        assert!(cache.has_events(date));
+
+        cache.google.clear();
+        assert!(!cache.has_events(date));
+
+        cache.icloud.store(vec![make_event("iCloud Event", date, "11:00")], month_date);
+        assert!(cache.has_events(date));
+    }
+
+    #[test]
+    fn test_event_cache_agenda_merges_sources_in_time_order() {
+        let mut cache = EventCache::new();
+        let day = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let next_day = day.succ_opt().unwrap();
+        let month_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        cache.google.store(vec![
+            make_event("Standup", day, "09:30"),
+            make_event("Review", day, "14:00"),
+            make_event("Planning", next_day, "10:00"),
+        ], month_date);
+        cache.icloud.store(vec![
+            make_event("Dentist", day, "11:00"),
+            make_event("Birthday", day, "All day"),
+        ], month_date);
+
+        let titles = |days| -> Vec<(String, EventSource, usize)> {
+            cache.agenda(day, days).iter().map(|e| (e.event.title.clone(), e.source, e.index)).collect()
+        };
+        // Stored days put the all-day strip first
+        assert_eq!(titles(1), vec![
+            ("Birthday".to_string(), EventSource::ICloud, 0),
+            ("Standup".to_string(), EventSource::Google, 0),
+            ("Dentist".to_string(), EventSource::ICloud, 1),
+            ("Review".to_string(), EventSource::Google, 1),
+        ]);
+        assert_eq!(titles(2).len(), 5);
+    }
+
+    #[test]
+    fn test_event_cache_hides_mirrored_icloud_copy() {
+        let mut cache = EventCache::new();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let month_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+
+        let organized_by = |title: &str, email: &str| DisplayEvent {
+            attendees: vec![DisplayAttendee {
+                name: None,
+                email: email.to_string(),
+                status: AttendeeStatus::Organizer,
+                is_self: false,
+            }],
+            ..make_event(title, date, "10:00")
+        };
+        let mut forwarded = organized_by("team sync ", "Lead@example.com");
+        forwarded.location = Some("Room 4".to_string());
+        let namesake = organized_by("Team Sync", "other@example.com");
+        cache.store(EventSource::ICloud, vec![forwarded, namesake, make_event("Gym", date, "18:00")], month_date);
+        assert_eq!(cache.icloud.get(date).len(), 3);
+
+        // Same title and time from another organizer is a different meeting
+        cache.store(EventSource::Google, vec![organized_by("Team Sync", "lead@example.com")], month_date);
+        assert_eq!(cache.icloud.get(date).len(), 2);
+        assert_eq!(cache.icloud.get(date)[0].attendees[0].email, "other@example.com");
+        assert_eq!(cache.icloud.get(date)[1].title, "Gym");
+        assert_eq!(cache.google.get(date)[0].location.as_deref(), Some("Room 4"));
+
+        // Merging copies only the displayed event; the stored one is unchanged,
+        // and untouched events are shared between the two
+        assert_eq!(cache.google.by_date[&date][0].location, None);
+        assert!(Arc::ptr_eq(&cache.icloud.by_date[&date][2], &cache.icloud.get(date)[1]));
+
+        // The hidden copy is still persisted and comes back once the Google event is gone
+        assert_eq!(cache.icloud.by_date[&date].len(), 3);
+        cache.store(EventSource::Google, vec![], month_date);
+        assert_eq!(cache.icloud.get(date).len(), 3);
+    }
+
+    #[test]
+    fn test_event_cache_dedup_by_uid_and_preference() {
+        let mut cache = EventCache::new();
+        cache.dedup.prefer = DedupPrefer::ICloud;
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let month_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+
+        let mut google = make_event("Planning", date, "09:00");
+        google.ical_uid = Some("uid-1".to_string());
+        let mut icloud = make_event("Planning (moved)", date, "09:30");
+        icloud.ical_uid = Some("uid-1".to_string());
+        cache.store(EventSource::Google, vec![google], month_date);
+        cache.store(EventSource::ICloud, vec![icloud], month_date);
+
+        assert!(cache.google.get(date).is_empty());
+        assert_eq!(cache.icloud.get(date).len(), 1);
+
+        cache.dedup.enabled = false;
+        cache.reconcile();
+        assert_eq!(cache.google.get(date).len(), 1);
+        assert_eq!(cache.icloud.get(date).len(), 1);
+    }
+
+    #[test]
+    fn test_event_cache_hides_muted_events() {
+        let mut cache = EventCache::new();
+        let rule = crate::config::MuteRule { title: Some("lunch block".to_string()), ..Default::default() };
+        cache.mute = MuteFilter::new(&[rule]).unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let month_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+
+        cache.store(EventSource::Google, vec![make_event("Lunch Block", date, "12:00")], month_date);
+        assert!(cache.google.get(date).is_empty());
+        assert!(!cache.has_events(date));
+        assert_eq!(cache.google.by_date[&date].len(), 1);
+    }
+
+    #[test]
+    fn test_event_cache_special_event_kinds() {
+        let mut cache = EventCache::new();
+        cache.hidden_kinds = vec![EventKind::FocusTime];
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let month_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let with_kind = |title: &str, kind: EventKind| DisplayEvent { kind, ..make_event(title, date, "09:00") };
+
+        cache.store(EventSource::Google, vec![
+            with_kind("Vacation", EventKind::OutOfOffice),
+            with_kind("Home", EventKind::WorkingLocation),
+            with_kind("Deep work", EventKind::FocusTime),
+            with_kind("Standup", EventKind::Default),
+        ], month_date);
+        let titles: Vec<&str> = cache.google.get(date).iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Standup"]);
+        let banners: Vec<&str> = cache.banners(date).iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(banners, vec!["Vacation"]);
+        assert_eq!(cache.working_location(date), Some("Home"));
+        assert_eq!(EventKind::from_google(Some("workingLocation")), EventKind::WorkingLocation);
+        assert_eq!(EventKind::from_google(Some("fromGmail")), EventKind::Default);
+    }
+
+    #[test]
+    fn test_event_cache_day_filter() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let next_day = date.succ_opt().unwrap();
+        let mut cache = EventCache::new();
+        cache.store(EventSource::Google, vec![
+            make_event("Standup", date, "09:00"),
+            make_event("Design review", date, "11:00"),
+            make_event("Standup", next_day, "09:00"),
+        ], date);
+
+        cache.day_filter = Some((date, "review".to_string()));
+        cache.reconcile();
+        let titles: Vec<&str> = cache.google.get(date).iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Design review"]);
+        assert_eq!(cache.google.get(next_day).len(), 1);
+
+        cache.day_filter = None;
+        cache.reconcile();
+        assert_eq!(cache.google.get(date).len(), 2);
+    }
+
+    #[test]
+    fn test_event_cache_details_survive_refetch() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let listed = || DisplayEvent { partial: true, ..make_event("Planning", date, "10:00") };
+        let mut cache = EventCache::new();
+        cache.store(EventSource::Google, vec![listed()], date);
+
+        let details = EventDetails {
+            description: Some("Agenda".to_string()),
+            meeting_url: Some("https://meet.google.com/abc-defg-hij".to_string()),
+            ..EventDetails::of(make_event("Planning", date, "10:00"))
+        };
+        cache.set_details(listed().id, details);
+        let event = &cache.google.get(date)[0];
+        assert!(!event.partial);
+        assert_eq!(event.description.as_deref(), Some("Agenda"));
+
+        // A refetch lists the event without details again; the fetched ones are kept
+        cache.store(EventSource::Google, vec![listed()], date);
+        let event = &cache.google.get(date)[0];
+        assert!(!event.partial);
+        assert!(event.meeting_url.is_some());
+    }
+
+    #[test]
+    fn test_event_cache_holidays() {
+        let mut cache = EventCache::new();
+        let date = NaiveDate::from_ymd_opt(2026, 3, 3).unwrap();
+        cache.set_holidays(vec![(date, "Liberation Day".to_string())]);
+        assert_eq!(cache.holidays(date), ["Liberation Day".to_string()]);
+        assert!(cache.holidays(date.succ_opt().unwrap()).is_empty());
+
+        // Holidays are an overlay, not events of either source
+        assert!(!cache.has_events(date));
+        cache.clear();
+        assert_eq!(cache.holidays(date).len(), 1);
+    }
+
+    #[test]
+    fn test_display_event_serialization() {
+        let event = make_event("Test Meeting", NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(), "14:30");
+
+        let json = serde_json::to_string(&event).unwrap();
+        let parsed: DisplayEvent = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.title, "Test Meeting");
+        assert_eq!(parsed.start_time, NaiveTime::from_hms_opt(14, 30, 0));
+        assert!(parsed.accepted);
+
+        // Caches written before times were parsed hold them as strings
+        let old = json.replace(r#""start_time":"14:30","end_time":null"#, r#""time_str":"All day","end_time_str":null"#);
+        assert_ne!(old, json);
+        let parsed: DisplayEvent = serde_json::from_str(&old).unwrap();
+        assert!(parsed.is_all_day());
+        assert_eq!(parsed.time_label(&TimeFormatConfig::default()), "All day");
+    }
+}
@@ -0,0 +1,964 @@
+use crate::cache::{DisplayEvent, EventKind, EventSource};
+use crate::error::{CalendarchyError, Result};
+use crate::google::TokenInfo;
+use crate::outlook::GraphToken;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc, Weekday};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::fs;
+use std::ops::Range;
+use std::path::PathBuf;
+
+/// Root configuration structure
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Config {
+    #[serde(default)]
+    pub google: Option<GoogleConfig>,
+    #[serde(default)]
+    pub icloud: Option<ICloudConfig>,
+    /// Microsoft 365 / Outlook account, read through Microsoft Graph
+    #[serde(default)]
+    pub outlook: Option<OutlookConfig>,
+    #[serde(default)]
+    pub week_start: WeekStart,
+    #[serde(default)]
+    pub time_format: TimeFormatConfig,
+    #[serde(default)]
+    pub countdown: CountdownConfig,
+    /// Capture headers and redacted bodies of failed HTTP requests
+    #[serde(default)]
+    pub verbose_http: bool,
+    /// HTTP log entries kept for the debug screen and debug reports (default 1000)
+    #[serde(default)]
+    pub http_log_capacity: Option<usize>,
+    #[serde(default)]
+    pub work_hours: WorkHoursConfig,
+    /// Days the agenda view lists, starting today (default 14)
+    #[serde(default)]
+    pub agenda_days: Option<u32>,
+    #[serde(default)]
+    pub dedup: DedupConfig,
+    /// Rules hiding noisy events everywhere they would be shown
+    #[serde(default)]
+    pub mute: Vec<MuteRule>,
+    /// Google holiday calendar region to overlay, e.g. "en.bulgarian" or "en.usa"
+    #[serde(default)]
+    pub holidays: Option<String>,
+    #[serde(default)]
+    pub contacts: ContactsConfig,
+    #[serde(default)]
+    pub colors: ColorsConfig,
+    /// Extra meeting URL regexes or domains (e.g. a self-hosted meeting server) used by [J]oin
+    #[serde(default)]
+    pub meeting_patterns: Vec<String>,
+    /// Command used to open links instead of the platform default, e.g. "firefox --new-window"
+    #[serde(default)]
+    pub browser_command: Option<String>,
+    /// Command that handles tel: links for dial-ins, e.g. "kdeconnect-cli -n phone --share {url}"
+    #[serde(default)]
+    pub dialer_command: Option<String>,
+    /// Special Google event types to leave out of the meeting lists, e.g. ["focus_time"]
+    #[serde(default)]
+    pub hide_event_types: Vec<EventKind>,
+    #[serde(default)]
+    pub journal: JournalConfig,
+    #[serde(default)]
+    pub travel: TravelConfig,
+    #[serde(default)]
+    pub confirm: ConfirmConfig,
+    /// What Enter does on a selected event: "none", "details", "join" or "browser"
+    #[serde(default)]
+    pub enter_action: EnterAction,
+    /// List both sources in one time-ordered panel instead of Work above Personal (toggled with u)
+    #[serde(default)]
+    pub unified_panel: bool,
+    #[serde(default)]
+    pub status_bar: StatusBarConfig,
+    /// Minutes between background refetches of the displayed month (default 5; 0 turns them off)
+    #[serde(default)]
+    pub refresh_interval_minutes: Option<u64>,
+    /// Calendar backends to fetch from; when empty, Google, iCloud and Outlook from their sections above
+    #[serde(default)]
+    pub providers: Vec<ProviderConfig>,
+}
+
+/// One entry of `providers`, e.g. `{"type": "ics_feed", "url": "webcal://…", "name": "Team"}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProviderConfig {
+    /// The calendar from the `google` section
+    Google,
+    /// The calendars discovered for the `icloud` account
+    Caldav,
+    /// Someone else's Google calendar shared with the signed-in account, shown read-only;
+    /// `:shared` lists the ids of the ones available
+    GoogleShared {
+        calendar_id: String,
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default = "default_shared_panel")]
+        panel: EventSource,
+    },
+    /// Another Google account, e.g. a personal one next to work, signed in with
+    /// `:google <account>` through the `google` section's client. Its events are labeled
+    /// with `name` (or the account) and answered, deleted and moved as that account.
+    GoogleAccount {
+        account: String,
+        /// Calendars to show; defaults to the account's primary one
+        #[serde(default)]
+        calendar_ids: Vec<String>,
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default = "default_shared_panel")]
+        panel: EventSource,
+    },
+    /// The calendar of the `outlook` account, shown read-only once signed in with `:outlook`
+    Outlook {
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default = "default_shared_panel")]
+        panel: EventSource,
+    },
+    /// Read-only subscribed calendar
+    IcsFeed {
+        url: String,
+        #[serde(default)]
+        name: Option<String>,
+        /// Panel the events show in: "google" (Work) or "icloud" (Personal)
+        #[serde(default = "default_feed_panel")]
+        panel: EventSource,
+    },
+    /// Read-only directory of .ics files
+    LocalDir {
+        path: PathBuf,
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default = "default_feed_panel")]
+        panel: EventSource,
+    },
+}
+
+fn default_feed_panel() -> EventSource {
+    EventSource::ICloud
+}
+
+/// Shared work calendars go next to the user's own
+fn default_shared_panel() -> EventSource {
+    EventSource::Google
+}
+
+/// Segments of the bottom bar from left to right, e.g. `["countdown", "pending", "clock"]`;
+/// leave one out to hide it. Status messages take the bar over while they are shown.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StatusBarConfig(pub Vec<StatusSegment>);
+
+impl Default for StatusBarConfig {
+    fn default() -> Self {
+        Self(vec![StatusSegment::Countdown])
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusSegment {
+    /// Time until the next event, or the event in progress (see `countdown`)
+    Countdown,
+    SelectedDate,
+    /// Fetch in progress, failed sources, or the time of the last successful fetch
+    Sync,
+    /// Invitations from today on still waiting for an answer
+    Pending,
+    Clock,
+}
+
+/// Fast path for Enter in event mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EnterAction {
+    /// Do nothing, as before Enter was configurable
+    #[default]
+    None,
+    /// Show all of the event's details in a scrollable modal
+    Details,
+    /// Open the meeting link
+    Join,
+    /// Open the event in the calendar's web UI
+    Browser,
+}
+
+/// Which event actions ask for confirmation first, e.g. `{"accept": false, "decline": false}`
+/// to RSVP with a single key while deletes still ask
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfirmConfig {
+    pub accept: bool,
+    pub decline: bool,
+    pub delete: bool,
+}
+
+impl Default for ConfirmConfig {
+    fn default() -> Self {
+        Self {
+            accept: true,
+            decline: true,
+            delete: true,
+        }
+    }
+}
+
+/// Daily note the day's attended meetings are appended to
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JournalConfig {
+    /// Markdown file template; `{date}` becomes YYYY-MM-DD, e.g. "~/notes/{date}.md"
+    pub path: Option<String>,
+    /// Also journal the previous day automatically when the date changes
+    pub on_rollover: bool,
+}
+
+/// Time to leave for events at a physical place, e.g.
+/// `{"minutes": 20, "places": {"Office": 35, "Cafe": 10}, "notify": true}`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TravelConfig {
+    /// Travel time to any physical location; leave-by hints are off when unset and no place matches
+    pub minutes: Option<i64>,
+    /// Travel time to locations containing the key (case-insensitive); the longest match wins
+    pub places: BTreeMap<String, i64>,
+    /// Also send a desktop notification when it's time to leave
+    pub notify: bool,
+}
+
+impl TravelConfig {
+    /// Minutes of travel to reach `location`, or None for online meetings of known providers
+    /// and unknown places
+    pub fn lead_minutes(&self, location: &str) -> Option<i64> {
+        let location = location.trim();
+        let online = crate::utils::extract_meeting_url(location, &crate::utils::MeetingPatterns::default()).is_some();
+        if location.is_empty() || location.starts_with("http") || online {
+            return None;
+        }
+        let lower = location.to_lowercase();
+        self.places
+            .iter()
+            .filter(|(place, _)| lower.contains(&place.to_lowercase()))
+            .max_by_key(|(place, _)| place.len())
+            .map(|(_, minutes)| *minutes)
+            .or(self.minutes)
+            .filter(|minutes| *minutes > 0)
+    }
+
+    /// When to set off for a timed event at a physical location; a location holding the
+    /// event's meeting link is online, whichever pattern found the link
+    pub fn leave_by(&self, event: &DisplayEvent) -> Option<NaiveDateTime> {
+        let start = event.start_time.filter(|_| !event.in_all_day_strip())?;
+        let location = event.location.as_deref()
+            .filter(|location| !event.meeting_url.as_deref().is_some_and(|url| location.contains(url)))?;
+        let minutes = self.lead_minutes(location)?;
+        Some(event.date.and_time(start) - Duration::minutes(minutes))
+    }
+}
+
+/// Accent colors, as names ("blue", "dark_cyan") or "#rrggbb"
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColorsConfig {
+    pub google: Option<String>,
+    pub icloud: Option<String>,
+    /// Per-calendar overrides keyed by calendar name, e.g. `{"Family": "#ff9500"}`
+    pub calendars: BTreeMap<String, String>,
+}
+
+/// Where to look up attendee display names
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContactsConfig {
+    /// Read the iCloud account's address book over CardDAV
+    pub icloud: bool,
+    /// Directory of .vcf files
+    pub vcard_dir: Option<PathBuf>,
+}
+
+/// Hide events matching all of the given case-insensitive regexes,
+/// e.g. `{"title": "^lunch block$", "calendar": "Team"}`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MuteRule {
+    pub title: Option<String>,
+    /// Matched against the organizer's name and email
+    pub organizer: Option<String>,
+    /// Matched against the calendar name
+    pub calendar: Option<String>,
+}
+
+/// Handling of events that show up in both Google and iCloud (e.g. forwarded invites)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DedupConfig {
+    /// Set to false to show both copies
+    pub enabled: bool,
+    /// Which copy to keep; Google is the default since its events can be accepted/declined
+    pub prefer: DedupPrefer,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            prefer: DedupPrefer::Google,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DedupPrefer {
+    #[default]
+    Google,
+    ICloud,
+}
+
+/// Bottom-bar countdown settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CountdownConfig {
+    /// Set to false to hide the countdown entirely
+    pub enabled: bool,
+    /// Template for upcoming events; placeholders: {title}, {rel}, {time}, {end}
+    pub format: String,
+    /// Template for the event in progress (same placeholders)
+    pub now_format: String,
+    /// Only show upcoming events starting within this many minutes
+    pub within_minutes: Option<i64>,
+    /// Only count down to events that start within `work_hours`
+    pub work_hours_only: bool,
+}
+
+impl Default for CountdownConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            format: "Next: {title} in {rel}".to_string(),
+            now_format: "Now: {title}".to_string(),
+            within_minutes: None,
+            work_hours_only: false,
+        }
+    }
+}
+
+/// Working hours bounding the availability grid, free time and (optionally) the countdown,
+/// e.g. `{"start": "09:30", "end": "17:30", "days": {"fri": {"start": "09:30", "end": "13:00"}, "sun": null}}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkHoursConfig {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    /// Hours of particular weekdays; null marks a day off (Saturday and Sunday unless given)
+    #[serde(deserialize_with = "work_days")]
+    pub days: HashMap<Weekday, Option<WorkDay>>,
+}
+
+/// The days given in the config over the weekend off, so naming Friday keeps Saturday free
+fn work_days<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<HashMap<Weekday, Option<WorkDay>>, D::Error> {
+    let mut days = WorkHoursConfig::default().days;
+    days.extend(HashMap::<Weekday, Option<WorkDay>>::deserialize(deserializer)?);
+    Ok(days)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WorkDay {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl Default for WorkHoursConfig {
+    fn default() -> Self {
+        Self {
+            start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+            days: HashMap::from([(Weekday::Sat, None), (Weekday::Sun, None)]),
+        }
+    }
+}
+
+impl WorkHoursConfig {
+    /// Start and end of work on `weekday`, or None on a day off
+    pub fn on(&self, weekday: Weekday) -> Option<(NaiveTime, NaiveTime)> {
+        match self.days.get(&weekday) {
+            Some(Some(day)) => Some((day.start, day.end)),
+            Some(None) => None,
+            None => Some((self.start, self.end)),
+        }
+    }
+
+    /// Whether `time` on `date` falls within working hours
+    pub fn contains(&self, date: NaiveDate, time: NaiveTime) -> bool {
+        self.on(date.weekday()).is_some_and(|(start, end)| (start..end).contains(&time))
+    }
+
+    /// Hours the availability grid covers for `weekdays`: their working hours with an hour
+    /// either side, as a range of starting hours (8..20 for the default 9:00-18:00)
+    pub fn grid_hours(&self, weekdays: impl IntoIterator<Item = Weekday>) -> Range<u32> {
+        let days: Vec<(NaiveTime, NaiveTime)> = weekdays.into_iter().filter_map(|w| self.on(w)).collect();
+        let start = days.iter().map(|(start, _)| start.hour()).min().unwrap_or(self.start.hour());
+        let end = days
+            .iter()
+            .map(|(_, end)| end.hour() + u32::from(end.minute() > 0))
+            .max()
+            .unwrap_or(self.end.hour());
+        start.saturating_sub(1)..(end + 2).min(24)
+    }
+}
+
+/// First day of the week used by the calendar grid and week numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    #[default]
+    Monday,
+    Sunday,
+}
+
+impl WeekStart {
+    pub fn first_weekday(self) -> Weekday {
+        match self {
+            WeekStart::Monday => Weekday::Mon,
+            WeekStart::Sunday => Weekday::Sun,
+        }
+    }
+
+    /// Days the weekday is positioned after the start of the week (0-6)
+    pub fn days_from_start(self, weekday: Weekday) -> u32 {
+        match self {
+            WeekStart::Monday => weekday.num_days_from_monday(),
+            WeekStart::Sunday => weekday.num_days_from_sunday(),
+        }
+    }
+
+    /// First day of the week containing the given date
+    pub fn start_of_week(self, date: NaiveDate) -> NaiveDate {
+        date - Duration::days(self.days_from_start(date.weekday()) as i64)
+    }
+
+    /// ISO week number of the week containing the date.
+    /// Sunday-start weeks take the number of the ISO week their Monday falls in.
+    pub fn week_number(self, date: NaiveDate) -> u32 {
+        let monday = self.start_of_week(date) + Duration::days(self.days_from_start(Weekday::Mon) as i64);
+        monday.iso_week().week()
+    }
+
+    /// Week label with ISO date, e.g. "W07 · 2026-02-10"
+    pub fn week_label(self, date: NaiveDate) -> String {
+        format!("W{:02} \u{b7} {}", self.week_number(date), date.format("%Y-%m-%d"))
+    }
+}
+
+/// How times of day are written, e.g. `{"clock": "12h"}` or `{"time": "%H.%M"}`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimeFormatConfig {
+    pub clock: Clock,
+    /// strftime pattern for times of day instead of the clock's ("%H:%M" or "%-I:%M%P")
+    pub time: Option<String>,
+    /// strftime pattern for the hour labels of the week view ("%H:00" or "%-I%P")
+    pub hour: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Clock {
+    #[default]
+    #[serde(rename = "24h")]
+    TwentyFourHour,
+    #[serde(rename = "12h")]
+    TwelveHour,
+}
+
+impl TimeFormatConfig {
+    /// A time of day, e.g. "14:30" or "2:30pm"
+    pub fn time(&self, time: NaiveTime) -> String {
+        let pattern = match self.clock {
+            Clock::TwentyFourHour => "%H:%M",
+            Clock::TwelveHour => "%-I:%M%P",
+        };
+        time.format(self.time.as_deref().unwrap_or(pattern)).to_string()
+    }
+
+    /// An hour as a label, e.g. "14:00" or "2pm"
+    pub fn hour(&self, hour: u32) -> String {
+        let pattern = match self.clock {
+            Clock::TwentyFourHour => "%H:00",
+            Clock::TwelveHour => "%-I%P",
+        };
+        let time = NaiveTime::from_hms_opt(hour % 24, 0, 0).unwrap_or_default();
+        time.format(self.hour.as_deref().unwrap_or(pattern)).to_string()
+    }
+
+    /// An hour in two columns, e.g. "14" or " 2", for the availability grid
+    pub fn short_hour(&self, hour: u32) -> String {
+        match self.clock {
+            Clock::TwentyFourHour => format!("{:2}", hour),
+            Clock::TwelveHour => format!("{:2}", (hour + 11) % 12 + 1),
+        }
+    }
+
+    /// Error for a strftime override chrono can't format a time of day with, such as one
+    /// asking for the date or the zone, which would otherwise fail on every render
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        for pattern in [&self.time, &self.hour].into_iter().flatten() {
+            let mut sample = String::new();
+            if write!(sample, "{}", NaiveTime::MIN.format(pattern)).is_err() {
+                return Err(format!("time format \"{}\" is not a valid strftime pattern for a time", pattern));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Google Calendar configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoogleConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    #[serde(default = "default_calendar_id")]
+    pub calendar_id: String,
+    /// Calendars of the account to show, by id as listed by `:calendars google`;
+    /// defaults to just `calendar_id`
+    #[serde(default)]
+    pub calendar_ids: Vec<String>,
+    /// Let Google parse `:add` lines for Google calendars (its own quick add)
+    #[serde(default)]
+    pub quick_add: bool,
+    /// Name of `calendar_id` in calendar pickers and `@calendar` prefixes; defaults to
+    /// its name in the calendar list
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl GoogleConfig {
+    /// Ids of the calendars to fetch
+    pub fn shown_calendar_ids(&self) -> Vec<String> {
+        if self.calendar_ids.is_empty() {
+            vec![self.calendar_id.clone()]
+        } else {
+            self.calendar_ids.clone()
+        }
+    }
+}
+
+/// iCloud Calendar configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ICloudConfig {
+    pub apple_id: String,
+    pub app_password: String,
+}
+
+/// Microsoft identity platform app registration; it must allow public client flows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutlookConfig {
+    pub client_id: String,
+    /// Directory (tenant) id or domain; "common" lets any work or personal account sign in
+    #[serde(default = "default_tenant")]
+    pub tenant: String,
+}
+
+fn default_tenant() -> String {
+    "common".to_string()
+}
+
+fn default_calendar_id() -> String {
+    "primary".to_string()
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct StoredTokens {
+    pub google: Option<GoogleTokens>,
+    pub icloud: Option<ICloudTokens>,
+    #[serde(default)]
+    pub outlook: Option<OutlookTokens>,
+    /// Tokens of the `google_account` providers, by account
+    #[serde(default)]
+    pub google_accounts: BTreeMap<String, GoogleTokens>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoogleTokens {
+    pub tokens: TokenInfo,
+    pub stored_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutlookTokens {
+    pub tokens: GraphToken,
+    pub stored_at: DateTime<Utc>,
+}
+
+/// Stored calendar entry with URL and optional display name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredCalendar {
+    pub url: String,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Unchecked in the calendar picker, so not fetched
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ICloudTokens {
+    /// Legacy field for backwards compatibility
+    #[serde(default)]
+    pub calendar_urls: Vec<String>,
+    /// New field with calendar names
+    #[serde(default)]
+    pub calendars: Vec<StoredCalendar>,
+    pub stored_at: DateTime<Utc>,
+}
+
+impl Config {
+    /// Configured backends in order, defaulting to Google and iCloud, plus Outlook when
+    /// its section is filled in
+    pub fn providers(&self) -> Vec<ProviderConfig> {
+        if self.providers.is_empty() {
+            let mut providers = vec![ProviderConfig::Google, ProviderConfig::Caldav];
+            if self.outlook.is_some() {
+                providers.push(ProviderConfig::Outlook { name: None, panel: default_shared_panel() });
+            }
+            providers
+        } else {
+            self.providers.clone()
+        }
+    }
+
+    pub fn config_dir() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("calendarchy")
+    }
+
+    pub fn config_path() -> PathBuf {
+        Self::config_dir().join("config.json")
+    }
+
+    pub fn token_path() -> PathBuf {
+        Self::config_dir().join("tokens.json")
+    }
+
+    pub fn load() -> Result<Config> {
+        let path = Self::config_path();
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let config: Config = serde_json::from_str(&content)?;
+        Ok(config)
+    }
+
+    pub fn ensure_config_dir() -> Result<()> {
+        let dir = Self::config_dir();
+        if !dir.exists() {
+            fs::create_dir_all(&dir)?;
+        }
+        Ok(())
+    }
+}
+
+/// Save Google tokens
+pub fn save_google_tokens(tokens: &TokenInfo) -> Result<()> {
+    Config::ensure_config_dir()?;
+
+    let mut stored = load_all_tokens().unwrap_or_default();
+
+    stored.google = Some(GoogleTokens {
+        tokens: tokens.clone(),
+        stored_at: Utc::now(),
+    });
+
+    save_all_tokens(&stored)
+}
+
+/// Save Outlook tokens
+pub fn save_outlook_tokens(tokens: &GraphToken) -> Result<()> {
+    Config::ensure_config_dir()?;
+
+    let mut stored = load_all_tokens().unwrap_or_default();
+
+    stored.outlook = Some(OutlookTokens {
+        tokens: tokens.clone(),
+        stored_at: Utc::now(),
+    });
+
+    save_all_tokens(&stored)
+}
+
+/// Save the tokens of a `google_account` provider
+pub fn save_google_account_tokens(account: &str, tokens: &TokenInfo) -> Result<()> {
+    Config::ensure_config_dir()?;
+
+    let mut stored = load_all_tokens().unwrap_or_default();
+    stored.google_accounts.insert(account.to_string(), GoogleTokens {
+        tokens: tokens.clone(),
+        stored_at: Utc::now(),
+    });
+
+    save_all_tokens(&stored)
+}
+
+/// Save iCloud discovery info
+pub fn save_icloud_tokens(calendars: &[StoredCalendar]) -> Result<()> {
+    Config::ensure_config_dir()?;
+
+    let mut stored = load_all_tokens().unwrap_or_default();
+
+    stored.icloud = Some(ICloudTokens {
+        calendar_urls: Vec::new(), // Legacy field, keep empty
+        calendars: calendars.to_vec(),
+        stored_at: Utc::now(),
+    });
+
+    save_all_tokens(&stored)
+}
+
+fn save_all_tokens(stored: &StoredTokens) -> Result<()> {
+    let path = Config::token_path();
+    let json = serde_json::to_string_pretty(stored)?;
+    fs::write(&path, &json)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    }
+
+    Ok(())
+}
+
+fn load_all_tokens() -> Result<StoredTokens> {
+    let path = Config::token_path();
+    if !path.exists() {
+        return Ok(StoredTokens::default());
+    }
+
+    let content = fs::read_to_string(&path)?;
+    serde_json::from_str(&content).map_err(|e| CalendarchyError::corrupted_file(&path, e))
+}
+
+/// Load Google tokens
+pub fn load_google_tokens() -> Result<Option<TokenInfo>> {
+    let stored = load_all_tokens()?;
+    Ok(stored.google.map(|g| g.tokens))
+}
+
+/// Load Outlook tokens
+pub fn load_outlook_tokens() -> Result<Option<GraphToken>> {
+    let stored = load_all_tokens()?;
+    Ok(stored.outlook.map(|o| o.tokens))
+}
+
+/// Load the tokens of the `google_account` providers, by account
+pub fn load_google_account_tokens() -> Result<BTreeMap<String, TokenInfo>> {
+    let stored = load_all_tokens()?;
+    Ok(stored.google_accounts.into_iter().map(|(account, g)| (account, g.tokens)).collect())
+}
+
+/// Load iCloud discovery info
+pub fn load_icloud_tokens() -> Result<Option<ICloudTokens>> {
+    let stored = load_all_tokens()?;
+    Ok(stored.icloud)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_week_start_defaults_to_monday() {
+        let config: Config = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.week_start, WeekStart::Monday);
+
+        let config: Config = serde_json::from_str(r#"{"week_start": "sunday"}"#).unwrap();
+        assert_eq!(config.week_start, WeekStart::Sunday);
+    }
+
+    #[test]
+    fn test_google_calendar_ids_default_to_calendar_id() {
+        let config: GoogleConfig = serde_json::from_str(r#"{"client_id": "id", "client_secret": "secret"}"#).unwrap();
+        assert_eq!(config.shown_calendar_ids(), vec!["primary"]);
+
+        let config: GoogleConfig = serde_json::from_str(
+            r#"{"client_id": "id", "client_secret": "secret", "calendar_ids": ["primary", "team@group.calendar.google.com"]}"#,
+        ).unwrap();
+        assert_eq!(config.shown_calendar_ids(), vec!["primary", "team@group.calendar.google.com"]);
+    }
+
+    #[test]
+    fn test_providers_config() {
+        let config: Config = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.providers(), vec![ProviderConfig::Google, ProviderConfig::Caldav]);
+
+        let config: Config = serde_json::from_str(
+            r#"{"providers": [
+                {"type": "google"},
+                {"type": "ics_feed", "url": "webcal://example.com/team.ics", "panel": "google"},
+                {"type": "local_dir", "path": "/tmp/ics", "name": "Exports"},
+                {"type": "google_shared", "calendar_id": "manager@example.com"},
+                {"type": "outlook", "panel": "icloud"}
+            ]}"#,
+        ).unwrap();
+        assert_eq!(config.providers(), vec![
+            ProviderConfig::Google,
+            ProviderConfig::IcsFeed { url: "webcal://example.com/team.ics".to_string(), name: None, panel: EventSource::Google },
+            ProviderConfig::LocalDir { path: PathBuf::from("/tmp/ics"), name: Some("Exports".to_string()), panel: EventSource::ICloud },
+            ProviderConfig::GoogleShared { calendar_id: "manager@example.com".to_string(), name: None, panel: EventSource::Google },
+            ProviderConfig::Outlook { name: None, panel: EventSource::ICloud },
+        ]);
+
+        let config: Config = serde_json::from_str(r#"{"outlook": {"client_id": "app"}}"#).unwrap();
+        assert_eq!(config.outlook.as_ref().map(|o| o.tenant.as_str()), Some("common"));
+        assert_eq!(config.providers().last(), Some(&ProviderConfig::Outlook { name: None, panel: EventSource::Google }));
+    }
+
+    #[test]
+    fn test_dedup_config() {
+        let config: Config = serde_json::from_str("{}").unwrap();
+        assert!(config.dedup.enabled);
+        assert_eq!(config.dedup.prefer, DedupPrefer::Google);
+
+        let config: Config = serde_json::from_str(r#"{"dedup": {"prefer": "icloud"}}"#).unwrap();
+        assert!(config.dedup.enabled);
+        assert_eq!(config.dedup.prefer, DedupPrefer::ICloud);
+    }
+
+    #[test]
+    fn test_countdown_config_partial() {
+        let config: Config = serde_json::from_str(r#"{"countdown": {"within_minutes": 30}}"#).unwrap();
+        assert!(config.countdown.enabled);
+        assert_eq!(config.countdown.format, "Next: {title} in {rel}");
+        assert_eq!(config.countdown.within_minutes, Some(30));
+    }
+
+    #[test]
+    fn test_travel_lead_minutes() {
+        let travel: TravelConfig = serde_json::from_str(r#"{"minutes": 20, "places": {"office": 35, "Office Annex": 5}}"#).unwrap();
+        assert_eq!(travel.lead_minutes("Dentist, Main St 4"), Some(20));
+        assert_eq!(travel.lead_minutes("Office, 3rd floor"), Some(35));
+        assert_eq!(travel.lead_minutes("office annex"), Some(5));
+        assert_eq!(travel.lead_minutes("https://meet.google.com/abc-defg-hij"), None);
+        assert_eq!(travel.lead_minutes(""), None);
+        // Without a default only the listed places get a hint
+        let places_only = TravelConfig { minutes: None, ..travel };
+        assert_eq!(places_only.lead_minutes("Dentist, Main St 4"), None);
+        assert_eq!(places_only.lead_minutes("Office"), Some(35));
+    }
+
+    #[test]
+    fn test_time_format() {
+        let afternoon = NaiveTime::from_hms_opt(14, 5, 0).unwrap();
+        let default = TimeFormatConfig::default();
+        assert_eq!((default.time(afternoon).as_str(), default.hour(9).as_str(), default.short_hour(9).as_str()), ("14:05", "09:00", " 9"));
+
+        let twelve: TimeFormatConfig = serde_json::from_str(r#"{"clock": "12h"}"#).unwrap();
+        assert_eq!(twelve.time(afternoon), "2:05pm");
+        assert_eq!(twelve.time(NaiveTime::MIN), "12:00am");
+        assert_eq!((twelve.hour(14).as_str(), twelve.short_hour(14).as_str(), twelve.short_hour(12).as_str()), ("2pm", " 2", "12"));
+
+        let custom: TimeFormatConfig = serde_json::from_str(r#"{"time": "%H.%M", "hour": "%Hh"}"#).unwrap();
+        assert_eq!((custom.time(afternoon).as_str(), custom.hour(8).as_str()), ("14.05", "08h"));
+        assert!(custom.validate().is_ok());
+        let broken = TimeFormatConfig { time: Some("%Q".to_string()), ..custom.clone() };
+        assert!(broken.validate().is_err());
+        // Dates and zones can't be written for a bare time
+        for pattern in ["%Y %H:%M", "%H:%M %z", "%H:%M %Z"] {
+            let dated = TimeFormatConfig { time: Some(pattern.to_string()), ..custom.clone() };
+            assert!(dated.validate().is_err(), "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_work_hours() {
+        use Weekday::*;
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+
+        // The default keeps the grid at 8:00-20:00 over weekdays
+        let default = WorkHoursConfig::default();
+        assert_eq!(default.grid_hours([Mon, Tue, Wed, Thu, Fri, Sat, Sun]), 8..20);
+        assert_eq!(default.on(Sat), None);
+
+        let config: Config = serde_json::from_str(
+            r#"{"work_hours": {"start": "10:00", "end": "16:30", "days": {"fri": {"start": "07:00", "end": "12:00"}, "wed": null}}}"#,
+        )
+        .unwrap();
+        let hours = &config.work_hours;
+        assert_eq!(hours.on(Mon), Some((time(10, 0), time(16, 30))));
+        assert_eq!(hours.on(Fri), Some((time(7, 0), time(12, 0))));
+        assert_eq!(hours.on(Wed), None);
+        // Weekends stay off when `days` leaves them out, and work days once given
+        assert_eq!(hours.on(Sun), None);
+        let config: Config =
+            serde_json::from_str(r#"{"work_hours": {"days": {"sat": {"start": "10:00", "end": "14:00"}}}}"#).unwrap();
+        assert_eq!(config.work_hours.on(Sat), Some((time(10, 0), time(14, 0))));
+        assert_eq!(config.work_hours.on(Sun), None);
+        assert_eq!(hours.grid_hours([Mon, Tue]), 9..19);
+        assert_eq!(hours.grid_hours([Mon, Fri]), 6..19);
+
+        let friday = NaiveDate::from_ymd_opt(2026, 3, 20).unwrap();
+        assert!(hours.contains(friday, time(11, 59)));
+        assert!(!hours.contains(friday, time(12, 0)));
+        assert!(!hours.contains(friday - Duration::days(2), time(11, 0)));
+    }
+
+    #[test]
+    fn test_confirm_config_partial() {
+        let config: Config = serde_json::from_str(r#"{"confirm": {"accept": false, "decline": false}}"#).unwrap();
+        assert!(!config.confirm.accept);
+        assert!(!config.confirm.decline);
+        assert!(config.confirm.delete);
+        assert!(Config::default().confirm.accept);
+    }
+
+    #[test]
+    fn test_enter_action() {
+        let config: Config = serde_json::from_str(r#"{"enter_action": "join"}"#).unwrap();
+        assert_eq!(config.enter_action, EnterAction::Join);
+        assert_eq!(Config::default().enter_action, EnterAction::None);
+    }
+
+    #[test]
+    fn test_status_bar_segments() {
+        let config: Config = serde_json::from_str(r#"{"status_bar": ["clock", "pending", "selected_date"]}"#).unwrap();
+        assert_eq!(config.status_bar.0, vec![StatusSegment::Clock, StatusSegment::Pending, StatusSegment::SelectedDate]);
+        assert_eq!(Config::default().status_bar.0, vec![StatusSegment::Countdown]);
+        assert!(serde_json::from_str::<Config>(r#"{"status_bar": ["weather"]}"#).is_err());
+    }
+
+    #[test]
+    fn test_start_of_week() {
+        // 2026-02-10 is a Tuesday
+        assert_eq!(WeekStart::Monday.start_of_week(date(2026, 2, 10)), date(2026, 2, 9));
+        assert_eq!(WeekStart::Sunday.start_of_week(date(2026, 2, 10)), date(2026, 2, 8));
+        // Sunday itself
+        assert_eq!(WeekStart::Monday.start_of_week(date(2026, 2, 15)), date(2026, 2, 9));
+        assert_eq!(WeekStart::Sunday.start_of_week(date(2026, 2, 15)), date(2026, 2, 15));
+    }
+
+    #[test]
+    fn test_week_label() {
+        assert_eq!(WeekStart::Monday.week_label(date(2026, 2, 10)), "W07 \u{b7} 2026-02-10");
+        // Sunday belongs to the previous ISO week when weeks start on Monday...
+        assert_eq!(WeekStart::Monday.week_number(date(2026, 2, 15)), 7);
+        // ...and to the following one when weeks start on Sunday
+        assert_eq!(WeekStart::Sunday.week_number(date(2026, 2, 15)), 8);
+        // Year boundary: 2026-01-01 is in ISO week 1
+        assert_eq!(WeekStart::Monday.week_number(date(2026, 1, 1)), 1);
+        assert_eq!(WeekStart::Monday.week_number(date(2027, 1, 1)), 53);
+    }
+}
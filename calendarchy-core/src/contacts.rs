@@ -0,0 +1,168 @@
+//! Attendee name resolution from address book contacts (iCloud CardDAV or a local vCard dir)
+
+use crate::cache::DisplayEvent;
+use crate::error::{CalendarchyError, Result};
+use crate::utils::{name_from_email, sort_attendees};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Display names keyed by lowercase email, cached in ~/.cache/calendarchy/contacts.json
+#[derive(Debug, Default)]
+pub struct ContactBook {
+    by_email: HashMap<String, String>,
+}
+
+impl ContactBook {
+    fn cache_path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|p| p.join("calendarchy").join("contacts.json"))
+    }
+
+    /// Load the cached contacts; a missing cache yields an empty book
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::cache_path() else { return Ok(Self::default()) };
+        let Ok(json) = fs::read_to_string(&path) else { return Ok(Self::default()) };
+        let by_email = serde_json::from_str(&json).map_err(|e| CalendarchyError::corrupted_file(&path, e))?;
+        Ok(Self { by_email })
+    }
+
+    pub fn save(&self) {
+        let Some(path) = Self::cache_path() else { return };
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string(&self.by_email) {
+            let _ = fs::write(&path, json);
+        }
+    }
+
+    /// Add the contacts found in vCard data; returns how many emails were added or updated
+    pub fn add_vcards(&mut self, data: &str) -> usize {
+        let entries = parse_vcards(data);
+        let count = entries.len();
+        for (email, name) in entries {
+            self.by_email.insert(email, name);
+        }
+        count
+    }
+
+    /// Add every .vcf file in a directory
+    pub fn add_vcard_dir(&mut self, dir: &Path) -> Result<usize> {
+        let mut count = 0;
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("vcf")) {
+                count += self.add_vcards(&fs::read_to_string(&path)?);
+            }
+        }
+        Ok(count)
+    }
+
+    pub fn name_for(&self, email: &str) -> Option<&str> {
+        self.by_email.get(&email.to_lowercase()).map(String::as_str)
+    }
+
+    /// Replace attendee names that were only guessed from the email address
+    pub fn resolve_names(&self, event: &mut DisplayEvent) {
+        if self.by_email.is_empty() {
+            return;
+        }
+        let mut changed = false;
+        for attendee in &mut event.attendees {
+            let guessed = attendee.name.as_ref().is_none_or(|n| *n == name_from_email(&attendee.email));
+            if guessed && let Some(name) = self.name_for(&attendee.email) {
+                attendee.name = Some(name.to_string());
+                changed = true;
+            }
+        }
+        if changed {
+            sort_attendees(&mut event.attendees);
+        }
+    }
+}
+
+/// Extract (lowercase email, display name) pairs from one or more vCards
+pub fn parse_vcards(data: &str) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    let mut name: Option<String> = None;
+    let mut emails: Vec<String> = Vec::new();
+
+    for line in data.lines() {
+        let line = line.trim();
+        let Some((key, value)) = line.split_once(':') else { continue };
+        // Drop group prefixes ("item1.EMAIL") and parameters ("EMAIL;TYPE=work")
+        let key = key.split(';').next().unwrap_or(key);
+        let key = key.rsplit('.').next().unwrap_or(key).to_uppercase();
+        match key.as_str() {
+            "BEGIN" => {
+                name = None;
+                emails.clear();
+            }
+            "FN" if !value.trim().is_empty() => name = Some(value.trim().replace("\\,", ",")),
+            "EMAIL" if !value.trim().is_empty() => emails.push(value.trim().to_lowercase()),
+            "END" => {
+                if let Some(ref name) = name {
+                    entries.extend(emails.drain(..).map(|email| (email, name.clone())));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{AttendeeStatus, DisplayAttendee, EventId};
+    use chrono::{NaiveDate, NaiveTime};
+
+    const VCARDS: &str = "BEGIN:VCARD\r\n\
+VERSION:3.0\r\n\
+FN:John Smith\r\n\
+item1.EMAIL;type=INTERNET;type=pref:JSmith@example.com\r\n\
+EMAIL;type=INTERNET:john@home.example\r\n\
+END:VCARD\r\n\
+BEGIN:VCARD\r\n\
+VERSION:3.0\r\n\
+EMAIL:nameless@example.com\r\n\
+END:VCARD\r\n";
+
+    #[test]
+    fn test_parse_vcards() {
+        assert_eq!(
+            parse_vcards(VCARDS),
+            vec![
+                ("jsmith@example.com".to_string(), "John Smith".to_string()),
+                ("john@home.example".to_string(), "John Smith".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_names_only_replaces_guesses() {
+        let mut book = ContactBook::default();
+        book.add_vcards(VCARDS);
+
+        let attendee = |name: &str, email: &str| DisplayAttendee {
+            name: Some(name.to_string()),
+            email: email.to_string(),
+            status: AttendeeStatus::Accepted,
+            is_self: false,
+        };
+        let mut event = DisplayEvent {
+            id: EventId::Google { calendar_id: "c".to_string(), event_id: "e".to_string(), calendar_name: None, account: None },
+            title: "Sync".to_string(),
+            start_time: NaiveTime::from_hms_opt(10, 0, 0),
+            date: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+            attendees: vec![attendee("Jsmith", "jsmith@example.com"), attendee("Johnny", "john@home.example")],
+            ..Default::default()
+        };
+
+        book.resolve_names(&mut event);
+        assert_eq!(event.attendees[0].name.as_deref(), Some("John Smith"));
+        // Names provided by the calendar are kept
+        assert_eq!(event.attendees[1].name.as_deref(), Some("Johnny"));
+    }
+}
@@ -0,0 +1,664 @@
+use crate::cache::{AttendeeStatus, DisplayAttendee, DisplayEvent, EventId, EventKind};
+use crate::google;
+use crate::icloud::ICalEvent;
+use crate::outlook::{GraphAttendee, GraphEvent};
+use crate::utils::{extract_dial_in, extract_meeting_url, name_from_email, resolve_local_datetime, sort_attendees, MeetingPatterns};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, Utc};
+use serde_json::json;
+
+/// Convert a Google CalendarEvent to a DisplayEvent
+pub fn google_event_to_display(
+    event: google::types::CalendarEvent,
+    calendar_id: String,
+    calendar_name: Option<String>,
+    account: Option<String>,
+    meeting_patterns: &MeetingPatterns,
+) -> Option<DisplayEvent> {
+    // Rooms are listed as attendees; one that declined isn't booked. Their addresses are
+    // opaque ids, so a room is shown by name only, which listings leave out until the
+    // event is opened.
+    let rooms: Vec<String> = event.attendees.iter().flatten()
+        .filter(|a| a.resource == Some(true) && a.response_status.as_deref() != Some("declined"))
+        .filter_map(|a| a.display_name.clone())
+        .collect();
+    let mut attendees: Vec<DisplayAttendee> = event.attendees.as_ref().map(|atts| {
+        atts.iter()
+            .filter(|a| a.resource != Some(true))
+            .filter_map(|a| {
+                let email = a.email.clone()?;
+                let status = if a.organizer == Some(true) {
+                    AttendeeStatus::Organizer
+                } else {
+                    match a.response_status.as_deref() {
+                        Some("accepted") => AttendeeStatus::Accepted,
+                        Some("declined") => AttendeeStatus::Declined,
+                        Some("tentative") => AttendeeStatus::Tentative,
+                        _ => AttendeeStatus::NeedsAction,
+                    }
+                };
+                Some(DisplayAttendee {
+                    name: Some(a.display_name.clone().unwrap_or_else(|| name_from_email(&email))),
+                    email,
+                    status,
+                    is_self: a.is_self == Some(true),
+                })
+            })
+            .collect()
+    }).unwrap_or_default();
+    sort_attendees(&mut attendees);
+
+    let date = event.start_date()?;
+    Some(DisplayEvent {
+        id: EventId::Google {
+            calendar_id,
+            event_id: event.id.clone(),
+            calendar_name,
+            account,
+        },
+        ical_uid: event.ical_uid.clone(),
+        color: event.color.clone(),
+        title: event.title().to_string(),
+        start_time: event.start_time(),
+        end_time: event.end_time(),
+        date,
+        end_date: event.end_date().filter(|&end| end > date),
+        accepted: event.is_accepted(),
+        is_organizer: event.is_organizer(),
+        is_free: event.is_free(),
+        kind: event.kind(),
+        meeting_url: event.meeting_url(meeting_patterns),
+        dial_in: event.dial_in(),
+        description: event.description.clone(),
+        location: event.location.clone(),
+        attendees,
+        rooms,
+        partial: false,
+    })
+}
+
+/// Convert an iCloud ICalEvent to a DisplayEvent
+pub fn icloud_event_to_display(event: ICalEvent, calendar_name: Option<String>, meeting_patterns: &MeetingPatterns) -> DisplayEvent {
+    let rooms: Vec<String> = event.attendees.iter()
+        .filter(|a| a.is_room && a.partstat != "DECLINED")
+        .map(|a| a.name.clone().unwrap_or_else(|| a.email.clone()))
+        .collect();
+    let mut attendees: Vec<DisplayAttendee> = event.attendees.iter()
+        .filter(|a| !a.is_room)
+        .map(|a| {
+            let status = if a.is_organizer {
+                AttendeeStatus::Organizer
+            } else {
+                match a.partstat.as_str() {
+                    "ACCEPTED" => AttendeeStatus::Accepted,
+                    "DECLINED" => AttendeeStatus::Declined,
+                    "TENTATIVE" => AttendeeStatus::Tentative,
+                    _ => AttendeeStatus::NeedsAction,
+                }
+            };
+            DisplayAttendee {
+                name: Some(a.name.clone().unwrap_or_else(|| name_from_email(&a.email))),
+                email: a.email.clone(),
+                status,
+                is_self: false,
+            }
+        })
+        .collect();
+    sort_attendees(&mut attendees);
+
+    // For iCloud, if there are no attendees, the user created the event
+    let is_organizer = attendees.is_empty();
+    let date = event.start_date();
+
+    DisplayEvent {
+        id: EventId::ICloud {
+            calendar_url: event.calendar_url.clone(),
+            event_uid: event.uid.clone(),
+            etag: event.etag.clone(),
+            calendar_name,
+            occurrence: event.recurrence_id.as_ref().map(|id| id.ical_value()),
+        },
+        ical_uid: Some(event.uid.clone()),
+        color: event.color.clone(),
+        title: event.title().to_string(),
+        start_time: event.start_time(),
+        end_time: event.end_time(),
+        date,
+        end_date: Some(event.end_date()).filter(|&end| end > date),
+        accepted: event.accepted,
+        is_organizer,
+        is_free: event.is_free(),
+        kind: EventKind::Default,
+        meeting_url: event.meeting_url(meeting_patterns),
+        dial_in: event.dial_in(),
+        description: event.description.clone(),
+        location: event.location.clone(),
+        attendees,
+        rooms,
+        partial: false,
+    }
+}
+
+/// Convert an Outlook event from Microsoft Graph to a read-only DisplayEvent; cancelled
+/// events are dropped
+pub fn outlook_event_to_display(event: GraphEvent, calendar_name: &str, meeting_patterns: &MeetingPatterns) -> Option<DisplayEvent> {
+    if event.is_cancelled {
+        return None;
+    }
+    let is_room = |a: &GraphAttendee| a.attendee_type.as_deref() == Some("resource");
+    let rooms: Vec<String> = event.attendees.iter()
+        .filter(|a| is_room(a))
+        .filter(|a| a.status.as_ref().and_then(|s| s.response.as_deref()) != Some("declined"))
+        .filter_map(|a| a.email_address.name.clone().or_else(|| a.email_address.address.clone()))
+        .collect();
+    let organizer = event.organizer.as_ref().and_then(|o| o.email_address.address.clone());
+    let mut attendees: Vec<DisplayAttendee> = event.attendees.iter()
+        .filter(|a| !is_room(a))
+        .filter_map(|a| {
+            let email = a.email_address.address.clone()?;
+            let status = if organizer.as_deref().is_some_and(|o| o.eq_ignore_ascii_case(&email)) {
+                AttendeeStatus::Organizer
+            } else {
+                match a.status.as_ref().and_then(|s| s.response.as_deref()) {
+                    Some("accepted") => AttendeeStatus::Accepted,
+                    Some("declined") => AttendeeStatus::Declined,
+                    Some("tentativelyAccepted") => AttendeeStatus::Tentative,
+                    _ => AttendeeStatus::NeedsAction,
+                }
+            };
+            Some(DisplayAttendee {
+                name: Some(a.email_address.name.clone().unwrap_or_else(|| name_from_email(&email))),
+                email,
+                status,
+                is_self: false,
+            })
+        })
+        .collect();
+    sort_attendees(&mut attendees);
+
+    let location = event.location.as_ref()
+        .and_then(|l| l.display_name.clone())
+        .filter(|l| !l.is_empty());
+    let description = event.body.as_ref()
+        .and_then(|b| b.content.as_deref())
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(str::to_string);
+    let meeting_url = event.online_meeting.as_ref().and_then(|m| m.join_url.clone())
+        .or_else(|| location.as_deref().and_then(|l| extract_meeting_url(l, meeting_patterns)))
+        .or_else(|| description.as_deref().and_then(|d| extract_meeting_url(d, meeting_patterns)));
+
+    let date = event.start_date()?;
+    Some(DisplayEvent {
+        id: EventId::Feed {
+            location: "outlook".to_string(),
+            event_uid: event.id.clone(),
+            calendar_name: Some(calendar_name.to_string()),
+        },
+        ical_uid: event.ical_uid.clone(),
+        color: None,
+        title: event.title().to_string(),
+        start_time: event.start_time(),
+        end_time: event.end_time(),
+        date,
+        end_date: event.end_date().filter(|&end| end > date),
+        accepted: event.is_accepted(),
+        is_organizer: event.is_organizer,
+        is_free: event.is_free(),
+        kind: EventKind::Default,
+        meeting_url,
+        dial_in: description.as_deref().and_then(extract_dial_in),
+        description,
+        location,
+        attendees,
+        rooms,
+        partial: false,
+    })
+}
+
+/// Start and end instants of a timed event. Events without an end last an hour; an end
+/// at midnight, or at or before the start of an event ending on its own day, falls on
+/// the day after.
+fn event_span(event: &DisplayEvent) -> Option<(DateTime<Local>, DateTime<Local>)> {
+    let start_time = event.start_time?;
+    let start = resolve_local_datetime(&Local, event.date.and_time(start_time))?;
+    let end = match event.end_time {
+        Some(end) => {
+            let last_day = event.end_date.unwrap_or(event.date);
+            let next_day = end == NaiveTime::MIN || (event.end_date.is_none() && end <= start_time);
+            let end_date = if next_day { last_day + Duration::days(1) } else { last_day };
+            resolve_local_datetime(&Local, end_date.and_time(end))?
+        }
+        None => start + Duration::hours(1),
+    };
+    Some((start, end))
+}
+
+/// All-day events span [date, last day + 1)
+fn all_day_span(event: &DisplayEvent) -> (NaiveDate, NaiveDate) {
+    (event.date, event.end_date.unwrap_or(event.date) + Duration::days(1))
+}
+
+/// Google API `start` and `end` of an event, also the body that moves one
+pub fn google_event_times(event: &DisplayEvent) -> serde_json::Value {
+    match event_span(event) {
+        Some((start, end)) => json!({
+            "start": { "dateTime": start.to_rfc3339() },
+            "end": { "dateTime": end.to_rfc3339() },
+        }),
+        None => {
+            let (start, end) = all_day_span(event);
+            json!({ "start": { "date": start.to_string() }, "end": { "date": end.to_string() } })
+        }
+    }
+}
+
+/// Google API body creating a copy of an event (attendees are not invited)
+pub fn display_to_google_event(event: &DisplayEvent) -> serde_json::Value {
+    let times = google_event_times(event);
+    json!({
+        "summary": event.title,
+        "location": event.location,
+        "description": event.description,
+        "start": times["start"],
+        "end": times["end"],
+        "transparency": if event.is_free { "transparent" } else { "opaque" },
+    })
+}
+
+/// DTSTART and DTEND lines of an event, in UTC for timed events
+fn ical_time_lines(event: &DisplayEvent) -> [String; 2] {
+    match event_span(event) {
+        Some((start, end)) => [
+            format!("DTSTART:{}", start.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ")),
+            format!("DTEND:{}", end.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ")),
+        ],
+        None => {
+            let (start, end) = all_day_span(event);
+            [
+                format!("DTSTART;VALUE=DATE:{}", start.format("%Y%m%d")),
+                format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")),
+            ]
+        }
+    }
+}
+
+/// iCalendar document creating a copy of an event under a new UID (attendees are not invited)
+pub fn display_to_ical(event: &DisplayEvent, uid: &str) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//calendarchy//EN".to_string(),
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}", uid),
+        format!("DTSTAMP:{}", Utc::now().format("%Y%m%dT%H%M%SZ")),
+    ];
+    lines.extend(ical_time_lines(event));
+    lines.push(format!("SUMMARY:{}", escape_ical(&event.title)));
+    if let Some(ref location) = event.location {
+        lines.push(format!("LOCATION:{}", escape_ical(location)));
+    }
+    if let Some(ref description) = event.description {
+        lines.push(format!("DESCRIPTION:{}", escape_ical(description)));
+    }
+    if let Some(ref url) = event.meeting_url {
+        lines.push(format!("URL:{}", url));
+    }
+    if event.is_free {
+        lines.push("TRANSP:TRANSPARENT".to_string());
+    }
+    lines.push("END:VEVENT".to_string());
+    lines.push("END:VCALENDAR".to_string());
+    lines.join("\r\n") + "\r\n"
+}
+
+/// Rewrite the times of a stored iCalendar document to those of `event` (already moved),
+/// leaving everything else as it was. Recurring events are refused: the document holds
+/// the whole series, not the one occurrence.
+pub fn reschedule_ical(ical: &str, event: &DisplayEvent) -> Result<String, String> {
+    let mut lines = Vec::new();
+    let mut in_event = false;
+    let mut skipping = false;
+    for line in ical.lines() {
+        // Folded continuation lines belong to the property before them
+        if line.starts_with([' ', '\t']) {
+            if !skipping {
+                lines.push(line.to_string());
+            }
+            continue;
+        }
+        skipping = false;
+        let name = line.split([':', ';']).next().unwrap_or_default();
+        match name {
+            "BEGIN" if line == "BEGIN:VEVENT" => in_event = true,
+            "END" if line == "END:VEVENT" => in_event = false,
+            "RRULE" | "RECURRENCE-ID" if in_event => {
+                return Err("Moving recurring iCloud events is not supported".to_string());
+            }
+            "DTSTART" if in_event => {
+                lines.extend(ical_time_lines(event));
+                skipping = true;
+                continue;
+            }
+            "DTEND" | "DURATION" if in_event => {
+                skipping = true;
+                continue;
+            }
+            _ => {}
+        }
+        lines.push(line.to_string());
+    }
+    Ok(lines.join("\r\n") + "\r\n")
+}
+
+/// Escape text for an iCalendar property value
+fn escape_ical(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::icloud;
+
+    fn make_google_event(id: &str, summary: &str, date: NaiveDate) -> google::types::CalendarEvent {
+        google::types::CalendarEvent {
+            id: id.to_string(),
+            summary: Some(summary.to_string()),
+            start: google::types::EventDateTime {
+                date: Some(date),
+                date_time: None,
+                time_zone: None,
+            },
+            end: google::types::EventDateTime {
+                date: Some(date + chrono::Duration::days(1)),
+                date_time: None,
+                time_zone: None,
+            },
+            location: None,
+            description: None,
+            status: None,
+            transparency: None,
+            attendees: None,
+            conference_data: None,
+            hangout_link: None,
+            ical_uid: None,
+            color_id: None,
+            event_type: None,
+            color: None,
+        }
+    }
+
+    #[test]
+    fn test_google_event_to_display_basic() {
+        let event = make_google_event("event-123", "Team Meeting", NaiveDate::from_ymd_opt(2026, 1, 15).unwrap());
+        let result = google_event_to_display(event, "cal-id".to_string(), Some("Work".to_string()), Some("me@work.com".to_string()), &MeetingPatterns::default());
+
+        assert!(result.is_some());
+        let display = result.unwrap();
+        assert_eq!(display.title, "Team Meeting");
+        assert_eq!(display.date, NaiveDate::from_ymd_opt(2026, 1, 15).unwrap());
+        assert!(matches!(display.id, EventId::Google { account: Some(ref account), .. } if account == "me@work.com"));
+    }
+
+    #[test]
+    fn test_google_event_to_display_with_attendees() {
+        let mut event = make_google_event("event-456", "Review", NaiveDate::from_ymd_opt(2026, 2, 1).unwrap());
+        event.attendees = Some(vec![
+            google::types::Attendee {
+                email: Some("organizer@example.com".to_string()),
+                display_name: Some("Organizer".to_string()),
+                response_status: Some("accepted".to_string()),
+                is_self: Some(false),
+                organizer: Some(true),
+                resource: None,
+            },
+            google::types::Attendee {
+                email: Some("attendee@example.com".to_string()),
+                display_name: None,
+                response_status: Some("tentative".to_string()),
+                is_self: Some(true),
+                organizer: None,
+                resource: None,
+            },
+            google::types::Attendee {
+                email: Some("c_1884@resource.calendar.google.com".to_string()),
+                display_name: Some("Room 4.1 (8)".to_string()),
+                response_status: Some("accepted".to_string()),
+                is_self: None,
+                organizer: None,
+                resource: Some(true),
+            },
+        ]);
+
+        let result = google_event_to_display(event, "cal-id".to_string(), None, None, &MeetingPatterns::default());
+        assert!(result.is_some());
+        let display = result.unwrap();
+
+        // The room is booked, not a participant
+        assert_eq!(display.attendees.len(), 2);
+        assert_eq!(display.rooms, vec!["Room 4.1 (8)"]);
+        // A listing leaves out names, and the room's address means nothing to anyone
+        let mut listed = make_google_event("event-789", "Sync", NaiveDate::from_ymd_opt(2026, 2, 1).unwrap());
+        listed.attendees = Some(vec![google::types::Attendee {
+            email: Some("c_1884@resource.calendar.google.com".to_string()),
+            display_name: None,
+            response_status: Some("accepted".to_string()),
+            is_self: None,
+            organizer: None,
+            resource: Some(true),
+        }]);
+        let listed = google_event_to_display(listed, "cal-id".to_string(), None, None, &MeetingPatterns::default()).unwrap();
+        assert!(listed.rooms.is_empty());
+        // Organizer should be sorted first
+        assert_eq!(display.attendees[0].status, AttendeeStatus::Organizer);
+        assert_eq!(display.attendees[1].status, AttendeeStatus::Tentative);
+    }
+
+    #[test]
+    fn test_icloud_event_to_display_basic() {
+        let event = ICalEvent {
+            uid: "uid-123".to_string(),
+            summary: Some("Personal Event".to_string()),
+            dtstart: icloud::EventTime::Date(NaiveDate::from_ymd_opt(2026, 1, 20).unwrap()),
+            dtend: Some(icloud::EventTime::Date(NaiveDate::from_ymd_opt(2026, 1, 21).unwrap())),
+            location: None,
+            description: None,
+            url: None,
+            attendees: vec![],
+            accepted: true,
+            transp: None,
+            color: None,
+            calendar_url: "https://caldav.example.com/cal".to_string(),
+            etag: Some("etag-abc".to_string()),
+            recurrence: None,
+            recurrence_id: None,
+        };
+
+        let trip = ICalEvent {
+            dtend: Some(icloud::EventTime::Date(NaiveDate::from_ymd_opt(2026, 1, 23).unwrap())),
+            ..event.clone()
+        };
+        let at = |day, hour, minute| {
+            let time = NaiveDate::from_ymd_opt(2026, 1, day).unwrap().and_hms_opt(hour, minute, 0).unwrap();
+            icloud::EventTime::DateTime(time.and_local_timezone(Local).unwrap().with_timezone(&Utc))
+        };
+        let late = ICalEvent { dtstart: at(20, 23, 0), dtend: Some(at(21, 0, 30)), ..event.clone() };
+        let shift = ICalEvent { dtstart: at(20, 8, 0), dtend: Some(at(21, 12, 0)), ..event.clone() };
+        let display = icloud_event_to_display(event, Some("Personal".to_string()), &MeetingPatterns::default());
+
+        assert_eq!(display.title, "Personal Event");
+        assert_eq!(display.date, NaiveDate::from_ymd_opt(2026, 1, 20).unwrap());
+        assert_eq!(display.end_date, None); // DTEND is exclusive
+        assert!(display.is_organizer); // No attendees means organizer
+        assert!(matches!(display.id, EventId::ICloud { .. }));
+
+        let trip = icloud_event_to_display(trip, None, &MeetingPatterns::default());
+        assert_eq!(trip.end_date, NaiveDate::from_ymd_opt(2026, 1, 22));
+
+        // A meeting past midnight is listed on its evening only; one lasting over a day on both
+        let late = icloud_event_to_display(late, None, &MeetingPatterns::default());
+        assert_eq!(late.end_date, None);
+        assert_eq!(late.duration_minutes(), Some(90));
+        let shift = icloud_event_to_display(shift, None, &MeetingPatterns::default());
+        assert_eq!(shift.end_date, NaiveDate::from_ymd_opt(2026, 1, 21));
+    }
+
+    #[test]
+    fn test_icloud_event_to_display_with_attendees() {
+        let event = ICalEvent {
+            uid: "uid-456".to_string(),
+            summary: Some("Meeting".to_string()),
+            dtstart: icloud::EventTime::Date(NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()),
+            dtend: None,
+            location: None,
+            description: None,
+            url: None,
+            attendees: vec![
+                icloud::ICalAttendee {
+                    email: "person@example.com".to_string(),
+                    name: Some("Person".to_string()),
+                    partstat: "ACCEPTED".to_string(),
+                    is_organizer: false,
+                    is_room: false,
+                },
+                icloud::ICalAttendee {
+                    email: "room4@example.com".to_string(),
+                    name: Some("Room 4".to_string()),
+                    partstat: "ACCEPTED".to_string(),
+                    is_organizer: false,
+                    is_room: true,
+                },
+            ],
+            accepted: true,
+            transp: None,
+            color: None,
+            calendar_url: "https://caldav.example.com/cal".to_string(),
+            etag: None,
+            recurrence: None,
+            recurrence_id: None,
+        };
+
+        let display = icloud_event_to_display(event, None, &MeetingPatterns::default());
+
+        assert!(!display.is_organizer); // Has attendees, not organizer
+        assert_eq!(display.attendees.len(), 1);
+        assert_eq!(display.rooms, vec!["Room 4".to_string()]);
+        assert_eq!(display.attendees[0].status, AttendeeStatus::Accepted);
+    }
+
+    #[test]
+    fn test_outlook_event_to_display() {
+        let event: GraphEvent = serde_json::from_str(r#"{
+            "id": "AAMk1",
+            "iCalUId": "040000008200E0",
+            "subject": "Offsite",
+            "start": {"dateTime": "2026-04-10T00:00:00.0000000", "timeZone": "UTC"},
+            "end": {"dateTime": "2026-04-11T00:00:00.0000000", "timeZone": "UTC"},
+            "isAllDay": true,
+            "showAs": "busy",
+            "responseStatus": {"response": "tentativelyAccepted"},
+            "location": {"displayName": ""},
+            "onlineMeeting": {"joinUrl": "https://teams.microsoft.com/l/meetup-join/123"},
+            "body": {"contentType": "text", "content": "Agenda in the doc\r\n"},
+            "organizer": {"emailAddress": {"name": "Lead", "address": "lead@example.com"}},
+            "attendees": [
+                {"type": "required", "status": {"response": "accepted"}, "emailAddress": {"name": "Lead", "address": "lead@example.com"}},
+                {"type": "optional", "status": {"response": "none"}, "emailAddress": {"address": "dev@example.com"}},
+                {"type": "resource", "status": {"response": "accepted"}, "emailAddress": {"name": "Room 4", "address": "room4@example.com"}}
+            ]
+        }"#).unwrap();
+
+        let display = outlook_event_to_display(event, "Work 365", &MeetingPatterns::default()).unwrap();
+
+        assert_eq!(display.date, NaiveDate::from_ymd_opt(2026, 4, 10).unwrap());
+        assert!(display.is_all_day());
+        assert!(!display.accepted);
+        assert_eq!(display.location, None);
+        assert_eq!(display.description.as_deref(), Some("Agenda in the doc"));
+        assert_eq!(display.meeting_url.as_deref(), Some("https://teams.microsoft.com/l/meetup-join/123"));
+        assert_eq!(display.rooms, vec!["Room 4".to_string()]);
+        assert_eq!(display.attendees.len(), 2);
+        assert_eq!(display.attendees[0].status, AttendeeStatus::Organizer);
+        assert!(matches!(display.id, EventId::Feed { calendar_name: Some(ref n), .. } if n == "Work 365"));
+    }
+
+    fn copy_source(start: &str, end: Option<&str>) -> DisplayEvent {
+        DisplayEvent {
+            id: EventId::Google { calendar_id: "primary".to_string(), event_id: "e1".to_string(), calendar_name: None, account: None },
+            title: "Dinner; with Ana, Bo".to_string(),
+            start_time: NaiveTime::parse_from_str(start, "%H:%M").ok(),
+            end_time: end.and_then(|t| NaiveTime::parse_from_str(t, "%H:%M").ok()),
+            date: NaiveDate::from_ymd_opt(2026, 1, 20).unwrap(),
+            is_organizer: true,
+            description: Some("Line one\nLine two".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_display_to_ical_copy() {
+        let ical = display_to_ical(&copy_source("All day", None), "copy-1");
+        assert!(ical.contains("UID:copy-1\r\n"));
+        assert!(ical.contains("DTSTART;VALUE=DATE:20260120\r\nDTEND;VALUE=DATE:20260121\r\n"));
+        assert!(ical.contains("SUMMARY:Dinner\\; with Ana\\, Bo\r\n"));
+        assert!(ical.contains("DESCRIPTION:Line one\\nLine two\r\n"));
+
+        // Round-trips through the iCloud parser
+        let parsed = ICalEvent::parse_ical(&ical);
+        assert_eq!(parsed[0].title(), "Dinner; with Ana, Bo");
+        assert_eq!(parsed[0].start_date(), NaiveDate::from_ymd_opt(2026, 1, 20).unwrap());
+    }
+
+    #[test]
+    fn test_display_to_google_event_past_midnight() {
+        let body = display_to_google_event(&copy_source("23:00", Some("01:00")));
+        let start = DateTime::parse_from_rfc3339(body["start"]["dateTime"].as_str().unwrap()).unwrap();
+        let end = DateTime::parse_from_rfc3339(body["end"]["dateTime"].as_str().unwrap()).unwrap();
+        assert_eq!((end - start).num_hours(), 2);
+        assert_eq!(body["summary"], "Dinner; with Ana, Bo");
+        assert_eq!(body["transparency"], "opaque");
+    }
+
+    #[test]
+    fn test_multi_day_events_round_trip() {
+        let mut timed = copy_source("10:00", Some("12:00"));
+        timed.end_date = Some(NaiveDate::from_ymd_opt(2026, 1, 22).unwrap());
+        let mut trip = copy_source("All day", None);
+        trip.end_date = Some(NaiveDate::from_ymd_opt(2026, 1, 22).unwrap());
+        let mut overnight = copy_source("18:00", Some("00:00"));
+        overnight.end_date = Some(NaiveDate::from_ymd_opt(2026, 1, 21).unwrap());
+
+        for source in [timed, trip, overnight] {
+            let times = google_event_times(&source);
+            let mut google = make_google_event("e1", "Trip", source.date);
+            google.start = serde_json::from_value(times["start"].clone()).unwrap();
+            google.end = serde_json::from_value(times["end"].clone()).unwrap();
+            let event = google_event_to_display(google, "primary".to_string(), None, None, &MeetingPatterns::default()).unwrap();
+            assert_eq!((event.date, event.end_date), (source.date, source.end_date));
+            assert_eq!((event.start_time, event.end_time), (source.start_time, source.end_time));
+
+            let ical = ICalEvent::parse_ical(&display_to_ical(&source, "copy-1")).remove(0);
+            let event = icloud_event_to_display(ical, None, &MeetingPatterns::default());
+            assert_eq!((event.date, event.end_date), (source.date, source.end_date));
+            assert_eq!((event.start_time, event.end_time), (source.start_time, source.end_time));
+        }
+    }
+
+    #[test]
+    fn test_reschedule_ical() {
+        let stored = "BEGIN:VCALENDAR\r\nBEGIN:VTIMEZONE\r\nTZID:Europe/Sofia\r\nBEGIN:STANDARD\r\n\
+                      DTSTART:19701025T040000\r\nEND:STANDARD\r\nEND:VTIMEZONE\r\nBEGIN:VEVENT\r\nUID:abc\r\n\
+                      DTSTART;TZID=Europe/Sofia:20260119T100000\r\nDURATION:PT1H\r\nSUMMARY:Dentist\r\n\
+                      END:VEVENT\r\nEND:VCALENDAR\r\n";
+        let moved = reschedule_ical(stored, &copy_source("All day", None)).unwrap();
+        assert!(moved.contains("DTSTART:19701025T040000\r\n")); // Time zone rules untouched
+        assert!(moved.contains("UID:abc\r\nDTSTART;VALUE=DATE:20260120\r\nDTEND;VALUE=DATE:20260121\r\nSUMMARY:Dentist\r\n"));
+        assert!(!moved.contains("DURATION"));
+
+        let recurring = stored.replace("SUMMARY:Dentist", "RRULE:FREQ=WEEKLY");
+        assert!(reschedule_ical(&recurring, &copy_source("All day", None)).is_err());
+    }
+}
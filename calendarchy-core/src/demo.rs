@@ -0,0 +1,222 @@
+//! Demo mode: a mock provider that generates realistic events without credentials
+
+use crate::cache::{AttendeeStatus, DisplayAttendee, DisplayEvent, EventId, EventSource};
+use crate::provider::{CalendarProvider, FetchFuture};
+use chrono::{Datelike, NaiveDate, NaiveTime, Weekday};
+
+/// Generates a deterministic set of events per day, so screenshots are reproducible
+pub struct MockProvider {
+    source: EventSource,
+}
+
+impl MockProvider {
+    pub fn new(source: EventSource) -> Self {
+        Self { source }
+    }
+
+    /// Generate events for a range synchronously
+    pub fn generate(&self, start: NaiveDate, end: NaiveDate) -> Vec<DisplayEvent> {
+        start
+            .iter_days()
+            .take_while(|d| *d <= end)
+            .flat_map(|date| match self.source {
+                EventSource::Google => work_events(date),
+                EventSource::ICloud => personal_events(date),
+            })
+            .collect()
+    }
+}
+
+impl CalendarProvider for MockProvider {
+    fn name(&self) -> &str {
+        "Demo"
+    }
+
+    fn source(&self) -> EventSource {
+        self.source
+    }
+
+    fn fetch_range(&self, start: NaiveDate, end: NaiveDate) -> FetchFuture {
+        let events = self.generate(start, end);
+        Box::pin(async move { Ok(events) })
+    }
+}
+
+/// Cheap deterministic per-day variation
+fn day_seed(date: NaiveDate) -> u32 {
+    (date.num_days_from_ce() as u32).wrapping_mul(2654435761) >> 16
+}
+
+fn attendee(name: &str, status: AttendeeStatus) -> DisplayAttendee {
+    let email = format!("{}@example.com", name.to_lowercase().replace(' ', "."));
+    DisplayAttendee { name: Some(name.to_string()), email, status, is_self: false }
+}
+
+fn event(source: EventSource, date: NaiveDate, slug: &str, title: &str, start: &str, end: Option<&str>) -> DisplayEvent {
+    let uid = format!("demo-{}-{}", slug, date);
+    let id = match source {
+        EventSource::Google => EventId::Google {
+            calendar_id: "demo@example.com".to_string(),
+            event_id: uid,
+            calendar_name: Some("Work".to_string()),
+            account: None,
+        },
+        EventSource::ICloud => EventId::ICloud {
+            calendar_url: "https://caldav.example.com/demo/".to_string(),
+            event_uid: uid,
+            etag: None,
+            calendar_name: Some("Home".to_string()),
+            occurrence: None,
+        },
+    };
+    DisplayEvent {
+        id,
+        title: title.to_string(),
+        start_time: NaiveTime::parse_from_str(start, "%H:%M").ok(),
+        end_time: end.and_then(|t| NaiveTime::parse_from_str(t, "%H:%M").ok()),
+        date,
+        ..Default::default()
+    }
+}
+
+fn work_events(date: NaiveDate) -> Vec<DisplayEvent> {
+    let weekday = date.weekday();
+    if matches!(weekday, Weekday::Sat | Weekday::Sun) {
+        return vec![];
+    }
+    let seed = day_seed(date);
+    let g = EventSource::Google;
+    let mut events = Vec::new();
+
+    if seed.is_multiple_of(23) {
+        let mut offsite = event(g, date, "offsite", "Company offsite", "All day", None);
+        offsite.location = Some("Main office".to_string());
+        // Two days long unless it would run into the weekend
+        offsite.end_date = date.succ_opt().filter(|_| weekday != Weekday::Fri);
+        events.push(offsite);
+    }
+
+    let mut standup = event(g, date, "standup", "Team standup", "09:30", Some("09:45"));
+    standup.meeting_url = Some("https://meet.google.com/abc-defg-hij".to_string());
+    standup.attendees = vec![
+        attendee("Maria Petrova", AttendeeStatus::Organizer),
+        attendee("Alex Chen", AttendeeStatus::Accepted),
+        attendee("Sam Patel", AttendeeStatus::Tentative),
+    ];
+    events.push(standup);
+
+    match weekday {
+        Weekday::Mon => {
+            let mut planning = event(g, date, "planning", "Sprint planning", "10:00", Some("11:30"));
+            planning.is_organizer = true;
+            planning.description = Some("Review the backlog and commit to sprint goals.".to_string());
+            planning.meeting_url = Some("https://zoom.us/j/1234567890".to_string());
+            events.push(planning);
+        }
+        Weekday::Wed => {
+            let mut review = event(g, date, "design", "Design review", "14:00", Some("15:30"));
+            review.location = Some("Room 4.02".to_string());
+            review.attendees = vec![
+                attendee("Jordan Lee", AttendeeStatus::Organizer),
+                attendee("Alex Chen", AttendeeStatus::Accepted),
+                attendee("Priya Rao", AttendeeStatus::Declined),
+                attendee("Sam Patel", AttendeeStatus::NeedsAction),
+            ];
+            events.push(review);
+        }
+        Weekday::Fri => {
+            let mut demo = event(g, date, "demo", "Sprint demo", "16:00", Some("17:00"));
+            demo.meeting_url = Some("https://teams.microsoft.com/l/meetup-join/demo".to_string());
+            events.push(demo);
+        }
+        _ => {}
+    }
+
+    if seed.is_multiple_of(3) {
+        events.push(event(g, date, "one-on-one", "1:1 with Maria", "11:00", Some("11:30")));
+    }
+    if seed % 4 == 1 {
+        // Pending invite that overlaps the afternoon
+        let mut invite = event(g, date, "invite", "Vendor sync", "14:30", Some("15:00"));
+        invite.accepted = false;
+        events.push(invite);
+    }
+    if seed % 5 == 2 {
+        let mut focus = event(g, date, "focus", "Focus time", "13:00", Some("15:00"));
+        focus.is_free = true;
+        events.push(focus);
+    }
+
+    events.sort_by_key(|e| e.start_time);
+    events
+}
+
+fn personal_events(date: NaiveDate) -> Vec<DisplayEvent> {
+    let seed = day_seed(date);
+    let i = EventSource::ICloud;
+    let mut events = Vec::new();
+
+    if date.day() == 14 {
+        events.push(event(i, date, "birthday", "Mom's birthday", "All day", None));
+    }
+    match date.weekday() {
+        Weekday::Tue | Weekday::Thu => {
+            let mut gym = event(i, date, "gym", "Gym", "07:00", Some("08:00"));
+            gym.location = Some("Fitness club".to_string());
+            events.push(gym);
+        }
+        Weekday::Sat => {
+            events.push(event(i, date, "market", "Farmers market", "10:00", Some("11:30")));
+            if seed.is_multiple_of(2) {
+                events.push(event(i, date, "dinner", "Dinner with friends", "19:30", Some("22:00")));
+            }
+        }
+        Weekday::Sun => {
+            events.push(event(i, date, "hike", "Hike", "09:00", Some("13:00")));
+        }
+        _ => {}
+    }
+    if seed % 11 == 3 {
+        events.push(event(i, date, "dentist", "Dentist", "17:30", Some("18:15")));
+    }
+
+    events.sort_by_key(|e| e.start_time);
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_provider_is_deterministic() {
+        let start = NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 2, 28).unwrap();
+        let provider = MockProvider::new(EventSource::Google);
+        let first = provider.generate(start, end);
+        let second = provider.generate(start, end);
+        assert!(!first.is_empty());
+        assert_eq!(first.len(), second.len());
+        assert!(first.iter().zip(&second).all(|(a, b)| a.title == b.title && a.date == b.date));
+    }
+
+    #[test]
+    fn test_mock_work_events_skip_weekends() {
+        let saturday = NaiveDate::from_ymd_opt(2026, 2, 14).unwrap();
+        let provider = MockProvider::new(EventSource::Google);
+        assert!(provider.generate(saturday, saturday).is_empty());
+
+        let monday = NaiveDate::from_ymd_opt(2026, 2, 9).unwrap();
+        let events = provider.generate(monday, monday);
+        assert!(events.iter().any(|e| e.title == "Sprint planning"));
+        assert!(events.iter().all(|e| matches!(e.id, EventId::Google { .. })));
+    }
+
+    #[test]
+    fn test_mock_events_stay_in_range() {
+        let start = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 3, 31).unwrap();
+        let events = MockProvider::new(EventSource::ICloud).generate(start, end);
+        assert!(events.iter().all(|e| e.date >= start && e.date <= end));
+    }
+}
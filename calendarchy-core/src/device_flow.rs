@@ -0,0 +1,74 @@
+//! Polling side of the OAuth device authorization grant (RFC 8628), shared by the Google
+//! and Microsoft sign-ins
+
+use crate::error::{CalendarchyError, Result};
+use chrono::{DateTime, Utc};
+use std::future::Future;
+use std::time::Duration;
+
+/// Added to the polling interval each time the server answers slow_down
+const SLOW_DOWN_STEP: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub enum PollResult<T> {
+    Success(T),
+    Pending,
+    SlowDown,
+    Denied,
+    Expired,
+}
+
+/// What an error answer to a token poll means; Microsoft spells some of them differently
+pub fn poll_error<T>(error: serde_json::Value) -> Result<PollResult<T>> {
+    match error.get("error").and_then(|e| e.as_str()) {
+        Some("authorization_pending") => Ok(PollResult::Pending),
+        Some("slow_down") => Ok(PollResult::SlowDown),
+        Some("access_denied") | Some("authorization_declined") => Ok(PollResult::Denied),
+        Some("expired_token") | Some("code_expired") => Ok(PollResult::Expired),
+        _ => Err(CalendarchyError::Auth(format!(
+            "Unknown error: {:?}",
+            error
+        ))),
+    }
+}
+
+/// Poll until the user has approved the device code, waiting `interval` between polls
+/// and backing off whenever the server asks to slow down
+pub async fn wait_for_token<T, F, Fut>(
+    mut poll: F,
+    mut interval: Duration,
+    expires_at: DateTime<Utc>,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<PollResult<T>>>,
+{
+    loop {
+        tokio::time::sleep(interval).await;
+        if Utc::now() >= expires_at {
+            return Err(CalendarchyError::Auth("Code expired".to_string()));
+        }
+        match poll().await? {
+            PollResult::Success(tokens) => return Ok(tokens),
+            PollResult::Pending => {}
+            PollResult::SlowDown => interval += SLOW_DOWN_STEP,
+            PollResult::Denied => return Err(CalendarchyError::Auth("Access denied".to_string())),
+            PollResult::Expired => return Err(CalendarchyError::Auth("Code expired".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poll_error() {
+        let answer = |code: &str| poll_error::<()>(serde_json::json!({ "error": code }));
+        assert!(matches!(answer("authorization_pending"), Ok(PollResult::Pending)));
+        assert!(matches!(answer("slow_down"), Ok(PollResult::SlowDown)));
+        assert!(matches!(answer("authorization_declined"), Ok(PollResult::Denied)));
+        assert!(matches!(answer("code_expired"), Ok(PollResult::Expired)));
+        assert!(answer("invalid_client").is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::logging::HttpLog;
+use reqwest::{Response, StatusCode};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CalendarchyError {
+    #[error("Network error: {0}")]
+    Network(#[from] reqwest::Error),
+
+    #[error("Authentication error: {0}")]
+    Auth(String),
+
+    #[error("API error: {0}")]
+    Api(String),
+
+    #[error("Configuration error: {0}")]
+    Config(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("XML error: {0}")]
+    Xml(#[from] quick_xml::Error),
+
+    #[error("CalDAV error: {0}")]
+    CalDav(String),
+
+    #[error("Microsoft Graph error: {0}")]
+    Graph(String),
+
+    /// Credentials refused by CalDAV; see `icloud::credential_guidance` for what to tell the user
+    #[error("iCloud refused the sign-in (HTTP {status})")]
+    ICloudCredentials { status: u16, body: String },
+
+    #[error("{0}")]
+    Corrupted(String),
+
+    #[error("Token expired")]
+    TokenExpired,
+
+    #[allow(dead_code)]
+    #[error("Not authenticated")]
+    NotAuthenticated,
+}
+
+pub type Result<T> = std::result::Result<T, CalendarchyError>;
+
+impl CalendarchyError {
+    /// Move a file that failed to parse aside and describe what happened
+    pub fn corrupted_file(path: &std::path::Path, err: impl std::fmt::Display) -> Self {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("file");
+        match crate::utils::quarantine_file(path) {
+            Ok(backup) => CalendarchyError::Corrupted(format!(
+                "{} was unreadable ({}); moved to {}",
+                name,
+                err,
+                backup.display()
+            )),
+            Err(_) => CalendarchyError::Corrupted(format!("{} was unreadable ({})", name, err)),
+        }
+    }
+}
+
+/// Read the body of a failed response, capturing headers and body for the debug screen
+pub async fn failure_body(response: Response, log: &HttpLog) -> String {
+    let status = response.status().as_u16();
+    let url = response.url().to_string();
+    let headers = response
+        .headers()
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_str().unwrap_or("<binary>").to_string()))
+        .collect();
+    let body = response.text().await.unwrap_or_default();
+    log.capture_failure(status, &url, headers, &body);
+    body
+}
+
+/// Check Google API response status and return appropriate error
+/// Returns the response body as text on success
+pub async fn check_google_response(response: Response, context: &str, log: &HttpLog) -> Result<String> {
+    if response.status() == StatusCode::UNAUTHORIZED {
+        failure_body(response, log).await;
+        return Err(CalendarchyError::TokenExpired);
+    }
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = failure_body(response, log).await;
+        return Err(CalendarchyError::Api(format!("{} {}: {}", context, status, body)));
+    }
+
+    Ok(response.text().await?)
+}
+
+/// Check Google API response for success, allowing NO_CONTENT (for DELETE)
+pub async fn check_google_response_no_body(response: Response, context: &str, log: &HttpLog) -> Result<()> {
+    if response.status() == StatusCode::UNAUTHORIZED {
+        failure_body(response, log).await;
+        return Err(CalendarchyError::TokenExpired);
+    }
+
+    if !response.status().is_success() && response.status() != StatusCode::NO_CONTENT {
+        let status = response.status();
+        let body = failure_body(response, log).await;
+        return Err(CalendarchyError::Api(format!("{} {}: {}", context, status, body)));
+    }
+
+    Ok(())
+}
+
+/// Check Microsoft Graph response status and return appropriate error
+/// Returns the response body as text on success
+pub async fn check_graph_response(response: Response, context: &str, log: &HttpLog) -> Result<String> {
+    if response.status() == StatusCode::UNAUTHORIZED {
+        failure_body(response, log).await;
+        return Err(CalendarchyError::TokenExpired);
+    }
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = failure_body(response, log).await;
+        return Err(CalendarchyError::Graph(format!("{} {}: {}", context, status, body)));
+    }
+
+    Ok(response.text().await?)
+}
+
+/// Check CalDAV response status and return appropriate error
+/// Returns the response body as text on success
+pub async fn check_caldav_response(response: Response, context: &str, log: &HttpLog) -> Result<String> {
+    Ok(check_caldav_status(response, context, log).await?.text().await?)
+}
+
+/// Check CalDAV response status, leaving the body unread for the caller to stream
+pub async fn check_caldav_status(response: Response, context: &str, log: &HttpLog) -> Result<Response> {
+    if response.status() == StatusCode::UNAUTHORIZED {
+        let body = failure_body(response, log).await;
+        return Err(CalendarchyError::ICloudCredentials { status: 401, body });
+    }
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = failure_body(response, log).await;
+        return Err(CalendarchyError::CalDav(format!("{} {}: {}", context, status, body)));
+    }
+
+    Ok(response)
+}
+
+/// Check CalDAV response for success, allowing NO_CONTENT and NOT_FOUND (for DELETE)
+pub async fn check_caldav_response_no_body(response: Response, context: &str, log: &HttpLog) -> Result<()> {
+    // 404 means already deleted, consider success
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(());
+    }
+
+    if response.status() == StatusCode::UNAUTHORIZED {
+        let body = failure_body(response, log).await;
+        return Err(CalendarchyError::ICloudCredentials { status: 401, body });
+    }
+
+    if !response.status().is_success() && response.status() != StatusCode::NO_CONTENT {
+        let status = response.status();
+        let body = failure_body(response, log).await;
+        return Err(CalendarchyError::CalDav(format!("{} {}: {}", context, status, body)));
+    }
+
+    Ok(())
+}
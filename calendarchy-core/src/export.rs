@@ -0,0 +1,185 @@
+//! Exports of events: CSV for time tracking and invoicing, and the agenda as text or JSON
+//! for scripts
+
+use crate::cache::{AgendaEntry, DisplayEvent, EventCache, EventSource};
+use crate::config::TimeFormatConfig;
+use crate::utils::month_bounds;
+use chrono::{Duration, NaiveDate};
+use serde_json::json;
+
+const HEADER: &str = "date,start,end,duration_minutes,title,calendar,attendees";
+
+/// Parse an export range like "2026-02-01 2026-02-28"; a missing end means a single day,
+/// and no dates at all means the month of `default_month`
+pub fn parse_range(args: &[&str], default_month: NaiveDate) -> Result<(NaiveDate, NaiveDate), String> {
+    let parse = |s: &str| {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", s))
+    };
+    let (from, to) = match args {
+        [] => month_bounds(default_month),
+        [day] => (parse(day)?, parse(day)?),
+        [from, to] => (parse(from)?, parse(to)?),
+        _ => return Err("Expected at most two dates".to_string()),
+    };
+    if to < from {
+        return Err(format!("{} is before {}", to, from));
+    }
+    Ok((from, to))
+}
+
+/// Displayed events between two dates (inclusive), by date and start time
+pub fn events_in_range(events: &EventCache, from: NaiveDate, to: NaiveDate) -> Vec<&DisplayEvent> {
+    let mut result = Vec::new();
+    let mut date = from;
+    while date <= to {
+        let mut day: Vec<&DisplayEvent> = events.day(date).collect();
+        day.sort_by_key(|e| e.start_time);
+        result.extend(day);
+        date += Duration::days(1);
+    }
+    result
+}
+
+/// CSV with one row per event; all-day events have empty times and duration
+pub fn to_csv(events: &[&DisplayEvent]) -> String {
+    let mut csv = format!("{}\n", HEADER);
+    for event in events {
+        let start = event.start_time.map(|t| t.format("%H:%M").to_string()).unwrap_or_default();
+        let end = event.end_time.map(|t| t.format("%H:%M").to_string()).unwrap_or_default();
+        let duration = event.duration_minutes().map(|m| m.to_string()).unwrap_or_default();
+        let fields = [
+            event.date.format("%Y-%m-%d").to_string(),
+            start,
+            end,
+            duration,
+            event.title.clone(),
+            event.calendar_label().to_string(),
+            event.attendees.len().to_string(),
+        ];
+        csv.push_str(&fields.iter().map(|f| escape_field(f)).collect::<Vec<_>>().join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// The agenda under one heading per day, e.g. "  09:30-10:00  Standup (Work)"
+pub fn agenda_to_text(entries: &[AgendaEntry], format: &TimeFormatConfig) -> String {
+    let mut text = String::new();
+    let mut last_date = None;
+    for entry in entries {
+        let event = entry.event;
+        if last_date != Some(event.date) {
+            if last_date.is_some() {
+                text.push('\n');
+            }
+            text.push_str(&format!("{}\n", event.date.format("%a %Y-%m-%d")));
+            last_date = Some(event.date);
+        }
+        let when = event.time_range_label(format);
+        text.push_str(&format!("  {:>11}  {} ({})\n", when, event.title, event.calendar_label()));
+    }
+    text
+}
+
+/// The agenda as a JSON array; all-day events have null times
+pub fn agenda_to_json(entries: &[AgendaEntry]) -> String {
+    let events: Vec<serde_json::Value> = entries
+        .iter()
+        .map(|entry| {
+            let event = entry.event;
+            json!({
+                "date": event.date.format("%Y-%m-%d").to_string(),
+                "start": event.start_time.map(|t| t.format("%H:%M").to_string()),
+                "end": event.end_time.map(|t| t.format("%H:%M").to_string()),
+                "title": event.title,
+                "calendar": event.calendar_label(),
+                "panel": match entry.source {
+                    EventSource::Google => "work",
+                    EventSource::ICloud => "personal",
+                },
+                "location": event.location,
+                "meeting_url": event.meeting_url,
+                "accepted": event.accepted,
+                "free": event.is_free,
+                "attendees": event.attendees.iter().map(|a| a.email.as_str()).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&events).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Quote a field containing a separator, quote or line break, doubling inner quotes
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{AttendeeStatus, DisplayAttendee, EventId};
+    use chrono::NaiveTime;
+
+    fn make_event(title: &str, time: &str, end: Option<&str>) -> DisplayEvent {
+        DisplayEvent {
+            id: EventId::Google { calendar_id: "primary".to_string(), event_id: title.to_string(), calendar_name: Some("Work".to_string()), account: None },
+            title: title.to_string(),
+            start_time: NaiveTime::parse_from_str(time, "%H:%M").ok(),
+            end_time: end.and_then(|t| NaiveTime::parse_from_str(t, "%H:%M").ok()),
+            date: NaiveDate::from_ymd_opt(2026, 2, 10).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let date = |d: u32| NaiveDate::from_ymd_opt(2026, 2, d).unwrap();
+        assert_eq!(parse_range(&[], date(10)), Ok((date(1), date(28))));
+        assert_eq!(parse_range(&["2026-02-03"], date(10)), Ok((date(3), date(3))));
+        assert_eq!(parse_range(&["2026-02-03", "2026-02-05"], date(10)), Ok((date(3), date(5))));
+        assert!(parse_range(&["2026-02-05", "2026-02-03"], date(10)).is_err());
+        assert!(parse_range(&["02/03"], date(10)).is_err());
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let mut review = make_event("Review, \"Q1\"", "10:00", Some("11:30"));
+        review.attendees = vec![DisplayAttendee {
+            name: None,
+            email: "alice@example.com".to_string(),
+            status: AttendeeStatus::Accepted,
+            is_self: false,
+        }];
+        let offsite = make_event("Offsite", "All day", None);
+        assert_eq!(
+            to_csv(&[&review, &offsite]),
+            "date,start,end,duration_minutes,title,calendar,attendees\n\
+             2026-02-10,10:00,11:30,90,\"Review, \"\"Q1\"\"\",Work,1\n\
+             2026-02-10,,,,Offsite,Work,0\n"
+        );
+    }
+
+    #[test]
+    fn test_agenda_exports() {
+        let standup = make_event("Standup", "09:30", Some("10:00"));
+        let offsite = make_event("Offsite", "All day", None);
+        let entries = [
+            AgendaEntry { source: EventSource::Google, index: 1, event: &offsite },
+            AgendaEntry { source: EventSource::Google, index: 0, event: &standup },
+        ];
+        assert_eq!(
+            agenda_to_text(&entries, &TimeFormatConfig::default()),
+            "Tue 2026-02-10\n      All day  Offsite (Work)\n  09:30-10:00  Standup (Work)\n"
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&agenda_to_json(&entries)).unwrap();
+        assert_eq!(json[0]["start"], serde_json::Value::Null);
+        assert_eq!(json[1]["start"], "09:30");
+        assert_eq!(json[1]["end"], "10:00");
+        assert_eq!(json[1]["panel"], "work");
+        assert_eq!(json[1]["calendar"], "Work");
+    }
+}
@@ -0,0 +1,188 @@
+//! Read-only calendars: subscribed ICS feeds and directories of .ics files
+
+use crate::cache::{DisplayEvent, EventId, EventSource};
+use crate::conversion::icloud_event_to_display;
+use crate::error::{check_caldav_response, Result};
+use crate::icloud::ICalEvent;
+use crate::logging::HttpLog;
+use crate::provider::{CalendarProvider, FetchFuture};
+use crate::utils::MeetingPatterns;
+use chrono::NaiveDate;
+use reqwest::Client;
+use std::path::{Path, PathBuf};
+
+/// A subscribed calendar fetched over HTTP, e.g. a team's published schedule
+pub struct IcsFeedProvider {
+    name: String,
+    url: String,
+    source: EventSource,
+    log: HttpLog,
+    meeting_patterns: MeetingPatterns,
+}
+
+impl IcsFeedProvider {
+    /// `webcal://` links (as calendar apps publish them) are fetched over https
+    pub fn new(url: String, name: Option<String>, source: EventSource, log: HttpLog, meeting_patterns: MeetingPatterns) -> Self {
+        let url = match url.strip_prefix("webcal://") {
+            Some(rest) => format!("https://{}", rest),
+            None => url,
+        };
+        Self { name: name.unwrap_or_else(|| url.clone()), url, source, log, meeting_patterns }
+    }
+}
+
+impl CalendarProvider for IcsFeedProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self) -> EventSource {
+        self.source
+    }
+
+    fn fetch_range(&self, start: NaiveDate, end: NaiveDate) -> FetchFuture {
+        let url = self.url.clone();
+        let name = self.name.clone();
+        let log = self.log.clone();
+        let meeting_patterns = self.meeting_patterns.clone();
+        Box::pin(async move {
+            let started = log.log_request("GET", &url);
+            let response = Client::new()
+                .get(&url)
+                .send()
+                .await
+                .inspect_err(|_| log.log_transport_error(&url, started))?;
+            log.log_response(response.status().as_u16(), &url, started);
+
+            let body = check_caldav_response(response, "Feed error", &log).await?;
+            Ok(feed_events(&body, &url, &name, start, end, &meeting_patterns))
+        })
+    }
+}
+
+/// Every `.ics` file in a directory, e.g. exports dropped there by another tool
+pub struct LocalDirProvider {
+    name: String,
+    path: PathBuf,
+    source: EventSource,
+    meeting_patterns: MeetingPatterns,
+}
+
+impl LocalDirProvider {
+    pub fn new(path: PathBuf, name: Option<String>, source: EventSource, meeting_patterns: MeetingPatterns) -> Self {
+        let name = name.unwrap_or_else(|| {
+            path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_else(|| path.display().to_string())
+        });
+        Self { name, path, source, meeting_patterns }
+    }
+}
+
+impl CalendarProvider for LocalDirProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self) -> EventSource {
+        self.source
+    }
+
+    fn fetch_range(&self, start: NaiveDate, end: NaiveDate) -> FetchFuture {
+        let path = self.path.clone();
+        let name = self.name.clone();
+        let meeting_patterns = self.meeting_patterns.clone();
+        Box::pin(async move { dir_events(&path, &name, start, end, &meeting_patterns) })
+    }
+}
+
+fn dir_events(path: &Path, name: &str, start: NaiveDate, end: NaiveDate, meeting_patterns: &MeetingPatterns) -> Result<Vec<DisplayEvent>> {
+    let mut events = Vec::new();
+    for entry in std::fs::read_dir(path)? {
+        let file = entry?.path();
+        if file.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("ics")) {
+            let ical = std::fs::read_to_string(&file)?;
+            events.extend(feed_events(&ical, &file.display().to_string(), name, start, end, meeting_patterns));
+        }
+    }
+    Ok(events)
+}
+
+/// Events of an ICS document that fall within the range, repeating ones expanded
+fn feed_events(
+    ical: &str,
+    location: &str,
+    name: &str,
+    start: NaiveDate,
+    end: NaiveDate,
+    meeting_patterns: &MeetingPatterns,
+) -> Vec<DisplayEvent> {
+    ICalEvent::expand_recurrences(ICalEvent::parse_ical(ical), start, end)
+        .into_iter()
+        .filter(|e| e.start_date() <= end && e.end_date() >= start)
+        .map(|e| {
+            let event_uid = e.uid.clone();
+            let mut event = icloud_event_to_display(e, Some(name.to_string()), meeting_patterns);
+            event.id = EventId::Feed {
+                location: location.to_string(),
+                event_uid,
+                calendar_name: Some(name.to_string()),
+            };
+            event
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ICS: &str = "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE:20260303\r\n\
+DTEND;VALUE=DATE:20260304\r\n\
+UID:release@example.com\r\n\
+SUMMARY:Release day\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE:20260410\r\n\
+UID:later@example.com\r\n\
+SUMMARY:Out of range\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_feed_events_in_range() {
+        let events = feed_events(ICS, "https://example.com/team.ics", "Team", date(2026, 3, 1), date(2026, 3, 31), &MeetingPatterns::default());
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].title, "Release day");
+        assert_eq!(events[0].calendar_label(), "Team");
+        assert!(matches!(&events[0].id, EventId::Feed { event_uid, .. } if event_uid == "release@example.com"));
+    }
+
+    #[test]
+    fn test_webcal_url_and_default_name() {
+        let feed = IcsFeedProvider::new("webcal://example.com/team.ics".to_string(), None, EventSource::Google, HttpLog::default(), MeetingPatterns::default());
+        assert_eq!(feed.url, "https://example.com/team.ics");
+        assert_eq!(feed.name(), "https://example.com/team.ics");
+        assert_eq!(feed.source(), EventSource::Google);
+    }
+
+    #[test]
+    fn test_local_dir_reads_ics_files() {
+        let dir = std::env::temp_dir().join(format!("calendarchy-feed-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("team.ics"), ICS).unwrap();
+        std::fs::write(dir.join("notes.txt"), ICS).unwrap();
+
+        let provider = LocalDirProvider::new(dir.clone(), None, EventSource::ICloud, MeetingPatterns::default());
+        let events = dir_events(&dir, provider.name(), date(2026, 3, 1), date(2026, 4, 30), &MeetingPatterns::default());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // notes.txt is skipped
+        assert_eq!(events.unwrap().len(), 2);
+        assert!(provider.name().starts_with("calendarchy-feed-test-"));
+    }
+}
@@ -1,8 +1,10 @@
 use crate::config::GoogleConfig;
-use crate::error::{CalendarchyError, Result};
+use crate::device_flow::{self, poll_error, PollResult};
+use crate::error::{failure_body, CalendarchyError, Result};
 use crate::google::types::{DeviceCodeResponse, TokenInfo, TokenResponse};
-use crate::logging::{log_request, log_response};
-use chrono::Utc;
+use crate::logging::HttpLog;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
 use reqwest::Client;
 
 const DEVICE_CODE_URL: &str = "https://oauth2.googleapis.com/device/code";
@@ -11,29 +13,22 @@ const CALENDAR_SCOPE: &str = "https://www.googleapis.com/auth/calendar";
 
 pub struct GoogleAuth {
     client: Client,
+    log: HttpLog,
     config: GoogleConfig,
 }
 
-#[derive(Debug)]
-pub enum PollResult {
-    Success(TokenInfo),
-    Pending,
-    SlowDown,
-    Denied,
-    Expired,
-}
-
 impl GoogleAuth {
-    pub fn new(config: GoogleConfig) -> Self {
+    pub fn new(config: GoogleConfig, log: HttpLog) -> Self {
         Self {
             client: Client::new(),
+            log,
             config,
         }
     }
 
     /// Step 1: Request device code
     pub async fn request_device_code(&self) -> Result<DeviceCodeResponse> {
-        log_request("POST", DEVICE_CODE_URL);
+        let started = self.log.log_request("POST", DEVICE_CODE_URL);
         let response = self
             .client
             .post(DEVICE_CODE_URL)
@@ -42,11 +37,12 @@ impl GoogleAuth {
                 ("scope", CALENDAR_SCOPE),
             ])
             .send()
-            .await?;
-        log_response(response.status().as_u16(), DEVICE_CODE_URL);
+            .await
+            .inspect_err(|_| self.log.log_transport_error(DEVICE_CODE_URL, started))?;
+        self.log.log_response(response.status().as_u16(), DEVICE_CODE_URL, started);
 
         if !response.status().is_success() {
-            let body = response.text().await.unwrap_or_default();
+            let body = failure_body(response, &self.log).await;
             return Err(CalendarchyError::Auth(format!(
                 "Failed to get device code: {}",
                 body
@@ -58,8 +54,8 @@ impl GoogleAuth {
     }
 
     /// Step 2: Poll for token (call this repeatedly)
-    pub async fn poll_for_token(&self, device_code: &str) -> Result<PollResult> {
-        log_request("POST", TOKEN_URL);
+    pub async fn poll_for_token(&self, device_code: &str) -> Result<PollResult<TokenInfo>> {
+        let started = self.log.log_request("POST", TOKEN_URL);
         let response = self
             .client
             .post(TOKEN_URL)
@@ -70,8 +66,9 @@ impl GoogleAuth {
                 ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
             ])
             .send()
-            .await?;
-        log_response(response.status().as_u16(), TOKEN_URL);
+            .await
+            .inspect_err(|_| self.log.log_transport_error(TOKEN_URL, started))?;
+        self.log.log_response(response.status().as_u16(), TOKEN_URL, started);
 
         if response.status().is_success() {
             let token_response: TokenResponse = response.json().await?;
@@ -83,23 +80,23 @@ impl GoogleAuth {
             };
             Ok(PollResult::Success(token_info))
         } else {
-            let error: serde_json::Value = response.json().await?;
-            match error.get("error").and_then(|e| e.as_str()) {
-                Some("authorization_pending") => Ok(PollResult::Pending),
-                Some("slow_down") => Ok(PollResult::SlowDown),
-                Some("access_denied") => Ok(PollResult::Denied),
-                Some("expired_token") => Ok(PollResult::Expired),
-                _ => Err(CalendarchyError::Auth(format!(
-                    "Unknown error: {:?}",
-                    error
-                ))),
-            }
+            poll_error(response.json().await?)
         }
     }
 
+    /// Poll until the user has approved the device code (see `device_flow::wait_for_token`)
+    pub async fn wait_for_token(
+        &self,
+        device_code: &str,
+        interval: Duration,
+        expires_at: DateTime<Utc>,
+    ) -> Result<TokenInfo> {
+        device_flow::wait_for_token(|| self.poll_for_token(device_code), interval, expires_at).await
+    }
+
     /// Refresh an expired token
     pub async fn refresh_token(&self, refresh_token: &str) -> Result<TokenInfo> {
-        log_request("POST", &format!("{} (refresh)", TOKEN_URL));
+        let started = self.log.log_request("POST", &format!("{} (refresh)", TOKEN_URL));
         let response = self
             .client
             .post(TOKEN_URL)
@@ -110,11 +107,12 @@ impl GoogleAuth {
                 ("grant_type", "refresh_token"),
             ])
             .send()
-            .await?;
-        log_response(response.status().as_u16(), TOKEN_URL);
+            .await
+            .inspect_err(|_| self.log.log_transport_error(TOKEN_URL, started))?;
+        self.log.log_response(response.status().as_u16(), TOKEN_URL, started);
 
         if !response.status().is_success() {
-            let body = response.text().await.unwrap_or_default();
+            let body = failure_body(response, &self.log).await;
             return Err(CalendarchyError::Auth(format!(
                 "Failed to refresh token: {}",
                 body
@@ -0,0 +1,481 @@
+use crate::error::{check_google_response, check_google_response_no_body, CalendarchyError, Result};
+use crate::google::types::{
+    BusyPeriod, CalendarEvent, CalendarListEntry, CalendarListResponse, ColorsResponse, EventsListResponse,
+    FreeBusyResponse, TokenInfo,
+};
+use crate::utils::{normalize_hex_color, resolve_local_datetime};
+use std::collections::HashMap;
+use crate::logging::HttpLog;
+use chrono::{Local, NaiveDate, NaiveTime, TimeZone};
+use reqwest::{Client, StatusCode};
+
+pub const CALENDAR_API_BASE: &str = "https://www.googleapis.com/calendar/v3";
+
+/// Fields requested when listing a month: enough to place, color and RSVP-mark events, and
+/// the description, where meeting links are often pasted. Attendee and room names are left
+/// out and fetched with `get_event` when an event is opened.
+const LIST_FIELDS: &str = "nextPageToken,items(id,iCalUID,summary,description,start,end,location,status,transparency,\
+colorId,eventType,hangoutLink,conferenceData/entryPoints,attendees(email,self,responseStatus,organizer,resource))";
+
+pub struct CalendarClient {
+    client: Client,
+    log: HttpLog,
+}
+
+impl CalendarClient {
+    pub fn new(log: HttpLog) -> Self {
+        Self {
+            client: Client::new(),
+            log,
+        }
+    }
+
+    /// Fetch events for a date range, without descriptions or attendee names
+    pub async fn list_events(
+        &self,
+        token: &TokenInfo,
+        calendar_id: &str,
+        time_min: NaiveDate,
+        time_max: NaiveDate,
+    ) -> Result<Vec<CalendarEvent>> {
+        self.events_between(token, calendar_id, time_min, time_max, None).await
+    }
+
+    /// Events of a date range matching free text in their summary, description, location
+    /// or attendees, found by the server
+    pub async fn search_events(
+        &self,
+        token: &TokenInfo,
+        calendar_id: &str,
+        query: &str,
+        time_min: NaiveDate,
+        time_max: NaiveDate,
+    ) -> Result<Vec<CalendarEvent>> {
+        self.events_between(token, calendar_id, time_min, time_max, Some(query)).await
+    }
+
+    async fn events_between(
+        &self,
+        token: &TokenInfo,
+        calendar_id: &str,
+        time_min: NaiveDate,
+        time_max: NaiveDate,
+        query: Option<&str>,
+    ) -> Result<Vec<CalendarEvent>> {
+        let url = format!(
+            "{}/calendars/{}/events",
+            CALENDAR_API_BASE,
+            urlencoding::encode(calendar_id)
+        );
+
+        // Convert dates to RFC3339 format
+        let time_min_str = format!("{}T00:00:00Z", time_min);
+        let time_max_str = format!("{}T23:59:59Z", time_max);
+
+        let mut all_events = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut request = self
+                .client
+                .get(&url)
+                .bearer_auth(&token.access_token)
+                .query(&[
+                    ("timeMin", time_min_str.as_str()),
+                    ("timeMax", time_max_str.as_str()),
+                    ("singleEvents", "true"),
+                    ("orderBy", "startTime"),
+                    ("maxResults", "250"),
+                    ("fields", LIST_FIELDS),
+                ]);
+
+            if let Some(q) = query {
+                request = request.query(&[("q", q)]);
+            }
+            if let Some(ref pt) = page_token {
+                request = request.query(&[("pageToken", pt.as_str())]);
+            }
+
+            let started = self.log.log_request("GET", &url);
+            let response = request
+                .send()
+                .await
+                .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+            self.log.log_response(response.status().as_u16(), &url, started);
+
+            let body = check_google_response(response, "Calendar API error", &self.log).await?;
+            let events_response: EventsListResponse = serde_json::from_str(&body)?;
+
+            if let Some(items) = events_response.items {
+                all_events.extend(items);
+            }
+
+            page_token = events_response.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(all_events)
+    }
+
+    /// Fetch a single event with all its fields
+    pub async fn get_event(&self, token: &TokenInfo, calendar_id: &str, event_id: &str) -> Result<CalendarEvent> {
+        let url = format!(
+            "{}/calendars/{}/events/{}",
+            CALENDAR_API_BASE,
+            urlencoding::encode(calendar_id),
+            urlencoding::encode(event_id)
+        );
+
+        let started = self.log.log_request("GET", &url);
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(&token.access_token)
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+        self.log.log_response(response.status().as_u16(), &url, started);
+
+        let body = check_google_response(response, "Failed to get event", &self.log).await?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    /// Update the current user's response status for an event
+    pub async fn respond_to_event(
+        &self,
+        token: &TokenInfo,
+        calendar_id: &str,
+        event_id: &str,
+        response: &str, // "accepted", "declined", "tentative"
+    ) -> Result<()> {
+        let url = format!(
+            "{}/calendars/{}/events/{}",
+            CALENDAR_API_BASE,
+            urlencoding::encode(calendar_id),
+            urlencoding::encode(event_id)
+        );
+
+        // First, get the current event to find our attendee entry
+        let mut event = self.get_event(token, calendar_id, event_id).await?;
+
+        // Update the self attendee's response status
+        if let Some(ref mut attendees) = event.attendees {
+            for attendee in attendees.iter_mut() {
+                if attendee.is_self == Some(true) {
+                    attendee.response_status = Some(response.to_string());
+                    break;
+                }
+            }
+        }
+
+        // PATCH the event back
+        let started = self.log.log_request("PATCH", &url);
+        let patch_response = self
+            .client
+            .patch(&url)
+            .bearer_auth(&token.access_token)
+            .query(&[("sendUpdates", "none")]) // Don't send notification emails
+            .json(&event)
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+        self.log.log_response(patch_response.status().as_u16(), &url, started);
+
+        check_google_response_no_body(patch_response, "Failed to update event", &self.log).await
+    }
+
+    /// Create an event from an API body (see `conversion::display_to_google_event`)
+    pub async fn insert_event(
+        &self,
+        token: &TokenInfo,
+        calendar_id: &str,
+        body: &serde_json::Value,
+    ) -> Result<()> {
+        let url = format!(
+            "{}/calendars/{}/events",
+            CALENDAR_API_BASE,
+            urlencoding::encode(calendar_id)
+        );
+
+        let started = self.log.log_request("POST", &url);
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&token.access_token)
+            .query(&[("sendUpdates", "none")]) // Don't send notification emails
+            .json(body)
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+        self.log.log_response(response.status().as_u16(), &url, started);
+
+        check_google_response_no_body(response, "Failed to create event", &self.log).await
+    }
+
+    /// Create an event from a line of text, parsed by Google (e.g. "Lunch tomorrow 12:30")
+    pub async fn quick_add(&self, token: &TokenInfo, calendar_id: &str, text: &str) -> Result<()> {
+        let url = format!(
+            "{}/calendars/{}/events/quickAdd",
+            CALENDAR_API_BASE,
+            urlencoding::encode(calendar_id)
+        );
+
+        let started = self.log.log_request("POST", &url);
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&token.access_token)
+            .query(&[("text", text), ("sendUpdates", "none")])
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+        self.log.log_response(response.status().as_u16(), &url, started);
+
+        check_google_response_no_body(response, "Failed to create event", &self.log).await
+    }
+
+    /// Change fields of an event with a partial API body (see `conversion::google_event_times`)
+    pub async fn patch_event(
+        &self,
+        token: &TokenInfo,
+        calendar_id: &str,
+        event_id: &str,
+        body: &serde_json::Value,
+    ) -> Result<()> {
+        let url = format!(
+            "{}/calendars/{}/events/{}",
+            CALENDAR_API_BASE,
+            urlencoding::encode(calendar_id),
+            urlencoding::encode(event_id)
+        );
+
+        let started = self.log.log_request("PATCH", &url);
+        let response = self
+            .client
+            .patch(&url)
+            .bearer_auth(&token.access_token)
+            .query(&[("sendUpdates", "none")]) // Don't send notification emails
+            .json(body)
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+        self.log.log_response(response.status().as_u16(), &url, started);
+
+        check_google_response_no_body(response, "Failed to update event", &self.log).await
+    }
+
+    /// Delete an event
+    pub async fn delete_event(
+        &self,
+        token: &TokenInfo,
+        calendar_id: &str,
+        event_id: &str,
+    ) -> Result<()> {
+        let url = format!(
+            "{}/calendars/{}/events/{}",
+            CALENDAR_API_BASE,
+            urlencoding::encode(calendar_id),
+            urlencoding::encode(event_id)
+        );
+
+        let started = self.log.log_request("DELETE", &url);
+        let response = self
+            .client
+            .delete(&url)
+            .bearer_auth(&token.access_token)
+            .query(&[("sendUpdates", "none")]) // Don't send notification emails
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+        self.log.log_response(response.status().as_u16(), &url, started);
+
+        check_google_response_no_body(response, "Failed to delete event", &self.log).await
+    }
+
+    /// Get calendar display name
+    pub async fn get_calendar_name(
+        &self,
+        token: &TokenInfo,
+        calendar_id: &str,
+    ) -> Result<Option<String>> {
+        let url = format!(
+            "{}/calendars/{}",
+            CALENDAR_API_BASE,
+            urlencoding::encode(calendar_id)
+        );
+
+        let started = self.log.log_request("GET", &url);
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(&token.access_token)
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+        self.log.log_response(response.status().as_u16(), &url, started);
+
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(CalendarchyError::TokenExpired);
+        }
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        #[derive(serde::Deserialize)]
+        struct CalendarMeta {
+            summary: Option<String>,
+        }
+
+        let meta: CalendarMeta = response.json().await?;
+        Ok(meta.summary)
+    }
+
+    /// List the calendars on the account's calendar list, including ones shared with it
+    pub async fn list_calendars(&self, token: &TokenInfo) -> Result<Vec<CalendarListEntry>> {
+        let url = format!("{}/users/me/calendarList", CALENDAR_API_BASE);
+
+        let mut all_calendars = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut request = self
+                .client
+                .get(&url)
+                .bearer_auth(&token.access_token)
+                .query(&[("fields", "nextPageToken,items(id,summary,summaryOverride,accessRole,primary,backgroundColor)")]);
+
+            if let Some(ref pt) = page_token {
+                request = request.query(&[("pageToken", pt.as_str())]);
+            }
+
+            let started = self.log.log_request("GET", &url);
+            let response = request
+                .send()
+                .await
+                .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+            self.log.log_response(response.status().as_u16(), &url, started);
+
+            let body = check_google_response(response, "Failed to list calendars", &self.log).await?;
+            let list: CalendarListResponse = serde_json::from_str(&body)?;
+
+            if let Some(items) = list.items {
+                all_calendars.extend(items);
+            }
+
+            page_token = list.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(all_calendars)
+    }
+
+    /// Busy periods of someone's calendar, by their email, for a date range
+    pub async fn free_busy(
+        &self,
+        token: &TokenInfo,
+        email: &str,
+        time_min: NaiveDate,
+        time_max: NaiveDate,
+    ) -> Result<Vec<BusyPeriod>> {
+        let url = format!("{}/freeBusy", CALENDAR_API_BASE);
+        let body = serde_json::json!({
+            "timeMin": day_start(&Local, time_min),
+            "timeMax": day_start(&Local, time_max.succ_opt().unwrap_or(time_max)),
+            "items": [{ "id": email }],
+        });
+
+        let started = self.log.log_request("POST", &url);
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&token.access_token)
+            .json(&body)
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+        self.log.log_response(response.status().as_u16(), &url, started);
+
+        let body = check_google_response(response, "FreeBusy error", &self.log).await?;
+        let mut free_busy: FreeBusyResponse = serde_json::from_str(&body)?;
+        let calendar = free_busy.calendars.remove(email).unwrap_or_default();
+        match calendar.errors.first() {
+            Some(error) if error.reason == "notFound" => {
+                Err(CalendarchyError::Api(format!("{} hasn't shared their calendar", email)))
+            }
+            Some(error) => Err(CalendarchyError::Api(format!("FreeBusy error for {}: {}", email, error.reason))),
+            None => Ok(calendar.busy),
+        }
+    }
+
+    /// Fetch the event color palette (color id -> hex background)
+    pub async fn get_event_colors(&self, token: &TokenInfo) -> Result<HashMap<String, String>> {
+        let url = format!("{}/colors", CALENDAR_API_BASE);
+
+        let started = self.log.log_request("GET", &url);
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(&token.access_token)
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+        self.log.log_response(response.status().as_u16(), &url, started);
+
+        let body = check_google_response(response, "Colors API error", &self.log).await?;
+        let colors: ColorsResponse = serde_json::from_str(&body)?;
+        Ok(colors
+            .event
+            .into_iter()
+            .filter_map(|(id, def)| Some((id, normalize_hex_color(&def.background)?)))
+            .collect())
+    }
+}
+
+/// The midnight starting `date` in `tz`, as RFC 3339, so busy times line up with the
+/// local days they're drawn on
+fn day_start<Tz: TimeZone>(tz: &Tz, date: NaiveDate) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    resolve_local_datetime(tz, date.and_time(NaiveTime::MIN))
+        .map(|start| start.to_rfc3339())
+        .unwrap_or_else(|| format!("{}T00:00:00Z", date))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversion::google_event_to_display;
+    use crate::utils::MeetingPatterns;
+    use chrono::FixedOffset;
+
+    #[test]
+    fn test_day_start_is_local_midnight() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let sofia = FixedOffset::east_opt(2 * 3600).unwrap();
+        assert_eq!(day_start(&sofia, date), "2026-03-02T00:00:00+02:00");
+        let new_york = FixedOffset::west_opt(5 * 3600).unwrap();
+        assert_eq!(day_start(&new_york, date), "2026-03-02T00:00:00-05:00");
+    }
+
+    #[test]
+    fn test_listed_description_meeting_link() {
+        assert!(LIST_FIELDS.contains(",description,"));
+        // A listed event as the fields above return it, with the link only in its description
+        let page: EventsListResponse = serde_json::from_str(r#"{"items": [{
+            "id": "abc",
+            "summary": "Vendor sync",
+            "description": "Join: https://acme.zoom.us/j/123456789",
+            "start": {"dateTime": "2026-02-10T10:00:00Z"},
+            "end": {"dateTime": "2026-02-10T10:30:00Z"}
+        }]}"#).unwrap();
+        let event = page.items.unwrap().remove(0);
+        let event = google_event_to_display(event, "primary".to_string(), None, None, &MeetingPatterns::default()).unwrap();
+        assert_eq!(event.meeting_url.as_deref(), Some("https://acme.zoom.us/j/123456789"));
+    }
+}
@@ -1,5 +1,7 @@
-use chrono::{DateTime, Local, NaiveDate, Utc};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
+use crate::cache::EventKind;
+use std::collections::HashMap;
 
 /// OAuth2 tokens from Google
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +25,13 @@ pub struct DeviceCodeResponse {
     pub user_code: String,
     pub verification_url: String,
     pub expires_in: u64,
+    /// Seconds to wait between token polls
+    #[serde(default = "default_poll_interval")]
+    pub interval: u64,
+}
+
+fn default_poll_interval() -> u64 {
+    5
 }
 
 /// Token endpoint response
@@ -50,6 +59,28 @@ pub struct CalendarEvent {
     pub attendees: Option<Vec<Attendee>>,
     pub conference_data: Option<ConferenceData>,
     pub hangout_link: Option<String>,
+    /// iCalendar UID shared with copies of this event in other calendars
+    #[serde(rename = "iCalUID")]
+    pub ical_uid: Option<String>,
+    /// Key into the event palette of the colors endpoint
+    pub color_id: Option<String>,
+    /// "default", "outOfOffice", "focusTime", "workingLocation", ...
+    pub event_type: Option<String>,
+    /// Background color resolved from `color_id`, e.g. "#a4bdfc"
+    #[serde(skip)]
+    pub color: Option<String>,
+}
+
+/// Response of the colors endpoint (only the event palette is used)
+#[derive(Debug, Deserialize)]
+pub struct ColorsResponse {
+    #[serde(default)]
+    pub event: HashMap<String, ColorDefinition>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ColorDefinition {
+    pub background: String,
 }
 
 /// Conference/meeting data
@@ -65,6 +96,8 @@ pub struct ConferenceData {
 pub struct EntryPoint {
     pub entry_point_type: Option<String>,
     pub uri: Option<String>,
+    /// PIN for phone entry points
+    pub pin: Option<String>,
 }
 
 /// Event attendee
@@ -77,6 +110,8 @@ pub struct Attendee {
     #[serde(rename = "self")]
     pub is_self: Option<bool>,
     pub organizer: Option<bool>,
+    /// A meeting room or other bookable resource rather than a person
+    pub resource: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -100,28 +135,41 @@ impl CalendarEvent {
         })
     }
 
+    /// Last day the event covers, in local timezone; the end of an all-day event is exclusive
+    pub fn end_date(&self) -> Option<NaiveDate> {
+        match (self.end.date, self.end.date_time) {
+            (Some(date), _) => date.pred_opt(),
+            (None, Some(dt)) => {
+                let local = |dt: DateTime<Utc>| dt.with_timezone(&Local).naive_local();
+                Some(last_covered_day(local(self.start.date_time.unwrap_or(dt)), local(dt)))
+            }
+            (None, None) => None,
+        }
+    }
+
+    /// Kind of event derived from `event_type`
+    pub fn kind(&self) -> EventKind {
+        EventKind::from_google(self.event_type.as_deref())
+    }
+
     /// Get display title
     pub fn title(&self) -> &str {
         self.summary.as_deref().unwrap_or("(No title)")
     }
 
-    /// Get start time as HH:MM or "All day" (converted to local timezone)
-    pub fn time_str(&self) -> String {
-        self.start
-            .date_time
-            .map(|dt| {
-                let local: DateTime<Local> = dt.with_timezone(&Local);
-                format!("{:02}:{:02}", local.time().hour(), local.time().minute())
-            })
-            .unwrap_or_else(|| "All day".to_string())
+    /// Resolve `color_id` against the event palette (color id -> hex background)
+    pub fn resolve_color(&mut self, palette: &HashMap<String, String>) {
+        self.color = self.color_id.as_ref().and_then(|id| palette.get(id)).cloned();
     }
 
-    /// Get end time as HH:MM or None for all-day events (converted to local timezone)
-    pub fn end_time_str(&self) -> Option<String> {
-        self.end.date_time.map(|dt| {
-            let local: DateTime<Local> = dt.with_timezone(&Local);
-            format!("{:02}:{:02}", local.time().hour(), local.time().minute())
-        })
+    /// Start time in the local timezone, or None for all-day events
+    pub fn start_time(&self) -> Option<NaiveTime> {
+        self.start.date_time.map(|dt| dt.with_timezone(&Local).time())
+    }
+
+    /// End time in the local timezone, or None for all-day events
+    pub fn end_time(&self) -> Option<NaiveTime> {
+        self.end.date_time.map(|dt| dt.with_timezone(&Local).time())
     }
 
     /// Check if the current user has accepted this event
@@ -167,7 +215,7 @@ impl CalendarEvent {
     }
 
     /// Extract meeting URL (Zoom, Google Meet, etc.)
-    pub fn meeting_url(&self) -> Option<String> {
+    pub fn meeting_url(&self, custom: &MeetingPatterns) -> Option<String> {
         // Check hangout_link first (Google Meet)
         if let Some(ref url) = self.hangout_link {
             return Some(url.clone());
@@ -186,23 +234,36 @@ impl CalendarEvent {
 
         // Check location for meeting URLs
         if let Some(ref loc) = self.location
-            && let Some(url) = extract_meeting_url(loc) {
+            && let Some(url) = extract_meeting_url(loc, custom) {
                 return Some(url);
             }
 
         // Check description for meeting URLs
         if let Some(ref desc) = self.description
-            && let Some(url) = extract_meeting_url(desc) {
+            && let Some(url) = extract_meeting_url(desc, custom) {
                 return Some(url);
             }
 
         None
     }
-}
 
-use crate::utils::extract_meeting_url;
+    /// Phone dial-in as a tel: URI with the PIN, from conference data or the event text
+    pub fn dial_in(&self) -> Option<String> {
+        if let Some(ref conf) = self.conference_data
+            && let Some(ref entry_points) = conf.entry_points
+            && let Some(uri) = entry_points.iter()
+                .filter(|ep| ep.entry_point_type.as_deref() == Some("phone"))
+                .find_map(|ep| Some(tel_uri(ep.uri.as_deref()?, ep.pin.as_deref())))
+        {
+            return Some(uri);
+        }
+
+        self.location.as_deref().and_then(extract_dial_in)
+            .or_else(|| self.description.as_deref().and_then(extract_dial_in))
+    }
+}
 
-use chrono::Timelike;
+use crate::utils::{extract_dial_in, extract_meeting_url, last_covered_day, tel_uri, MeetingPatterns};
 
 /// Response from events.list API
 #[derive(Debug, Deserialize)]
@@ -212,6 +273,72 @@ pub struct EventsListResponse {
     pub next_page_token: Option<String>,
 }
 
+/// One calendar of the account's calendar list, own or shared with it
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarListEntry {
+    pub id: String,
+    pub summary: Option<String>,
+    /// The name the user gave someone else's calendar
+    pub summary_override: Option<String>,
+    /// "owner", "writer", "reader" or "freeBusyReader"
+    pub access_role: String,
+    #[serde(default)]
+    pub primary: bool,
+    /// The calendar's color, e.g. "#9fe1e7"; events without their own color take it
+    pub background_color: Option<String>,
+}
+
+impl CalendarListEntry {
+    pub fn name(&self) -> &str {
+        self.summary_override.as_deref().or(self.summary.as_deref()).unwrap_or(&self.id)
+    }
+
+    /// Whether this is the calendar configured as `calendar_id`, which may be "primary"
+    pub fn has_id(&self, calendar_id: &str) -> bool {
+        self.id == calendar_id || (self.primary && calendar_id == "primary")
+    }
+
+    /// Another person's calendar the account can only look at
+    pub fn is_shared_read_only(&self) -> bool {
+        matches!(self.access_role.as_str(), "reader" | "freeBusyReader")
+    }
+}
+
+/// Response from the freeBusy API, by queried calendar id
+#[derive(Debug, Deserialize)]
+pub struct FreeBusyResponse {
+    pub calendars: HashMap<String, FreeBusyCalendar>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct FreeBusyCalendar {
+    #[serde(default)]
+    pub busy: Vec<BusyPeriod>,
+    /// Why the calendar couldn't be read, e.g. "notFound" when it isn't shared with the account
+    #[serde(default)]
+    pub errors: Vec<FreeBusyError>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BusyPeriod {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FreeBusyError {
+    pub reason: String,
+}
+
+/// Response from calendarList.list API
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarListResponse {
+    pub items: Option<Vec<CalendarListEntry>>,
+    pub next_page_token: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,6 +364,10 @@ mod tests {
             attendees: None,
             conference_data: None,
             hangout_link: None,
+            ical_uid: None,
+            color_id: None,
+            event_type: None,
+            color: None,
         }
     }
 
@@ -261,6 +392,10 @@ mod tests {
             attendees: None,
             conference_data: None,
             hangout_link: None,
+            ical_uid: None,
+            color_id: None,
+            event_type: None,
+            color: None,
         }
     }
 
@@ -278,9 +413,9 @@ mod tests {
     }
 
     #[test]
-    fn test_time_str_all_day() {
+    fn test_start_time_all_day() {
         let event = make_all_day_event("Holiday", NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
-        assert_eq!(event.time_str(), "All day");
+        assert_eq!(event.start_time(), None);
     }
 
     #[test]
@@ -315,6 +450,7 @@ mod tests {
             response_status: Some("accepted".to_string()),
             is_self: Some(true),
             organizer: None,
+            resource: None,
         }]);
         assert!(event.is_accepted());
     }
@@ -328,6 +464,7 @@ mod tests {
             response_status: Some("declined".to_string()),
             is_self: Some(true),
             organizer: None,
+            resource: None,
         }]);
         assert!(!event.is_accepted());
     }
@@ -341,6 +478,7 @@ mod tests {
             response_status: Some("tentative".to_string()),
             is_self: Some(true),
             organizer: None,
+            resource: None,
         }]);
         assert!(!event.is_accepted());
     }
@@ -350,7 +488,7 @@ mod tests {
         let mut event = make_timed_event("Meeting", Utc::now());
         event.hangout_link = Some("https://meet.google.com/abc-defg-hij".to_string());
         assert_eq!(
-            event.meeting_url(),
+            event.meeting_url(&MeetingPatterns::default()),
             Some("https://meet.google.com/abc-defg-hij".to_string())
         );
     }
@@ -362,20 +500,34 @@ mod tests {
             entry_points: Some(vec![EntryPoint {
                 entry_point_type: Some("video".to_string()),
                 uri: Some("https://zoom.us/j/123456789".to_string()),
+                pin: None,
             }]),
         });
         assert_eq!(
-            event.meeting_url(),
+            event.meeting_url(&MeetingPatterns::default()),
             Some("https://zoom.us/j/123456789".to_string())
         );
     }
 
+    #[test]
+    fn test_dial_in_from_phone_entry_point() {
+        let mut event = make_timed_event("Meeting", Utc::now());
+        event.conference_data = Some(ConferenceData {
+            entry_points: Some(vec![EntryPoint {
+                entry_point_type: Some("phone".to_string()),
+                uri: Some("tel:+1-646-555-0123".to_string()),
+                pin: Some("123456789".to_string()),
+            }]),
+        });
+        assert_eq!(event.dial_in(), Some("tel:+16465550123,,123456789#".to_string()));
+    }
+
     #[test]
     fn test_meeting_url_from_location() {
         let mut event = make_timed_event("Meeting", Utc::now());
         event.location = Some("Join at https://zoom.us/j/987654321".to_string());
         assert_eq!(
-            event.meeting_url(),
+            event.meeting_url(&MeetingPatterns::default()),
             Some("https://zoom.us/j/987654321".to_string())
         );
     }
@@ -385,7 +537,7 @@ mod tests {
         let mut event = make_timed_event("Meeting", Utc::now());
         event.description = Some("Click here: https://teams.microsoft.com/l/meetup-join/123".to_string());
         assert_eq!(
-            event.meeting_url(),
+            event.meeting_url(&MeetingPatterns::default()),
             Some("https://teams.microsoft.com/l/meetup-join/123".to_string())
         );
     }
@@ -429,4 +581,31 @@ mod tests {
         };
         assert!(!still_valid.is_expired());
     }
+
+    #[test]
+    fn test_device_code_poll_interval() {
+        let with: DeviceCodeResponse = serde_json::from_str(
+            r#"{"device_code": "d", "user_code": "ABC-DEF", "verification_url": "https://www.google.com/device", "expires_in": 1800, "interval": 8}"#,
+        ).unwrap();
+        assert_eq!(with.interval, 8);
+        let without: DeviceCodeResponse = serde_json::from_str(
+            r#"{"device_code": "d", "user_code": "ABC-DEF", "verification_url": "https://www.google.com/device", "expires_in": 1800}"#,
+        ).unwrap();
+        assert_eq!(without.interval, 5);
+    }
+
+    #[test]
+    fn test_calendar_list_shared_entries() {
+        let list: CalendarListResponse = serde_json::from_str(r#"{"items": [
+            {"id": "me@example.com", "summary": "me@example.com", "accessRole": "owner", "primary": true},
+            {"id": "boss@example.com", "summary": "boss@example.com", "summaryOverride": "Manager", "accessRole": "freeBusyReader"},
+            {"id": "team@group.calendar.google.com", "summary": "Team", "accessRole": "writer"}
+        ]}"#).unwrap();
+        let items = list.items.unwrap();
+        let shared: Vec<&str> = items.iter().filter(|c| c.is_shared_read_only()).map(|c| c.name()).collect();
+        assert_eq!(shared, vec!["Manager"]);
+        assert!(items[0].primary);
+        assert!(items[0].has_id("primary"));
+        assert!(!items[2].has_id("primary"));
+    }
 }
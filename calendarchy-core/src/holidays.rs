@@ -0,0 +1,72 @@
+//! Public holidays overlaid from Google's public holiday calendars
+
+use crate::error::{check_google_response, Result};
+use crate::icloud::ICalEvent;
+use crate::logging::HttpLog;
+use chrono::NaiveDate;
+use reqwest::Client;
+
+/// Public ICS feed of a Google holiday calendar, e.g. region "en.bulgarian" or "en.usa"
+pub fn calendar_url(region: &str) -> String {
+    format!(
+        "https://calendar.google.com/calendar/ical/{}/public/basic.ics",
+        urlencoding::encode(&format!("{}#holiday@group.v.calendar.google.com", region))
+    )
+}
+
+/// Fetch the holidays of a region as (date, name) pairs
+pub async fn fetch(region: &str, log: &HttpLog) -> Result<Vec<(NaiveDate, String)>> {
+    let url = calendar_url(region);
+    let started = log.log_request("GET", &url);
+    let response = Client::new()
+        .get(&url)
+        .send()
+        .await
+        .inspect_err(|_| log.log_transport_error(&url, started))?;
+    log.log_response(response.status().as_u16(), &url, started);
+
+    let body = check_google_response(response, "Holiday calendar error", log).await?;
+    Ok(parse_holidays(&body))
+}
+
+/// Extract all-day entries from holiday ICS data
+fn parse_holidays(ical_data: &str) -> Vec<(NaiveDate, String)> {
+    ICalEvent::parse_ical_with_source(ical_data, String::new(), None)
+        .into_iter()
+        .filter(|e| e.start_time().is_none())
+        .map(|e| (e.start_date(), e.title().to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calendar_url() {
+        assert_eq!(
+            calendar_url("en.bulgarian"),
+            "https://calendar.google.com/calendar/ical/en.bulgarian%23holiday%40group.v.calendar.google.com/public/basic.ics"
+        );
+    }
+
+    #[test]
+    fn test_parse_holidays() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE:20260303\r\n\
+DTEND;VALUE=DATE:20260304\r\n\
+UID:20260303_liberation@google.com\r\n\
+SUMMARY:Liberation Day\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART:20260303T100000Z\r\n\
+UID:timed@google.com\r\n\
+SUMMARY:Not a holiday\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let holidays = parse_holidays(ics);
+        assert_eq!(holidays, vec![(NaiveDate::from_ymd_opt(2026, 3, 3).unwrap(), "Liberation Day".to_string())]);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::config::ICloudConfig;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// iCloud authentication helper
+pub struct ICloudAuth {
+    config: ICloudConfig,
+}
+
+impl ICloudAuth {
+    pub fn new(config: ICloudConfig) -> Self {
+        Self { config }
+    }
+
+    /// Generate Basic auth header value
+    pub fn auth_header(&self) -> String {
+        let credentials = format!("{}:{}", self.config.apple_id, self.config.app_password);
+        let encoded = STANDARD.encode(credentials.as_bytes());
+        format!("Basic {}", encoded)
+    }
+
+    /// The account's email, as which it is invited to events
+    pub fn apple_id(&self) -> &str {
+        &self.config.apple_id
+    }
+}
+
+/// Likely reasons iCloud refused the credentials, most specific first, each with what to
+/// do about it; `status` and `body` come from `CalendarchyError::ICloudCredentials`
+pub fn credential_guidance(config: &ICloudConfig, status: u16, body: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    if body.to_lowercase().contains("locked") {
+        lines.push("Your Apple ID looks locked. Unlock it at iforgot.apple.com, then create a new app-specific password.".to_string());
+    }
+    if !config.apple_id.contains('@') {
+        lines.push(format!("apple_id \"{}\" should be the email address of your Apple ID.", config.apple_id));
+    }
+    if !is_app_specific_password(&config.app_password) {
+        lines.push("app_password doesn't look like an app-specific password (xxxx-xxxx-xxxx-xxxx).".to_string());
+        lines.push("Your regular Apple ID password doesn't work here: turn on two-factor authentication,".to_string());
+        lines.push("then create one at appleid.apple.com > Sign-In and Security > App-Specific Passwords.".to_string());
+    }
+    if lines.is_empty() {
+        lines.push(match status {
+            403 => "iCloud refused access: check that Calendars is turned on for this Apple ID at icloud.com.",
+            _ => "The app-specific password was rejected; it may have been revoked. Create a new one at appleid.apple.com.",
+        }.to_string());
+    }
+    lines
+}
+
+/// Four groups of four letters, as Apple shows them; the dashes are optional
+fn is_app_specific_password(password: &str) -> bool {
+    let letters: Vec<char> = password.chars().filter(|c| *c != '-').collect();
+    letters.len() == 16 && letters.iter().all(|c| c.is_ascii_alphabetic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(apple_id: &str, app_password: &str) -> ICloudConfig {
+        ICloudConfig { apple_id: apple_id.to_string(), app_password: app_password.to_string() }
+    }
+
+    #[test]
+    fn test_credential_guidance() {
+        let regular_password = credential_guidance(&config("me@icloud.com", "hunter2!"), 401, "");
+        assert!(regular_password[0].contains("app-specific password"));
+
+        let revoked = credential_guidance(&config("me@icloud.com", "abcd-efgh-ijkl-mnop"), 401, "");
+        assert_eq!(revoked.len(), 1);
+        assert!(revoked[0].contains("revoked"));
+
+        let locked = credential_guidance(&config("me", "abcdefghijklmnop"), 403, "<error>Account locked</error>");
+        assert!(locked[0].contains("locked"));
+        assert!(locked[1].contains("email address"));
+    }
+}
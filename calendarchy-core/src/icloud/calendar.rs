@@ -0,0 +1,737 @@
+use crate::error::{
+    check_caldav_response, check_caldav_response_no_body, check_caldav_status, failure_body, CalendarchyError, Result,
+};
+use crate::icloud::auth::ICloudAuth;
+use crate::icloud::sync::{CalendarMirror, MirrorStore, MultistatusParser, SyncReport, SyncResource};
+use crate::icloud::types::{exclude_occurrence, set_attendee_partstat, ICalEvent};
+use crate::logging::HttpLog;
+use crate::utils::normalize_hex_color;
+use chrono::NaiveDate;
+use futures_util::TryStreamExt;
+use quick_xml::escape::escape;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use reqwest::{Client, Response, StatusCode};
+use tokio_util::io::StreamReader;
+
+pub const CALDAV_SERVER: &str = "https://caldav.icloud.com";
+
+/// Resources fetched per calendar-multiget REPORT
+const MULTIGET_BATCH: usize = 100;
+
+/// CalDAV client for iCloud Calendar
+pub struct CalDavClient {
+    client: Client,
+    log: HttpLog,
+    auth: ICloudAuth,
+}
+
+impl CalDavClient {
+    pub fn new(auth: ICloudAuth, log: HttpLog) -> Self {
+        Self {
+            client: Client::new(),
+            log,
+            auth,
+        }
+    }
+
+    /// Discover the user's principal URL and calendar home
+    pub async fn discover_calendars(&self) -> Result<Vec<CalendarInfo>> {
+        // Step 1: Get principal URL
+        let principal_url = self.discover_principal().await?;
+
+        // Step 2: Get calendar home set
+        let calendar_home = self.get_calendar_home(&principal_url).await?;
+
+        // Step 3: List calendars
+        let calendars = self.list_calendars(&calendar_home).await?;
+
+        Ok(calendars)
+    }
+
+    /// Fetch events for a date range
+    pub async fn fetch_events(
+        &self,
+        calendar_url: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<ICalEvent>> {
+        let mut events = Vec::new();
+        self.stream_events(calendar_url, start, end, |event| events.push(event)).await?;
+        Ok(events)
+    }
+
+    /// Fetch events for a date range, handing each to `on_event` as soon as its part of
+    /// the response has arrived instead of buffering the whole response first
+    pub async fn stream_events(
+        &self,
+        calendar_url: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+        on_event: impl FnMut(ICalEvent),
+    ) -> Result<()> {
+        self.query_events(calendar_url, calendar_query(start, end, None), start, end, on_event).await
+    }
+
+    /// Events of a date range whose title contains `query` (case-insensitive), found
+    /// by the server with a text-match filter
+    pub async fn search_events(
+        &self,
+        calendar_url: &str,
+        query: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<ICalEvent>> {
+        let mut events = Vec::new();
+        let body = calendar_query(start, end, Some(query));
+        self.query_events(calendar_url, body, start, end, |event| events.push(event)).await?;
+        Ok(events)
+    }
+
+    /// Run a calendar-query REPORT, handing each event of the response to `on_event`
+    async fn query_events(
+        &self,
+        calendar_url: &str,
+        body: String,
+        start: NaiveDate,
+        end: NaiveDate,
+        mut on_event: impl FnMut(ICalEvent),
+    ) -> Result<()> {
+        let response = self.report(calendar_url, body, true).await?;
+        let response = check_caldav_status(response, "REPORT failed", &self.log).await?;
+        read_multistatus(response, |resource| {
+            expanded_events(calendar_url, resource, start, end).into_iter().for_each(&mut on_event)
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Fetch events for a date range from the calendar's mirror. The first fetch of a
+    /// refresh brings it up to date with a sync-collection REPORT, so only resources
+    /// changed since are downloaded; months it doesn't hold yet are filled in with a
+    /// time-range query. Servers that refuse the sync REPORT get the query every refresh.
+    pub async fn sync_events(
+        &self,
+        mirrors: &MirrorStore,
+        calendar_url: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<ICalEvent>> {
+        let generation = mirrors.generation();
+        let slot = mirrors.slot(calendar_url);
+        let mut guard = slot.lock().await;
+        let slot = &mut *guard;
+        let mirror = slot.mirror.get_or_insert_with(|| CalendarMirror::load(calendar_url, &self.log));
+
+        let mut changed = false;
+        if slot.synced != Some(generation) {
+            self.sync_mirror(mirror, calendar_url).await?;
+            slot.synced = Some(generation);
+            changed = true;
+        }
+        if !mirror.covers(start, end) {
+            let response = self.report(calendar_url, calendar_query(start, end, None), true).await?;
+            let response = check_caldav_status(response, "REPORT failed", &self.log).await?;
+            read_multistatus(response, |resource| mirror.apply(calendar_url, resource)).await?;
+            mirror.covered.push((start, end));
+            changed = true;
+        }
+        if changed && mirror.token.is_some() {
+            mirror.save(calendar_url, &self.log);
+        }
+
+        Ok(mirror.events(start, end))
+    }
+
+    /// Bring `mirror` up to the server's latest sync token. Without a token there's
+    /// nothing to catch up on, so only a token is asked for and the months are filled
+    /// in as they're fetched.
+    async fn sync_mirror(&self, mirror: &mut CalendarMirror, calendar_url: &str) -> Result<()> {
+        let mut report = match mirror.token.as_deref() {
+            Some(token) => self.sync_collection(calendar_url, Some(token)).await?,
+            None => None,
+        };
+        if report.is_none() {
+            // Nothing mirrored yet, or an expired token was refused: start over
+            mirror.clear();
+            report = self.sync_collection(calendar_url, None).await?;
+        }
+        // Without incremental sync the mirror stays empty, so every refresh queries again
+        let Some(mut report) = report else { return Ok(()) };
+        let listing = mirror.token.is_none();
+
+        loop {
+            if !listing {
+                let outdated = mirror.outdated(&report.resources);
+                for hrefs in outdated.chunks(MULTIGET_BATCH) {
+                    self.multiget(calendar_url, hrefs, |resource| mirror.apply(calendar_url, resource)).await?;
+                }
+                for resource in report.resources {
+                    mirror.apply(calendar_url, resource);
+                }
+            }
+            mirror.token = report.token;
+            if !report.truncated {
+                break;
+            }
+            match self.sync_collection(calendar_url, mirror.token.as_deref()).await? {
+                Some(next) => report = next,
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Changes since `token` (everything without one) with their etags; None if the
+    /// server refused the REPORT, e.g. for an expired token
+    async fn sync_collection(&self, calendar_url: &str, token: Option<&str>) -> Result<Option<SyncReport>> {
+        let body = format!(
+            r#"<?xml version="1.0" encoding="utf-8" ?>
+<d:sync-collection xmlns:d="DAV:">
+  <d:sync-token>{}</d:sync-token>
+  <d:sync-level>1</d:sync-level>
+  <d:prop>
+    <d:getetag/>
+  </d:prop>
+</d:sync-collection>"#,
+            escape(token.unwrap_or_default())
+        );
+
+        let response = self.report(calendar_url, body, false).await?;
+        if response.status() != StatusCode::UNAUTHORIZED && !response.status().is_success() {
+            failure_body(response, &self.log).await;
+            return Ok(None);
+        }
+        let response = check_caldav_status(response, "Sync failed", &self.log).await?;
+        let mut resources = Vec::new();
+        let parser = read_multistatus(response, |resource| resources.push(resource)).await?;
+        Ok(Some(SyncReport { resources, token: parser.token, truncated: parser.truncated }))
+    }
+
+    /// Fetch the data of the resources at `hrefs` with a calendar-multiget REPORT
+    async fn multiget(&self, calendar_url: &str, hrefs: &[&str], on_resource: impl FnMut(SyncResource)) -> Result<()> {
+        let hrefs: String = hrefs.iter().map(|href| format!("  <d:href>{}</d:href>\n", escape(*href))).collect();
+        let body = format!(
+            r#"<?xml version="1.0" encoding="utf-8" ?>
+<c:calendar-multiget xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop>
+    <d:getetag/>
+    <c:calendar-data/>
+  </d:prop>
+{}</c:calendar-multiget>"#,
+            hrefs
+        );
+
+        let response = self.report(calendar_url, body, true).await?;
+        let response = check_caldav_status(response, "Multiget failed", &self.log).await?;
+        read_multistatus(response, on_resource).await?;
+        Ok(())
+    }
+
+    /// Send a REPORT request, with `Depth: 1` when `depth` is set
+    async fn report(&self, calendar_url: &str, body: String, depth: bool) -> Result<Response> {
+        let started = self.log.log_request("REPORT", calendar_url);
+        let mut request = self
+            .client
+            .request(reqwest::Method::from_bytes(b"REPORT").unwrap(), calendar_url)
+            .header("Authorization", self.auth.auth_header())
+            .header("Content-Type", "application/xml; charset=utf-8");
+        if depth {
+            request = request.header("Depth", "1");
+        }
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(calendar_url, started))?;
+        self.log.log_response(response.status().as_u16(), calendar_url, started);
+        Ok(response)
+    }
+
+    /// Discover principal URL
+    async fn discover_principal(&self) -> Result<String> {
+        let body = r#"<?xml version="1.0" encoding="utf-8" ?>
+<d:propfind xmlns:d="DAV:">
+  <d:prop>
+    <d:current-user-principal/>
+  </d:prop>
+</d:propfind>"#;
+
+        let started = self.log.log_request("PROPFIND", CALDAV_SERVER);
+        let response = self
+            .client
+            .request(reqwest::Method::from_bytes(b"PROPFIND").unwrap(), CALDAV_SERVER)
+            .header("Authorization", self.auth.auth_header())
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .header("Depth", "0")
+            .body(body)
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(CALDAV_SERVER, started))?;
+        self.log.log_response(response.status().as_u16(), CALDAV_SERVER, started);
+
+        // Before anything else is read, a 403 is about the account rather than a calendar
+        if response.status() == StatusCode::FORBIDDEN {
+            let body = failure_body(response, &self.log).await;
+            return Err(CalendarchyError::ICloudCredentials { status: 403, body });
+        }
+        let xml = check_caldav_response(response, "Principal discovery failed", &self.log).await?;
+        extract_href(&xml, "current-user-principal")
+            .ok_or_else(|| CalendarchyError::CalDav("Could not find principal URL".to_string()))
+    }
+
+    /// Get calendar home set from principal
+    async fn get_calendar_home(&self, principal_url: &str) -> Result<String> {
+        let url = self.resolve_url(principal_url);
+
+        let body = r#"<?xml version="1.0" encoding="utf-8" ?>
+<d:propfind xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop>
+    <c:calendar-home-set/>
+  </d:prop>
+</d:propfind>"#;
+
+        let started = self.log.log_request("PROPFIND", &url);
+        let response = self
+            .client
+            .request(reqwest::Method::from_bytes(b"PROPFIND").unwrap(), &url)
+            .header("Authorization", self.auth.auth_header())
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .header("Depth", "0")
+            .body(body)
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+        self.log.log_response(response.status().as_u16(), &url, started);
+
+        let xml = check_caldav_response(response, "Calendar home discovery failed", &self.log).await?;
+        extract_href(&xml, "calendar-home-set")
+            .ok_or_else(|| CalendarchyError::CalDav("Could not find calendar home".to_string()))
+    }
+
+    /// List calendars in calendar home
+    async fn list_calendars(&self, calendar_home: &str) -> Result<Vec<CalendarInfo>> {
+        let url = self.resolve_url(calendar_home);
+
+        let body = r#"<?xml version="1.0" encoding="utf-8" ?>
+<d:propfind xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav" xmlns:cs="http://calendarserver.org/ns/" xmlns:ical="http://apple.com/ns/ical/">
+  <d:prop>
+    <d:displayname/>
+    <d:resourcetype/>
+    <cs:getctag/>
+    <ical:calendar-color/>
+  </d:prop>
+</d:propfind>"#;
+
+        let started = self.log.log_request("PROPFIND", &url);
+        let response = self
+            .client
+            .request(reqwest::Method::from_bytes(b"PROPFIND").unwrap(), &url)
+            .header("Authorization", self.auth.auth_header())
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .header("Depth", "1")
+            .body(body)
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+        self.log.log_response(response.status().as_u16(), &url, started);
+
+        let xml = check_caldav_response(response, "Calendar list failed", &self.log).await?;
+        Ok(self.parse_calendar_list(&xml))
+    }
+
+    /// Parse calendar list response
+    fn parse_calendar_list(&self, xml: &str) -> Vec<CalendarInfo> {
+        let mut calendars = Vec::new();
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().trim_text(true);
+
+        let mut buf = Vec::new();
+        let mut current_href: Option<String> = None;
+        let mut current_name: Option<String> = None;
+        let mut current_color: Option<String> = None;
+        let mut is_calendar = false;
+        let mut in_response = false;
+        let mut current_tag = String::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) => {
+                    let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                    current_tag = name.clone();
+
+                    if name == "response" {
+                        in_response = true;
+                        current_href = None;
+                        current_name = None;
+                        current_color = None;
+                        is_calendar = false;
+                    } else if name == "calendar" && in_response {
+                        is_calendar = true;
+                    }
+                }
+                Ok(Event::End(e)) => {
+                    let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                    if name == "response" && in_response {
+                        if is_calendar
+                            && let Some(href) = current_href.take() {
+                                calendars.push(CalendarInfo {
+                                    url: self.resolve_url(&href),
+                                    name: current_name.take(),
+                                    color: current_color.take(),
+                                });
+                            }
+                        in_response = false;
+                    }
+                }
+                Ok(Event::Text(e)) if in_response => {
+                    let text = e.unescape().unwrap_or_default().to_string();
+                    if current_tag == "href" && current_href.is_none() {
+                        current_href = Some(text);
+                    } else if current_tag == "displayname" {
+                        current_name = Some(text);
+                    } else if current_tag == "calendar-color" {
+                        current_color = normalize_hex_color(&text);
+                    }
+                }
+                Ok(Event::Empty(e)) => {
+                    let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                    if name == "calendar" && in_response {
+                        is_calendar = true;
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(_) => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        calendars
+    }
+
+    /// Resolve relative URL to absolute
+    fn resolve_url(&self, path: &str) -> String {
+        if path.starts_with("http") {
+            path.to_string()
+        } else {
+            format!("{}{}", CALDAV_SERVER, path)
+        }
+    }
+
+    /// Create an event from an iCalendar document stored as `<uid>.ics`
+    pub async fn create_event(&self, calendar_url: &str, event_uid: &str, ical: String) -> Result<()> {
+        let event_url = format!("{}/{}.ics", calendar_url.trim_end_matches('/'), event_uid);
+
+        let started = self.log.log_request("PUT", &event_url);
+        let response = self
+            .client
+            .put(&event_url)
+            .header("Authorization", self.auth.auth_header())
+            .header("Content-Type", "text/calendar; charset=utf-8")
+            .header("If-None-Match", "*") // Never overwrite an existing event
+            .body(ical)
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&event_url, started))?;
+        self.log.log_response(response.status().as_u16(), &event_url, started);
+
+        check_caldav_response_no_body(response, "Failed to create event", &self.log).await
+    }
+
+    /// Fetch the iCalendar document stored as `<uid>.ics`, with its etag
+    pub async fn get_event(&self, calendar_url: &str, event_uid: &str) -> Result<(String, Option<String>)> {
+        let event_url = format!("{}/{}.ics", calendar_url.trim_end_matches('/'), event_uid);
+
+        let started = self.log.log_request("GET", &event_url);
+        let response = self
+            .client
+            .get(&event_url)
+            .header("Authorization", self.auth.auth_header())
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&event_url, started))?;
+        self.log.log_response(response.status().as_u16(), &event_url, started);
+
+        let etag = response
+            .headers()
+            .get("ETag")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.trim_matches('"').to_string());
+        let ical = check_caldav_response(response, "Failed to get event", &self.log).await?;
+        Ok((ical, etag))
+    }
+
+    /// Replace the iCalendar document stored as `<uid>.ics`, unless it changed since `etag`
+    pub async fn update_event(
+        &self,
+        calendar_url: &str,
+        event_uid: &str,
+        ical: String,
+        etag: Option<&str>,
+    ) -> Result<()> {
+        let event_url = format!("{}/{}.ics", calendar_url.trim_end_matches('/'), event_uid);
+
+        let started = self.log.log_request("PUT", &event_url);
+        let mut request = self
+            .client
+            .put(&event_url)
+            .header("Authorization", self.auth.auth_header())
+            .header("Content-Type", "text/calendar; charset=utf-8");
+        if let Some(tag) = etag {
+            request = request.header("If-Match", format!("\"{}\"", tag));
+        }
+
+        let response = request
+            .body(ical)
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&event_url, started))?;
+        self.log.log_response(response.status().as_u16(), &event_url, started);
+
+        check_caldav_response(response, "Failed to update event", &self.log).await.map(|_| ())
+    }
+
+    /// Answer an invitation: set the account's PARTSTAT ("ACCEPTED" or "DECLINED") in the
+    /// stored document and put it back, unless it changed meanwhile. The server sends the
+    /// organizer the reply (implicit CalDAV scheduling).
+    pub async fn respond_to_event(&self, calendar_url: &str, event_uid: &str, partstat: &str) -> Result<()> {
+        let (ical, etag) = self.get_event(calendar_url, event_uid).await?;
+        let Some(ical) = set_attendee_partstat(&ical, self.auth.apple_id(), partstat) else {
+            return Err(CalendarchyError::CalDav(format!("{} is not invited to this event", self.auth.apple_id())));
+        };
+        self.update_event(calendar_url, event_uid, ical, etag.as_deref()).await
+    }
+
+    /// Skip one occurrence of a repeating event, keeping the rest of the series
+    pub async fn delete_occurrence(&self, calendar_url: &str, event_uid: &str, occurrence: &str) -> Result<()> {
+        let (ical, etag) = self.get_event(calendar_url, event_uid).await?;
+        let Some(ical) = exclude_occurrence(&ical, occurrence) else {
+            return Err(CalendarchyError::CalDav("The repeating event was not found".to_string()));
+        };
+        self.update_event(calendar_url, event_uid, ical, etag.as_deref()).await
+    }
+
+    /// Delete an event by its UID
+    pub async fn delete_event(
+        &self,
+        calendar_url: &str,
+        event_uid: &str,
+        etag: Option<&str>,
+    ) -> Result<()> {
+        // Construct event URL: calendar_url + uid + ".ics"
+        let event_url = format!(
+            "{}{}.ics",
+            calendar_url.trim_end_matches('/').to_string() + "/",
+            event_uid
+        );
+
+        let started = self.log.log_request("DELETE", &event_url);
+        let mut request = self
+            .client
+            .delete(&event_url)
+            .header("Authorization", self.auth.auth_header());
+
+        // Use etag for conditional delete if available
+        if let Some(tag) = etag {
+            request = request.header("If-Match", format!("\"{}\"", tag));
+        }
+
+        let response = request
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&event_url, started))?;
+        self.log.log_response(response.status().as_u16(), &event_url, started);
+
+        check_caldav_response_no_body(response, "Failed to delete event", &self.log).await
+    }
+}
+
+/// Extract href from XML response
+pub(super) fn extract_href(xml: &str, parent_tag: &str) -> Option<String> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut buf = Vec::new();
+    let mut in_parent = false;
+    let mut in_href = false;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                if name == parent_tag {
+                    in_parent = true;
+                } else if name == "href" && in_parent {
+                    in_href = true;
+                }
+            }
+            Ok(Event::End(e)) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                if name == parent_tag {
+                    in_parent = false;
+                } else if name == "href" {
+                    in_href = false;
+                }
+            }
+            Ok(Event::Text(e)) if in_href => {
+                return Some(e.unescape().unwrap_or_default().to_string());
+            }
+            Ok(Event::Eof) => break,
+            Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    None
+}
+
+/// Body of a calendar-query REPORT for the events between `start` and `end`, narrowed
+/// to titles containing `text` when given
+fn calendar_query(start: NaiveDate, end: NaiveDate, text: Option<&str>) -> String {
+    let start_str = format!("{}T000000Z", start.format("%Y%m%d"));
+    let end_str = format!("{}T235959Z", end.format("%Y%m%d"));
+    let text_match = text
+        .map(|text| format!(
+            r#"
+        <c:prop-filter name="SUMMARY">
+          <c:text-match>{}</c:text-match>
+        </c:prop-filter>"#,
+            escape(text)
+        ))
+        .unwrap_or_default();
+
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8" ?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop>
+    <d:getetag/>
+    <c:calendar-data/>
+  </d:prop>
+  <c:filter>
+    <c:comp-filter name="VCALENDAR">
+      <c:comp-filter name="VEVENT">
+        <c:time-range start="{}" end="{}"/>{}
+      </c:comp-filter>
+    </c:comp-filter>
+  </c:filter>
+</c:calendar-query>"#,
+        start_str, end_str, text_match
+    )
+}
+
+/// Read a multistatus as it arrives, handing over each `<response>` once it's complete
+async fn read_multistatus(response: Response, mut on_resource: impl FnMut(SyncResource)) -> Result<MultistatusParser> {
+    let body = StreamReader::new(response.bytes_stream().map_err(std::io::Error::other));
+    let mut reader = Reader::from_reader(body);
+    reader.config_mut().trim_text(true);
+
+    let mut parser = MultistatusParser::default();
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into_async(&mut buf).await {
+            Ok(Event::Eof) => break,
+            Ok(event) => parser.handle(event, &mut on_resource),
+            Err(e) => return Err(CalendarchyError::CalDav(format!("XML parse error: {}", e))),
+        }
+        buf.clear();
+    }
+    Ok(parser)
+}
+
+/// Events of a queried resource between `start` and `end`; a repeating event comes with
+/// its overrides in the same resource
+fn expanded_events(calendar_url: &str, resource: SyncResource, start: NaiveDate, end: NaiveDate) -> Vec<ICalEvent> {
+    let Some(ical) = resource.ical else { return Vec::new() };
+    let events = ICalEvent::parse_ical_with_source(&ical, calendar_url.to_string(), resource.etag);
+    ICalEvent::expand_recurrences(events, start, end)
+}
+
+/// Information about a calendar
+#[derive(Debug, Clone)]
+pub struct CalendarInfo {
+    pub url: String,
+    pub name: Option<String>,
+    /// Apple calendar-color as "#rrggbb"
+    pub color: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    /// Run a REPORT body through the parser the way `stream_events` does, reading it a
+    /// few bytes at a time
+    fn parse_report(xml: &str) -> Vec<ICalEvent> {
+        let mut reader = Reader::from_reader(BufReader::with_capacity(16, xml.as_bytes()));
+        reader.config_mut().trim_text(true);
+        let month = (NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2026, 1, 31).unwrap());
+        let calendar_url = "https://caldav.icloud.com/123/calendars/home/";
+        let mut parser = MultistatusParser::default();
+        let mut events = Vec::new();
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf).unwrap() {
+                Event::Eof => break,
+                event => parser.handle(event, &mut |r| events.extend(expanded_events(calendar_url, r, month.0, month.1))),
+            }
+            buf.clear();
+        }
+        events
+    }
+
+    #[test]
+    fn test_parse_report_in_small_chunks() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:cal="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/123/calendars/home/a.ics</d:href>
+    <d:propstat><d:prop>
+      <d:getetag>"etag-a"</d:getetag>
+      <cal:calendar-data>BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:a
+SUMMARY:Standup &amp; planning
+DTSTART:20260115T093000Z
+END:VEVENT
+END:VCALENDAR</cal:calendar-data>
+    </d:prop></d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/123/calendars/home/b.ics</d:href>
+    <d:propstat><d:prop>
+      <cal:calendar-data><![CDATA[BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:b
+SUMMARY:Review
+DTSTART;VALUE=DATE:20260116
+END:VEVENT
+END:VCALENDAR]]></cal:calendar-data>
+      <d:getetag>"etag-b"</d:getetag>
+    </d:prop></d:propstat>
+  </d:response>
+</d:multistatus>"#;
+
+        let events = parse_report(xml);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].title(), "Standup & planning");
+        assert_eq!(events[0].etag.as_deref(), Some("etag-a"));
+        assert_eq!(events[0].calendar_url, "https://caldav.icloud.com/123/calendars/home/");
+        // The etag may follow the calendar data
+        assert_eq!(events[1].uid, "b");
+        assert_eq!(events[1].etag.as_deref(), Some("etag-b"));
+    }
+
+    #[test]
+    fn test_calendar_query_text_match() {
+        let month = (NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2026, 1, 31).unwrap());
+        let listing = calendar_query(month.0, month.1, None);
+        assert!(listing.contains(r#"<c:time-range start="20260101T000000Z" end="20260131T235959Z"/>"#));
+        assert!(!listing.contains("text-match"));
+
+        let search = calendar_query(month.0, month.1, Some("R&D <sync>"));
+        assert!(search.contains("<c:text-match>R&amp;D &lt;sync&gt;</c:text-match>"));
+    }
+}
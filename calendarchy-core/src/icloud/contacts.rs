@@ -0,0 +1,240 @@
+use crate::error::{check_caldav_response, CalendarchyError, Result};
+use crate::icloud::auth::ICloudAuth;
+use crate::icloud::calendar::extract_href;
+use crate::logging::HttpLog;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use reqwest::Client;
+
+pub const CARDDAV_SERVER: &str = "https://contacts.icloud.com";
+
+/// CardDAV client for the iCloud address book
+pub struct CardDavClient {
+    client: Client,
+    log: HttpLog,
+    auth: ICloudAuth,
+}
+
+impl CardDavClient {
+    pub fn new(auth: ICloudAuth, log: HttpLog) -> Self {
+        Self {
+            client: Client::new(),
+            log,
+            auth,
+        }
+    }
+
+    /// Fetch the raw vCards of every address book in the account
+    pub async fn fetch_vcards(&self) -> Result<Vec<String>> {
+        let principal = self
+            .propfind(CARDDAV_SERVER, "<d:current-user-principal/>", "0", "Principal discovery failed")
+            .await
+            .and_then(|xml| {
+                extract_href(&xml, "current-user-principal")
+                    .ok_or_else(|| CalendarchyError::CalDav("Could not find principal URL".to_string()))
+            })?;
+
+        let home = self
+            .propfind(&resolve_url(&principal), "<card:addressbook-home-set/>", "0", "Address book home discovery failed")
+            .await
+            .and_then(|xml| {
+                extract_href(&xml, "addressbook-home-set")
+                    .ok_or_else(|| CalendarchyError::CalDav("Could not find address book home".to_string()))
+            })?;
+
+        let listing = self
+            .propfind(&resolve_url(&home), "<d:resourcetype/>", "1", "Address book list failed")
+            .await?;
+
+        let mut vcards = Vec::new();
+        for addressbook in parse_addressbooks(&listing) {
+            vcards.extend(self.fetch_addressbook(&resolve_url(&addressbook)).await?);
+        }
+        Ok(vcards)
+    }
+
+    /// Fetch all vCards of one address book
+    async fn fetch_addressbook(&self, url: &str) -> Result<Vec<String>> {
+        let body = r#"<?xml version="1.0" encoding="utf-8" ?>
+<card:addressbook-query xmlns:d="DAV:" xmlns:card="urn:ietf:params:xml:ns:carddav">
+  <d:prop>
+    <card:address-data>
+      <card:prop name="FN"/>
+      <card:prop name="EMAIL"/>
+    </card:address-data>
+  </d:prop>
+</card:addressbook-query>"#;
+
+        let started = self.log.log_request("REPORT", url);
+        let response = self
+            .client
+            .request(reqwest::Method::from_bytes(b"REPORT").unwrap(), url)
+            .header("Authorization", self.auth.auth_header())
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .header("Depth", "1")
+            .body(body)
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(url, started))?;
+        self.log.log_response(response.status().as_u16(), url, started);
+
+        let xml = check_caldav_response(response, "Address book REPORT failed", &self.log).await?;
+        Ok(parse_address_data(&xml))
+    }
+
+    async fn propfind(&self, url: &str, prop: &str, depth: &str, context: &str) -> Result<String> {
+        let body = format!(
+            r#"<?xml version="1.0" encoding="utf-8" ?>
+<d:propfind xmlns:d="DAV:" xmlns:card="urn:ietf:params:xml:ns:carddav">
+  <d:prop>
+    {}
+  </d:prop>
+</d:propfind>"#,
+            prop
+        );
+
+        let started = self.log.log_request("PROPFIND", url);
+        let response = self
+            .client
+            .request(reqwest::Method::from_bytes(b"PROPFIND").unwrap(), url)
+            .header("Authorization", self.auth.auth_header())
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .header("Depth", depth)
+            .body(body)
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(url, started))?;
+        self.log.log_response(response.status().as_u16(), url, started);
+
+        check_caldav_response(response, context, &self.log).await
+    }
+}
+
+/// Resolve relative URL to absolute
+fn resolve_url(path: &str) -> String {
+    if path.starts_with("http") {
+        path.to_string()
+    } else {
+        format!("{}{}", CARDDAV_SERVER, path)
+    }
+}
+
+/// Hrefs of the responses whose resourcetype is an address book
+fn parse_addressbooks(xml: &str) -> Vec<String> {
+    let mut addressbooks = Vec::new();
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut buf = Vec::new();
+    let mut current_href: Option<String> = None;
+    let mut is_addressbook = false;
+    let mut current_tag = String::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => {
+                current_tag = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                if current_tag == "response" {
+                    current_href = None;
+                    is_addressbook = false;
+                }
+            }
+            Ok(Event::Empty(e)) if e.local_name().as_ref() == b"addressbook" => {
+                is_addressbook = true;
+            }
+            Ok(Event::End(e)) => {
+                if e.local_name().as_ref() == b"response"
+                    && is_addressbook
+                    && let Some(href) = current_href.take()
+                {
+                    addressbooks.push(href);
+                }
+                current_tag.clear();
+            }
+            Ok(Event::Text(e)) if current_tag == "href" && current_href.is_none() => {
+                current_href = Some(e.unescape().unwrap_or_default().to_string());
+            }
+            Ok(Event::Eof) => break,
+            Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    addressbooks
+}
+
+/// Contents of every address-data element in a REPORT response
+fn parse_address_data(xml: &str) -> Vec<String> {
+    let mut vcards = Vec::new();
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut buf = Vec::new();
+    let mut current: Option<String> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"address-data" => {
+                current = Some(String::new());
+            }
+            Ok(Event::End(e)) if e.local_name().as_ref() == b"address-data" => {
+                if let Some(vcard) = current.take() {
+                    vcards.push(vcard);
+                }
+            }
+            Ok(Event::Text(e)) => {
+                if let Some(ref mut vcard) = current {
+                    vcard.push_str(&e.unescape().unwrap_or_default());
+                }
+            }
+            Ok(Event::CData(e)) => {
+                if let Some(ref mut vcard) = current {
+                    vcard.push_str(&String::from_utf8_lossy(&e));
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    vcards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_addressbooks() {
+        let xml = r#"<d:multistatus xmlns:d="DAV:" xmlns:card="urn:ietf:params:xml:ns:carddav">
+  <d:response>
+    <d:href>/123/carddavhome/</d:href>
+    <d:propstat><d:prop><d:resourcetype><d:collection/></d:resourcetype></d:prop></d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/123/carddavhome/card/</d:href>
+    <d:propstat><d:prop><d:resourcetype><d:collection/><card:addressbook/></d:resourcetype></d:prop></d:propstat>
+  </d:response>
+</d:multistatus>"#;
+        assert_eq!(parse_addressbooks(xml), vec!["/123/carddavhome/card/"]);
+    }
+
+    #[test]
+    fn test_parse_address_data() {
+        let xml = r#"<d:multistatus xmlns:d="DAV:" xmlns:card="urn:ietf:params:xml:ns:carddav">
+  <d:response>
+    <d:href>/123/carddavhome/card/a.vcf</d:href>
+    <d:propstat><d:prop><card:address-data>BEGIN:VCARD
+FN:John Smith
+EMAIL:jsmith@example.com
+END:VCARD</card:address-data></d:prop></d:propstat>
+  </d:response>
+</d:multistatus>"#;
+        let vcards = parse_address_data(xml);
+        assert_eq!(vcards.len(), 1);
+        assert!(vcards[0].contains("FN:John Smith"));
+    }
+}
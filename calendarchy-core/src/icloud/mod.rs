@@ -0,0 +1,16 @@
+mod auth;
+mod calendar;
+mod contacts;
+mod sync;
+mod timezone;
+mod types;
+
+pub use auth::{credential_guidance, ICloudAuth};
+pub use calendar::{CalDavClient, CALDAV_SERVER};
+pub use contacts::CardDavClient;
+pub use sync::MirrorStore;
+pub use types::ICalEvent;
+
+// These are only used in tests
+#[cfg(test)]
+pub use types::{EventTime, ICalAttendee};
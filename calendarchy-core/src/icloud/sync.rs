@@ -0,0 +1,371 @@
+//! Incremental CalDAV refreshes with sync-collection REPORTs (RFC 6578): each calendar's
+//! resources are mirrored in memory, and on disk across runs, with the sync token they
+//! were current at, so a refresh only downloads the resources that changed since
+
+use crate::icloud::types::ICalEvent;
+use crate::logging::HttpLog;
+use chrono::NaiveDate;
+use quick_xml::events::Event;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// The mirrors of every calendar, shared by clones. A calendar is synced at most once per
+/// refresh; fetches of its other months meanwhile read what's mirrored, and fetches of the
+/// same calendar wait for each other instead of racing on its sync token.
+#[derive(Clone, Default)]
+pub struct MirrorStore {
+    generation: Arc<AtomicU64>,
+    calendars: Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<MirrorSlot>>>>>,
+}
+
+#[derive(Default)]
+pub(super) struct MirrorSlot {
+    /// Loaded from disk on first use
+    pub mirror: Option<CalendarMirror>,
+    /// Refresh the mirror was last synced in
+    pub synced: Option<u64>,
+}
+
+impl MirrorStore {
+    /// Make the next fetch of each calendar ask the server for changes again
+    pub fn refresh(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    pub(super) fn slot(&self, calendar_url: &str) -> Arc<tokio::sync::Mutex<MirrorSlot>> {
+        let mut calendars = self.calendars.lock().unwrap_or_else(|e| e.into_inner());
+        calendars.entry(calendar_url.to_string()).or_default().clone()
+    }
+}
+
+/// A calendar's resources as of `token`, cached in ~/.cache/calendarchy/caldav/
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct CalendarMirror {
+    pub token: Option<String>,
+    /// Resources by href
+    pub resources: BTreeMap<String, MirroredResource>,
+    /// Date ranges whose resources were all fetched; changes keep them current after that
+    #[serde(default)]
+    pub covered: Vec<(NaiveDate, NaiveDate)>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct MirroredResource {
+    pub etag: Option<String>,
+    pub ical: String,
+    /// `ical` parsed, so months are read without parsing it again
+    #[serde(skip)]
+    pub events: Vec<ICalEvent>,
+}
+
+impl MirroredResource {
+    fn new(calendar_url: &str, etag: Option<String>, ical: String) -> Self {
+        let events = ICalEvent::parse_ical_with_source(&ical, calendar_url.to_string(), etag.clone());
+        Self { etag, ical, events }
+    }
+}
+
+impl CalendarMirror {
+    fn path(calendar_url: &str) -> Option<PathBuf> {
+        let name: String = calendar_url
+            .trim_end_matches('/')
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        dirs::cache_dir().map(|p| p.join("calendarchy").join("caldav").join(format!("{}.json", name)))
+    }
+
+    /// Load the mirror of a calendar; a missing or unreadable one starts over empty
+    pub fn load(calendar_url: &str, log: &HttpLog) -> Self {
+        let Some(path) = Self::path(calendar_url) else { return Self::default() };
+        let json = match fs::read_to_string(&path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                log.log_note(&format!("Could not read {}: {}", path.display(), e));
+                return Self::default();
+            }
+        };
+        match serde_json::from_str::<Self>(&json) {
+            Ok(mut mirror) => {
+                for resource in mirror.resources.values_mut() {
+                    resource.events = ICalEvent::parse_ical_with_source(&resource.ical, calendar_url.to_string(), resource.etag.clone());
+                }
+                mirror
+            }
+            Err(e) => {
+                log.log_note(&format!("Ignoring unreadable {}: {}", path.display(), e));
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, calendar_url: &str, log: &HttpLog) {
+        let Some(path) = Self::path(calendar_url) else { return };
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| serde_json::to_string(self).map_err(std::io::Error::other))
+            .and_then(|json| fs::write(&path, json));
+        if let Err(e) = written {
+            log.log_note(&format!("Could not write {}: {}", path.display(), e));
+        }
+    }
+
+    /// Forget everything, e.g. when the server no longer accepts the sync token
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Whether the resources of every day from `start` to `end` are mirrored
+    pub fn covers(&self, start: NaiveDate, end: NaiveDate) -> bool {
+        self.covered.iter().any(|&(from, to)| from <= start && end <= to)
+    }
+
+    /// Hrefs in `resources` whose data is missing or older than the listed etag
+    pub fn outdated<'a>(&self, resources: &'a [SyncResource]) -> Vec<&'a str> {
+        resources
+            .iter()
+            .filter(|r| !r.removed)
+            .filter(|r| self.resources.get(&r.href).is_none_or(|m| r.etag.is_none() || m.etag != r.etag))
+            .map(|r| r.href.as_str())
+            .collect()
+    }
+
+    /// Drop a removed resource, or store one that came with its data
+    pub fn apply(&mut self, calendar_url: &str, resource: SyncResource) {
+        if resource.removed {
+            self.resources.remove(&resource.href);
+        } else if let Some(ical) = resource.ical {
+            self.resources.insert(resource.href, MirroredResource::new(calendar_url, resource.etag, ical));
+        }
+    }
+
+    /// Events falling between `start` and `end`, with repeating events expanded
+    pub fn events(&self, start: NaiveDate, end: NaiveDate) -> Vec<ICalEvent> {
+        let mut events = Vec::new();
+        for resource in self.resources.values() {
+            // A repeating event comes with its overrides in the same resource
+            events.extend(
+                ICalEvent::expand_recurrences(resource.events.clone(), start, end)
+                    .into_iter()
+                    .filter(|e| e.start_date() <= end && e.end_date() >= start),
+            );
+        }
+        events
+    }
+}
+
+/// One `<response>` of a multistatus: a calendar-query, sync-collection or
+/// calendar-multiget REPORT
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct SyncResource {
+    pub href: String,
+    pub etag: Option<String>,
+    /// Not in sync-collection responses
+    pub ical: Option<String>,
+    /// The resource was deleted (a 404 status)
+    pub removed: bool,
+}
+
+/// Parsed multistatus of a sync-collection REPORT
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct SyncReport {
+    pub resources: Vec<SyncResource>,
+    pub token: Option<String>,
+    /// The server left changes out (507 on the collection); syncing again from
+    /// `token` returns the rest
+    pub truncated: bool,
+}
+
+/// Incremental parser of a multistatus. Collects one `<response>` at a time and hands it
+/// over once it's complete, so its etag is known whichever order the server sends the
+/// properties in; the sync token and truncation are kept for the end.
+#[derive(Default)]
+pub(super) struct MultistatusParser {
+    current: Option<SyncResource>,
+    status: Option<String>,
+    in_propstat: bool,
+    current_tag: Vec<u8>,
+    pub token: Option<String>,
+    pub truncated: bool,
+}
+
+impl MultistatusParser {
+    pub fn handle(&mut self, event: Event, on_resource: &mut impl FnMut(SyncResource)) {
+        match event {
+            Event::Start(e) => {
+                self.current_tag = e.local_name().as_ref().to_vec();
+                match self.current_tag.as_slice() {
+                    b"response" => {
+                        self.current = Some(SyncResource::default());
+                        self.status = None;
+                    }
+                    b"propstat" => self.in_propstat = true,
+                    _ => {}
+                }
+            }
+            Event::End(e) => {
+                self.current_tag.clear();
+                match e.local_name().as_ref() {
+                    b"response" => {
+                        let Some(mut resource) = self.current.take() else { return };
+                        match self.status.as_deref() {
+                            Some(s) if s.contains(" 404") => resource.removed = true,
+                            Some(s) if s.contains(" 507") => {
+                                self.truncated = true;
+                                return;
+                            }
+                            _ => {}
+                        }
+                        // The collection itself isn't an event
+                        if !resource.href.is_empty() && !resource.href.ends_with('/') {
+                            on_resource(resource);
+                        }
+                    }
+                    b"propstat" => self.in_propstat = false,
+                    _ => {}
+                }
+            }
+            Event::Text(e) => {
+                let text = e.unescape().unwrap_or_default();
+                match (self.current_tag.as_slice(), self.current.as_mut()) {
+                    (b"sync-token", None) => self.token = Some(text.to_string()),
+                    (b"href", Some(resource)) => resource.href = text.to_string(),
+                    (b"getetag", Some(resource)) => resource.etag = Some(text.trim_matches('"').to_string()),
+                    (b"calendar-data", Some(resource)) => resource.ical.get_or_insert_default().push_str(&text),
+                    (b"status", Some(_)) if !self.in_propstat => self.status = Some(text.to_string()),
+                    _ => {}
+                }
+            }
+            Event::CData(e) if self.current_tag == b"calendar-data" => {
+                if let Some(resource) = self.current.as_mut() {
+                    resource.ical.get_or_insert_default().push_str(&String::from_utf8_lossy(&e));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::Reader;
+
+    fn parse_sync_report(xml: &str) -> SyncReport {
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().trim_text(true);
+        let mut parser = MultistatusParser::default();
+        let mut resources = Vec::new();
+        loop {
+            match reader.read_event().unwrap() {
+                Event::Eof => break,
+                event => parser.handle(event, &mut |r| resources.push(r)),
+            }
+        }
+        SyncReport { resources, token: parser.token, truncated: parser.truncated }
+    }
+
+    #[test]
+    fn test_parse_sync_report() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:">
+  <d:response>
+    <d:href>/123/calendars/home/a.ics</d:href>
+    <d:propstat><d:prop><d:getetag>"etag-a2"</d:getetag></d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/123/calendars/home/b.ics</d:href>
+    <d:status>HTTP/1.1 404 Not Found</d:status>
+  </d:response>
+  <d:response>
+    <d:href>/123/calendars/home/c.ics</d:href>
+    <d:propstat><d:prop><d:getetag>"etag-c"</d:getetag></d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat>
+  </d:response>
+  <d:sync-token>https://caldav.icloud.com/sync/42</d:sync-token>
+</d:multistatus>"#;
+        let calendar_url = "https://caldav.icloud.com/123/calendars/home/";
+
+        let report = parse_sync_report(xml);
+        assert_eq!(report.token.as_deref(), Some("https://caldav.icloud.com/sync/42"));
+        assert!(!report.truncated);
+        assert_eq!(report.resources.len(), 3);
+        assert!(report.resources[1].removed);
+
+        let mut mirror = CalendarMirror::default();
+        for (href, etag) in [("/123/calendars/home/a.ics", "etag-a1"), ("/123/calendars/home/b.ics", "etag-b"), ("/123/calendars/home/c.ics", "etag-c")] {
+            mirror.resources.insert(href.to_string(), MirroredResource::new(calendar_url, Some(etag.to_string()), String::new()));
+        }
+        // Only a's data changed; b is gone and c is unchanged
+        assert_eq!(mirror.outdated(&report.resources), vec!["/123/calendars/home/a.ics"]);
+        for resource in report.resources {
+            mirror.apply(calendar_url, resource);
+        }
+        assert_eq!(mirror.resources.len(), 2);
+
+        let multiget = r#"<d:multistatus xmlns:d="DAV:" xmlns:cal="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/123/calendars/home/a.ics</d:href>
+    <d:propstat><d:prop>
+      <d:getetag>"etag-a2"</d:getetag>
+      <cal:calendar-data><![CDATA[BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:a
+SUMMARY:Moved
+DTSTART:20260120T093000Z
+END:VEVENT
+END:VCALENDAR]]></cal:calendar-data>
+    </d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat>
+  </d:response>
+</d:multistatus>"#;
+        for resource in parse_sync_report(multiget).resources {
+            mirror.apply(calendar_url, resource);
+        }
+        assert_eq!(mirror.resources["/123/calendars/home/a.ics"].etag.as_deref(), Some("etag-a2"));
+
+        let january = (NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2026, 1, 31).unwrap());
+        let events = mirror.events(january.0, january.1);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].title(), "Moved");
+        assert_eq!(events[0].etag.as_deref(), Some("etag-a2"));
+        assert_eq!(events[0].calendar_url, calendar_url);
+        let february = (NaiveDate::from_ymd_opt(2026, 2, 1).unwrap(), NaiveDate::from_ymd_opt(2026, 2, 28).unwrap());
+        assert!(mirror.events(february.0, february.1).is_empty());
+    }
+
+    #[test]
+    fn test_mirror_covers_seeded_months() {
+        let day = |d| NaiveDate::from_ymd_opt(2026, 1, d).unwrap();
+        let mut mirror = CalendarMirror::default();
+        assert!(!mirror.covers(day(1), day(31)));
+        mirror.covered.push((day(1), day(31)));
+        assert!(mirror.covers(day(1), day(31)));
+        assert!(mirror.covers(day(5), day(11)));
+        assert!(!mirror.covers(day(1), day(31) + chrono::Duration::days(1)));
+
+        mirror.clear();
+        assert!(mirror.covered.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetches_of_a_calendar_share_its_slot() {
+        let store = MirrorStore::default();
+        let slot = store.slot("https://caldav.icloud.com/123/calendars/home/");
+        slot.lock().await.synced = Some(store.generation());
+        let again = store.clone().slot("https://caldav.icloud.com/123/calendars/home/");
+        assert_eq!(again.lock().await.synced, Some(0));
+
+        store.refresh();
+        assert_eq!(store.generation(), 1);
+    }
+}
@@ -0,0 +1,278 @@
+//! Time zones of iCalendar times: IANA names, and VTIMEZONE blocks for the TZIDs that
+//! aren't one (e.g. "W. Europe Standard Time" from Outlook invitations)
+
+use super::types::{EventTime, Frequency, RecurrenceRule};
+use crate::utils::resolve_local_datetime;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+
+/// Zone a TZID refers to
+#[derive(Debug, Clone)]
+pub enum EventZone {
+    Iana(Tz),
+    Defined(VTimezone),
+}
+
+impl EventZone {
+    /// Look a TZID up: an IANA name, one wrapped in a vendor prefix such as
+    /// "/mozilla.org/20050126_1/Europe/Sofia", or a VTIMEZONE of the same document
+    pub fn resolve(tzid: &str, zones: &[VTimezone]) -> Option<EventZone> {
+        let tzid = tzid.trim_matches('"');
+        if let Some(tz) = parse_iana(tzid) {
+            return Some(EventZone::Iana(tz));
+        }
+        let defined = zones.iter().find(|z| z.tzid == tzid)?;
+        match defined.location.as_deref().and_then(parse_iana) {
+            Some(tz) => Some(EventZone::Iana(tz)),
+            None if !defined.observances.is_empty() => Some(EventZone::Defined(defined.clone())),
+            None => None,
+        }
+    }
+
+    /// The instant of a wall-clock time in this zone; see [`resolve_local_datetime`] for
+    /// ambiguous and nonexistent times
+    pub fn to_utc(&self, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self {
+            EventZone::Iana(tz) => resolve_local_datetime(tz, naive).map(|dt| dt.with_timezone(&Utc)),
+            EventZone::Defined(zone) => zone.to_utc(naive),
+        }
+    }
+
+    /// Wall-clock time of an instant in this zone
+    pub fn wall_clock(&self, dt: &DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            EventZone::Iana(tz) => dt.with_timezone(tz).naive_local(),
+            EventZone::Defined(zone) => dt.naive_utc() + zone.offset_at(dt.naive_utc()),
+        }
+    }
+}
+
+/// Wall-clock time of an instant in `zone`, or in the local zone for floating times
+pub fn wall_clock(dt: &DateTime<Utc>, zone: Option<&EventZone>) -> NaiveDateTime {
+    match zone {
+        Some(zone) => zone.wall_clock(dt),
+        None => dt.with_timezone(&Local).naive_local(),
+    }
+}
+
+pub fn from_wall_clock(naive: NaiveDateTime, zone: Option<&EventZone>) -> Option<DateTime<Utc>> {
+    match zone {
+        Some(zone) => zone.to_utc(naive),
+        None => resolve_local_datetime(&Local, naive).map(|dt| dt.with_timezone(&Utc)),
+    }
+}
+
+fn parse_iana(tzid: &str) -> Option<Tz> {
+    tzid.parse().ok().or_else(|| {
+        // Keep the trailing "Area/City" of a prefixed name
+        let mut parts = tzid.rsplit('/');
+        let city = parts.next()?;
+        let area = parts.next()?;
+        format!("{}/{}", area, city).parse().ok()
+    })
+}
+
+/// A VTIMEZONE block: the zone's standard and daylight observances
+#[derive(Debug, Clone)]
+pub struct VTimezone {
+    pub tzid: String,
+    /// X-LIC-LOCATION, the IANA name some producers add
+    location: Option<String>,
+    observances: Vec<Observance>,
+}
+
+/// A STANDARD or DAYLIGHT part of a VTIMEZONE
+#[derive(Debug, Clone)]
+struct Observance {
+    /// First onset, in wall-clock time before the change
+    start: NaiveDateTime,
+    offset_from: Duration,
+    offset_to: Duration,
+    rule: Option<RecurrenceRule>,
+    rdates: Vec<NaiveDateTime>,
+}
+
+impl VTimezone {
+    /// All VTIMEZONE blocks of an iCalendar document (given unfolded lines)
+    pub fn parse_all(lines: impl IntoIterator<Item = impl AsRef<str>>) -> Vec<VTimezone> {
+        let mut zones = Vec::new();
+        let mut zone: Option<VTimezone> = None;
+        let mut observance: Option<ObservanceBuilder> = None;
+        for line in lines {
+            let line = line.as_ref().trim();
+            match line {
+                "BEGIN:VTIMEZONE" => {
+                    zone = Some(VTimezone { tzid: String::new(), location: None, observances: Vec::new() });
+                }
+                "END:VTIMEZONE" => zones.extend(zone.take().filter(|z| !z.tzid.is_empty())),
+                "BEGIN:STANDARD" | "BEGIN:DAYLIGHT" if zone.is_some() => observance = Some(ObservanceBuilder::default()),
+                "END:STANDARD" | "END:DAYLIGHT" => {
+                    if let (Some(zone), Some(built)) = (zone.as_mut(), observance.take().and_then(ObservanceBuilder::build)) {
+                        zone.observances.push(built);
+                    }
+                }
+                _ => {
+                    let (Some(zone), Some((key, value))) = (zone.as_mut(), line.split_once(':')) else { continue };
+                    let name = key.split(';').next().unwrap_or(key);
+                    match (name, observance.as_mut()) {
+                        ("TZID", None) => zone.tzid = value.to_string(),
+                        ("X-LIC-LOCATION", None) => zone.location = Some(value.to_string()),
+                        ("DTSTART", Some(o)) => o.start = parse_naive(value),
+                        ("TZOFFSETFROM", Some(o)) => o.offset_from = parse_offset(value),
+                        ("TZOFFSETTO", Some(o)) => o.offset_to = parse_offset(value),
+                        ("RRULE", Some(o)) => o.rule = RecurrenceRule::parse(value),
+                        ("RDATE", Some(o)) => o.rdates.extend(value.split(',').filter_map(parse_naive)),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        zones
+    }
+
+    /// UTC offset in effect at a UTC time: that of the latest onset before it, or the
+    /// offset before the first onset
+    fn offset_at(&self, utc: NaiveDateTime) -> Duration {
+        // Each observance changes at least once a year, so the year before is enough to look at
+        let first = NaiveDate::from_ymd_opt(utc.year() - 1, 1, 1).unwrap_or(utc.date());
+        let last = NaiveDate::from_ymd_opt(utc.year() + 1, 1, 1).unwrap_or(utc.date());
+        self.observances.iter()
+            .flat_map(|o| o.onsets(first, last).into_iter().map(move |onset| (onset - o.offset_from, o.offset_to)))
+            .filter(|(onset, _)| *onset <= utc)
+            .max_by_key(|(onset, _)| *onset)
+            .map(|(_, offset)| offset)
+            .or_else(|| self.observances.iter().min_by_key(|o| o.start).map(|o| o.offset_from))
+            .unwrap_or_default()
+    }
+
+    /// Wall-clock time to UTC: ambiguous times take the first occurrence, times in a gap
+    /// the offset before it
+    fn to_utc(&self, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+        let exact = |naive: NaiveDateTime| {
+            self.observances.iter()
+                .flat_map(|o| [o.offset_from, o.offset_to])
+                .map(|offset| naive - offset)
+                .filter(|utc| naive - *utc == self.offset_at(*utc))
+                .min()
+        };
+        let utc = exact(naive).or_else(|| {
+            let before = exact(naive - Duration::hours(1))?;
+            Some(naive - self.offset_at(before))
+        })?;
+        Some(utc.and_utc())
+    }
+}
+
+impl Observance {
+    /// Onsets up to `last`, in wall-clock time before the change. Yearly rules, which is
+    /// what zones use, are only expanded from `first` on, since their DTSTART is often
+    /// centuries back (e.g. 1601 in Outlook's zones).
+    fn onsets(&self, first: NaiveDate, last: NaiveDate) -> Vec<NaiveDateTime> {
+        let time = self.start.time();
+        let Some(rule) = &self.rule else {
+            return std::iter::once(self.start).chain(self.rdates.iter().copied()).filter(|d| d.date() <= last).collect();
+        };
+        let within_until = |date: NaiveDate| match &rule.until {
+            Some(EventTime::DateTime(until)) => date.and_time(time) - self.offset_from <= until.naive_utc(),
+            Some(EventTime::Date(until)) => date <= *until,
+            None => true,
+        };
+        let skip_ahead = rule.freq == Frequency::Yearly && rule.interval == 1 && rule.count.is_none() && first > self.start.date();
+        let mut onsets: Vec<NaiveDateTime> = if skip_ahead {
+            // The shifted start is only where expansion begins, not an onset itself
+            rule.dates(first, last, within_until).into_iter().skip(1).map(|d| d.and_time(time)).collect()
+        } else {
+            rule.dates(self.start.date(), last, within_until).into_iter().map(|d| d.and_time(time)).collect()
+        };
+        onsets.extend(self.rdates.iter().filter(|d| d.date() <= last));
+        onsets
+    }
+}
+
+#[derive(Default)]
+struct ObservanceBuilder {
+    start: Option<NaiveDateTime>,
+    offset_from: Option<Duration>,
+    offset_to: Option<Duration>,
+    rule: Option<RecurrenceRule>,
+    rdates: Vec<NaiveDateTime>,
+}
+
+impl ObservanceBuilder {
+    fn build(self) -> Option<Observance> {
+        let offset_to = self.offset_to?;
+        Some(Observance {
+            start: self.start?,
+            offset_from: self.offset_from.unwrap_or(offset_to),
+            offset_to,
+            rule: self.rule,
+            rdates: self.rdates,
+        })
+    }
+}
+
+/// Parse a local date-time such as "19701025T040000"
+pub fn parse_naive(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S").ok()
+}
+
+/// Parse a UTC offset such as "+0200", "-0500" or "+053000"
+fn parse_offset(value: &str) -> Option<Duration> {
+    let sign = match value.get(..1)? {
+        "+" => 1,
+        "-" => -1,
+        _ => return None,
+    };
+    let hours: i64 = value.get(1..3)?.parse().ok()?;
+    let minutes: i64 = value.get(3..5)?.parse().ok()?;
+    let seconds: i64 = value.get(5..7).and_then(|s| s.parse().ok()).unwrap_or(0);
+    Some(Duration::seconds(sign * (hours * 3600 + minutes * 60 + seconds)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTLOOK_ZONE: &str = "BEGIN:VTIMEZONE
+TZID:FLE Standard Time
+BEGIN:STANDARD
+DTSTART:16010101T040000
+TZOFFSETFROM:+0300
+TZOFFSETTO:+0200
+RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10
+END:STANDARD
+BEGIN:DAYLIGHT
+DTSTART:16010101T030000
+TZOFFSETFROM:+0200
+TZOFFSETTO:+0300
+RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=3
+END:DAYLIGHT
+END:VTIMEZONE";
+
+    fn naive(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_defined_zone_follows_its_rules() {
+        let zones = VTimezone::parse_all(OUTLOOK_ZONE.lines());
+        let Some(EventZone::Defined(zone)) = EventZone::resolve("FLE Standard Time", &zones) else {
+            panic!("zone not defined");
+        };
+        assert_eq!(zone.to_utc(naive("2026-01-15 10:00")).unwrap().naive_utc(), naive("2026-01-15 08:00"));
+        assert_eq!(zone.to_utc(naive("2026-07-15 10:00")).unwrap().naive_utc(), naive("2026-07-15 07:00"));
+        // Spring forward on the last Sunday of March: 03:30 doesn't exist and moves ahead
+        assert_eq!(zone.to_utc(naive("2026-03-29 03:30")).unwrap().naive_utc(), naive("2026-03-29 01:30"));
+        // Fall back: 03:30 happens twice, the first one wins
+        assert_eq!(zone.to_utc(naive("2026-10-25 03:30")).unwrap().naive_utc(), naive("2026-10-25 00:30"));
+        assert_eq!(EventZone::Defined(zone).wall_clock(&naive("2026-07-15 07:00").and_utc()), naive("2026-07-15 10:00"));
+    }
+
+    #[test]
+    fn test_resolve_prefers_iana_names() {
+        assert!(matches!(EventZone::resolve("/mozilla.org/20050126_1/Europe/Sofia", &[]), Some(EventZone::Iana(chrono_tz::Europe::Sofia))));
+        let zones = VTimezone::parse_all("BEGIN:VTIMEZONE\nTZID:Custom\nX-LIC-LOCATION:Europe/Sofia\nEND:VTIMEZONE".lines());
+        assert!(matches!(EventZone::resolve("Custom", &zones), Some(EventZone::Iana(chrono_tz::Europe::Sofia))));
+        assert!(EventZone::resolve("Nowhere", &zones).is_none());
+    }
+}
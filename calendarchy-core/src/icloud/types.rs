@@ -0,0 +1,1463 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, Utc, Weekday};
+use super::timezone::{from_wall_clock, wall_clock, EventZone, VTimezone};
+use chrono_tz::Tz;
+use std::borrow::Cow;
+
+/// Attendee from iCal ATTENDEE line
+#[derive(Debug, Clone)]
+pub struct ICalAttendee {
+    pub name: Option<String>,
+    pub email: String,
+    pub partstat: String,  // ACCEPTED, DECLINED, TENTATIVE, NEEDS-ACTION
+    pub is_organizer: bool,
+    pub is_room: bool, // CUTYPE=ROOM or RESOURCE: booked, not invited
+}
+
+/// An event from iCloud Calendar (parsed from iCal/VCALENDAR format)
+#[derive(Debug, Clone)]
+pub struct ICalEvent {
+    pub uid: String,
+    pub summary: Option<String>,
+    pub dtstart: EventTime,
+    pub dtend: Option<EventTime>,
+    pub location: Option<String>,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub accepted: bool, // true if accepted or no PARTSTAT found
+    pub attendees: Vec<ICalAttendee>,
+    /// "TRANSPARENT" = free, "OPAQUE" = busy (default)
+    pub transp: Option<String>,
+    /// Event color as "#rrggbb" (Apple COLOR property, or the calendar color)
+    pub color: Option<String>,
+    /// The calendar URL this event belongs to (set by CalDavClient)
+    pub calendar_url: String,
+    /// The etag for conditional updates
+    pub etag: Option<String>,
+    /// RRULE, RDATE and EXDATE of a repeating event; expanded by [`ICalEvent::expand_recurrences`]
+    pub recurrence: Option<Recurrence>,
+    /// Set on one occurrence of a repeating event, expanded or changed: the start it
+    /// stands for
+    pub recurrence_id: Option<EventTime>,
+}
+
+/// Event time - can be all-day (date only) or specific time
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventTime {
+    Date(NaiveDate),
+    DateTime(DateTime<Utc>),
+}
+
+impl EventTime {
+    /// Whether both name the same occurrence; a date matches any time on that (local) day
+    fn same_occurrence(&self, other: &EventTime) -> bool {
+        match (self, other) {
+            (EventTime::DateTime(a), EventTime::DateTime(b)) => a == b,
+            _ => self.local_date() == other.local_date(),
+        }
+    }
+
+    fn local_date(&self) -> NaiveDate {
+        match self {
+            EventTime::Date(d) => *d,
+            EventTime::DateTime(dt) => dt.with_timezone(&Local).date_naive(),
+        }
+    }
+
+    /// The time as an iCalendar value, in UTC: "20260115" or "20260115T093000Z"
+    pub fn ical_value(&self) -> String {
+        match self {
+            EventTime::Date(d) => d.format("%Y%m%d").to_string(),
+            EventTime::DateTime(dt) => dt.format("%Y%m%dT%H%M%SZ").to_string(),
+        }
+    }
+}
+
+/// How a repeating event repeats
+#[derive(Debug, Clone, Default)]
+pub struct Recurrence {
+    pub rule: Option<RecurrenceRule>,
+    /// Extra occurrences (RDATE)
+    pub rdates: Vec<EventTime>,
+    /// Skipped occurrences (EXDATE)
+    pub exdates: Vec<EventTime>,
+    /// Zone DTSTART is written in, so occurrences keep their wall-clock time across DST;
+    /// None for floating times
+    pub tz: Option<EventZone>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// An RRULE. Sub-daily frequencies are not supported; such events show only their first
+/// occurrence.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecurrenceRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<EventTime>,
+    /// Weekdays, with an optional ordinal in the month or year, e.g. -1FR for the last Friday
+    pub by_day: Vec<(Option<i32>, Weekday)>,
+    pub by_month_day: Vec<i32>,
+    pub by_month: Vec<u32>,
+    pub by_set_pos: Vec<i32>,
+    pub week_start: Weekday,
+}
+
+/// Periods looked at before giving up on a rule that never produces another occurrence
+const MAX_RECURRENCE_PERIODS: u32 = 50_000;
+
+impl RecurrenceRule {
+    /// Parse an RRULE value, e.g. "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE"
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut freq = None;
+        let mut rule = RecurrenceRule {
+            freq: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+            by_set_pos: Vec::new(),
+            week_start: Weekday::Mon,
+        };
+        for part in value.split(';') {
+            let (name, value) = part.split_once('=')?;
+            let list = || value.split(',').filter_map(|v| v.parse().ok());
+            match name {
+                "FREQ" => {
+                    freq = match value {
+                        "DAILY" => Some(Frequency::Daily),
+                        "WEEKLY" => Some(Frequency::Weekly),
+                        "MONTHLY" => Some(Frequency::Monthly),
+                        "YEARLY" => Some(Frequency::Yearly),
+                        _ => return None,
+                    }
+                }
+                "INTERVAL" => rule.interval = value.parse().ok().filter(|i| *i > 0)?,
+                "COUNT" => rule.count = value.parse().ok(),
+                "UNTIL" => rule.until = parse_ical_datetime("UNTIL", value),
+                "BYDAY" => rule.by_day = value.split(',').filter_map(parse_by_day).collect(),
+                "BYMONTHDAY" => rule.by_month_day = list().collect(),
+                "BYMONTH" => rule.by_month = value.split(',').filter_map(|v| v.parse().ok()).collect(),
+                "BYSETPOS" => rule.by_set_pos = list().collect(),
+                "WKST" => rule.week_start = parse_weekday(value)?,
+                _ => {}
+            }
+        }
+        rule.freq = freq?;
+        Some(rule)
+    }
+
+    /// Start dates the rule produces, in order, from `dtstart` (always the first) until
+    /// COUNT or UNTIL runs out or the dates pass `last`
+    pub(super) fn dates(&self, dtstart: NaiveDate, last: NaiveDate, within_until: impl Fn(NaiveDate) -> bool) -> Vec<NaiveDate> {
+        let mut dates = vec![dtstart];
+        for period in 0..MAX_RECURRENCE_PERIODS {
+            let Some((period_start, candidates)) = self.period(dtstart, period) else { break };
+            if period_start > last {
+                break;
+            }
+            for date in candidates.into_iter().filter(|d| *d > dtstart) {
+                if self.count.is_some_and(|count| dates.len() >= count as usize) || !within_until(date) {
+                    return dates;
+                }
+                if date > last {
+                    return dates;
+                }
+                dates.push(date);
+            }
+        }
+        dates
+    }
+
+    /// First day of the `index`th period (every INTERVAL days, weeks, months or years from
+    /// `dtstart`) and the dates the rule picks in it
+    fn period(&self, dtstart: NaiveDate, index: u32) -> Option<(NaiveDate, Vec<NaiveDate>)> {
+        let step = index.checked_mul(self.interval)?;
+        let (start, mut dates) = match self.freq {
+            Frequency::Daily => {
+                let day = dtstart + Duration::days(step as i64);
+                let matches = (self.by_day.is_empty() || self.by_day.iter().any(|(_, wd)| *wd == day.weekday()))
+                    && (self.by_month_day.is_empty() || self.by_month_day.iter().any(|d| month_day(day.year(), day.month(), *d) == Some(day)));
+                (day, if matches { vec![day] } else { Vec::new() })
+            }
+            Frequency::Weekly => {
+                let days_since = (7 + dtstart.weekday().num_days_from_monday() - self.week_start.num_days_from_monday()) % 7;
+                let week = dtstart - Duration::days(days_since as i64) + Duration::weeks(step as i64);
+                let weekdays: Vec<Weekday> = if self.by_day.is_empty() {
+                    vec![dtstart.weekday()]
+                } else {
+                    self.by_day.iter().map(|(_, wd)| *wd).collect()
+                };
+                let dates = weekdays.iter()
+                    .map(|wd| week + Duration::days(((7 + wd.num_days_from_monday() - self.week_start.num_days_from_monday()) % 7) as i64))
+                    .collect();
+                (week, dates)
+            }
+            Frequency::Monthly => {
+                let months = dtstart.year() * 12 + dtstart.month0() as i32 + step as i32;
+                let first = NaiveDate::from_ymd_opt(months.div_euclid(12), months.rem_euclid(12) as u32 + 1, 1)?;
+                (first, self.month_dates(first.year(), first.month(), dtstart.day()))
+            }
+            Frequency::Yearly => {
+                let year = dtstart.year().checked_add(step as i32)?;
+                let first = NaiveDate::from_ymd_opt(year, 1, 1)?;
+                let dates = if self.by_month.is_empty() && self.by_month_day.is_empty() && !self.by_day.is_empty() {
+                    // Weekdays counted through the whole year, e.g. the 20th Monday
+                    let last = NaiveDate::from_ymd_opt(year, 12, 31)?;
+                    weekdays_between(first, last, &self.by_day)
+                } else {
+                    let months = if self.by_month.is_empty() { vec![dtstart.month()] } else { self.by_month.clone() };
+                    months.iter().flat_map(|m| self.month_dates(year, *m, dtstart.day())).collect()
+                };
+                (first, dates)
+            }
+        };
+        if !self.by_month.is_empty() {
+            dates.retain(|d| self.by_month.contains(&d.month()));
+        }
+        dates.sort();
+        dates.dedup();
+        if !self.by_set_pos.is_empty() {
+            let all = std::mem::take(&mut dates);
+            dates = self.by_set_pos.iter()
+                .filter_map(|pos| match *pos {
+                    pos if pos > 0 => all.get(pos as usize - 1).copied(),
+                    pos if pos < 0 => all.len().checked_sub(pos.unsigned_abs() as usize).and_then(|i| all.get(i)).copied(),
+                    _ => None,
+                })
+                .collect();
+            dates.sort();
+        }
+        Some((start, dates))
+    }
+
+    /// Days of a month picked by BYMONTHDAY and/or BYDAY, or `default_day` without either
+    fn month_dates(&self, year: i32, month: u32, default_day: u32) -> Vec<NaiveDate> {
+        if !self.by_month_day.is_empty() {
+            return self.by_month_day.iter()
+                .filter_map(|d| month_day(year, month, *d))
+                .filter(|date| self.by_day.is_empty() || self.by_day.iter().any(|(_, wd)| *wd == date.weekday()))
+                .collect();
+        }
+        if !self.by_day.is_empty() {
+            let (Some(first), Some(last)) = (month_day(year, month, 1), month_day(year, month, -1)) else { return Vec::new() };
+            return weekdays_between(first, last, &self.by_day);
+        }
+        NaiveDate::from_ymd_opt(year, month, default_day).into_iter().collect()
+    }
+}
+
+/// The `day`th day of a month, counting from the end when negative
+fn month_day(year: i32, month: u32, day: i32) -> Option<NaiveDate> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    if day > 0 {
+        NaiveDate::from_ymd_opt(year, month, day as u32)
+    } else if day < 0 {
+        let next = first.checked_add_months(chrono::Months::new(1))?;
+        let date = next - Duration::days(day.unsigned_abs() as i64);
+        (date.month() == month).then_some(date)
+    } else {
+        None
+    }
+}
+
+/// Days between `first` and `last` matching BYDAY entries; an ordinal picks the nth
+/// (or nth from the end) such weekday in the span
+fn weekdays_between(first: NaiveDate, last: NaiveDate, by_day: &[(Option<i32>, Weekday)]) -> Vec<NaiveDate> {
+    let mut dates = Vec::new();
+    for (ordinal, weekday) in by_day {
+        let matching: Vec<NaiveDate> = first.iter_days()
+            .take_while(|d| *d <= last)
+            .filter(|d| d.weekday() == *weekday)
+            .collect();
+        match *ordinal {
+            None => dates.extend(matching),
+            Some(n) if n > 0 => dates.extend(matching.get(n as usize - 1)),
+            Some(n) => dates.extend(matching.len().checked_sub(n.unsigned_abs() as usize).and_then(|i| matching.get(i))),
+        }
+    }
+    dates
+}
+
+/// Parse a BYDAY entry such as "MO", "2TU" or "-1FR"
+fn parse_by_day(value: &str) -> Option<(Option<i32>, Weekday)> {
+    let split = value.len().checked_sub(2)?;
+    let weekday = parse_weekday(value.get(split..)?)?;
+    let ordinal = match value.get(..split)? {
+        "" => None,
+        n => Some(n.trim_start_matches('+').parse().ok().filter(|n: &i32| *n != 0)?),
+    };
+    Some((ordinal, weekday))
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+impl ICalEvent {
+    /// Get the start date (works for both all-day and timed events, in local timezone)
+    pub fn start_date(&self) -> NaiveDate {
+        match &self.dtstart {
+            EventTime::Date(d) => *d,
+            EventTime::DateTime(dt) => dt.with_timezone(&Local).date_naive(),
+        }
+    }
+
+    /// Last day the event covers, in local timezone; the end of an all-day event is exclusive
+    pub fn end_date(&self) -> NaiveDate {
+        match &self.dtend {
+            Some(EventTime::Date(d)) => d.pred_opt().unwrap_or(*d).max(self.start_date()),
+            Some(EventTime::DateTime(dt)) => {
+                let start = match &self.dtstart {
+                    EventTime::Date(d) => d.and_time(NaiveTime::MIN),
+                    EventTime::DateTime(start) => start.with_timezone(&Local).naive_local(),
+                };
+                last_covered_day(start, dt.with_timezone(&Local).naive_local()).max(self.start_date())
+            }
+            None => self.start_date(),
+        }
+    }
+
+    /// Get display title
+    pub fn title(&self) -> &str {
+        self.summary.as_deref().unwrap_or("(No title)")
+    }
+
+    /// Start time in the local timezone, or None for all-day events
+    pub fn start_time(&self) -> Option<NaiveTime> {
+        match &self.dtstart {
+            EventTime::Date(_) => None,
+            EventTime::DateTime(dt) => Some(dt.with_timezone(&Local).time()),
+        }
+    }
+
+    /// End time in the local timezone, or None for all-day events
+    pub fn end_time(&self) -> Option<NaiveTime> {
+        match &self.dtend {
+            Some(EventTime::DateTime(dt)) => Some(dt.with_timezone(&Local).time()),
+            _ => None,
+        }
+    }
+
+    /// Extract meeting URL (Zoom, Google Meet, etc.)
+    pub fn meeting_url(&self, custom: &MeetingPatterns) -> Option<String> {
+        // Check URL field first
+        if let Some(ref url) = self.url
+            && is_meeting_url(url, custom) {
+                return Some(url.clone());
+            }
+
+        // Check location for meeting URLs
+        if let Some(ref loc) = self.location
+            && let Some(url) = extract_meeting_url(loc, custom) {
+                return Some(url);
+            }
+
+        // Check description for meeting URLs
+        if let Some(ref desc) = self.description
+            && let Some(url) = extract_meeting_url(desc, custom) {
+                return Some(url);
+            }
+
+        None
+    }
+
+    /// Phone dial-in found in the location or description, as a tel: URI
+    pub fn dial_in(&self) -> Option<String> {
+        self.location.as_deref().and_then(extract_dial_in)
+            .or_else(|| self.description.as_deref().and_then(extract_dial_in))
+    }
+
+    /// Check if the event is marked as "free" (doesn't block time)
+    pub fn is_free(&self) -> bool {
+        self.transp.as_deref() == Some("TRANSPARENT")
+    }
+
+    /// Replace repeating events by their occurrences starting between `start` and `end`
+    /// (local dates), leaving out EXDATEs and occurrences replaced by a RECURRENCE-ID
+    /// override. Other events are kept as they are.
+    pub fn expand_recurrences(events: Vec<ICalEvent>, start: NaiveDate, end: NaiveDate) -> Vec<ICalEvent> {
+        let overrides: Vec<(String, EventTime)> = events.iter()
+            .filter_map(|e| Some((e.uid.clone(), e.recurrence_id.clone()?)))
+            .collect();
+        let mut expanded = Vec::new();
+        for event in events {
+            let Some(recurrence) = event.recurrence.clone() else {
+                if event.recurrence_id.is_none() || (start..=end).contains(&event.start_date()) {
+                    expanded.push(event);
+                }
+                continue;
+            };
+            let overridden = |time: &EventTime| {
+                recurrence.exdates.iter().any(|ex| ex.same_occurrence(time))
+                    || overrides.iter().any(|(uid, id)| *uid == event.uid && id.same_occurrence(time))
+            };
+            for time in event.occurrences(&recurrence, end) {
+                if !overridden(&time) && (start..=end).contains(&time.local_date()) {
+                    expanded.push(event.occurrence_at(time));
+                }
+            }
+        }
+        expanded
+    }
+
+    /// Start times of a repeating event up to `last` (local date), in order
+    fn occurrences(&self, recurrence: &Recurrence, last: NaiveDate) -> Vec<EventTime> {
+        // A day of slack for zones ahead of or behind the local one
+        let last = last + Duration::days(1);
+        let (first_date, time) = match &self.dtstart {
+            EventTime::Date(d) => (*d, None),
+            EventTime::DateTime(dt) => {
+                let wall = wall_clock(dt, recurrence.tz.as_ref());
+                (wall.date(), Some(wall.time()))
+            }
+        };
+        let at = |date: NaiveDate| -> Option<EventTime> {
+            match time {
+                None => Some(EventTime::Date(date)),
+                Some(time) => from_wall_clock(date.and_time(time), recurrence.tz.as_ref()).map(EventTime::DateTime),
+            }
+        };
+        let until = recurrence.rule.as_ref().and_then(|r| r.until.as_ref());
+        let within_until = |date: NaiveDate| match (until, at(date)) {
+            (Some(EventTime::DateTime(until)), Some(EventTime::DateTime(instant))) => instant <= *until,
+            (Some(until), _) => date <= until.local_date(),
+            (None, _) => true,
+        };
+        let dates = match &recurrence.rule {
+            Some(rule) => rule.dates(first_date, last, within_until),
+            None => vec![first_date],
+        };
+        let mut times: Vec<EventTime> = dates.into_iter().filter_map(at).collect();
+        for rdate in &recurrence.rdates {
+            if !times.iter().any(|t| t.same_occurrence(rdate)) {
+                times.push(rdate.clone());
+            }
+        }
+        times
+    }
+
+    /// A copy of the event moved to one of its occurrences, keeping its length
+    fn occurrence_at(&self, start: EventTime) -> ICalEvent {
+        let dtend = self.dtend.as_ref().map(|end| match (&self.dtstart, end, &start) {
+            (EventTime::DateTime(s), EventTime::DateTime(e), EventTime::DateTime(new)) => EventTime::DateTime(*new + (*e - *s)),
+            (EventTime::Date(s), EventTime::Date(e), EventTime::Date(new)) => EventTime::Date(*new + (*e - *s)),
+            _ => end.clone(),
+        });
+        ICalEvent {
+            recurrence_id: Some(start.clone()),
+            dtstart: start,
+            dtend,
+            recurrence: None,
+            ..self.clone()
+        }
+    }
+
+    /// Parse an iCal VCALENDAR string into events
+    pub fn parse_ical(ical_data: &str) -> Vec<ICalEvent> {
+        Self::parse_ical_with_source(ical_data, String::new(), None)
+    }
+
+    /// Parse an iCal VCALENDAR string into events with calendar source info
+    pub fn parse_ical_with_source(ical_data: &str, calendar_url: String, etag: Option<String>) -> Vec<ICalEvent> {
+        let mut events = Vec::new();
+        let mut current_event: Option<ICalEventBuilder> = None;
+        // Zones may be defined after the events using them, so they're read first
+        let zones = if ical_data.contains("BEGIN:VTIMEZONE") {
+            VTimezone::parse_all(unfold_ical_lines(ical_data))
+        } else {
+            Vec::new()
+        };
+
+        for line in unfold_ical_lines(ical_data) {
+            let line = line.trim();
+
+            if line == "BEGIN:VEVENT" {
+                current_event = Some(ICalEventBuilder {
+                    calendar_url: calendar_url.clone(),
+                    etag: etag.clone(),
+                    ..Default::default()
+                });
+            } else if line == "END:VEVENT" {
+                if let Some(builder) = current_event.take()
+                    && let Some(event) = builder.build() {
+                        events.push(event);
+                    }
+            } else if let Some(ref mut builder) = current_event
+                && let Some((key, value)) = parse_ical_line(line) {
+                    let base_key = key.split(';').next().unwrap_or(key);
+                    match base_key {
+                        "UID" => builder.uid = Some(value.to_string()),
+                        "SUMMARY" => builder.summary = Some(unescape_ical(value)),
+                        "DTSTART" => {
+                            builder.dtstart = parse_ical_datetime_in(key, value, &zones);
+                            builder.tz = if value.ends_with('Z') {
+                                Some(EventZone::Iana(Tz::UTC))
+                            } else {
+                                extract_tzid(key).and_then(|tzid| EventZone::resolve(tzid, &zones))
+                            };
+                        }
+                        "DTEND" => builder.dtend = parse_ical_datetime_in(key, value, &zones),
+                        "LOCATION" => builder.location = Some(unescape_ical(value)),
+                        "DESCRIPTION" => builder.description = Some(unescape_ical(value)),
+                        "URL" => builder.url = Some(unescape_ical(value)),
+                        "ATTENDEE" => {
+                            // Extract PARTSTAT from ATTENDEE line for self acceptance; a
+                            // room's answer is the room's, not yours
+                            if let Some(partstat) = extract_partstat(key).filter(|_| !is_room_cutype(key)) {
+                                builder.partstat = Some(partstat.to_string());
+                            }
+                            // Parse attendee details
+                            if let Some(attendee) = parse_attendee(key, value) {
+                                builder.attendees.push(attendee);
+                            }
+                        }
+                        "ORGANIZER" => {
+                            // Parse organizer as an attendee
+                            if let Some(mut attendee) = parse_attendee(key, value) {
+                                attendee.is_organizer = true;
+                                attendee.partstat = "ACCEPTED".to_string();
+                                builder.attendees.push(attendee);
+                            }
+                        }
+                        "RRULE" => builder.rrule = RecurrenceRule::parse(value),
+                        "RDATE" => builder.rdates.extend(value.split(',').filter_map(|v| parse_ical_datetime_in(key, v, &zones))),
+                        "EXDATE" => builder.exdates.extend(value.split(',').filter_map(|v| parse_ical_datetime_in(key, v, &zones))),
+                        "RECURRENCE-ID" => builder.recurrence_id = parse_ical_datetime_in(key, value, &zones),
+                        "TRANSP" => builder.transp = Some(value.to_string()),
+                        "COLOR" => builder.color = normalize_hex_color(value),
+                        _ => {}
+                    }
+                }
+        }
+
+        events
+    }
+}
+
+#[derive(Default)]
+struct ICalEventBuilder {
+    uid: Option<String>,
+    summary: Option<String>,
+    dtstart: Option<EventTime>,
+    dtend: Option<EventTime>,
+    location: Option<String>,
+    description: Option<String>,
+    url: Option<String>,
+    partstat: Option<String>, // NEEDS-ACTION, ACCEPTED, DECLINED, TENTATIVE
+    attendees: Vec<ICalAttendee>,
+    transp: Option<String>,
+    color: Option<String>,
+    calendar_url: String,
+    etag: Option<String>,
+    rrule: Option<RecurrenceRule>,
+    rdates: Vec<EventTime>,
+    exdates: Vec<EventTime>,
+    recurrence_id: Option<EventTime>,
+    tz: Option<EventZone>,
+}
+
+impl ICalEventBuilder {
+    fn build(self) -> Option<ICalEvent> {
+        // Default to accepted if no PARTSTAT or if ACCEPTED
+        let accepted = match self.partstat.as_deref() {
+            None => true,
+            Some("ACCEPTED") => true,
+            Some("NEEDS-ACTION") | Some("TENTATIVE") | Some("DECLINED") => false,
+            _ => true,
+        };
+        let recurrence = (self.rrule.is_some() || !self.rdates.is_empty()).then_some(Recurrence {
+            rule: self.rrule,
+            rdates: self.rdates,
+            exdates: self.exdates,
+            tz: self.tz,
+        });
+
+        Some(ICalEvent {
+            uid: self.uid?,
+            summary: self.summary,
+            dtstart: self.dtstart?,
+            dtend: self.dtend,
+            location: self.location,
+            description: self.description,
+            url: self.url,
+            accepted,
+            attendees: self.attendees,
+            transp: self.transp,
+            color: self.color,
+            calendar_url: self.calendar_url,
+            etag: self.etag,
+            recurrence,
+            recurrence_id: self.recurrence_id,
+        })
+    }
+}
+
+/// Unfold iCal lines (lines starting with space/tab are continuations). Lines that
+/// aren't folded are borrowed from `data`; only folded ones are copied to be joined.
+fn unfold_ical_lines(data: &str) -> impl Iterator<Item = Cow<'_, str>> {
+    let mut lines = data.lines().peekable();
+    std::iter::from_fn(move || {
+        let mut line = Cow::Borrowed(lines.next()?);
+        while let Some(continuation) = lines.next_if(|l| l.starts_with(' ') || l.starts_with('\t')) {
+            line.to_mut().push_str(continuation.trim_start());
+        }
+        Some(line)
+    })
+    .filter(|line| !line.is_empty())
+}
+
+/// Parse a single iCal line into key and value
+fn parse_ical_line(line: &str) -> Option<(&str, &str)> {
+    let colon_pos = line.find(':')?;
+    Some((&line[..colon_pos], &line[colon_pos + 1..]))
+}
+
+/// Parse iCal datetime value
+fn parse_ical_datetime(key: &str, value: &str) -> Option<EventTime> {
+    parse_ical_datetime_in(key, value, &[])
+}
+
+/// Parse an iCal datetime value whose TZID may name one of the document's VTIMEZONEs
+fn parse_ical_datetime_in(key: &str, value: &str, zones: &[VTimezone]) -> Option<EventTime> {
+    // Check if it's a date-only value (VALUE=DATE parameter or 8-digit date)
+    if key.contains("VALUE=DATE") && !key.contains("VALUE=DATE-TIME") {
+        // Parse YYYYMMDD
+        let year = value.get(0..4)?.parse().ok()?;
+        let month = value.get(4..6)?.parse().ok()?;
+        let day = value.get(6..8)?.parse().ok()?;
+        return NaiveDate::from_ymd_opt(year, month, day).map(EventTime::Date);
+    }
+
+    // Handle pure date without time (8 digits, no T)
+    if value.len() == 8 && !value.contains('T') {
+        let year = value.get(0..4)?.parse().ok()?;
+        let month = value.get(4..6)?.parse().ok()?;
+        let day = value.get(6..8)?.parse().ok()?;
+        return NaiveDate::from_ymd_opt(year, month, day).map(EventTime::Date);
+    }
+
+    // Parse datetime: YYYYMMDDTHHMMSS, YYYYMMDDTHHMMSSZ, or with TZID
+    // Handles: DTSTART:20260108T200000Z                  (UTC)
+    //          DTSTART;TZID=Europe/Sofia:20260108T200000 (zoned wall-clock time)
+    //          DTSTART:20260108T200000                   (floating, local time)
+    let is_utc = value.ends_with('Z');
+    let value = value.trim_end_matches('Z');
+    if value.contains('T') {
+        let t_pos = value.find('T')?;
+        let date_part = &value[..t_pos];
+        let time_part = &value[t_pos + 1..];
+
+        if date_part.len() >= 8 && time_part.len() >= 6 {
+            let year = date_part.get(0..4)?.parse().ok()?;
+            let month = date_part.get(4..6)?.parse().ok()?;
+            let day = date_part.get(6..8)?.parse().ok()?;
+
+            let hour = time_part.get(0..2)?.parse().ok()?;
+            let minute = time_part.get(2..4)?.parse().ok()?;
+            let second = time_part.get(4..6)?.parse().ok()?;
+
+            let naive = NaiveDate::from_ymd_opt(year, month, day)?
+                .and_hms_opt(hour, minute, second)?;
+
+            if is_utc {
+                return Some(EventTime::DateTime(DateTime::from_naive_utc_and_offset(naive, Utc)));
+            }
+
+            // Floating time or unknown TZID: interpret as local wall-clock time
+            let zone = extract_tzid(key).and_then(|tzid| EventZone::resolve(tzid, zones));
+            let utc = from_wall_clock(naive, zone.as_ref())?;
+            return Some(EventTime::DateTime(utc));
+        }
+    }
+
+    None
+}
+
+/// Extract the TZID parameter from a property key
+/// e.g., "DTSTART;TZID=Europe/Sofia" -> "Europe/Sofia"
+fn extract_tzid(key: &str) -> Option<&str> {
+    key.split(';')
+        .find_map(|part| part.strip_prefix("TZID="))
+        .map(|tzid| tzid.trim_matches('"'))
+}
+
+/// Unescape iCal text values in one pass; unknown escapes are kept as written
+fn unescape_ical(value: &str) -> String {
+    if !value.contains('\\') {
+        return value.to_string();
+    }
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => result.push('\n'),
+            Some(escaped @ (',' | ';' | '\\')) => result.push(escaped),
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Extract PARTSTAT value from an ATTENDEE line key
+/// e.g., "ATTENDEE;PARTSTAT=ACCEPTED;CN=..." -> "ACCEPTED"
+fn extract_partstat(key: &str) -> Option<&str> {
+    key.split(';').find_map(|part| part.strip_prefix("PARTSTAT="))
+}
+
+/// Set the participation status of the attendee with `email` in every VEVENT of an
+/// iCalendar document (a series and its overrides alike) and drop the RSVP request, leaving
+/// every other line as it was. None if `email` isn't among the attendees.
+pub fn set_attendee_partstat(ical: &str, email: &str, partstat: &str) -> Option<String> {
+    let mut lines: Vec<Cow<'_, str>> = Vec::new();
+    let mut found = false;
+    let mut raw = ical.lines().peekable();
+    while let Some(first) = raw.next() {
+        let mut property = vec![first];
+        while let Some(continuation) = raw.next_if(|l| l.starts_with([' ', '\t'])) {
+            property.push(continuation);
+        }
+        let unfolded: String = std::iter::once(first).chain(property[1..].iter().map(|l| &l[1..])).collect();
+        let rewritten = parse_ical_line(&unfolded)
+            .filter(|(key, value)| {
+                key.split(';').next() == Some("ATTENDEE")
+                    && value.get(..7).is_some_and(|scheme| scheme.eq_ignore_ascii_case("mailto:"))
+                    && value[7..].eq_ignore_ascii_case(email)
+            })
+            .map(|(key, value)| {
+                let params = key.split(';').filter(|p| !p.starts_with("PARTSTAT=") && !p.starts_with("RSVP="));
+                format!("{};PARTSTAT={}:{}", params.collect::<Vec<_>>().join(";"), partstat, value)
+            });
+        match rewritten {
+            Some(line) => {
+                found = true;
+                lines.push(Cow::Owned(line));
+            }
+            None => lines.extend(property.into_iter().map(Cow::Borrowed)),
+        }
+    }
+    found.then(|| lines.join("\r\n") + "\r\n")
+}
+
+/// Whether an ATTENDEE line key books a room or other resource rather than a person
+/// e.g., "ATTENDEE;CUTYPE=ROOM;CN=Room 4" -> true
+fn is_room_cutype(key: &str) -> bool {
+    key.split(';').filter_map(|part| part.strip_prefix("CUTYPE=")).any(|cutype| {
+        cutype.eq_ignore_ascii_case("ROOM") || cutype.eq_ignore_ascii_case("RESOURCE")
+    })
+}
+
+/// Skip one occurrence of a repeating event: add an EXDATE for `occurrence` (an
+/// [`EventTime::ical_value`]) to the series and drop the override changing it, if any,
+/// leaving every other line as it was. None if the document holds no series.
+pub fn exclude_occurrence(ical: &str, occurrence: &str) -> Option<String> {
+    let excluded = parse_ical_datetime_in("EXDATE", occurrence, &[])?;
+    let zones = if ical.contains("BEGIN:VTIMEZONE") {
+        VTimezone::parse_all(unfold_ical_lines(ical))
+    } else {
+        Vec::new()
+    };
+    let exdate = match excluded {
+        EventTime::Date(_) => format!("EXDATE;VALUE=DATE:{}", occurrence),
+        EventTime::DateTime(_) => format!("EXDATE:{}", occurrence),
+    };
+    let mut lines: Vec<&str> = Vec::new();
+    let mut event: Option<Vec<&str>> = None;
+    let mut found = false;
+    for line in ical.lines() {
+        match event.as_mut() {
+            None if line == "BEGIN:VEVENT" => event = Some(vec![line]),
+            None => lines.push(line),
+            Some(block) if line != "END:VEVENT" => block.push(line),
+            Some(block) => {
+                let unfolded = block.join("\r\n");
+                let recurrence_id = unfold_ical_lines(&unfolded)
+                    .find_map(|l| {
+                        let (key, value) = parse_ical_line(&l)?;
+                        (key.split(';').next() == Some("RECURRENCE-ID")).then(|| parse_ical_datetime_in(key, value, &zones))
+                    });
+                match recurrence_id {
+                    // The series
+                    None => {
+                        found = true;
+                        lines.append(block);
+                        lines.push(&exdate);
+                        lines.push(line);
+                    }
+                    Some(Some(id)) if id.same_occurrence(&excluded) => {}
+                    Some(_) => {
+                        lines.append(block);
+                        lines.push(line);
+                    }
+                }
+                event = None;
+            }
+        }
+    }
+    found.then(|| lines.join("\r\n") + "\r\n")
+}
+
+/// Extract CN (Common Name) from ATTENDEE/ORGANIZER line key
+/// e.g., "ATTENDEE;CN=John Smith;PARTSTAT=ACCEPTED" -> "John Smith"
+fn extract_cn(key: &str) -> Option<String> {
+    for part in key.split(';') {
+        if let Some(name) = part.strip_prefix("CN=") {
+            // Remove surrounding quotes if present
+            let name = name.trim_matches('"');
+            if !name.is_empty() {
+                return Some(name.to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Parse ATTENDEE or ORGANIZER line into ICalAttendee
+/// key: "ATTENDEE;PARTSTAT=ACCEPTED;CN=John Smith"
+/// value: "mailto:john@example.com"
+fn parse_attendee(key: &str, value: &str) -> Option<ICalAttendee> {
+    // Extract email from mailto: value
+    let email = value
+        .strip_prefix("mailto:")
+        .unwrap_or(value)
+        .to_string();
+
+    // Skip if no valid email
+    if email.is_empty() {
+        return None;
+    }
+
+    // Extract display name (CN)
+    let name = extract_cn(key);
+
+    // Extract participation status
+    let partstat = extract_partstat(key).unwrap_or("NEEDS-ACTION").to_string();
+
+    Some(ICalAttendee {
+        name,
+        email,
+        partstat,
+        is_organizer: false, // Caller sets this for ORGANIZER lines
+        is_room: is_room_cutype(key),
+    })
+}
+
+use crate::utils::{extract_dial_in, extract_meeting_url, is_meeting_url, last_covered_day, normalize_hex_color, MeetingPatterns};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_attendee_partstat() {
+        let ical = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:a\r\nDTSTART:20260115T093000Z\r\nDESCRIPTION:Agenda fol\r\n ded\r\nATTENDEE;CN=Boss;PARTSTAT=ACCEPTED:mailto:boss@example.com\r\nATTENDEE;CN=Me;PARTSTAT=NEEDS-ACTION;RSVP=TRUE:mai\r\n lto:Me@iCloud.com\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+        let accepted = set_attendee_partstat(ical, "me@icloud.com", "ACCEPTED").unwrap();
+        assert!(accepted.contains("ATTENDEE;CN=Me;PARTSTAT=ACCEPTED:mailto:Me@iCloud.com\r\n"));
+        assert!(accepted.contains("DESCRIPTION:Agenda fol\r\n ded\r\n"));
+        assert!(accepted.contains("ATTENDEE;CN=Boss;PARTSTAT=ACCEPTED:mailto:boss@example.com"));
+        assert!(!accepted.contains("RSVP"));
+        assert!(ICalEvent::parse_ical(&accepted)[0].accepted);
+
+        assert!(set_attendee_partstat(ical, "someone@example.com", "DECLINED").is_none());
+    }
+
+    #[test]
+    fn test_exclude_occurrence() {
+        let ical = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:a\r\nDTSTART:20260105T093000Z\r\nRRULE:FREQ=WEEKLY\r\nEND:VEVENT\r\n\
+BEGIN:VEVENT\r\nUID:a\r\nRECURRENCE-ID:20260112T093000Z\r\nDTSTART:20260112T110000Z\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 1, 31).unwrap();
+        let count = |ical: &str| ICalEvent::expand_recurrences(ICalEvent::parse_ical(ical), start, end).len();
+        assert_eq!(count(ical), 4);
+
+        // A plain occurrence
+        let skipped = exclude_occurrence(ical, "20260119T093000Z").unwrap();
+        assert!(skipped.contains("RRULE:FREQ=WEEKLY\r\nEXDATE:20260119T093000Z\r\nEND:VEVENT"));
+        assert_eq!(count(&skipped), 3);
+
+        // A changed one loses its override too
+        let skipped = exclude_occurrence(ical, "20260112T093000Z").unwrap();
+        assert!(!skipped.contains("RECURRENCE-ID"));
+        assert_eq!(count(&skipped), 3);
+
+        let single = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:b\r\nRECURRENCE-ID:20260112T093000Z\r\nDTSTART:20260112T110000Z\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+        assert!(exclude_occurrence(single, "20260112T093000Z").is_none());
+    }
+
+    #[test]
+    fn test_parse_simple_ical_event() {
+        let ical = r#"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:test-123@example.com
+SUMMARY:Team Meeting
+DTSTART:20260115T143000Z
+DTEND:20260115T153000Z
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].title(), "Team Meeting");
+        assert_eq!(events[0].uid, "test-123@example.com");
+    }
+
+    #[test]
+    fn test_parse_all_day_event() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:holiday-123
+SUMMARY:Company Holiday
+DTSTART;VALUE=DATE:20260101
+DTEND;VALUE=DATE:20260102
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].start_time(), None);
+        assert_eq!(events[0].start_date(), NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+    }
+
+    #[test]
+    fn test_parse_event_with_timezone() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:tz-event
+SUMMARY:Sofia Meeting
+DTSTART;TZID=Europe/Sofia:20260108T200000
+DTEND;TZID=Europe/Sofia:20260108T210000
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].title(), "Sofia Meeting");
+        // 20:00 in Sofia (EET, UTC+2) is 18:00 UTC
+        assert_eq!(utc_start(&events[0]), "2026-01-08 18:00");
+    }
+
+    fn utc_start(event: &ICalEvent) -> String {
+        match event.dtstart {
+            EventTime::DateTime(dt) => dt.format("%Y-%m-%d %H:%M").to_string(),
+            EventTime::Date(d) => d.to_string(),
+        }
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_expand_weekly_with_exdate_and_override() {
+        let ical = "BEGIN:VCALENDAR\r
+BEGIN:VEVENT\r
+UID:standup\r
+SUMMARY:Standup\r
+DTSTART;TZID=Europe/Sofia:20260302T100000\r
+DTEND;TZID=Europe/Sofia:20260302T103000\r
+RRULE:FREQ=WEEKLY;BYDAY=MO,TH;UNTIL=20260402T000000Z\r
+EXDATE;TZID=Europe/Sofia:20260305T100000\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:standup\r
+RECURRENCE-ID;TZID=Europe/Sofia:20260312T100000\r
+SUMMARY:Standup (moved)\r
+DTSTART;TZID=Europe/Sofia:20260313T120000\r
+DTEND;TZID=Europe/Sofia:20260313T123000\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+        let events = ICalEvent::expand_recurrences(ICalEvent::parse_ical(ical), date(2026, 3, 1), date(2026, 4, 30));
+        let starts: Vec<(&str, EventTime)> = events.iter().map(|e| (e.title(), e.dtstart.clone())).collect();
+        let at = |s: &str| EventTime::DateTime(utc(s));
+        assert_eq!(starts, vec![
+            ("Standup", at("2026-03-02T08:00:00Z")),
+            ("Standup", at("2026-03-09T08:00:00Z")),
+            ("Standup", at("2026-03-16T08:00:00Z")),
+            ("Standup", at("2026-03-19T08:00:00Z")),
+            ("Standup", at("2026-03-23T08:00:00Z")),
+            ("Standup", at("2026-03-26T08:00:00Z")),
+            // Same wall-clock time after the switch to summer time
+            ("Standup", at("2026-03-30T07:00:00Z")),
+            ("Standup (moved)", at("2026-03-13T10:00:00Z")),
+        ]);
+        assert_eq!(events[6].dtend, Some(at("2026-03-30T07:30:00Z")));
+        assert!(events.iter().all(|e| e.recurrence.is_none()));
+    }
+
+    #[test]
+    fn test_expand_monthly_and_yearly_rules() {
+        let ical = "BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:retro
+DTSTART;VALUE=DATE:20260130
+RRULE:FREQ=MONTHLY;BYDAY=-1FR;COUNT=4
+END:VEVENT
+BEGIN:VEVENT
+UID:rent
+DTSTART;VALUE=DATE:20260131
+RRULE:FREQ=MONTHLY;INTERVAL=3
+RDATE;VALUE=DATE:20260415
+END:VEVENT
+BEGIN:VEVENT
+UID:birthday
+DTSTART;VALUE=DATE:20200229
+RRULE:FREQ=YEARLY
+END:VEVENT
+END:VCALENDAR";
+        let events = ICalEvent::expand_recurrences(ICalEvent::parse_ical(ical), date(2026, 1, 1), date(2028, 12, 31));
+        let dates = |uid: &str| -> Vec<NaiveDate> {
+            events.iter().filter(|e| e.uid == uid).map(|e| e.start_date()).collect()
+        };
+        assert_eq!(dates("retro"), vec![date(2026, 1, 30), date(2026, 2, 27), date(2026, 3, 27), date(2026, 4, 24)]);
+        // Months without a 31st are skipped, not moved
+        assert_eq!(dates("rent")[..4], [date(2026, 1, 31), date(2026, 7, 31), date(2026, 10, 31), date(2027, 1, 31)]);
+        assert!(dates("rent").contains(&date(2026, 4, 15)));
+        assert_eq!(dates("birthday"), vec![date(2028, 2, 29)]);
+    }
+
+    #[test]
+    fn test_parse_rrule() {
+        let rule = RecurrenceRule::parse("FREQ=MONTHLY;INTERVAL=2;BYDAY=2TU,-1FR;WKST=SU").unwrap();
+        assert_eq!(rule.freq, Frequency::Monthly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.by_day, vec![(Some(2), Weekday::Tue), (Some(-1), Weekday::Fri)]);
+        assert_eq!(rule.week_start, Weekday::Sun);
+        assert!(RecurrenceRule::parse("FREQ=HOURLY").is_none());
+    }
+
+    #[test]
+    fn test_parse_event_spring_forward_day() {
+        // Sofia switches to EEST (UTC+3) at 03:00 on 2026-03-29
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:dst-spring
+SUMMARY:Morning run
+DTSTART;TZID=Europe/Sofia:20260329T090000
+DTEND;TZID=Europe/Sofia:20260329T100000
+END:VEVENT
+BEGIN:VEVENT
+UID:dst-spring-gap
+SUMMARY:Inside the gap
+DTSTART;TZID=Europe/Sofia:20260329T033000
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 2);
+        assert_eq!(utc_start(&events[0]), "2026-03-29 06:00");
+        // Nonexistent wall-clock time is shifted forward by the gap
+        assert_eq!(utc_start(&events[1]), "2026-03-29 01:30");
+    }
+
+    #[test]
+    fn test_parse_event_fall_back_day() {
+        // Sofia returns to EET (UTC+2) at 04:00 on 2026-10-25
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:dst-fall
+SUMMARY:Afternoon call
+DTSTART;TZID=Europe/Sofia:20261025T150000
+END:VEVENT
+BEGIN:VEVENT
+UID:dst-fall-ambiguous
+SUMMARY:Repeated hour
+DTSTART;TZID=Europe/Sofia:20261025T033000
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 2);
+        assert_eq!(utc_start(&events[0]), "2026-10-25 13:00");
+        // Ambiguous wall-clock time resolves to its first occurrence (still EEST)
+        assert_eq!(utc_start(&events[1]), "2026-10-25 00:30");
+    }
+
+    #[test]
+    fn test_parse_event_utc_suffix_ignores_local_zone() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:utc-event
+DTSTART:20260329T023000Z
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(utc_start(&events[0]), "2026-03-29 02:30");
+    }
+
+    #[test]
+    fn test_extract_tzid() {
+        assert_eq!(extract_tzid("DTSTART;TZID=Europe/Sofia"), Some("Europe/Sofia"));
+        assert_eq!(extract_tzid("DTSTART;TZID=\"America/New_York\";VALUE=DATE-TIME"), Some("America/New_York"));
+        assert_eq!(extract_tzid("DTSTART"), None);
+    }
+
+    #[test]
+    fn test_parse_event_no_title() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:no-title
+DTSTART:20260115T100000Z
+DTEND:20260115T110000Z
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].title(), "(No title)");
+    }
+
+    #[test]
+    fn test_parse_event_with_location_and_description() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:full-event
+SUMMARY:Office Meeting
+DTSTART:20260115T140000Z
+DTEND:20260115T150000Z
+LOCATION:Conference Room A
+DESCRIPTION:Weekly sync meeting
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].location, Some("Conference Room A".to_string()));
+        assert_eq!(events[0].description, Some("Weekly sync meeting".to_string()));
+    }
+
+    #[test]
+    fn test_parse_event_color() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:colored
+SUMMARY:Gym
+DTSTART:20260115T140000Z
+COLOR:#FF2968FF
+END:VEVENT
+BEGIN:VEVENT
+UID:named-color
+SUMMARY:Walk
+DTSTART:20260115T160000Z
+COLOR:turquoise
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events[0].color.as_deref(), Some("#ff2968"));
+        // CSS color names are not mapped
+        assert_eq!(events[1].color, None);
+    }
+
+    #[test]
+    fn test_parse_event_with_escaped_characters() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:escaped
+SUMMARY:Meeting\, with comma
+DTSTART:20260115T100000Z
+DTEND:20260115T110000Z
+DESCRIPTION:Line 1\nLine 2
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary, Some("Meeting, with comma".to_string()));
+        assert_eq!(events[0].description, Some("Line 1\nLine 2".to_string()));
+    }
+
+    #[test]
+    fn test_parse_multiple_events() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:event-1
+SUMMARY:First
+DTSTART:20260115T090000Z
+DTEND:20260115T100000Z
+END:VEVENT
+BEGIN:VEVENT
+UID:event-2
+SUMMARY:Second
+DTSTART:20260115T110000Z
+DTEND:20260115T120000Z
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].title(), "First");
+        assert_eq!(events[1].title(), "Second");
+    }
+
+    #[test]
+    fn test_parse_folded_lines() {
+        let ical = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:folded\r\nSUMMARY:This is a very long summary that has been\r\n  folded across multiple lines\r\nDTSTART:20260115T100000Z\r\nDTEND:20260115T110000Z\r\nEND:VEVENT\r\nEND:VCALENDAR";
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        assert!(events[0].title().contains("folded across multiple lines"));
+    }
+
+    #[test]
+    fn test_partstat_accepted() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:accepted-event
+SUMMARY:Meeting
+DTSTART:20260115T100000Z
+DTEND:20260115T110000Z
+ATTENDEE;PARTSTAT=ACCEPTED;CN=Me:mailto:me@example.com
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        assert!(events[0].accepted);
+    }
+
+    #[test]
+    fn test_partstat_declined() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:declined-event
+SUMMARY:Meeting
+DTSTART:20260115T100000Z
+DTEND:20260115T110000Z
+ATTENDEE;PARTSTAT=DECLINED;CN=Me:mailto:me@example.com
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        assert!(!events[0].accepted);
+    }
+
+    #[test]
+    fn test_partstat_needs_action() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:needs-action
+SUMMARY:Meeting
+DTSTART:20260115T100000Z
+DTEND:20260115T110000Z
+ATTENDEE;PARTSTAT=NEEDS-ACTION;CN=Me:mailto:me@example.com
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        assert!(!events[0].accepted);
+    }
+
+    #[test]
+    fn test_meeting_url_from_url_field() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:zoom-event
+SUMMARY:Zoom Call
+DTSTART:20260115T100000Z
+DTEND:20260115T110000Z
+URL:https://zoom.us/j/123456789
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].meeting_url(&MeetingPatterns::default()), Some("https://zoom.us/j/123456789".to_string()));
+    }
+
+    #[test]
+    fn test_meeting_url_from_location() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:meet-event
+SUMMARY:Google Meet
+DTSTART:20260115T100000Z
+DTEND:20260115T110000Z
+LOCATION:https://meet.google.com/abc-defg-hij
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].meeting_url(&MeetingPatterns::default()), Some("https://meet.google.com/abc-defg-hij".to_string()));
+    }
+
+    #[test]
+    fn test_unfold_ical_lines() {
+        let folded = "SUMMARY:This is\r\n  a folded line";
+        let unfolded: Vec<_> = unfold_ical_lines(folded).collect();
+        assert_eq!(unfolded.len(), 1);
+        assert_eq!(unfolded[0], "SUMMARY:This isa folded line");
+    }
+
+    #[test]
+    fn test_parse_ical_line() {
+        assert_eq!(parse_ical_line("SUMMARY:Test"), Some(("SUMMARY", "Test")));
+        assert_eq!(parse_ical_line("DTSTART;VALUE=DATE:20260101"), Some(("DTSTART;VALUE=DATE", "20260101")));
+        assert_eq!(parse_ical_line("no colon here"), None);
+    }
+
+    #[test]
+    fn test_unescape_ical() {
+        assert_eq!(unescape_ical("test\\nline"), "test\nline");
+        assert_eq!(unescape_ical("a\\,b\\;c"), "a,b;c");
+        assert_eq!(unescape_ical("path\\\\to\\\\file"), "path\\to\\file");
+        assert_eq!(unescape_ical("C:\\\\new"), "C:\\new");
+        assert_eq!(unescape_ical("odd\\x"), "odd\\x");
+    }
+
+    #[test]
+    fn test_extract_partstat() {
+        assert_eq!(extract_partstat("ATTENDEE;PARTSTAT=ACCEPTED;CN=Test"), Some("ACCEPTED"));
+        assert_eq!(extract_partstat("ATTENDEE;CN=Test;PARTSTAT=DECLINED"), Some("DECLINED"));
+        assert_eq!(extract_partstat("ATTENDEE;CN=Test"), None);
+    }
+
+    #[test]
+    fn test_extract_cn() {
+        assert_eq!(extract_cn("ATTENDEE;CN=John Smith;PARTSTAT=ACCEPTED"), Some("John Smith".to_string()));
+        assert_eq!(extract_cn("ATTENDEE;PARTSTAT=ACCEPTED;CN=Jane Doe"), Some("Jane Doe".to_string()));
+        assert_eq!(extract_cn("ATTENDEE;CN=\"Quoted Name\""), Some("Quoted Name".to_string()));
+        assert_eq!(extract_cn("ATTENDEE;PARTSTAT=ACCEPTED"), None);
+    }
+
+    #[test]
+    fn test_parse_attendee() {
+        let attendee = parse_attendee("ATTENDEE;PARTSTAT=ACCEPTED;CN=John Smith", "mailto:john@example.com").unwrap();
+        assert_eq!(attendee.name, Some("John Smith".to_string()));
+        assert_eq!(attendee.email, "john@example.com");
+        assert_eq!(attendee.partstat, "ACCEPTED");
+        assert!(!attendee.is_organizer);
+    }
+
+    #[test]
+    fn test_parse_attendee_no_cn() {
+        let attendee = parse_attendee("ATTENDEE;PARTSTAT=DECLINED", "mailto:bob@example.com").unwrap();
+        assert_eq!(attendee.name, None);
+        assert_eq!(attendee.email, "bob@example.com");
+        assert_eq!(attendee.partstat, "DECLINED");
+    }
+
+    #[test]
+    fn test_parse_attendee_room() {
+        let room = parse_attendee("ATTENDEE;CUTYPE=ROOM;PARTSTAT=ACCEPTED;CN=Room 4", "mailto:room4@example.com").unwrap();
+        assert!(room.is_room);
+        let person = parse_attendee("ATTENDEE;CUTYPE=INDIVIDUAL;CN=Bob", "mailto:bob@example.com").unwrap();
+        assert!(!person.is_room);
+    }
+
+    #[test]
+    fn test_parse_attendee_no_partstat() {
+        let attendee = parse_attendee("ATTENDEE;CN=Unknown", "mailto:unknown@example.com").unwrap();
+        assert_eq!(attendee.partstat, "NEEDS-ACTION");
+    }
+
+    #[test]
+    fn test_parse_event_with_attendees() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:meeting-with-attendees
+SUMMARY:Team Standup
+DTSTART:20260115T100000Z
+DTEND:20260115T110000Z
+ORGANIZER;CN=Alice Manager:mailto:alice@example.com
+ATTENDEE;PARTSTAT=ACCEPTED;CN=Bob Developer:mailto:bob@example.com
+ATTENDEE;PARTSTAT=DECLINED;CN=Charlie Designer:mailto:charlie@example.com
+ATTENDEE;PARTSTAT=TENTATIVE:mailto:dave@example.com
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].attendees.len(), 4);
+
+        // Check organizer
+        let organizer = events[0].attendees.iter().find(|a| a.is_organizer).unwrap();
+        assert_eq!(organizer.name, Some("Alice Manager".to_string()));
+        assert_eq!(organizer.email, "alice@example.com");
+        assert_eq!(organizer.partstat, "ACCEPTED");
+
+        // Check accepted attendee
+        let bob = events[0].attendees.iter().find(|a| a.email == "bob@example.com").unwrap();
+        assert_eq!(bob.name, Some("Bob Developer".to_string()));
+        assert_eq!(bob.partstat, "ACCEPTED");
+
+        // Check declined attendee
+        let charlie = events[0].attendees.iter().find(|a| a.email == "charlie@example.com").unwrap();
+        assert_eq!(charlie.partstat, "DECLINED");
+
+        // Check attendee without name
+        let dave = events[0].attendees.iter().find(|a| a.email == "dave@example.com").unwrap();
+        assert_eq!(dave.name, None);
+        assert_eq!(dave.partstat, "TENTATIVE");
+    }
+
+    #[test]
+    fn test_end_time() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:timed-event
+SUMMARY:Meeting
+DTSTART:20260115T143000Z
+DTEND:20260115T160000Z
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        let expected = DateTime::parse_from_rfc3339("2026-01-15T16:00:00Z")
+            .unwrap()
+            .with_timezone(&Local)
+            .time();
+        assert_eq!(events[0].end_time(), Some(expected));
+    }
+
+    #[test]
+    fn test_end_time_all_day() {
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:all-day
+SUMMARY:Holiday
+DTSTART;VALUE=DATE:20260115
+DTEND;VALUE=DATE:20260116
+END:VEVENT
+END:VCALENDAR"#;
+
+        let events = ICalEvent::parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].end_time(), None);
+    }
+}
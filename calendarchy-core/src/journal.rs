@@ -0,0 +1,172 @@
+//! Daily note journaling: append the meetings of a day to a markdown file
+
+use crate::cache::{DisplayEvent, EventKind};
+use crate::config::{JournalConfig, TimeFormatConfig};
+use crate::error::{CalendarchyError, Result};
+use chrono::{NaiveDate, NaiveDateTime};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+const HEADING: &str = "## Meetings";
+
+/// Resolve the note path for a date: `{date}` becomes YYYY-MM-DD and a leading `~` the home directory
+pub fn note_path(config: &JournalConfig, date: NaiveDate) -> Option<PathBuf> {
+    let template = config.path.as_deref()?;
+    let path = template.replace("{date}", &date.format("%Y-%m-%d").to_string());
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map(|home| home.join(rest)),
+        None => Some(PathBuf::from(path)),
+    }
+}
+
+/// Meetings of the day that were accepted and had ended by `now`, in start order
+pub fn attended<'a>(events: impl IntoIterator<Item = &'a DisplayEvent>, now: NaiveDateTime) -> Vec<&'a DisplayEvent> {
+    let mut attended: Vec<&DisplayEvent> = events
+        .into_iter()
+        .filter(|e| e.accepted && !e.is_free && e.kind == EventKind::Default)
+        .filter(|e| {
+            let end = e.end_time.or(e.start_time);
+            end.is_some_and(|end| e.date.and_time(end) <= now)
+        })
+        .collect();
+    attended.sort_by_key(|e| e.start_time);
+    attended
+}
+
+/// Markdown entry for one meeting: a bullet with time, title and attendees, then its notes quoted
+pub fn format_entry(event: &DisplayEvent, format: &TimeFormatConfig) -> String {
+    let time = event.time_range_label(format);
+    let mut entry = format!("- {} **{}**", time, event.title);
+    let attendees: Vec<&str> = event.attendees.iter()
+        .filter(|a| !a.is_resource())
+        .map(|a| a.name.as_deref().unwrap_or(&a.email))
+        .collect();
+    if !attendees.is_empty() {
+        entry.push_str(&format!(" ({})", attendees.join(", ")));
+    }
+    entry.push('\n');
+    for line in event.description.iter().flat_map(|d| d.lines()).map(str::trim_end).filter(|l| !l.is_empty()) {
+        entry.push_str(&format!("  > {}\n", line));
+    }
+    entry
+}
+
+/// Text to append to a note: entries whose bullet line is already in it are skipped,
+/// and the heading is only added once
+fn new_content(existing: &str, events: &[&DisplayEvent], format: &TimeFormatConfig) -> String {
+    let entries: Vec<String> = events.iter()
+        .map(|e| format_entry(e, format))
+        .filter(|entry| entry.lines().next().is_none_or(|bullet| !existing.lines().any(|l| l == bullet)))
+        .collect();
+    if entries.is_empty() {
+        return String::new();
+    }
+
+    let mut content = String::new();
+    if !existing.lines().any(|l| l == HEADING) {
+        if !existing.is_empty() {
+            content.push_str(if existing.ends_with('\n') { "\n" } else { "\n\n" });
+        }
+        content.push_str(HEADING);
+        content.push_str("\n\n");
+    }
+    content.extend(entries);
+    content
+}
+
+/// Append the given meetings to the day's note, creating it if needed.
+/// Returns the note path and the number of meetings added.
+pub fn append(
+    config: &JournalConfig,
+    date: NaiveDate,
+    events: &[&DisplayEvent],
+    format: &TimeFormatConfig,
+) -> Result<(PathBuf, usize)> {
+    let path = note_path(config, date)
+        .ok_or_else(|| CalendarchyError::Config("Set journal.path to a note template, e.g. \"~/notes/{date}.md\"".to_string()))?;
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    let content = new_content(&existing, events, format);
+    if content.is_empty() {
+        return Ok((path, 0));
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::OpenOptions::new().create(true).append(true).open(&path)?.write_all(content.as_bytes())?;
+    let added = content.lines().filter(|l| l.starts_with("- ")).count();
+    Ok((path, added))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{AttendeeStatus, DisplayAttendee, EventId};
+    use chrono::NaiveTime;
+
+    fn make_event(title: &str, time: &str, end: &str) -> DisplayEvent {
+        DisplayEvent {
+            id: EventId::Google { calendar_id: "primary".to_string(), event_id: title.to_string(), calendar_name: None, account: None },
+            title: title.to_string(),
+            start_time: NaiveTime::parse_from_str(time, "%H:%M").ok(),
+            end_time: NaiveTime::parse_from_str(end, "%H:%M").ok(),
+            date: NaiveDate::from_ymd_opt(2026, 2, 10).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_note_path() {
+        let config = JournalConfig { path: Some("/notes/{date}.md".to_string()), on_rollover: false };
+        let date = NaiveDate::from_ymd_opt(2026, 2, 10).unwrap();
+        assert_eq!(note_path(&config, date), Some(PathBuf::from("/notes/2026-02-10.md")));
+        assert_eq!(note_path(&JournalConfig::default(), date), None);
+    }
+
+    #[test]
+    fn test_attended_only_accepted_and_finished() {
+        let mut declined = make_event("Declined", "09:00", "09:30");
+        declined.accepted = false;
+        let mut focus = make_event("Focus", "09:00", "11:00");
+        focus.kind = EventKind::FocusTime;
+        let events = vec![
+            make_event("Retro", "10:00", "11:00"),
+            make_event("Standup", "09:00", "09:15"),
+            make_event("Later", "14:00", "15:00"),
+            declined,
+            focus,
+        ];
+        let now = NaiveDate::from_ymd_opt(2026, 2, 10).unwrap().and_hms_opt(12, 0, 0).unwrap();
+        let titles: Vec<&str> = attended(&events, now).iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Standup", "Retro"]);
+    }
+
+    #[test]
+    fn test_format_entry() {
+        let mut event = make_event("Planning", "10:00", "11:00");
+        event.attendees = vec![
+            DisplayAttendee { name: Some("Alice".to_string()), email: "alice@example.com".to_string(), status: AttendeeStatus::Accepted, is_self: false },
+            DisplayAttendee { name: None, email: "bob@example.com".to_string(), status: AttendeeStatus::Accepted, is_self: false },
+            DisplayAttendee { name: None, email: "room@resource.calendar.google.com".to_string(), status: AttendeeStatus::Accepted, is_self: false },
+        ];
+        event.description = Some("Agenda\n\nQ3 goals".to_string());
+        assert_eq!(
+            format_entry(&event, &TimeFormatConfig::default()),
+            "- 10:00-11:00 **Planning** (Alice, bob@example.com)\n  > Agenda\n  > Q3 goals\n"
+        );
+    }
+
+    #[test]
+    fn test_new_content_skips_journaled_entries() {
+        let standup = make_event("Standup", "09:00", "09:15");
+        let retro = make_event("Retro", "10:00", "11:00");
+
+        let first = new_content("# Tuesday", &[&standup], &TimeFormatConfig::default());
+        assert_eq!(first, "\n\n## Meetings\n\n- 09:00-09:15 **Standup**\n");
+
+        let existing = format!("# Tuesday{}", first);
+        assert_eq!(new_content(&existing, &[&standup, &retro], &TimeFormatConfig::default()), "- 10:00-11:00 **Retro**\n");
+        assert_eq!(new_content(&existing, &[&standup], &TimeFormatConfig::default()), "");
+    }
+}
@@ -0,0 +1,28 @@
+//! Calendar protocols, clients, caching and parsing shared by the calendarchy frontends;
+//! nothing here touches the terminal
+
+pub mod auth;
+pub mod brief;
+pub mod cache;
+pub mod config;
+pub mod contacts;
+pub mod conversion;
+pub mod demo;
+pub mod device_flow;
+pub mod error;
+pub mod export;
+pub mod feed;
+pub mod google;
+pub mod holidays;
+pub mod icloud;
+pub mod journal;
+pub mod logging;
+pub mod mute;
+pub mod outlook;
+pub mod provider;
+pub mod quick_add;
+pub mod review;
+pub mod search;
+pub mod snooze;
+pub mod tags;
+pub mod utils;
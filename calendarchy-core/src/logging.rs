@@ -0,0 +1,632 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// HTTP log entries kept unless `http_log_capacity` is configured
+pub const DEFAULT_LOG_CAPACITY: usize = 1000;
+const MAX_CAPTURES: usize = 20;
+const MAX_LATENCY_SAMPLES: usize = 1000;
+const MAX_CAPTURED_BODY: usize = 4096;
+
+/// JSON keys and form fields whose values are never written to captures
+const SENSITIVE_KEYS: &[&str] = &[
+    "access_token",
+    "refresh_token",
+    "id_token",
+    "client_secret",
+    "device_code",
+    "code",
+    "password",
+];
+
+/// Headers whose values are never written to captures
+const SENSITIVE_HEADERS: &[&str] = &["authorization", "cookie", "set-cookie", "proxy-authorization"];
+
+/// Fixed-capacity queue that drops its oldest items to make room
+#[derive(Debug, Clone)]
+pub struct RingBuffer<T> {
+    items: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> RingBuffer<T> {
+    pub const fn new(capacity: usize) -> Self {
+        Self { items: VecDeque::new(), capacity }
+    }
+
+    pub fn push(&mut self, item: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.items.len() == self.capacity {
+            self.items.pop_front();
+        }
+        self.items.push_back(item);
+    }
+
+    /// Change the capacity, dropping the oldest items that no longer fit
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let excess = self.items.len().saturating_sub(capacity);
+        self.items.drain(..excess);
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Items from oldest to newest
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.items.iter()
+    }
+}
+
+/// Headers and (redacted) body of a failed HTTP response
+#[derive(Debug, Clone)]
+pub struct HttpCapture {
+    pub timestamp: String,
+    pub status: u16,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+/// Which calendar service a logged request went to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogSource {
+    Google,
+    ICloud,
+    Other,
+}
+
+/// Status class filter for the log viewer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusFilter {
+    All,
+    Success,     // 2xx/3xx responses
+    ClientError, // 4xx responses
+    ServerError, // 5xx responses
+}
+
+impl StatusFilter {
+    pub fn next(self) -> Self {
+        match self {
+            StatusFilter::All => StatusFilter::Success,
+            StatusFilter::Success => StatusFilter::ClientError,
+            StatusFilter::ClientError => StatusFilter::ServerError,
+            StatusFilter::ServerError => StatusFilter::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StatusFilter::All => "all",
+            StatusFilter::Success => "2xx/3xx",
+            StatusFilter::ClientError => "4xx",
+            StatusFilter::ServerError => "5xx",
+        }
+    }
+}
+
+/// A single logged request or response
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub method: Option<String>, // Set for requests
+    pub status: Option<u16>,    // Set for responses
+    pub url: String,
+}
+
+impl LogEntry {
+    pub fn source(&self) -> LogSource {
+        if self.url.contains("googleapis.com") {
+            LogSource::Google
+        } else if self.url.contains("icloud.com") {
+            LogSource::ICloud
+        } else {
+            LogSource::Other
+        }
+    }
+
+    pub fn matches(&self, source: Option<LogSource>, status: StatusFilter) -> bool {
+        if source.is_some_and(|s| s != self.source()) {
+            return false;
+        }
+        match (status, self.status) {
+            (StatusFilter::All, _) => true,
+            (_, None) => false, // Requests have no status
+            (StatusFilter::Success, Some(code)) => code < 400,
+            (StatusFilter::ClientError, Some(code)) => (400..500).contains(&code),
+            (StatusFilter::ServerError, Some(code)) => code >= 500,
+        }
+    }
+
+    pub fn to_line(&self) -> String {
+        match (&self.method, self.status) {
+            (Some(method), _) => format!("[{}] {} {}", self.timestamp, method, self.url),
+            (None, Some(status)) => format!("[{}] <- {} {}", self.timestamp, status, self.url),
+            (None, None) => format!("[{}] {}", self.timestamp, self.url),
+        }
+    }
+}
+
+/// Request counts, error counts and recent latency samples for one source
+#[derive(Debug, Clone)]
+pub struct LatencyStats {
+    pub count: usize,
+    pub errors: usize,
+    samples_ms: RingBuffer<u64>,
+}
+
+impl LatencyStats {
+    const fn new() -> Self {
+        Self { count: 0, errors: 0, samples_ms: RingBuffer::new(MAX_LATENCY_SAMPLES) }
+    }
+
+    fn record(&mut self, elapsed: Duration, is_error: bool) {
+        self.count += 1;
+        if is_error {
+            self.errors += 1;
+        }
+        self.samples_ms.push(elapsed.as_millis() as u64);
+    }
+
+    /// Nearest-rank percentile (0-100) of the recent samples
+    pub fn percentile(&self, pct: usize) -> Option<u64> {
+        if self.samples_ms.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<u64> = self.samples_ms.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (pct * sorted.len()).div_ceil(100).max(1);
+        sorted.get(rank - 1).copied()
+    }
+}
+
+struct SessionMetrics {
+    google: LatencyStats,
+    icloud: LatencyStats,
+    other: LatencyStats,
+    render: LatencyStats,
+}
+
+/// Everything behind an [`HttpLog`] handle
+struct LogStore {
+    entries: RingBuffer<LogEntry>,
+    /// Full captures of failed requests (only recorded in verbose mode)
+    captures: RingBuffer<HttpCapture>,
+    metrics: SessionMetrics,
+}
+
+/// Handle to the session's HTTP log, failure captures and request/render metrics.
+/// Clones share the same log: HTTP clients write to it, the debug screen and debug
+/// reports read from it.
+#[derive(Clone)]
+pub struct HttpLog {
+    store: Arc<Mutex<LogStore>>,
+    /// Whether failed responses are captured with headers and bodies
+    verbose: Arc<AtomicBool>,
+}
+
+impl Default for HttpLog {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_LOG_CAPACITY)
+    }
+}
+
+impl HttpLog {
+    pub fn with_capacity(capacity: usize) -> Self {
+        let store = LogStore {
+            entries: RingBuffer::new(capacity),
+            captures: RingBuffer::new(MAX_CAPTURES),
+            metrics: SessionMetrics {
+                google: LatencyStats::new(),
+                icloud: LatencyStats::new(),
+                other: LatencyStats::new(),
+                render: LatencyStats::new(),
+            },
+        };
+        Self { store: Arc::new(Mutex::new(store)), verbose: Arc::new(AtomicBool::new(false)) }
+    }
+
+    fn with_store<R: Default>(&self, f: impl FnOnce(&mut LogStore) -> R) -> R {
+        self.store.lock().map(|mut store| f(&mut store)).unwrap_or_default()
+    }
+
+    /// How many HTTP log entries to keep; shrinking drops the oldest right away
+    pub fn set_capacity(&self, capacity: usize) {
+        self.with_store(|store| store.entries.set_capacity(capacity));
+    }
+
+    /// Entries kept and the most that will be kept
+    pub fn usage(&self) -> (usize, usize) {
+        self.with_store(|store| (store.entries.len(), store.entries.capacity()))
+    }
+
+    /// Log an HTTP request; returns the start time to pass to `log_response`
+    pub fn log_request(&self, method: &str, url: &str) -> Instant {
+        let entry = LogEntry {
+            timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+            method: Some(method.to_string()),
+            status: None,
+            url: url.to_string(),
+        };
+        self.with_store(|store| store.entries.push(entry));
+        Instant::now()
+    }
+
+    /// Log an HTTP response and record its latency
+    pub fn log_response(&self, status: u16, url: &str, started: Instant) {
+        self.record_outcome(Some(status), url.to_string(), started);
+    }
+
+    /// Log a request that got no response (connection, TLS or timeout error) and count
+    /// it as an error of its source
+    pub fn log_transport_error(&self, url: &str, started: Instant) {
+        self.record_outcome(None, format!("{} (no response)", url), started);
+    }
+
+    fn record_outcome(&self, status: Option<u16>, url: String, started: Instant) {
+        let entry = LogEntry {
+            timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+            method: None,
+            status,
+            url,
+        };
+        self.with_store(|store| {
+            let stats = match entry.source() {
+                LogSource::Google => &mut store.metrics.google,
+                LogSource::ICloud => &mut store.metrics.icloud,
+                LogSource::Other => &mut store.metrics.other,
+            };
+            stats.record(started.elapsed(), status.is_none_or(|code| code >= 400));
+            store.entries.push(entry);
+        });
+    }
+
+    /// Log something that went wrong besides a request, e.g. a cache file that couldn't be
+    /// written
+    pub fn log_note(&self, message: &str) {
+        let entry = LogEntry {
+            timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+            method: None,
+            status: None,
+            url: message.to_string(),
+        };
+        self.with_store(|store| store.entries.push(entry));
+    }
+
+    /// Record how long a frame took to render
+    pub fn record_render(&self, elapsed: Duration) {
+        self.with_store(|store| store.metrics.render.record(elapsed, false));
+    }
+
+    /// Metrics table for the debug screen
+    pub fn metrics_lines(&self) -> Vec<String> {
+        self.with_store(|store| metrics_table(&store.metrics))
+    }
+
+    /// All log entries, newest first
+    pub fn entries(&self) -> Vec<LogEntry> {
+        self.with_store(|store| store.entries.iter().rev().cloned().collect())
+    }
+
+    pub fn set_verbose(&self, enabled: bool) {
+        self.verbose.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn is_verbose(&self) -> bool {
+        self.verbose.load(Ordering::Relaxed)
+    }
+
+    /// Record headers and body of a failed response (no-op unless verbose mode is on)
+    pub fn capture_failure(&self, status: u16, url: &str, headers: Vec<(String, String)>, body: &str) {
+        if !self.is_verbose() {
+            return;
+        }
+
+        let headers = headers
+            .into_iter()
+            .map(|(name, value)| {
+                if SENSITIVE_HEADERS.contains(&name.to_lowercase().as_str()) {
+                    (name, "[REDACTED]".to_string())
+                } else {
+                    (name, value)
+                }
+            })
+            .collect();
+
+        let mut body = redact_secrets(body);
+        if body.len() > MAX_CAPTURED_BODY {
+            let mut end = MAX_CAPTURED_BODY;
+            while !body.is_char_boundary(end) {
+                end -= 1;
+            }
+            body.truncate(end);
+            body.push_str("\n[truncated]");
+        }
+
+        let capture = HttpCapture {
+            timestamp: chrono::Local::now().format("%H:%M:%S").to_string(),
+            status,
+            url: url.to_string(),
+            headers,
+            body,
+        };
+        self.with_store(|store| store.captures.push(capture));
+    }
+
+    /// Captured failures, newest first
+    pub fn captures(&self) -> Vec<HttpCapture> {
+        self.with_store(|store| store.captures.iter().rev().cloned().collect())
+    }
+
+    /// Write the HTTP log and all captures to a file in the cache dir for bug reports
+    pub fn export_debug_report(&self) -> std::io::Result<PathBuf> {
+        let dir = dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("calendarchy");
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("debug-{}.log", chrono::Local::now().format("%Y%m%d-%H%M%S")));
+        fs::write(&path, self.debug_report())?;
+        Ok(path)
+    }
+
+    fn debug_report(&self) -> String {
+        self.with_store(|store| {
+            let mut report = format!(
+                "== HTTP log ({} of up to {} entries) ==\n",
+                store.entries.len(),
+                store.entries.capacity()
+            );
+            for entry in store.entries.iter() {
+                report.push_str(&entry.to_line());
+                report.push('\n');
+            }
+            report.push_str("\n== Metrics ==\n");
+            for line in metrics_table(&store.metrics) {
+                report.push_str(&line);
+                report.push('\n');
+            }
+            report.push_str("\n== Failed responses ==\n");
+            for capture in store.captures.iter() {
+                for line in format_capture(capture) {
+                    report.push_str(&line);
+                    report.push('\n');
+                }
+                report.push('\n');
+            }
+            report
+        })
+    }
+}
+
+fn metrics_table(metrics: &SessionMetrics) -> Vec<String> {
+    let ms = |v: Option<u64>| v.map(|v| format!("{}ms", v)).unwrap_or_else(|| "-".to_string());
+
+    let mut lines = vec![format!(
+        "{:<8} {:>8} {:>12} {:>8} {:>8} {:>8}",
+        "Source", "Count", "Errors", "p50", "p95", "p99"
+    )];
+    // Frames never fail, so the render row leaves the error column blank
+    for (name, stats, counts_errors) in [
+        ("Google", &metrics.google, true),
+        ("iCloud", &metrics.icloud, true),
+        ("Other", &metrics.other, true),
+        ("Render", &metrics.render, false),
+    ] {
+        let errors = match (counts_errors, (stats.errors * 100).checked_div(stats.count)) {
+            (false, _) => "-".to_string(),
+            (true, Some(rate)) => format!("{} ({}%)", stats.errors, rate),
+            (true, None) => "0".to_string(),
+        };
+        lines.push(format!(
+            "{:<8} {:>8} {:>12} {:>8} {:>8} {:>8}",
+            name,
+            stats.count,
+            errors,
+            ms(stats.percentile(50)),
+            ms(stats.percentile(95)),
+            ms(stats.percentile(99)),
+        ));
+    }
+    lines
+}
+
+/// Format a capture as plain text lines
+pub fn format_capture(capture: &HttpCapture) -> Vec<String> {
+    let mut lines = vec![format!("[{}] <- {} {}", capture.timestamp, capture.status, capture.url)];
+    for (name, value) in &capture.headers {
+        lines.push(format!("  {}: {}", name, value));
+    }
+    if !capture.body.is_empty() {
+        lines.push(String::new());
+        lines.extend(capture.body.lines().map(|l| format!("  {}", l)));
+    }
+    lines
+}
+
+/// Mask values of sensitive keys in JSON (`"key": "value"`) and form-encoded (`key=value`) text
+pub fn redact_secrets(text: &str) -> String {
+    let mut result = text.to_string();
+    for key in SENSITIVE_KEYS {
+        result = redact_json_key(&result, key);
+        result = redact_form_key(&result, key);
+    }
+    result
+}
+
+fn redact_json_key(text: &str, key: &str) -> String {
+    let needle = format!("\"{}\"", key);
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(pos) = rest.find(&needle) {
+        let after_key = pos + needle.len();
+        result.push_str(&rest[..after_key]);
+        rest = &rest[after_key..];
+
+        // Expect optional whitespace, a colon, optional whitespace, then a quoted string
+        let trimmed = rest.trim_start();
+        let Some(after_colon) = trimmed.strip_prefix(':') else { continue };
+        let value = after_colon.trim_start();
+        let Some(value_body) = value.strip_prefix('"') else { continue };
+        let Some(end) = value_body.find('"') else { continue };
+
+        result.push_str(&rest[..rest.len() - value.len()]);
+        result.push_str("\"[REDACTED]\"");
+        rest = &value_body[end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+fn redact_form_key(text: &str, key: &str) -> String {
+    text.split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((k, _)) if k == key => format!("{}=[REDACTED]", k),
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_drops_oldest() {
+        let mut buffer = RingBuffer::new(3);
+        for i in 0..5 {
+            buffer.push(i);
+        }
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+
+        buffer.set_capacity(2);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![3, 4]);
+
+        buffer.set_capacity(0);
+        buffer.push(5);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_http_logs_are_independent() {
+        let log = HttpLog::with_capacity(2);
+        let shared = log.clone();
+        let other = HttpLog::default();
+
+        let started = log.log_request("GET", "https://www.googleapis.com/calendar/v3");
+        shared.log_response(200, "https://www.googleapis.com/calendar/v3", started);
+        log.log_request("GET", "https://example.com/team.ics");
+        shared.set_verbose(true);
+
+        let urls: Vec<String> = log.entries().into_iter().map(|e| e.url).collect();
+        assert_eq!(urls, vec!["https://example.com/team.ics", "https://www.googleapis.com/calendar/v3"]);
+        assert_eq!(log.usage(), (2, 2));
+        assert!(log.is_verbose());
+        assert!(other.entries().is_empty());
+        assert!(!other.is_verbose());
+    }
+
+    fn entry(method: Option<&str>, status: Option<u16>, url: &str) -> LogEntry {
+        LogEntry {
+            timestamp: "12:00:00".to_string(),
+            method: method.map(String::from),
+            status,
+            url: url.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_log_entry_source() {
+        assert_eq!(entry(Some("GET"), None, "https://www.googleapis.com/calendar/v3").source(), LogSource::Google);
+        assert_eq!(entry(Some("REPORT"), None, "https://p42-caldav.icloud.com/123/calendars/").source(), LogSource::ICloud);
+        assert_eq!(entry(Some("GET"), None, "https://example.com").source(), LogSource::Other);
+    }
+
+    #[test]
+    fn test_log_entry_filters() {
+        let request = entry(Some("GET"), None, "https://www.googleapis.com/calendar/v3");
+        let ok = entry(None, Some(200), "https://www.googleapis.com/calendar/v3");
+        let not_found = entry(None, Some(404), "https://caldav.icloud.com/");
+        let server_error = entry(None, Some(503), "https://caldav.icloud.com/");
+
+        assert!(request.matches(None, StatusFilter::All));
+        assert!(!request.matches(None, StatusFilter::Success));
+        assert!(ok.matches(Some(LogSource::Google), StatusFilter::Success));
+        assert!(!ok.matches(Some(LogSource::ICloud), StatusFilter::All));
+        assert!(not_found.matches(Some(LogSource::ICloud), StatusFilter::ClientError));
+        assert!(!not_found.matches(None, StatusFilter::ServerError));
+        assert!(server_error.matches(None, StatusFilter::ServerError));
+    }
+
+    #[test]
+    fn test_latency_percentiles() {
+        let mut stats = LatencyStats::new();
+        assert_eq!(stats.percentile(50), None);
+        for ms in 1..=100 {
+            stats.record(Duration::from_millis(ms), ms > 95);
+        }
+        assert_eq!(stats.count, 100);
+        assert_eq!(stats.errors, 5);
+        assert_eq!(stats.percentile(50), Some(50));
+        assert_eq!(stats.percentile(95), Some(95));
+        assert_eq!(stats.percentile(100), Some(100));
+    }
+
+    #[test]
+    fn test_transport_errors_count_as_errors() {
+        let log = HttpLog::default();
+        let url = "https://caldav.icloud.com/";
+        let started = log.log_request("PROPFIND", url);
+        log.log_transport_error(url, started);
+        let started = log.log_request("PROPFIND", url);
+        log.log_response(207, url, started);
+
+        let lines = log.metrics_lines();
+        let icloud = lines.iter().find(|line| line.starts_with("iCloud")).unwrap();
+        assert!(icloud.contains(" 2 "), "{}", icloud);
+        assert!(icloud.contains("1 (50%)"), "{}", icloud);
+        let render = lines.iter().find(|line| line.starts_with("Render")).unwrap();
+        assert!(render.contains(" - "), "{}", render);
+        assert_eq!(log.entries()[2].url, "https://caldav.icloud.com/ (no response)");
+    }
+
+    #[test]
+    fn test_redact_json_secrets() {
+        let body = r#"{"access_token": "ya29.secret", "expires_in": 3599, "refresh_token":"1//abc"}"#;
+        let redacted = redact_secrets(body);
+        assert_eq!(
+            redacted,
+            r#"{"access_token": "[REDACTED]", "expires_in": 3599, "refresh_token":"[REDACTED]"}"#
+        );
+    }
+
+    #[test]
+    fn test_redact_leaves_similar_keys() {
+        let body = r#"{"user_code": "ABCD-EFGH", "error": "invalid_grant"}"#;
+        assert_eq!(redact_secrets(body), body);
+    }
+
+    #[test]
+    fn test_redact_form_secrets() {
+        let body = "client_id=abc&client_secret=shh&grant_type=refresh_token&refresh_token=tok";
+        assert_eq!(
+            redact_secrets(body),
+            "client_id=abc&client_secret=[REDACTED]&grant_type=refresh_token&refresh_token=[REDACTED]"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! Mute rules: hide noisy events (e.g. a shared "Lunch block") from the UI
+
+use crate::cache::{AttendeeStatus, DisplayEvent, EventId};
+use crate::config::MuteRule;
+use regex::{Regex, RegexBuilder};
+
+/// A mute rule with its patterns compiled
+struct CompiledRule {
+    title: Option<Regex>,
+    organizer: Option<Regex>,
+    calendar: Option<Regex>,
+}
+
+impl CompiledRule {
+    fn matches(&self, event: &DisplayEvent) -> bool {
+        let title_ok = self.title.as_ref().is_none_or(|re| re.is_match(&event.title));
+        let organizer_ok = self.organizer.as_ref().is_none_or(|re| {
+            event.attendees.iter()
+                .filter(|a| a.status == AttendeeStatus::Organizer)
+                .any(|a| re.is_match(&a.email) || a.name.as_deref().is_some_and(|n| re.is_match(n)))
+        });
+        let calendar_ok = self.calendar.as_ref().is_none_or(|re| {
+            let name = match &event.id {
+                EventId::Google { calendar_name, .. }
+                | EventId::ICloud { calendar_name, .. }
+                | EventId::Feed { calendar_name, .. } => calendar_name,
+            };
+            name.as_deref().is_some_and(|n| re.is_match(n))
+        });
+        title_ok && organizer_ok && calendar_ok
+    }
+}
+
+/// Compiled set of mute rules; an event is muted if any rule matches it
+#[derive(Default)]
+pub struct MuteFilter {
+    rules: Vec<CompiledRule>,
+}
+
+impl MuteFilter {
+    /// Compile the configured rules. Rules with no patterns are ignored, since they would
+    /// hide everything; an invalid pattern is reported with the rule's position.
+    pub fn new(rules: &[MuteRule]) -> Result<Self, String> {
+        let compile = |pattern: &Option<String>, index: usize| -> Result<Option<Regex>, String> {
+            pattern.as_deref()
+                .map(|p| RegexBuilder::new(p).case_insensitive(true).build())
+                .transpose()
+                .map_err(|e| format!("mute rule {}: {}", index + 1, e))
+        };
+
+        let mut compiled = Vec::new();
+        for (i, rule) in rules.iter().enumerate() {
+            let rule = CompiledRule {
+                title: compile(&rule.title, i)?,
+                organizer: compile(&rule.organizer, i)?,
+                calendar: compile(&rule.calendar, i)?,
+            };
+            if rule.title.is_some() || rule.organizer.is_some() || rule.calendar.is_some() {
+                compiled.push(rule);
+            }
+        }
+        Ok(Self { rules: compiled })
+    }
+
+    pub fn is_muted(&self, event: &DisplayEvent) -> bool {
+        self.rules.iter().any(|rule| rule.matches(event))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::DisplayAttendee;
+    use chrono::{NaiveDate, NaiveTime};
+
+    fn make_event(title: &str, calendar: Option<&str>, organizer: Option<&str>) -> DisplayEvent {
+        DisplayEvent {
+            id: EventId::Google {
+                calendar_id: "team".to_string(),
+                event_id: "id".to_string(),
+                calendar_name: calendar.map(String::from),
+                account: None,
+            },
+            title: title.to_string(),
+            start_time: NaiveTime::from_hms_opt(12, 0, 0),
+            date: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+            attendees: organizer.map(|email| vec![DisplayAttendee {
+                name: None,
+                email: email.to_string(),
+                status: AttendeeStatus::Organizer,
+                is_self: false,
+            }]).unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    fn rule(title: Option<&str>, organizer: Option<&str>, calendar: Option<&str>) -> MuteRule {
+        MuteRule {
+            title: title.map(String::from),
+            organizer: organizer.map(String::from),
+            calendar: calendar.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_mute_by_title_case_insensitive() {
+        let filter = MuteFilter::new(&[rule(Some("^lunch block$"), None, None)]).unwrap();
+        assert!(filter.is_muted(&make_event("Lunch Block", None, None)));
+        assert!(!filter.is_muted(&make_event("Lunch block with Ana", None, None)));
+    }
+
+    #[test]
+    fn test_mute_requires_all_patterns() {
+        let filter = MuteFilter::new(&[rule(Some("lunch"), Some("@corp\\.com$"), Some("Team"))]).unwrap();
+        assert!(filter.is_muted(&make_event("Lunch", Some("Team Calendar"), Some("hr@corp.com"))));
+        assert!(!filter.is_muted(&make_event("Lunch", Some("Personal"), Some("hr@corp.com"))));
+        assert!(!filter.is_muted(&make_event("Lunch", Some("Team Calendar"), None)));
+    }
+
+    #[test]
+    fn test_empty_rule_ignored_and_invalid_pattern_reported() {
+        let filter = MuteFilter::new(&[MuteRule::default()]).unwrap();
+        assert!(!filter.is_muted(&make_event("Anything", None, None)));
+
+        let err = MuteFilter::new(&[rule(Some("ok"), None, None), rule(Some("(unclosed"), None, None)])
+            .err()
+            .unwrap();
+        assert!(err.starts_with("mute rule 2:"));
+    }
+}
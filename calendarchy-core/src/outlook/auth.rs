@@ -0,0 +1,135 @@
+use crate::config::OutlookConfig;
+use crate::device_flow::{self, poll_error, PollResult};
+use crate::error::{failure_body, CalendarchyError, Result};
+use crate::logging::HttpLog;
+use crate::outlook::types::{DeviceCodeResponse, GraphToken, GraphTokenResponse};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use reqwest::Client;
+
+const LOGIN_BASE: &str = "https://login.microsoftonline.com";
+/// Reading calendars, plus a refresh token so the sign-in survives restarts
+const CALENDAR_SCOPE: &str = "offline_access Calendars.Read";
+
+/// Device-code sign-in against the Microsoft identity platform. The app registration
+/// must be a public client, so no secret is sent.
+pub struct OutlookAuth {
+    client: Client,
+    log: HttpLog,
+    config: OutlookConfig,
+}
+
+impl OutlookAuth {
+    pub fn new(config: OutlookConfig, log: HttpLog) -> Self {
+        Self {
+            client: Client::new(),
+            log,
+            config,
+        }
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}/{}/oauth2/v2.0/{}", LOGIN_BASE, urlencoding::encode(&self.config.tenant), path)
+    }
+
+    /// Step 1: Request device code
+    pub async fn request_device_code(&self) -> Result<DeviceCodeResponse> {
+        let url = self.endpoint("devicecode");
+        let started = self.log.log_request("POST", &url);
+        let response = self
+            .client
+            .post(&url)
+            .form(&[
+                ("client_id", self.config.client_id.as_str()),
+                ("scope", CALENDAR_SCOPE),
+            ])
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+        self.log.log_response(response.status().as_u16(), &url, started);
+
+        if !response.status().is_success() {
+            let body = failure_body(response, &self.log).await;
+            return Err(CalendarchyError::Auth(format!(
+                "Failed to get device code: {}",
+                body
+            )));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Step 2: Poll for token once
+    pub async fn poll_for_token(&self, device_code: &str) -> Result<PollResult<GraphToken>> {
+        let url = self.endpoint("token");
+        let started = self.log.log_request("POST", &url);
+        let response = self
+            .client
+            .post(&url)
+            .form(&[
+                ("client_id", self.config.client_id.as_str()),
+                ("device_code", device_code),
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+            ])
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+        self.log.log_response(response.status().as_u16(), &url, started);
+
+        if response.status().is_success() {
+            let token_response: GraphTokenResponse = response.json().await?;
+            Ok(PollResult::Success(graph_token(token_response, None)))
+        } else {
+            poll_error(response.json().await?)
+        }
+    }
+
+    /// Poll until the user has approved the device code (see `device_flow::wait_for_token`)
+    pub async fn wait_for_token(
+        &self,
+        device_code: &str,
+        interval: Duration,
+        expires_at: DateTime<Utc>,
+    ) -> Result<GraphToken> {
+        device_flow::wait_for_token(|| self.poll_for_token(device_code), interval, expires_at).await
+    }
+
+    /// Refresh an expired token. Microsoft rotates refresh tokens, so the new one is kept
+    /// when it comes back.
+    pub async fn refresh_token(&self, refresh_token: &str) -> Result<GraphToken> {
+        let url = self.endpoint("token");
+        let started = self.log.log_request("POST", &format!("{} (refresh)", url));
+        let response = self
+            .client
+            .post(&url)
+            .form(&[
+                ("client_id", self.config.client_id.as_str()),
+                ("refresh_token", refresh_token),
+                ("grant_type", "refresh_token"),
+                ("scope", CALENDAR_SCOPE),
+            ])
+            .send()
+            .await
+            .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+        self.log.log_response(response.status().as_u16(), &url, started);
+
+        if !response.status().is_success() {
+            let body = failure_body(response, &self.log).await;
+            return Err(CalendarchyError::Auth(format!(
+                "Failed to refresh token: {}",
+                body
+            )));
+        }
+
+        let token_response: GraphTokenResponse = response.json().await?;
+        Ok(graph_token(token_response, Some(refresh_token)))
+    }
+}
+
+fn graph_token(response: GraphTokenResponse, previous_refresh: Option<&str>) -> GraphToken {
+    GraphToken {
+        access_token: response.access_token,
+        refresh_token: response.refresh_token.or_else(|| previous_refresh.map(str::to_string)),
+        expires_at: Utc::now() + chrono::Duration::seconds(response.expires_in as i64),
+    }
+}
@@ -0,0 +1,71 @@
+use crate::error::{check_graph_response, Result};
+use crate::logging::HttpLog;
+use crate::outlook::types::{CalendarViewResponse, GraphEvent, GraphToken};
+use chrono::NaiveDate;
+use reqwest::Client;
+
+pub const GRAPH_API_BASE: &str = "https://graph.microsoft.com/v1.0";
+
+/// Fields requested when listing: enough to place, mark and join events. The whole body is
+/// asked for, since `bodyPreview` stops at 255 characters, before most pasted meeting links.
+const VIEW_FIELDS: &str = "id,iCalUId,subject,start,end,isAllDay,isCancelled,isOrganizer,showAs,\
+responseStatus,location,onlineMeeting,body,organizer,attendees";
+
+pub struct GraphClient {
+    client: Client,
+    log: HttpLog,
+}
+
+impl GraphClient {
+    pub fn new(log: HttpLog) -> Self {
+        Self {
+            client: Client::new(),
+            log,
+        }
+    }
+
+    /// Fetch the signed-in user's events between two dates, recurring ones expanded
+    pub async fn list_events(
+        &self,
+        token: &GraphToken,
+        time_min: NaiveDate,
+        time_max: NaiveDate,
+    ) -> Result<Vec<GraphEvent>> {
+        let mut url = format!(
+            "{}/me/calendarView?startDateTime={}T00:00:00Z&endDateTime={}T23:59:59Z&$top=250&$select={}",
+            GRAPH_API_BASE,
+            time_min,
+            time_max,
+            VIEW_FIELDS
+        );
+
+        let mut all_events = Vec::new();
+        loop {
+            let started = self.log.log_request("GET", &url);
+            let response = self
+                .client
+                .get(&url)
+                .bearer_auth(&token.access_token)
+                // Times come back in UTC instead of each event's own zone, bodies as text
+                // instead of HTML
+                .header("Prefer", "outlook.timezone=\"UTC\"")
+                .header("Prefer", "outlook.body-content-type=\"text\"")
+                .send()
+                .await
+                .inspect_err(|_| self.log.log_transport_error(&url, started))?;
+            self.log.log_response(response.status().as_u16(), &url, started);
+
+            let body = check_graph_response(response, "Calendar view", &self.log).await?;
+            let page: CalendarViewResponse = serde_json::from_str(&body)?;
+            all_events.extend(page.value);
+
+            // The next link carries the original query along
+            match page.next_link {
+                Some(next) => url = next,
+                None => break,
+            }
+        }
+
+        Ok(all_events)
+    }
+}
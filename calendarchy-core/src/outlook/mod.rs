@@ -0,0 +1,9 @@
+//! Microsoft 365 / Outlook calendars through Microsoft Graph, signed in with the device flow
+
+pub mod auth;
+pub mod calendar;
+pub mod types;
+
+pub use auth::OutlookAuth;
+pub use calendar::GraphClient;
+pub use types::*;
@@ -0,0 +1,196 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::utils::last_covered_day;
+
+/// Device code response from the Microsoft identity platform
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceCodeResponse {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub expires_in: u64,
+    /// Seconds to wait between token polls
+    #[serde(default = "default_poll_interval")]
+    pub interval: u64,
+}
+
+fn default_poll_interval() -> u64 {
+    5
+}
+
+/// Microsoft identity platform tokens for Graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl GraphToken {
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.expires_at - chrono::Duration::minutes(5)
+    }
+}
+
+/// Token endpoint response; Microsoft rotates the refresh token on every refresh
+#[derive(Debug, Deserialize)]
+pub struct GraphTokenResponse {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_in: u64,
+}
+
+/// One page of `/me/calendarView`
+#[derive(Debug, Deserialize)]
+pub struct CalendarViewResponse {
+    #[serde(default)]
+    pub value: Vec<GraphEvent>,
+    #[serde(rename = "@odata.nextLink")]
+    pub next_link: Option<String>,
+}
+
+/// Event as returned by Graph, with times in UTC (see the `Prefer` header in `GraphClient`)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphEvent {
+    pub id: String,
+    #[serde(rename = "iCalUId")]
+    pub ical_uid: Option<String>,
+    pub subject: Option<String>,
+    pub start: GraphDateTime,
+    pub end: GraphDateTime,
+    #[serde(default)]
+    pub is_all_day: bool,
+    #[serde(default)]
+    pub is_cancelled: bool,
+    #[serde(default)]
+    pub is_organizer: bool,
+    /// "free", "tentative", "busy", "oof", "workingElsewhere" or "unknown"
+    pub show_as: Option<String>,
+    pub response_status: Option<GraphResponseStatus>,
+    pub location: Option<GraphLocation>,
+    pub online_meeting: Option<GraphOnlineMeeting>,
+    pub body: Option<GraphBody>,
+    pub organizer: Option<GraphRecipient>,
+    #[serde(default)]
+    pub attendees: Vec<GraphAttendee>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphDateTime {
+    /// e.g. "2026-03-02T09:00:00.0000000", in `time_zone`
+    pub date_time: String,
+    pub time_zone: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphResponseStatus {
+    /// "none", "organizer", "tentativelyAccepted", "accepted", "declined" or "notResponded"
+    pub response: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphLocation {
+    pub display_name: Option<String>,
+}
+
+/// Event body, as plain text (see the `Prefer` header in `GraphClient`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphBody {
+    pub content: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphOnlineMeeting {
+    pub join_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphRecipient {
+    pub email_address: GraphEmailAddress,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphEmailAddress {
+    pub name: Option<String>,
+    pub address: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphAttendee {
+    pub email_address: GraphEmailAddress,
+    pub status: Option<GraphResponseStatus>,
+    /// "required", "optional" or "resource"
+    #[serde(rename = "type")]
+    pub attendee_type: Option<String>,
+}
+
+impl GraphDateTime {
+    /// The instant, taking the naive time as UTC
+    pub fn to_utc(&self) -> Option<DateTime<Utc>> {
+        NaiveDateTime::parse_from_str(&self.date_time, "%Y-%m-%dT%H:%M:%S%.f")
+            .ok()
+            .map(|dt| dt.and_utc())
+    }
+
+    /// The calendar date as written, for all-day events
+    pub fn date(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(self.date_time.get(..10)?, "%Y-%m-%d").ok()
+    }
+}
+
+impl GraphEvent {
+    /// Local start date; all-day events keep the date they were written for
+    pub fn start_date(&self) -> Option<NaiveDate> {
+        if self.is_all_day {
+            self.start.date()
+        } else {
+            self.start.to_utc().map(|dt| dt.with_timezone(&Local).date_naive())
+        }
+    }
+
+    /// Last day the event covers; the end of an all-day event is exclusive
+    pub fn end_date(&self) -> Option<NaiveDate> {
+        if self.is_all_day {
+            self.end.date()?.pred_opt()
+        } else {
+            let local = |time: &GraphDateTime| time.to_utc().map(|dt| dt.with_timezone(&Local).naive_local());
+            Some(last_covered_day(local(&self.start)?, local(&self.end)?))
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        self.subject.as_deref().filter(|s| !s.is_empty()).unwrap_or("(No title)")
+    }
+
+    pub fn start_time(&self) -> Option<NaiveTime> {
+        if self.is_all_day {
+            return None;
+        }
+        self.start.to_utc().map(|dt| dt.with_timezone(&Local).time())
+    }
+
+    pub fn end_time(&self) -> Option<NaiveTime> {
+        if self.is_all_day {
+            return None;
+        }
+        self.end.to_utc().map(|dt| dt.with_timezone(&Local).time())
+    }
+
+    /// Accepted, organized or never asked; tentative and unanswered invitations are not
+    pub fn is_accepted(&self) -> bool {
+        matches!(
+            self.response_status.as_ref().and_then(|r| r.response.as_deref()),
+            None | Some("none") | Some("organizer") | Some("accepted")
+        )
+    }
+
+    pub fn is_free(&self) -> bool {
+        matches!(self.show_as.as_deref(), Some("free") | Some("workingElsewhere"))
+    }
+}
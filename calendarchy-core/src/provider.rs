@@ -0,0 +1,785 @@
+//! Event providers: sources that can fill the cache for a date range
+
+use crate::auth::{CalendarEntry, GoogleAuthState, ICloudAuthState};
+use crate::cache::{DisplayEvent, EventId, EventSource};
+use crate::config::{Config, ICloudConfig, ProviderConfig};
+use crate::conversion::{
+    display_to_google_event, display_to_ical, google_event_times, google_event_to_display, icloud_event_to_display,
+    outlook_event_to_display, reschedule_ical,
+};
+use crate::demo::MockProvider;
+use crate::error::{CalendarchyError, Result};
+use crate::feed::{IcsFeedProvider, LocalDirProvider};
+use crate::google::{CalendarClient, CalendarEvent, CalendarListEntry, TokenInfo};
+use crate::icloud::{CalDavClient, ICloudAuth, MirrorStore};
+use crate::logging::HttpLog;
+use crate::outlook::{GraphClient, GraphToken};
+use crate::utils::MeetingPatterns;
+use chrono::{NaiveDate, Utc};
+use futures_util::future::join_all;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+/// Events fetched by a provider; owns everything it needs so it can be spawned
+pub type FetchFuture = Pin<Box<dyn Future<Output = Result<Vec<DisplayEvent>>> + Send>>;
+
+/// A change sent to a calendar; owns everything it needs so it can be spawned
+pub type WriteFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+
+/// A calendar backend that produces display-ready events for a date range. Backends that
+/// can change their calendars take the writes for their own events; the others return
+/// `None`, so their events stay read-only.
+pub trait CalendarProvider: Send + Sync {
+    /// Short name used in fetch errors
+    fn name(&self) -> &str;
+
+    /// Which panel the events belong to
+    fn source(&self) -> EventSource;
+
+    /// Fetch all events between `start` and `end` (inclusive)
+    fn fetch_range(&self, start: NaiveDate, end: NaiveDate) -> FetchFuture;
+
+    /// Ask the server for events between `start` and `end` matching `query`, for searches
+    /// reaching beyond the cached months; `None` when the backend can't search
+    fn search(&self, _query: &str, _start: NaiveDate, _end: NaiveDate) -> Option<FetchFuture> {
+        None
+    }
+
+    /// Accept or decline an invitation
+    fn respond(&self, _id: &EventId, _accept: bool) -> Option<WriteFuture> {
+        None
+    }
+
+    /// Delete an event
+    fn delete(&self, _id: &EventId) -> Option<WriteFuture> {
+        None
+    }
+
+    /// Send a moved event's new date and times
+    fn reschedule(&self, _event: &DisplayEvent) -> Option<WriteFuture> {
+        None
+    }
+
+    /// Create an event in one of the provider's calendars
+    fn create(&self, _target: &CopyTarget, _event: &DisplayEvent) -> Option<WriteFuture> {
+        None
+    }
+
+    /// Create an event from free-form text the server parses, e.g. "Lunch with Ana Friday 1pm"
+    fn quick_add(&self, _target: &CopyTarget, _text: &str) -> Option<WriteFuture> {
+        None
+    }
+}
+
+/// A calendar an event can be copied to
+#[derive(Debug, Clone, PartialEq)]
+pub enum CopyTarget {
+    Google { calendar_id: String, name: String },
+    ICloud { calendar_url: String, name: String },
+}
+
+impl CopyTarget {
+    pub fn name(&self) -> &str {
+        match self {
+            CopyTarget::Google { name, .. } | CopyTarget::ICloud { name, .. } => name,
+        }
+    }
+}
+
+/// Send a change through the first provider that takes it; `None` when every one
+/// declines, e.g. because the event is read-only
+pub fn write_with(
+    providers: &[Box<dyn CalendarProvider>],
+    send: impl Fn(&dyn CalendarProvider) -> Option<WriteFuture>,
+) -> Option<WriteFuture> {
+    providers.iter().find_map(|provider| send(provider.as_ref()))
+}
+
+/// State the providers share across the fetches of a session, so what one refresh
+/// learned carries over to the next
+#[derive(Clone, Default)]
+pub struct ProviderContext {
+    pub log: HttpLog,
+    /// iCloud calendars mirrored in memory, synced once per refresh
+    mirrors: MirrorStore,
+    google_listings: GoogleListings,
+}
+
+impl ProviderContext {
+    pub fn new(log: HttpLog) -> Self {
+        Self { log, ..Self::default() }
+    }
+
+    /// Make the next fetches ask the servers for changes again
+    pub fn refresh(&self) {
+        self.mirrors.refresh();
+        self.google_listings.clear();
+    }
+}
+
+/// The calendar lists and color palettes of the Google accounts by account, fetched
+/// once per refresh instead of with every month
+#[derive(Clone, Default)]
+struct GoogleListings(Arc<Mutex<HashMap<String, ListingCell>>>);
+
+/// Filled by the first fetch of the refresh that gets the listing; the others wait for it
+type ListingCell = Arc<OnceCell<Arc<GoogleListing>>>;
+
+#[derive(Default)]
+struct GoogleListing {
+    calendars: Vec<CalendarListEntry>,
+    /// Color ids on events refer to the account's palette
+    palette: HashMap<String, String>,
+}
+
+impl GoogleListings {
+    fn clear(&self) {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// The listing of `account`, fetched by the first fetch that needs it. A failed
+    /// listing is asked for again by the next one; names and colors are fine to miss.
+    async fn get(&self, account: &str, client: &CalendarClient, tokens: &TokenInfo) -> Arc<GoogleListing> {
+        let cell = self.0.lock().unwrap_or_else(|e| e.into_inner()).entry(account.to_string()).or_default().clone();
+        cell.get_or_try_init(|| async {
+            let calendars = client.list_calendars(tokens).await?;
+            let palette = client.get_event_colors(tokens).await?;
+            Ok::<_, CalendarchyError>(Arc::new(GoogleListing { calendars, palette }))
+        })
+        .await
+        .cloned()
+        .unwrap_or_default()
+    }
+}
+
+/// Events of the calendars that could be fetched. The failed ones are logged so one
+/// removed calendar doesn't hide the others; only when every one failed is that an error.
+fn fetched_calendars(results: Vec<(&str, Result<Vec<DisplayEvent>>)>, log: &HttpLog) -> Result<Vec<DisplayEvent>> {
+    let mut events = Vec::new();
+    let mut first_error = None;
+    let mut fetched_any = results.is_empty();
+    for (calendar_id, result) in results {
+        match result {
+            Ok(calendar_events) => {
+                events.extend(calendar_events);
+                fetched_any = true;
+            }
+            Err(e) => {
+                log.log_note(&format!("Calendar {} could not be fetched: {}", calendar_id, e));
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    match first_error {
+        Some(e) if !fetched_any => Err(e),
+        _ => Ok(events),
+    }
+}
+
+/// Instantiate the configured backends that are ready to fetch. Google accounts, CalDAV and
+/// Outlook only take part once authenticated; demo mode replaces everything with mock data.
+pub fn registry(
+    config: &Config,
+    google: &GoogleAuthState,
+    icloud: &ICloudAuthState,
+    outlook: Option<&GraphToken>,
+    google_accounts: &BTreeMap<String, TokenInfo>,
+    context: &ProviderContext,
+    demo: bool,
+) -> Vec<Box<dyn CalendarProvider>> {
+    let log = &context.log;
+    if demo {
+        return vec![
+            Box::new(MockProvider::new(EventSource::Google)),
+            Box::new(MockProvider::new(EventSource::ICloud)),
+        ];
+    }
+
+    // Invalid patterns are reported when the config is loaded
+    let meeting_patterns = MeetingPatterns::new(&config.meeting_patterns).unwrap_or_default();
+    let mut providers: Vec<Box<dyn CalendarProvider>> = Vec::new();
+    for entry in config.providers() {
+        match entry {
+            ProviderConfig::Google => {
+                if let GoogleAuthState::Authenticated(tokens) = google {
+                    let calendar_ids = config.google.as_ref()
+                        .map(|c| c.shown_calendar_ids())
+                        .unwrap_or_else(|| vec!["primary".to_string()]);
+                    providers.push(Box::new(GoogleProvider {
+                        tokens: tokens.clone(),
+                        calendar_ids,
+                        log: log.clone(),
+                        listings: context.google_listings.clone(),
+                        meeting_patterns: meeting_patterns.clone(),
+                    }));
+                }
+            }
+            ProviderConfig::Caldav => {
+                if let (ICloudAuthState::Authenticated { calendars }, Some(icloud_config)) = (icloud, &config.icloud) {
+                    providers.push(Box::new(CalDavProvider {
+                        config: icloud_config.clone(),
+                        calendars: calendars.iter().filter(|c| !c.hidden).cloned().collect(),
+                        log: log.clone(),
+                        mirrors: context.mirrors.clone(),
+                        meeting_patterns: meeting_patterns.clone(),
+                    }));
+                }
+            }
+            ProviderConfig::GoogleShared { calendar_id, name, panel } => {
+                if let GoogleAuthState::Authenticated(tokens) = google {
+                    providers.push(Box::new(SharedGoogleProvider {
+                        tokens: tokens.clone(),
+                        name: name.unwrap_or_else(|| calendar_id.clone()),
+                        calendar_id,
+                        panel,
+                        log: log.clone(),
+                        meeting_patterns: meeting_patterns.clone(),
+                    }));
+                }
+            }
+            ProviderConfig::GoogleAccount { account, calendar_ids, name, panel } => {
+                if let Some(tokens) = google_accounts.get(&account) {
+                    providers.push(Box::new(GoogleAccountProvider {
+                        account: account.clone(),
+                        tokens: tokens.clone(),
+                        calendar_ids: if calendar_ids.is_empty() { vec!["primary".to_string()] } else { calendar_ids },
+                        name: name.unwrap_or(account),
+                        panel,
+                        log: log.clone(),
+                        listings: context.google_listings.clone(),
+                        meeting_patterns: meeting_patterns.clone(),
+                    }));
+                }
+            }
+            ProviderConfig::Outlook { name, panel } => {
+                if let Some(tokens) = outlook {
+                    providers.push(Box::new(OutlookProvider {
+                        tokens: tokens.clone(),
+                        name: name.unwrap_or_else(|| "Outlook".to_string()),
+                        panel,
+                        log: log.clone(),
+                        meeting_patterns: meeting_patterns.clone(),
+                    }));
+                }
+            }
+            ProviderConfig::IcsFeed { url, name, panel } => {
+                providers.push(Box::new(IcsFeedProvider::new(url, name, panel, log.clone(), meeting_patterns.clone())));
+            }
+            ProviderConfig::LocalDir { path, name, panel } => {
+                providers.push(Box::new(LocalDirProvider::new(path, name, panel, meeting_patterns.clone())));
+            }
+        }
+    }
+    providers
+}
+
+/// The shown calendars of the Google account, fetched in parallel, with colors resolved
+/// from the account's palette. With several calendars shown, events without a color of
+/// their own take their calendar's.
+pub struct GoogleProvider {
+    tokens: TokenInfo,
+    calendar_ids: Vec<String>,
+    log: HttpLog,
+    listings: GoogleListings,
+    meeting_patterns: MeetingPatterns,
+}
+
+impl CalendarProvider for GoogleProvider {
+    fn name(&self) -> &str {
+        "Google"
+    }
+
+    fn source(&self) -> EventSource {
+        EventSource::Google
+    }
+
+    fn fetch_range(&self, start: NaiveDate, end: NaiveDate) -> FetchFuture {
+        self.fetch(start, end, None)
+    }
+
+    fn search(&self, query: &str, start: NaiveDate, end: NaiveDate) -> Option<FetchFuture> {
+        Some(self.fetch(start, end, Some(query.to_string())))
+    }
+
+    fn respond(&self, id: &EventId, accept: bool) -> Option<WriteFuture> {
+        let EventId::Google { calendar_id, event_id, account: None, .. } = id.clone() else { return None };
+        Some(respond_google(self.client(), calendar_id, event_id, accept))
+    }
+
+    fn delete(&self, id: &EventId) -> Option<WriteFuture> {
+        let EventId::Google { calendar_id, event_id, account: None, .. } = id.clone() else { return None };
+        Some(delete_google(self.client(), calendar_id, event_id))
+    }
+
+    fn reschedule(&self, event: &DisplayEvent) -> Option<WriteFuture> {
+        let EventId::Google { calendar_id, event_id, account: None, .. } = event.id.clone() else { return None };
+        Some(reschedule_google(self.client(), calendar_id, event_id, event))
+    }
+
+    fn create(&self, target: &CopyTarget, event: &DisplayEvent) -> Option<WriteFuture> {
+        let CopyTarget::Google { calendar_id, .. } = target.clone() else { return None };
+        let (client, tokens) = self.client();
+        let body = display_to_google_event(event);
+        Some(Box::pin(async move { client.insert_event(&tokens, &calendar_id, &body).await }))
+    }
+
+    fn quick_add(&self, target: &CopyTarget, text: &str) -> Option<WriteFuture> {
+        let CopyTarget::Google { calendar_id, .. } = target.clone() else { return None };
+        let (client, tokens) = self.client();
+        let text = text.to_string();
+        Some(Box::pin(async move { client.quick_add(&tokens, &calendar_id, &text).await }))
+    }
+}
+
+impl GoogleProvider {
+    fn client(&self) -> (CalendarClient, TokenInfo) {
+        (CalendarClient::new(self.log.clone()), self.tokens.clone())
+    }
+
+    /// Events of the shown calendars, or only those the server matches to `query`
+    fn fetch(&self, start: NaiveDate, end: NaiveDate, query: Option<String>) -> FetchFuture {
+        let tokens = self.tokens.clone();
+        let calendar_ids = self.calendar_ids.clone();
+        let meeting_patterns = self.meeting_patterns.clone();
+        let listings = self.listings.clone();
+        let log = self.log.clone();
+        let client = CalendarClient::new(self.log.clone());
+        Box::pin(async move {
+            let listing = listings.get("", &client, &tokens).await;
+            let several = calendar_ids.len() > 1;
+            let fetches = calendar_ids.iter().map(|calendar_id| {
+                let (client, tokens, listing, query, meeting_patterns) = (&client, &tokens, &listing, &query, &meeting_patterns);
+                let calendar = listing.calendars.iter().find(|c| c.has_id(calendar_id));
+                let fallback_color = calendar.and_then(|c| c.background_color.clone()).filter(|_| several);
+                async move {
+                    let calendar_name = match calendar {
+                        Some(calendar) => Some(calendar.name().to_string()),
+                        None => client.get_calendar_name(tokens, calendar_id).await.ok().flatten(),
+                    };
+                    let mut events = match query {
+                        Some(query) => client.search_events(tokens, calendar_id, query, start, end).await?,
+                        None => client.list_events(tokens, calendar_id, start, end).await?,
+                    };
+                    for e in &mut events {
+                        e.resolve_color(&listing.palette);
+                        e.color = e.color.take().or_else(|| fallback_color.clone());
+                    }
+                    // Listings leave out attendee and room names until an event is opened
+                    Ok(events
+                        .into_iter()
+                        .filter_map(|e| google_event_to_display(e, calendar_id.clone(), calendar_name.clone(), None, meeting_patterns))
+                        .map(|e| DisplayEvent { partial: true, ..e })
+                        .collect())
+                }
+            });
+            let results = join_all(fetches).await;
+            fetched_calendars(calendar_ids.iter().map(String::as_str).zip(results).collect(), &log)
+        })
+    }
+}
+
+fn respond_google((client, tokens): (CalendarClient, TokenInfo), calendar_id: String, event_id: String, accept: bool) -> WriteFuture {
+    let response = if accept { "accepted" } else { "declined" };
+    Box::pin(async move { client.respond_to_event(&tokens, &calendar_id, &event_id, response).await })
+}
+
+fn delete_google((client, tokens): (CalendarClient, TokenInfo), calendar_id: String, event_id: String) -> WriteFuture {
+    Box::pin(async move { client.delete_event(&tokens, &calendar_id, &event_id).await })
+}
+
+fn reschedule_google((client, tokens): (CalendarClient, TokenInfo), calendar_id: String, event_id: String, event: &DisplayEvent) -> WriteFuture {
+    let body = google_event_times(event);
+    Box::pin(async move { client.patch_event(&tokens, &calendar_id, &event_id, &body).await })
+}
+
+/// Someone else's Google calendar, read through the signed-in account. Its events are
+/// read-only like feed events, so no action is offered on them.
+pub struct SharedGoogleProvider {
+    tokens: TokenInfo,
+    calendar_id: String,
+    name: String,
+    panel: EventSource,
+    log: HttpLog,
+    meeting_patterns: MeetingPatterns,
+}
+
+impl CalendarProvider for SharedGoogleProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self) -> EventSource {
+        self.panel
+    }
+
+    fn fetch_range(&self, start: NaiveDate, end: NaiveDate) -> FetchFuture {
+        let tokens = self.tokens.clone();
+        let calendar_id = self.calendar_id.clone();
+        let name = self.name.clone();
+        let meeting_patterns = self.meeting_patterns.clone();
+        let client = CalendarClient::new(self.log.clone());
+        Box::pin(async move {
+            let events = client.list_events(&tokens, &calendar_id, start, end).await?;
+            Ok(events
+                .into_iter()
+                .filter_map(|mut e| {
+                    // Free/busy access leaves out everything but the times
+                    e.summary.get_or_insert_with(|| "Busy".to_string());
+                    read_only_google_event(e, &calendar_id, &name, &meeting_patterns)
+                })
+                // Someone else's meetings aren't yours to attend: they stay out of the next
+                // event, meeting alerts and the review
+                .map(|e| DisplayEvent { accepted: false, ..e })
+                .collect())
+        })
+    }
+}
+
+/// A Google event shown like a feed event, so no action is offered on it
+fn read_only_google_event(
+    event: CalendarEvent,
+    calendar_id: &str,
+    name: &str,
+    meeting_patterns: &MeetingPatterns,
+) -> Option<DisplayEvent> {
+    let event_id = event.id.clone();
+    let mut event = google_event_to_display(event, calendar_id.to_string(), Some(name.to_string()), None, meeting_patterns)?;
+    event.id = EventId::Feed {
+        location: calendar_id.to_string(),
+        event_uid: event_id,
+        calendar_name: Some(name.to_string()),
+    };
+    Some(event)
+}
+
+/// The calendars of another Google account, signed in with `:google <account>`. Its events
+/// name the account, so answering, deleting and moving them go through its own tokens.
+pub struct GoogleAccountProvider {
+    account: String,
+    tokens: TokenInfo,
+    calendar_ids: Vec<String>,
+    name: String,
+    panel: EventSource,
+    log: HttpLog,
+    listings: GoogleListings,
+    meeting_patterns: MeetingPatterns,
+}
+
+impl CalendarProvider for GoogleAccountProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self) -> EventSource {
+        self.panel
+    }
+
+    fn fetch_range(&self, start: NaiveDate, end: NaiveDate) -> FetchFuture {
+        let tokens = self.tokens.clone();
+        let calendar_ids = self.calendar_ids.clone();
+        let name = self.name.clone();
+        let meeting_patterns = self.meeting_patterns.clone();
+        let account = self.account.clone();
+        let listings = self.listings.clone();
+        let log = self.log.clone();
+        let client = CalendarClient::new(self.log.clone());
+        Box::pin(async move {
+            let listing = listings.get(&account, &client, &tokens).await;
+            let fetches = calendar_ids.iter().map(|calendar_id| {
+                let (client, tokens, listing, name, account) = (&client, &tokens, &listing, &name, &account);
+                let meeting_patterns = &meeting_patterns;
+                async move {
+                    let events = client.list_events(tokens, calendar_id, start, end).await?;
+                    // Listings leave out attendee and room names until an event is opened
+                    Ok(events
+                        .into_iter()
+                        .filter_map(|mut e| {
+                            e.resolve_color(&listing.palette);
+                            google_event_to_display(e, calendar_id.clone(), Some(name.clone()), Some(account.clone()), meeting_patterns)
+                        })
+                        .map(|e| DisplayEvent { partial: true, ..e })
+                        .collect())
+                }
+            });
+            let results = join_all(fetches).await;
+            fetched_calendars(calendar_ids.iter().map(String::as_str).zip(results).collect(), &log)
+        })
+    }
+
+    fn respond(&self, id: &EventId, accept: bool) -> Option<WriteFuture> {
+        let (calendar_id, event_id) = self.own(id)?;
+        Some(respond_google(self.client(), calendar_id, event_id, accept))
+    }
+
+    fn delete(&self, id: &EventId) -> Option<WriteFuture> {
+        let (calendar_id, event_id) = self.own(id)?;
+        Some(delete_google(self.client(), calendar_id, event_id))
+    }
+
+    fn reschedule(&self, event: &DisplayEvent) -> Option<WriteFuture> {
+        let (calendar_id, event_id) = self.own(&event.id)?;
+        Some(reschedule_google(self.client(), calendar_id, event_id, event))
+    }
+}
+
+impl GoogleAccountProvider {
+    fn client(&self) -> (CalendarClient, TokenInfo) {
+        (CalendarClient::new(self.log.clone()), self.tokens.clone())
+    }
+
+    /// Calendar and event id of one of this account's events
+    fn own(&self, id: &EventId) -> Option<(String, String)> {
+        match id {
+            EventId::Google { calendar_id, event_id, account: Some(account), .. } if *account == self.account => {
+                Some((calendar_id.clone(), event_id.clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The default calendar of a Microsoft 365 / Outlook account. Its events are read-only
+/// like feed events.
+pub struct OutlookProvider {
+    tokens: GraphToken,
+    name: String,
+    panel: EventSource,
+    log: HttpLog,
+    meeting_patterns: MeetingPatterns,
+}
+
+impl CalendarProvider for OutlookProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self) -> EventSource {
+        self.panel
+    }
+
+    fn fetch_range(&self, start: NaiveDate, end: NaiveDate) -> FetchFuture {
+        let tokens = self.tokens.clone();
+        let name = self.name.clone();
+        let meeting_patterns = self.meeting_patterns.clone();
+        let client = GraphClient::new(self.log.clone());
+        Box::pin(async move {
+            let events = client.list_events(&tokens, start, end).await?;
+            Ok(events
+                .into_iter()
+                .filter_map(|e| outlook_event_to_display(e, &name, &meeting_patterns))
+                .collect())
+        })
+    }
+}
+
+/// All discovered calendars of an iCloud (CalDAV) account
+pub struct CalDavProvider {
+    config: ICloudConfig,
+    calendars: Vec<CalendarEntry>,
+    log: HttpLog,
+    mirrors: MirrorStore,
+    meeting_patterns: MeetingPatterns,
+}
+
+impl CalendarProvider for CalDavProvider {
+    fn name(&self) -> &str {
+        "iCloud"
+    }
+
+    fn source(&self) -> EventSource {
+        EventSource::ICloud
+    }
+
+    fn fetch_range(&self, start: NaiveDate, end: NaiveDate) -> FetchFuture {
+        self.fetch(start, end, None)
+    }
+
+    fn search(&self, query: &str, start: NaiveDate, end: NaiveDate) -> Option<FetchFuture> {
+        Some(self.fetch(start, end, Some(query.to_string())))
+    }
+
+    /// Rewrites the account's PARTSTAT in the stored document
+    fn respond(&self, id: &EventId, accept: bool) -> Option<WriteFuture> {
+        let EventId::ICloud { calendar_url, event_uid, occurrence, .. } = id.clone() else { return None };
+        if occurrence.is_some() {
+            return Some(single_occurrence_refused("answered"));
+        }
+        let client = self.client();
+        let partstat = if accept { "ACCEPTED" } else { "DECLINED" };
+        Some(Box::pin(async move { client.respond_to_event(&calendar_url, &event_uid, partstat).await }))
+    }
+
+    /// Deletes the document, or for one occurrence of a repeating event, excludes it
+    fn delete(&self, id: &EventId) -> Option<WriteFuture> {
+        let EventId::ICloud { calendar_url, event_uid, etag, occurrence, .. } = id.clone() else { return None };
+        let client = self.client();
+        Some(Box::pin(async move {
+            match occurrence {
+                Some(occurrence) => client.delete_occurrence(&calendar_url, &event_uid, &occurrence).await,
+                None => client.delete_event(&calendar_url, &event_uid, etag.as_deref()).await,
+            }
+        }))
+    }
+
+    /// Rewrites the times in the stored document, keeping everything else
+    fn reschedule(&self, event: &DisplayEvent) -> Option<WriteFuture> {
+        let EventId::ICloud { calendar_url, event_uid, occurrence, .. } = event.id.clone() else { return None };
+        if occurrence.is_some() {
+            return Some(single_occurrence_refused("moved"));
+        }
+        let client = self.client();
+        let event = event.clone();
+        Some(Box::pin(async move {
+            let (ical, etag) = client.get_event(&calendar_url, &event_uid).await?;
+            let ical = reschedule_ical(&ical, &event).map_err(CalendarchyError::CalDav)?;
+            client.update_event(&calendar_url, &event_uid, ical, etag.as_deref()).await
+        }))
+    }
+
+    fn create(&self, target: &CopyTarget, event: &DisplayEvent) -> Option<WriteFuture> {
+        let CopyTarget::ICloud { calendar_url, .. } = target.clone() else { return None };
+        let client = self.client();
+        let uid = format!("calendarchy-{}", Utc::now().timestamp_nanos_opt().unwrap_or_default());
+        let ical = display_to_ical(event, &uid);
+        Some(Box::pin(async move { client.create_event(&calendar_url, &uid, ical).await }))
+    }
+}
+
+/// Answers and moves rewrite the whole document, which would change every occurrence of a
+/// repeating event instead of the one picked
+fn single_occurrence_refused(change: &'static str) -> WriteFuture {
+    Box::pin(async move {
+        Err(CalendarchyError::CalDav(format!("One occurrence of a repeating iCloud event can't be {} here", change)))
+    })
+}
+
+impl CalDavProvider {
+    fn client(&self) -> CalDavClient {
+        CalDavClient::new(ICloudAuth::new(self.config.clone()), self.log.clone())
+    }
+
+    /// Events of the shown calendars, or only those whose title matches `query`
+    fn fetch(&self, start: NaiveDate, end: NaiveDate, query: Option<String>) -> FetchFuture {
+        let client = self.client();
+        let calendars = self.calendars.clone();
+        let mirrors = self.mirrors.clone();
+        let meeting_patterns = self.meeting_patterns.clone();
+        Box::pin(async move {
+            let mut all_events = Vec::new();
+            for cal in &calendars {
+                let events = match query {
+                    Some(ref query) => client.search_events(&cal.url, query, start, end).await?,
+                    None => client.sync_events(&mirrors, &cal.url, start, end).await?,
+                };
+                for mut e in events {
+                    e.color = e.color.or_else(|| cal.color.clone());
+                    all_events.push(icloud_event_to_display(e, cal.name.clone(), &meeting_patterns));
+                }
+            }
+            Ok(all_events)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt;
+
+    fn sources(providers: &[Box<dyn CalendarProvider>]) -> Vec<(&str, EventSource)> {
+        providers.iter().map(|p| (p.name(), p.source())).collect()
+    }
+
+    #[test]
+    fn test_registry_skips_unauthenticated_backends() {
+        let config: Config = serde_json::from_str(
+            r#"{"providers": [{"type": "google"}, {"type": "caldav"}, {"type": "outlook"}, {"type": "ics_feed", "url": "https://example.com/team.ics", "name": "Team"}]}"#,
+        ).unwrap();
+        let providers = registry(&config, &GoogleAuthState::NotAuthenticated, &ICloudAuthState::NotConfigured, None, &BTreeMap::new(), &ProviderContext::default(), false);
+        assert_eq!(sources(&providers), vec![("Team", EventSource::ICloud)]);
+    }
+
+    #[test]
+    fn test_registry_google_accounts_once_signed_in() {
+        let config: Config = serde_json::from_str(
+            r#"{"providers": [{"type": "google_account", "account": "personal", "panel": "icloud"}, {"type": "google_account", "account": "school", "name": "Uni"}]}"#,
+        ).unwrap();
+        let tokens = TokenInfo {
+            access_token: "token".to_string(),
+            refresh_token: None,
+            expires_at: chrono::Utc::now() + chrono::Duration::hours(1),
+            token_type: "Bearer".to_string(),
+        };
+        let accounts = BTreeMap::from([("personal".to_string(), tokens)]);
+        let providers = registry(&config, &GoogleAuthState::NotConfigured, &ICloudAuthState::NotConfigured, None, &accounts, &ProviderContext::default(), false);
+        assert_eq!(sources(&providers), vec![("personal", EventSource::ICloud)]);
+
+        // The account's events are answered with its own tokens, the main account's aren't
+        let event = |account: Option<&str>| EventId::Google {
+            calendar_id: "primary".to_string(),
+            event_id: "e".to_string(),
+            calendar_name: None,
+            account: account.map(String::from),
+        };
+        assert!(write_with(&providers, |p| p.respond(&event(Some("personal")), true)).is_some());
+        assert!(write_with(&providers, |p| p.delete(&event(Some("school")))).is_none());
+        assert!(write_with(&providers, |p| p.delete(&event(None))).is_none());
+    }
+
+    #[test]
+    fn test_writes_go_to_the_owning_provider() {
+        let config: Config = serde_json::from_str(
+            r#"{"google": {"client_id": "id", "client_secret": "secret"}, "icloud": {"apple_id": "me@icloud.com", "app_password": "pw"}, "providers": [{"type": "google"}, {"type": "caldav"}, {"type": "ics_feed", "url": "https://example.com/team.ics"}]}"#,
+        ).unwrap();
+        let tokens = TokenInfo {
+            access_token: "token".to_string(),
+            refresh_token: None,
+            expires_at: chrono::Utc::now() + chrono::Duration::hours(1),
+            token_type: "Bearer".to_string(),
+        };
+        let icloud = ICloudAuthState::Authenticated { calendars: Vec::new() };
+        let providers = registry(&config, &GoogleAuthState::Authenticated(tokens), &icloud, None, &BTreeMap::new(), &ProviderContext::default(), false);
+        let takers = |id: &EventId| -> Vec<&str> {
+            providers.iter().filter(|p| p.delete(id).is_some()).map(|p| p.name()).collect()
+        };
+
+        let google = EventId::Google { calendar_id: "primary".to_string(), event_id: "e".to_string(), calendar_name: None, account: None };
+        let icloud = EventId::ICloud { calendar_url: "u".to_string(), event_uid: "e".to_string(), etag: None, calendar_name: None, occurrence: None };
+        let feed = EventId::Feed { location: "https://example.com/team.ics".to_string(), event_uid: "e".to_string(), calendar_name: None };
+        assert_eq!(takers(&google), vec!["Google"]);
+        assert_eq!(takers(&icloud), vec!["iCloud"]);
+        assert!(write_with(&providers, |p| p.respond(&feed, true)).is_none());
+
+        // Answering one occurrence would answer the whole series, so it's refused
+        let occurrence = EventId::ICloud {
+            calendar_url: "u".to_string(),
+            event_uid: "e".to_string(),
+            etag: None,
+            calendar_name: None,
+            occurrence: Some("20260112T093000Z".to_string()),
+        };
+        let answer = write_with(&providers, |p| p.respond(&occurrence, true)).unwrap();
+        assert!(matches!(answer.now_or_never(), Some(Err(CalendarchyError::CalDav(_)))));
+    }
+
+    #[test]
+    fn test_failed_calendars_dont_hide_the_others() {
+        let log = HttpLog::default();
+        let removed = || Err(CalendarchyError::Api("Not Found".to_string()));
+        assert!(fetched_calendars(vec![("primary", Ok(Vec::new())), ("gone@group.calendar.google.com", removed())], &log).is_ok());
+        assert!(log.entries()[0].url.contains("gone@group.calendar.google.com"));
+
+        assert!(fetched_calendars(vec![("primary", removed()), ("team", removed())], &log).is_err());
+        assert!(fetched_calendars(Vec::new(), &log).is_ok());
+    }
+
+    #[test]
+    fn test_registry_demo_mode() {
+        let config: Config = serde_json::from_str("{}").unwrap();
+        let providers = registry(&config, &GoogleAuthState::NotConfigured, &ICloudAuthState::NotConfigured, None, &BTreeMap::new(), &ProviderContext::default(), true);
+        assert_eq!(sources(&providers), vec![("Demo", EventSource::Google), ("Demo", EventSource::ICloud)]);
+    }
+}
@@ -0,0 +1,268 @@
+//! New events from pasted text: an ICS invite, or a line like "Thu 15:00 Design review"
+//! or "Lunch with Maria tomorrow 12:30-13:15 at Bistro"; and from .ics files to import
+
+use crate::cache::{DisplayEvent, EventId};
+use crate::conversion::icloud_event_to_display;
+use crate::icloud::ICalEvent;
+use crate::utils::MeetingPatterns;
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Weekday};
+
+/// Parse a pasted invite: the first event of an ICS blob, otherwise the first non-empty line
+pub fn parse_pasted(text: &str, today: NaiveDate, meeting_patterns: &MeetingPatterns) -> Result<DisplayEvent, String> {
+    if text.contains("BEGIN:VEVENT") {
+        return ICalEvent::parse_ical(text)
+            .into_iter()
+            .next()
+            .map(|event| icloud_event_to_display(event, None, meeting_patterns))
+            .ok_or_else(|| "No event found in the pasted calendar data".to_string());
+    }
+    let line = text.lines().map(str::trim).find(|l| !l.is_empty()).ok_or("Clipboard is empty")?;
+    parse_line(line, today)
+}
+
+/// Every event of an .ics file, for importing into a calendar, and the titles of the
+/// repeating ones, which are left out: only their first occurrence would arrive, without
+/// the rule or the changed occurrences.
+pub fn parse_ics(text: &str, meeting_patterns: &MeetingPatterns) -> (Vec<DisplayEvent>, Vec<String>) {
+    let (repeating, single): (Vec<_>, Vec<_>) = ICalEvent::parse_ical(text)
+        .into_iter()
+        .filter(|event| event.recurrence_id.is_none())
+        .map(|event| (event.recurrence.is_some(), icloud_event_to_display(event, None, meeting_patterns)))
+        .partition(|(repeats, _)| *repeats);
+    (
+        single.into_iter().map(|(_, event)| event).collect(),
+        repeating.into_iter().map(|(_, event)| event.title).collect(),
+    )
+}
+
+/// Parse "[day] [start[-end]] title", where day is a weekday name (the next one from today),
+/// "today", "tomorrow" or YYYY-MM-DD and defaults to today; without a time the event is all-day
+pub fn parse_line(line: &str, today: NaiveDate) -> Result<DisplayEvent, String> {
+    let mut words = line.split_whitespace().peekable();
+
+    let date = match words.peek().and_then(|w| parse_day(w, today)) {
+        Some(date) => {
+            words.next();
+            date
+        }
+        None => today,
+    };
+    let (start, end) = match words.peek().and_then(|w| parse_time_range(w)) {
+        Some(range) => {
+            words.next();
+            range
+        }
+        None => (None, None),
+    };
+    let title = words.collect::<Vec<_>>().join(" ");
+    new_event(line, title, date, start, end)
+}
+
+/// Parse a free-form line like "Lunch with Maria tomorrow 12:30-13:15 at Bistro": the day
+/// and time may be anywhere in it, and a trailing "at <place>" becomes the location.
+/// Day and time default as in `parse_line`.
+pub fn parse_natural(line: &str, today: NaiveDate) -> Result<DisplayEvent, String> {
+    let mut date = None;
+    let mut range = None;
+    let mut rest: Vec<&str> = Vec::new();
+    for word in line.split_whitespace() {
+        let preposition = rest.last().map(|w| w.to_lowercase());
+        if date.is_none() && let Some(day) = parse_day(word, today) {
+            // "on Friday"
+            if preposition.as_deref() == Some("on") {
+                rest.pop();
+            }
+            date = Some(day);
+        } else if range.is_none() && let Some(times) = parse_time_range(word) {
+            // "at 12:30"
+            if preposition.as_deref() == Some("at") {
+                rest.pop();
+            }
+            range = Some(times);
+        } else {
+            rest.push(word);
+        }
+    }
+    let location = rest.iter()
+        .rposition(|w| w.eq_ignore_ascii_case("at"))
+        .filter(|&at| at > 0 && at + 1 < rest.len())
+        .map(|at| {
+            let place = rest[at + 1..].join(" ");
+            rest.truncate(at);
+            place
+        });
+    let (start, end) = range.unwrap_or((None, None));
+    let event = new_event(line, rest.join(" "), date.unwrap_or(today), start, end)?;
+    Ok(DisplayEvent { location, ..event })
+}
+
+/// A new event of this machine's making; `line` is what it was parsed from
+fn new_event(
+    line: &str,
+    title: String,
+    date: NaiveDate,
+    start: Option<NaiveTime>,
+    end: Option<NaiveTime>,
+) -> Result<DisplayEvent, String> {
+    if title.is_empty() {
+        return Err(format!("No title in \"{}\"", line));
+    }
+
+    Ok(DisplayEvent {
+        id: EventId::ICloud { calendar_url: String::new(), event_uid: String::new(), etag: None, calendar_name: None, occurrence: None },
+        title,
+        start_time: start,
+        end_time: end,
+        date,
+        is_organizer: true,
+        ..Default::default()
+    })
+}
+
+/// The line `parse_line` reads back into the same event, e.g. "2026-02-12 15:00-16:00 Design review"
+pub fn to_line(event: &DisplayEvent) -> String {
+    let date = event.date.format("%Y-%m-%d");
+    match (event.start_time, event.end_time) {
+        (Some(start), Some(end)) => format!("{} {}-{} {}", date, start.format("%H:%M"), end.format("%H:%M"), event.title),
+        (Some(start), None) => format!("{} {} {}", date, start.format("%H:%M"), event.title),
+        _ => format!("{} {}", date, event.title),
+    }
+}
+
+fn parse_day(word: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let lower = word.to_lowercase();
+    match lower.as_str() {
+        "today" => return Some(today),
+        "tomorrow" => return Some(today + Duration::days(1)),
+        _ => {}
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(word, "%Y-%m-%d") {
+        return Some(date);
+    }
+    // "Thu", "thursday" and "Thu," all name the next Thursday (today if it is one)
+    let weekday: Weekday = lower.trim_end_matches(',').parse().ok()?;
+    let ahead = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+    Some(today + Duration::days(ahead as i64))
+}
+
+/// "15:00", "9:30", "3pm", "3:30pm" or a range of them like "15:00-16:30"
+fn parse_time_range(word: &str) -> Option<(Option<NaiveTime>, Option<NaiveTime>)> {
+    match word.split_once(['-', '\u{2013}']) {
+        Some((start, end)) => Some((Some(parse_clock(start)?), Some(parse_clock(end)?))),
+        None => Some((Some(parse_clock(word)?), None)),
+    }
+}
+
+fn parse_clock(text: &str) -> Option<NaiveTime> {
+    let lower = text.to_lowercase();
+    let (clock, offset) = match (lower.strip_suffix("am"), lower.strip_suffix("pm")) {
+        (Some(clock), _) => (clock, Some(0)),
+        (_, Some(clock)) => (clock, Some(12)),
+        _ => (lower.as_str(), None),
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 => (hour.parse::<u32>().ok()?, minute.parse().ok()?),
+        // A bare number is only a time with am/pm
+        None if offset.is_some() => (clock.parse::<u32>().ok()?, 0),
+        _ => return None,
+    };
+    let hour = match offset {
+        Some(offset) if (1..=12).contains(&hour) => hour % 12 + offset,
+        Some(_) => return None,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TimeFormatConfig;
+
+    fn tuesday() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 2, 10).unwrap()
+    }
+
+    #[test]
+    fn test_parse_line_weekday_and_time() {
+        let event = parse_line("Thu 15:00 Design review", tuesday()).unwrap();
+        assert_eq!(event.date, NaiveDate::from_ymd_opt(2026, 2, 12).unwrap());
+        assert_eq!(event.time_label(&TimeFormatConfig::default()), "15:00");
+        assert_eq!(event.end_time, None);
+        assert_eq!(event.title, "Design review");
+
+        let same_day = parse_line("tuesday 9:30 Standup", tuesday()).unwrap();
+        assert_eq!((same_day.date, same_day.time_label(&TimeFormatConfig::default()).as_str()), (tuesday(), "09:30"));
+    }
+
+    #[test]
+    fn test_parse_line_defaults() {
+        let event = parse_line("15:00-16:30 Retro", tuesday()).unwrap();
+        assert_eq!(event.date, tuesday());
+        assert_eq!(event.end_time, NaiveTime::from_hms_opt(16, 30, 0));
+
+        let all_day = parse_line("tomorrow Offsite", tuesday()).unwrap();
+        assert_eq!(all_day.date, NaiveDate::from_ymd_opt(2026, 2, 11).unwrap());
+        assert!(all_day.is_all_day());
+
+        assert!(parse_line("Thu 15:00", tuesday()).is_err());
+    }
+
+    #[test]
+    fn test_to_line_round_trip() {
+        let event = parse_line("Thu 15:00-16:00 Design review", tuesday()).unwrap();
+        let line = to_line(&event);
+        assert_eq!(line, "2026-02-12 15:00-16:00 Design review");
+        let again = parse_line(&line, tuesday()).unwrap();
+        assert_eq!((again.date, again.start_time, again.end_time, again.title), (event.date, event.start_time, event.end_time, event.title));
+    }
+
+    #[test]
+    fn test_parse_natural() {
+        let event = parse_natural("Lunch with Maria tomorrow 12:30-13:15 at Bistro", tuesday()).unwrap();
+        assert_eq!(event.title, "Lunch with Maria");
+        assert_eq!(event.date, NaiveDate::from_ymd_opt(2026, 2, 11).unwrap());
+        assert_eq!(event.time_range_label(&TimeFormatConfig::default()), "12:30-13:15");
+        assert_eq!(event.location.as_deref(), Some("Bistro"));
+
+        let event = parse_natural("Dentist on Friday at 3:30pm", tuesday()).unwrap();
+        assert_eq!((event.title.as_str(), event.time_label(&TimeFormatConfig::default()).as_str()), ("Dentist", "15:30"));
+        assert_eq!(event.date, NaiveDate::from_ymd_opt(2026, 2, 13).unwrap());
+        assert_eq!(event.location, None);
+
+        let event = parse_natural("Standup 9am", tuesday()).unwrap();
+        assert_eq!((event.title.as_str(), event.date, event.time_label(&TimeFormatConfig::default()).as_str()), ("Standup", tuesday(), "09:00"));
+        assert!(parse_natural("Standup 13pm", tuesday()).unwrap().is_all_day());
+
+        assert!(parse_natural("tomorrow at 12:00", tuesday()).is_err());
+    }
+
+    #[test]
+    fn test_parse_pasted_ics() {
+        let ics = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:abc\r\nSUMMARY:Vendor call\r\nLOCATION:Room 4\r\n\
+                   DTSTART;VALUE=DATE:20260213\r\nDTEND;VALUE=DATE:20260214\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+        let event = parse_pasted(ics, tuesday(), &MeetingPatterns::default()).unwrap();
+        assert_eq!(event.title, "Vendor call");
+        assert_eq!(event.location.as_deref(), Some("Room 4"));
+        assert_eq!(event.date, NaiveDate::from_ymd_opt(2026, 2, 13).unwrap());
+
+        assert!(parse_pasted("\n  \n", tuesday(), &MeetingPatterns::default()).is_err());
+    }
+
+    #[test]
+    fn test_parse_ics_file() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+                   BEGIN:VEVENT\r\nUID:a\r\nSUMMARY:Kickoff\r\nDTSTART:20260216T100000\r\nDTEND:20260216T110000\r\nEND:VEVENT\r\n\
+                   BEGIN:VEVENT\r\nUID:b\r\nSUMMARY:Weekly\r\nDTSTART:20260217T090000\r\nRRULE:FREQ=WEEKLY\r\nEND:VEVENT\r\n\
+                   BEGIN:VEVENT\r\nUID:b\r\nRECURRENCE-ID:20260224T090000\r\nSUMMARY:Weekly (moved)\r\nDTSTART:20260224T100000\r\nEND:VEVENT\r\n\
+                   END:VCALENDAR\r\n";
+        let (events, repeating) = parse_ics(ics, &MeetingPatterns::default());
+        let titles: Vec<&str> = events.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Kickoff"]);
+        assert_eq!(events[0].start_time, NaiveTime::from_hms_opt(10, 0, 0));
+        // The series and its moved occurrence are reported rather than half imported
+        assert_eq!(repeating, vec!["Weekly"]);
+        let (events, repeating) = parse_ics("not a calendar", &MeetingPatterns::default());
+        assert!(events.is_empty() && repeating.is_empty());
+    }
+}
@@ -0,0 +1,410 @@
+//! Weekly review: meeting load, calendars, focus gaps and collaborators of a week
+
+use crate::cache::{DisplayEvent, EventCache, EventKind};
+use crate::config::{TimeFormatConfig, WorkHoursConfig};
+use crate::utils::format_duration;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use std::collections::HashMap;
+
+/// Shorter gaps are too small to count as focus time
+const MIN_GAP_MINUTES: i64 = 30;
+const TOP_GAPS: usize = 3;
+const TOP_COLLABORATORS: usize = 5;
+
+/// Meeting time spent in one calendar
+#[derive(Debug, PartialEq)]
+pub struct CalendarShare {
+    pub name: String,
+    pub meetings: usize,
+    pub minutes: i64,
+}
+
+/// Uninterrupted time between meetings within working hours
+#[derive(Debug, PartialEq)]
+pub struct FocusGap {
+    pub date: NaiveDate,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl FocusGap {
+    pub fn minutes(&self) -> i64 {
+        (self.end - self.start).num_minutes()
+    }
+}
+
+/// Summary of the week starting at `start`
+#[derive(Debug)]
+pub struct WeeklyReview {
+    pub start: NaiveDate,
+    pub meetings: usize,
+    pub minutes: i64,
+    /// Busiest calendar first
+    pub calendars: Vec<CalendarShare>,
+    /// Longest first
+    pub focus_gaps: Vec<FocusGap>,
+    /// Display name and number of shared meetings, most frequent first
+    pub collaborators: Vec<(String, usize)>,
+}
+
+/// Timed, accepted meetings within a day that take up time (not focus blocks or free events)
+pub fn is_meeting(event: &DisplayEvent) -> bool {
+    event.accepted && is_busy(event)
+}
+
+/// Timed events within a day that block the time, like Google's free/busy: accepted ones and
+/// invitations not answered yet, but not declined, free or focus events
+fn is_busy(event: &DisplayEvent) -> bool {
+    !event.declined()
+        && !event.is_free
+        && event.kind == EventKind::Default
+        && !event.in_all_day_strip()
+        && event.duration_minutes().is_some()
+}
+
+/// Meeting count and time per calendar, busiest first
+pub fn calendar_shares(meetings: &[&DisplayEvent]) -> Vec<CalendarShare> {
+    let mut shares: Vec<CalendarShare> = Vec::new();
+    for event in meetings {
+        let minutes = event.duration_minutes().unwrap_or(0);
+        match shares.iter_mut().find(|c| c.name == event.calendar_label()) {
+            Some(share) => {
+                share.meetings += 1;
+                share.minutes += minutes;
+            }
+            None => shares.push(CalendarShare { name: event.calendar_label().to_string(), meetings: 1, minutes }),
+        }
+    }
+    shares.sort_by(|a, b| b.minutes.cmp(&a.minutes).then_with(|| a.name.cmp(&b.name)));
+    shares
+}
+
+/// Free stretches of at least half an hour within a day's working hours around its meetings
+/// (given in start order)
+pub fn free_blocks(meetings: &[&DisplayEvent], (workday_start, workday_end): (NaiveTime, NaiveTime)) -> Vec<(NaiveTime, NaiveTime)> {
+    let mut blocks = Vec::new();
+
+    // Walk the meetings tracking the end of the busy stretch
+    let mut free_from = workday_start;
+    for event in meetings {
+        let (Some(begin), Some(end)) = (event.start_time, event.end_time) else { continue };
+        let begin = begin.min(workday_end);
+        if (begin - free_from).num_minutes() >= MIN_GAP_MINUTES {
+            blocks.push((free_from, begin));
+        }
+        // Events running past midnight end "before" they start
+        let end = if end <= begin { workday_end } else { end };
+        free_from = free_from.max(end);
+    }
+    if (workday_end - free_from).num_minutes() >= MIN_GAP_MINUTES {
+        blocks.push((free_from, workday_end));
+    }
+    blocks
+}
+
+/// Free stretches within working hours long enough for `minutes`, from `from` on over
+/// the next `days` days, around the meetings of both calendars. The time left today
+/// starts on the next half hour.
+pub fn open_slots(
+    events: &EventCache,
+    from: NaiveDateTime,
+    days: i64,
+    minutes: i64,
+    work_hours: &WorkHoursConfig,
+) -> Vec<(NaiveDate, NaiveTime, NaiveTime)> {
+    let mut slots = Vec::new();
+    for date in (0..days).map(|offset| from.date() + Duration::days(offset)) {
+        let Some((mut start, end)) = work_hours.on(date.weekday()) else { continue };
+        if date == from.date() {
+            let earliest = (from.hour() * 60 + from.minute()).div_ceil(30) * 30;
+            let Some(earliest) = NaiveTime::from_hms_opt(earliest / 60, earliest % 60, 0) else { continue };
+            start = start.max(earliest);
+        }
+        if start >= end {
+            continue;
+        }
+        let mut meetings: Vec<&DisplayEvent> = events.day(date).filter(|e| is_busy(e)).collect();
+        meetings.sort_by_key(|e| e.start_time);
+        slots.extend(
+            free_blocks(&meetings, (start, end))
+                .into_iter()
+                .filter(|(begin, finish)| (*finish - *begin).num_minutes() >= minutes)
+                .map(|(begin, finish)| (date, begin, finish)),
+        );
+    }
+    slots
+}
+
+/// The first `minutes` free within working hours over a week from `from`, for proposing
+/// another time for a meeting
+pub fn first_free_slot(
+    events: &EventCache,
+    from: NaiveDateTime,
+    minutes: i64,
+    work_hours: &WorkHoursConfig,
+) -> Option<(NaiveDate, NaiveTime, NaiveTime)> {
+    open_slots(events, from, 7, minutes, work_hours)
+        .into_iter()
+        .next()
+        .map(|(date, begin, _)| (date, begin, begin + Duration::minutes(minutes)))
+}
+
+/// Parse a duration like "45", "45m", "1h" or "1h30m" into minutes
+pub fn parse_minutes(text: &str) -> Option<i64> {
+    let text = text.trim().to_lowercase();
+    let (hours, rest) = match text.split_once('h') {
+        Some((hours, rest)) => (hours.parse::<i64>().ok()?, rest),
+        None => (0, text.as_str()),
+    };
+    let rest = rest.trim_end_matches("min").trim_end_matches('m');
+    let minutes = if rest.is_empty() { 0 } else { rest.parse::<i64>().ok()? };
+    let total = hours * 60 + minutes;
+    (total > 0).then_some(total)
+}
+
+impl WeeklyReview {
+    pub fn compute(events: &EventCache, start: NaiveDate, work_hours: &WorkHoursConfig) -> Self {
+        let mut review = Self {
+            start,
+            meetings: 0,
+            minutes: 0,
+            calendars: Vec::new(),
+            focus_gaps: Vec::new(),
+            collaborators: Vec::new(),
+        };
+        let mut collaborators: HashMap<String, (String, usize)> = HashMap::new();
+        let mut week = Vec::new();
+
+        for date in (0..7).map(|offset| start + Duration::days(offset)) {
+            let mut meetings: Vec<&DisplayEvent> = events.day(date)
+                .filter(|e| is_meeting(e))
+                .collect();
+            meetings.sort_by_key(|e| e.start_time);
+
+            for event in &meetings {
+                review.meetings += 1;
+                review.minutes += event.duration_minutes().unwrap_or(0);
+                for attendee in &event.attendees {
+                    if attendee.is_self || attendee.is_resource() {
+                        continue;
+                    }
+                    let name = attendee.name.clone().unwrap_or_else(|| attendee.email.clone());
+                    collaborators.entry(attendee.email.to_lowercase()).or_insert((name, 0)).1 += 1;
+                }
+            }
+
+            if let Some(hours) = work_hours.on(date.weekday()) {
+                review.focus_gaps.extend(
+                    free_blocks(&meetings, hours).into_iter().map(|(start, end)| FocusGap { date, start, end }),
+                );
+            }
+            week.extend(meetings);
+        }
+
+        review.calendars = calendar_shares(&week);
+        review.focus_gaps.sort_by(|a, b| b.minutes().cmp(&a.minutes()).then_with(|| (a.date, a.start).cmp(&(b.date, b.start))));
+        review.focus_gaps.truncate(TOP_GAPS);
+        review.collaborators = collaborators.into_values().collect();
+        review.collaborators.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        review.collaborators.truncate(TOP_COLLABORATORS);
+        review
+    }
+
+    pub fn title(&self) -> String {
+        format!("Week of {}", self.start.format("%b %d, %Y"))
+    }
+
+    /// Report lines for the TUI
+    pub fn lines(&self, format: &TimeFormatConfig) -> Vec<String> {
+        let mut lines = vec![format!("Meetings: {} ({})", self.meetings, format_duration(self.minutes))];
+
+        lines.push(String::new());
+        lines.push("Calendars".to_string());
+        for share in &self.calendars {
+            lines.push(format!("  {:<20} {:>3}  {}", share.name, share.meetings, format_duration(share.minutes)));
+        }
+
+        lines.push(String::new());
+        lines.push("Longest focus gaps".to_string());
+        if self.focus_gaps.is_empty() {
+            lines.push("  None".to_string());
+        }
+        for gap in &self.focus_gaps {
+            lines.push(format!(
+                "  {}  {}-{}  {}",
+                gap.date.format("%a %b %d"),
+                format.time(gap.start),
+                format.time(gap.end),
+                format_duration(gap.minutes())
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push("Top collaborators".to_string());
+        if self.collaborators.is_empty() {
+            lines.push("  None".to_string());
+        }
+        for (name, count) in &self.collaborators {
+            lines.push(format!("  {:<24} {}", name, count));
+        }
+        lines
+    }
+
+    /// Markdown version of the report
+    pub fn to_markdown(&self, format: &TimeFormatConfig) -> String {
+        let mut md = format!("# {}\n\n", self.title());
+        md.push_str(&format!("**Meetings:** {} ({})\n\n", self.meetings, format_duration(self.minutes)));
+
+        md.push_str("## Calendars\n\n| Calendar | Meetings | Time |\n|---|---:|---:|\n");
+        for share in &self.calendars {
+            md.push_str(&format!("| {} | {} | {} |\n", share.name, share.meetings, format_duration(share.minutes)));
+        }
+
+        md.push_str("\n## Longest focus gaps\n\n");
+        for gap in &self.focus_gaps {
+            md.push_str(&format!(
+                "- {} {}-{} ({})\n",
+                gap.date.format("%a %b %d"),
+                format.time(gap.start),
+                format.time(gap.end),
+                format_duration(gap.minutes())
+            ));
+        }
+
+        md.push_str("\n## Top collaborators\n\n");
+        for (name, count) in &self.collaborators {
+            md.push_str(&format!("- {} ({})\n", name, count));
+        }
+        md
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{AttendeeStatus, DisplayAttendee, EventId, EventSource};
+
+    fn make_event(title: &str, date: NaiveDate, start: &str, end: &str, attendees: &[&str]) -> DisplayEvent {
+        DisplayEvent {
+            id: EventId::Google { calendar_id: "primary".to_string(), event_id: title.to_string(), calendar_name: Some("Work".to_string()), account: None },
+            title: title.to_string(),
+            start_time: NaiveTime::parse_from_str(start, "%H:%M").ok(),
+            end_time: NaiveTime::parse_from_str(end, "%H:%M").ok(),
+            date,
+            attendees: attendees.iter().map(|email| DisplayAttendee {
+                name: None,
+                email: email.to_string(),
+                status: AttendeeStatus::Accepted,
+                is_self: *email == "me@example.com",
+            }).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_weekly_review() {
+        let monday = NaiveDate::from_ymd_opt(2026, 2, 9).unwrap();
+        let tuesday = monday + Duration::days(1);
+        let mut focus = make_event("Focus", tuesday, "09:00", "12:00", &[]);
+        focus.kind = EventKind::FocusTime;
+        let mut cache = EventCache::new();
+        cache.store(EventSource::Google, vec![
+            make_event("Standup", monday, "09:00", "09:30", &["me@example.com", "alice@example.com"]),
+            make_event("Planning", monday, "10:00", "17:00", &["me@example.com", "alice@example.com", "bob@example.com"]),
+            make_event("1:1", tuesday, "13:00", "13:30", &["bob@example.com"]),
+            focus,
+        ], monday);
+
+        let review = WeeklyReview::compute(&cache, monday, &WorkHoursConfig::default());
+        assert_eq!(review.meetings, 3);
+        assert_eq!(review.minutes, 8 * 60);
+        assert_eq!(review.calendars, vec![CalendarShare { name: "Work".to_string(), meetings: 3, minutes: 8 * 60 }]);
+        assert_eq!(review.collaborators, vec![("alice@example.com".to_string(), 2), ("bob@example.com".to_string(), 2)]);
+
+        // Tuesday's focus block doesn't count as a meeting, so the morning is a gap
+        let longest = &review.focus_gaps[0];
+        assert_eq!((longest.date, longest.minutes()), (monday + Duration::days(2), 9 * 60));
+        assert_eq!(review.focus_gaps.len(), TOP_GAPS);
+    }
+
+    #[test]
+    fn test_calendar_shares() {
+        let monday = NaiveDate::from_ymd_opt(2026, 2, 9).unwrap();
+        let standup = make_event("Standup", monday, "09:00", "09:30", &[]);
+        let planning = make_event("Planning", monday, "10:00", "12:00", &[]);
+        let mut dentist = make_event("Dentist", monday, "15:00", "16:00", &[]);
+        dentist.id = EventId::ICloud {
+            calendar_url: "https://caldav.icloud.com/home/".to_string(),
+            event_uid: "dentist".to_string(),
+            etag: None,
+            calendar_name: Some("Home".to_string()),
+            occurrence: None,
+        };
+        assert_eq!(calendar_shares(&[&standup, &dentist, &planning]), vec![
+            CalendarShare { name: "Work".to_string(), meetings: 2, minutes: 150 },
+            CalendarShare { name: "Home".to_string(), meetings: 1, minutes: 60 },
+        ]);
+    }
+
+    #[test]
+    fn test_first_free_slot() {
+        let monday = NaiveDate::from_ymd_opt(2026, 2, 9).unwrap();
+        let mut cache = EventCache::new();
+        cache.store(EventSource::Google, vec![
+            make_event("Standup", monday, "09:00", "09:30", &[]),
+            make_event("Planning", monday, "10:00", "17:00", &[]),
+        ], monday);
+        let work_hours = WorkHoursConfig::default();
+        let at = |date: NaiveDate, h, m| date.and_hms_opt(h, m, 0).unwrap();
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+
+        // Half an hour fits between standup and planning
+        assert_eq!(first_free_slot(&cache, at(monday, 8, 0), 30, &work_hours), Some((monday, time(9, 30), time(10, 0))));
+        // An hour only fits after planning, from the next half hour on
+        assert_eq!(first_free_slot(&cache, at(monday, 8, 0), 60, &work_hours), Some((monday, time(17, 0), time(18, 0))));
+        assert_eq!(first_free_slot(&cache, at(monday, 17, 10), 30, &work_hours), Some((monday, time(17, 30), time(18, 0))));
+        // Nothing left today moves to tomorrow
+        let tuesday = monday + Duration::days(1);
+        assert_eq!(first_free_slot(&cache, at(monday, 17, 40), 60, &work_hours), Some((tuesday, time(9, 0), time(10, 0))));
+    }
+
+    #[test]
+    fn test_open_slots() {
+        let monday = NaiveDate::from_ymd_opt(2026, 2, 9).unwrap();
+        let tuesday = monday + Duration::days(1);
+        let mut cache = EventCache::new();
+        cache.store(EventSource::Google, vec![
+            make_event("Standup", monday, "09:00", "09:30", &[]),
+            make_event("Planning", monday, "10:00", "17:00", &[]),
+        ], monday);
+        cache.store(EventSource::ICloud, vec![make_event("Dentist", tuesday, "08:00", "12:15", &[])], monday);
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+
+        // Busy blocks of both calendars count; the weekend is outside working hours
+        let slots = open_slots(&cache, monday.and_hms_opt(8, 0, 0).unwrap(), 7, 45, &WorkHoursConfig::default());
+        assert_eq!(&slots[..2], &[(monday, time(17, 0), time(18, 0)), (tuesday, time(12, 15), time(18, 0))]);
+        assert_eq!(slots.len(), 5);
+
+        // An unanswered invitation blocks its time, a declined one doesn't
+        let wednesday = tuesday + Duration::days(1);
+        let invitation = |title, start, end, status| {
+            let mut event = make_event(title, wednesday, start, end, &["me@example.com"]);
+            event.accepted = false;
+            event.attendees[0].status = status;
+            event
+        };
+        cache.store(EventSource::Google, vec![
+            invitation("Offsite", "09:00", "13:00", AttendeeStatus::NeedsAction),
+            invitation("Town hall", "13:00", "18:00", AttendeeStatus::Declined),
+        ], wednesday);
+        let slots = open_slots(&cache, wednesday.and_hms_opt(8, 0, 0).unwrap(), 1, 45, &WorkHoursConfig::default());
+        assert_eq!(slots, vec![(wednesday, time(13, 0), time(18, 0))]);
+
+        assert_eq!(parse_minutes("45"), Some(45));
+        assert_eq!(parse_minutes("45m"), Some(45));
+        assert_eq!(parse_minutes("1h30"), Some(90));
+        assert_eq!(parse_minutes("2h"), Some(120));
+        assert_eq!(parse_minutes("soon"), None);
+        assert_eq!(parse_minutes("0"), None);
+    }
+}
@@ -0,0 +1,313 @@
+//! Matching events against a search query by title or participant, narrowed by
+//! `key:value` filters
+
+use crate::cache::{DisplayAttendee, DisplayEvent, EventId, EventSource};
+use chrono::NaiveDate;
+
+/// Whether a search result matched on title or participant
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MatchType {
+    Title,
+    Participant,
+}
+
+/// Check if an event matches the search query (case-insensitive)
+#[cfg(test)]
+fn event_matches_query(event: &DisplayEvent, query_lower: &str) -> bool {
+    event_match_type(event, query_lower).is_some()
+}
+
+/// Determine how an event matches the search query, returning the match type.
+/// Title matches take priority over participant matches.
+pub fn event_match_type(event: &DisplayEvent, query_lower: &str) -> Option<MatchType> {
+    if event.title.to_lowercase().contains(query_lower) {
+        return Some(MatchType::Title);
+    }
+    if event.attendees.iter().any(|a| attendee_matches(a, query_lower)) {
+        return Some(MatchType::Participant);
+    }
+    None
+}
+
+/// Whether an attendee's name or email contains the (lowercase) query
+pub fn attendee_matches(attendee: &DisplayAttendee, query_lower: &str) -> bool {
+    attendee.name.as_ref().is_some_and(|name| name.to_lowercase().contains(query_lower))
+        || attendee.email.to_lowercase().contains(query_lower)
+}
+
+/// What a `has:` filter asks of an event
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Feature {
+    /// `has:meet`: a video call link
+    Meeting,
+    /// `has:location`
+    Location,
+    /// `has:guests`: anyone invited besides me
+    Guests,
+}
+
+impl Feature {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "meet" | "meeting" | "link" | "video" => Some(Self::Meeting),
+            "location" | "place" | "room" => Some(Self::Location),
+            "guests" | "attendees" => Some(Self::Guests),
+            _ => None,
+        }
+    }
+
+    fn present(self, event: &DisplayEvent) -> bool {
+        match self {
+            Self::Meeting => event.meeting_url.is_some(),
+            Self::Location => event.location.as_deref().is_some_and(|l| !l.is_empty()) || !event.rooms.is_empty(),
+            Self::Guests => event.attendees.iter().any(|a| !a.is_self),
+        }
+    }
+}
+
+/// A search as typed: free text plus filters like `from:google`, `with:alice@`,
+/// `before:2026-02-01`, `after:2026-01-01` and `has:meet`. Words that don't parse as a
+/// filter stay part of the text.
+#[derive(Debug, Default, PartialEq)]
+pub struct SearchQuery {
+    /// The words that aren't filters, lowercase
+    pub text: String,
+    /// Provider (`google`, `icloud`, `feed`), panel (`work`, `personal`) or calendar name
+    pub from: Vec<String>,
+    /// Attendee names or emails, lowercase
+    pub with: Vec<String>,
+    /// Events before this day
+    pub before: Option<NaiveDate>,
+    /// Events on or after this day
+    pub after: Option<NaiveDate>,
+    pub has: Vec<Feature>,
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Self {
+        let mut query = Self::default();
+        let mut words = Vec::new();
+        for word in input.split_whitespace() {
+            let lower = word.to_lowercase();
+            let parsed = match lower.split_once(':') {
+                Some(("from", value)) if !value.is_empty() => {
+                    query.from.push(value.to_string());
+                    true
+                }
+                Some(("with", value)) if !value.is_empty() => {
+                    query.with.push(value.to_string());
+                    true
+                }
+                Some(("before", value)) => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .map(|date| query.before = Some(date))
+                    .is_ok(),
+                Some(("after", value)) => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .map(|date| query.after = Some(date))
+                    .is_ok(),
+                Some(("has", value)) => Feature::parse(value).map(|feature| query.has.push(feature)).is_some(),
+                _ => false,
+            };
+            if !parsed {
+                words.push(lower);
+            }
+        }
+        query.text = words.join(" ");
+        query
+    }
+
+    /// Nothing to search for yet
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The query limits the dates searched, so events before today count too
+    pub fn has_date_range(&self) -> bool {
+        self.before.is_some() || self.after.is_some()
+    }
+
+    /// Whether an event shown in `panel` passes every filter; the text is matched
+    /// separately with `event_match_type`
+    pub fn filters_match(&self, event: &DisplayEvent, panel: EventSource) -> bool {
+        self.from.iter().all(|from| from_matches(event, panel, from))
+            && self.with.iter().all(|with| event.attendees.iter().any(|a| attendee_matches(a, with)))
+            && self.before.is_none_or(|before| event.date < before)
+            && self.after.is_none_or(|after| event.date >= after)
+            && self.has.iter().all(|feature| feature.present(event))
+    }
+}
+
+fn from_matches(event: &DisplayEvent, panel: EventSource, from: &str) -> bool {
+    match from {
+        "google" => matches!(event.id, EventId::Google { .. }),
+        "icloud" | "caldav" => matches!(event.id, EventId::ICloud { .. }),
+        "feed" => matches!(event.id, EventId::Feed { .. }),
+        "work" => panel == EventSource::Google,
+        "personal" => panel == EventSource::ICloud,
+        name => event.calendar_label().to_lowercase().contains(name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{AttendeeStatus, EventId};
+    use chrono::{NaiveDate, NaiveTime};
+
+    fn make_event_with_attendees(title: &str, attendees: Vec<DisplayAttendee>) -> DisplayEvent {
+        DisplayEvent {
+            id: EventId::Google { calendar_id: "test".to_string(), event_id: "test-id".to_string(), calendar_name: None, account: None },
+            title: title.to_string(),
+            start_time: NaiveTime::from_hms_opt(10, 0, 0),
+            date: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+            attendees,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_event_matches_query_title() {
+        let event = make_event_with_attendees("Sprint Planning", vec![]);
+        assert!(event_matches_query(&event, "sprint"));
+        assert!(event_matches_query(&event, "planning"));
+    }
+
+    #[test]
+    fn test_event_matches_query_attendee_name() {
+        let event = make_event_with_attendees("Meeting", vec![
+            DisplayAttendee {
+                name: Some("Alice Johnson".to_string()),
+                email: "alice@example.com".to_string(),
+                status: AttendeeStatus::Accepted,
+                is_self: false,
+            },
+        ]);
+        assert!(event_matches_query(&event, "alice"));
+        assert!(event_matches_query(&event, "johnson"));
+    }
+
+    #[test]
+    fn test_event_matches_query_attendee_email() {
+        let event = make_event_with_attendees("Meeting", vec![
+            DisplayAttendee {
+                name: None,
+                email: "bob@company.org".to_string(),
+                status: AttendeeStatus::Accepted,
+                is_self: false,
+            },
+        ]);
+        assert!(event_matches_query(&event, "bob@company"));
+        assert!(event_matches_query(&event, "company.org"));
+    }
+
+    #[test]
+    fn test_event_matches_query_case_insensitive() {
+        let event = make_event_with_attendees("Team Standup", vec![
+            DisplayAttendee {
+                name: Some("Charlie Brown".to_string()),
+                email: "Charlie@Example.COM".to_string(),
+                status: AttendeeStatus::Accepted,
+                is_self: false,
+            },
+        ]);
+        assert!(event_matches_query(&event, "team standup"));
+        assert!(event_matches_query(&event, "charlie brown"));
+        assert!(event_matches_query(&event, "charlie@example.com"));
+    }
+
+    #[test]
+    fn test_event_match_type_title() {
+        let event = make_event_with_attendees("Sprint Planning", vec![
+            DisplayAttendee {
+                name: Some("Alice".to_string()),
+                email: "alice@example.com".to_string(),
+                status: AttendeeStatus::Accepted,
+                is_self: false,
+            },
+        ]);
+        assert_eq!(event_match_type(&event, "sprint"), Some(MatchType::Title));
+    }
+
+    #[test]
+    fn test_event_match_type_participant() {
+        let event = make_event_with_attendees("Sprint Planning", vec![
+            DisplayAttendee {
+                name: Some("Alice Johnson".to_string()),
+                email: "alice@example.com".to_string(),
+                status: AttendeeStatus::Accepted,
+                is_self: false,
+            },
+        ]);
+        assert_eq!(event_match_type(&event, "alice"), Some(MatchType::Participant));
+    }
+
+    #[test]
+    fn test_event_match_type_title_takes_priority() {
+        // "Alice" appears in both title and attendees — title wins
+        let event = make_event_with_attendees("Meeting with Alice", vec![
+            DisplayAttendee {
+                name: Some("Alice Johnson".to_string()),
+                email: "alice@example.com".to_string(),
+                status: AttendeeStatus::Accepted,
+                is_self: false,
+            },
+        ]);
+        assert_eq!(event_match_type(&event, "alice"), Some(MatchType::Title));
+    }
+
+    #[test]
+    fn test_event_match_type_no_match() {
+        let event = make_event_with_attendees("Sprint Planning", vec![
+            DisplayAttendee {
+                name: Some("Alice".to_string()),
+                email: "alice@example.com".to_string(),
+                status: AttendeeStatus::Accepted,
+                is_self: false,
+            },
+        ]);
+        assert_eq!(event_match_type(&event, "bob"), None);
+    }
+
+    #[test]
+    fn test_search_query_filters() {
+        let query = SearchQuery::parse("Planning from:Google with:alice@ before:2026-02-01 has:meet re:budget");
+        assert_eq!(query.text, "planning re:budget");
+        assert_eq!(query.from, vec!["google"]);
+        assert_eq!(query.with, vec!["alice@"]);
+        assert_eq!(query.before, NaiveDate::from_ymd_opt(2026, 2, 1));
+        assert_eq!(query.has, vec![Feature::Meeting]);
+        // A date that doesn't parse is searched for as text
+        assert_eq!(SearchQuery::parse("after:soon").text, "after:soon");
+        assert!(SearchQuery::parse("  ").is_empty());
+
+        let alice = DisplayAttendee {
+            name: Some("Alice".to_string()),
+            email: "alice@example.com".to_string(),
+            status: AttendeeStatus::Accepted,
+            is_self: false,
+        };
+        let mut event = make_event_with_attendees("Sprint Planning", vec![alice]);
+        let query = SearchQuery::parse("from:google with:alice@ before:2026-02-01");
+        assert!(query.filters_match(&event, EventSource::Google));
+        assert!(!SearchQuery::parse("from:personal").filters_match(&event, EventSource::Google));
+        assert!(!SearchQuery::parse("with:bob").filters_match(&event, EventSource::Google));
+        assert!(!SearchQuery::parse("after:2026-01-16").filters_match(&event, EventSource::Google));
+        assert!(!SearchQuery::parse("has:meet").filters_match(&event, EventSource::Google));
+        event.meeting_url = Some("https://meet.google.com/abc".to_string());
+        assert!(SearchQuery::parse("has:meet").filters_match(&event, EventSource::Google));
+    }
+
+    #[test]
+    fn test_event_matches_query_no_match() {
+        let event = make_event_with_attendees("Sprint Planning", vec![
+            DisplayAttendee {
+                name: Some("Alice".to_string()),
+                email: "alice@example.com".to_string(),
+                status: AttendeeStatus::Accepted,
+                is_self: false,
+            },
+        ]);
+        assert!(!event_matches_query(&event, "retro"));
+        assert!(!event_matches_query(&event, "bob"));
+        assert!(!event_matches_query(&event, "xyz"));
+    }
+}
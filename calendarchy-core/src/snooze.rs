@@ -0,0 +1,98 @@
+//! Events hidden for a day (e.g. a placeholder you're skipping), kept on this machine only
+
+use crate::cache::DisplayEvent;
+use crate::config::Config;
+use crate::error::{CalendarchyError, Result};
+use chrono::NaiveDate;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+
+/// Days each event UID is hidden on, persisted to ~/.config/calendarchy/snoozed.json
+#[derive(Debug, Default)]
+pub struct SnoozeStore {
+    by_uid: BTreeMap<String, BTreeSet<NaiveDate>>,
+}
+
+impl SnoozeStore {
+    fn path() -> PathBuf {
+        Config::config_dir().join("snoozed.json")
+    }
+
+    /// Load hidden events; a missing file yields an empty store
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        let Ok(json) = fs::read_to_string(&path) else { return Ok(Self::default()) };
+        let by_uid = serde_json::from_str(&json).map_err(|e| CalendarchyError::corrupted_file(&path, e))?;
+        Ok(Self { by_uid })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        Config::ensure_config_dir()?;
+        fs::write(Self::path(), serde_json::to_string_pretty(&self.by_uid)?)?;
+        Ok(())
+    }
+
+    pub fn is_snoozed(&self, event: &DisplayEvent) -> bool {
+        self.by_uid.get(event.uid()).is_some_and(|dates| dates.contains(&event.date))
+    }
+
+    /// Hide the event on its day; other occurrences of a recurring event stay
+    pub fn snooze(&mut self, event: &DisplayEvent) {
+        self.by_uid.entry(event.uid().to_string()).or_default().insert(event.date);
+    }
+
+    /// Show every event hidden on `date` again; returns how many there were
+    pub fn unsnooze_day(&mut self, date: NaiveDate) -> usize {
+        let mut count = 0;
+        for dates in self.by_uid.values_mut() {
+            count += dates.remove(&date) as usize;
+        }
+        self.by_uid.retain(|_, dates| !dates.is_empty());
+        count
+    }
+
+    /// Forget days before `today`, which are never shown as upcoming again
+    pub fn prune(&mut self, today: NaiveDate) {
+        for dates in self.by_uid.values_mut() {
+            dates.retain(|date| *date >= today);
+        }
+        self.by_uid.retain(|_, dates| !dates.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::EventId;
+    use chrono::NaiveTime;
+
+    fn make_event(uid: &str, date: NaiveDate) -> DisplayEvent {
+        DisplayEvent {
+            id: EventId::ICloud { calendar_url: "cal".to_string(), event_uid: uid.to_string(), etag: None, calendar_name: None, occurrence: None },
+            ical_uid: Some(uid.to_string()),
+            title: "Placeholder".to_string(),
+            start_time: NaiveTime::from_hms_opt(10, 0, 0),
+            end_time: NaiveTime::from_hms_opt(11, 0, 0),
+            date,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_snooze_hides_one_day() {
+        let monday = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let tuesday = monday.succ_opt().unwrap();
+        let mut store = SnoozeStore::default();
+        store.snooze(&make_event("weekly", monday));
+        assert!(store.is_snoozed(&make_event("weekly", monday)));
+        assert!(!store.is_snoozed(&make_event("weekly", tuesday)));
+
+        store.prune(tuesday);
+        assert!(!store.is_snoozed(&make_event("weekly", monday)));
+
+        store.snooze(&make_event("weekly", tuesday));
+        assert_eq!(store.unsnooze_day(tuesday), 1);
+        assert!(!store.is_snoozed(&make_event("weekly", tuesday)));
+    }
+}
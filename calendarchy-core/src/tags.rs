@@ -0,0 +1,123 @@
+//! Local event tags (e.g. #interview, #1on1), kept on this machine only
+
+use crate::cache::DisplayEvent;
+use crate::config::Config;
+use crate::error::{CalendarchyError, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Tags keyed by event UID, persisted to ~/.config/calendarchy/tags.json
+#[derive(Debug, Default)]
+pub struct TagStore {
+    by_uid: BTreeMap<String, Vec<String>>,
+}
+
+impl TagStore {
+    fn path() -> PathBuf {
+        Config::config_dir().join("tags.json")
+    }
+
+    /// Load saved tags; a missing file yields an empty store
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        let Ok(json) = fs::read_to_string(&path) else { return Ok(Self::default()) };
+        let by_uid = serde_json::from_str(&json).map_err(|e| CalendarchyError::corrupted_file(&path, e))?;
+        Ok(Self { by_uid })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        Config::ensure_config_dir()?;
+        fs::write(Self::path(), serde_json::to_string_pretty(&self.by_uid)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, event: &DisplayEvent) -> &[String] {
+        self.by_uid.get(event.uid()).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Replace an event's tags; an empty list removes the entry
+    pub fn set(&mut self, event: &DisplayEvent, tags: Vec<String>) {
+        if tags.is_empty() {
+            self.by_uid.remove(event.uid());
+        } else {
+            self.by_uid.insert(event.uid().to_string(), tags);
+        }
+    }
+
+    pub fn has_tag(&self, event: &DisplayEvent, tag: &str) -> bool {
+        self.get(event).iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// All tags in use with the number of events carrying each, sorted by name
+    pub fn counts(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for tag in self.by_uid.values().flatten() {
+            *counts.entry(tag.as_str()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+/// Parse tag editor input like "#interview 1on1, #hiring" into unique lowercase tags
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for word in input.split(|c: char| c.is_whitespace() || c == ',') {
+        let tag = word.trim_start_matches('#').to_lowercase();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Format tags for display, e.g. "#interview #1on1"
+pub fn format_tags(tags: &[String]) -> String {
+    tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::EventId;
+    use chrono::{NaiveDate, NaiveTime};
+
+    fn make_event(uid: &str) -> DisplayEvent {
+        DisplayEvent {
+            id: EventId::ICloud { calendar_url: "cal".to_string(), event_uid: uid.to_string(), etag: None, calendar_name: None, occurrence: None },
+            ical_uid: Some(uid.to_string()),
+            title: "Event".to_string(),
+            start_time: NaiveTime::from_hms_opt(10, 0, 0),
+            date: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        assert_eq!(parse_tags("#Interview 1on1, #interview  #hiring"), vec!["interview", "1on1", "hiring"]);
+        assert!(parse_tags("  # ").is_empty());
+    }
+
+    #[test]
+    fn test_format_tags() {
+        assert_eq!(format_tags(&["interview".to_string(), "1on1".to_string()]), "#interview #1on1");
+    }
+
+    #[test]
+    fn test_tag_store_set_and_counts() {
+        let mut store = TagStore::default();
+        let a = make_event("a");
+        let b = make_event("b");
+
+        store.set(&a, vec!["interview".to_string(), "hiring".to_string()]);
+        store.set(&b, vec!["interview".to_string()]);
+        assert!(store.has_tag(&a, "Hiring"));
+        assert!(!store.has_tag(&b, "hiring"));
+        assert_eq!(store.counts().get("interview"), Some(&2));
+
+        store.set(&a, vec![]);
+        assert!(store.get(&a).is_empty());
+        assert_eq!(store.counts().get("hiring"), None);
+    }
+}
@@ -0,0 +1,691 @@
+//! Shared utility functions
+
+use crate::cache::{AttendeeStatus, DisplayAttendee};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone};
+use qrcode::render::unicode::Dense1x2;
+use qrcode::QrCode;
+use regex::{Regex, RegexBuilder};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Resolve a wall-clock time in `tz` to an instant, handling DST transitions
+/// the way RFC 5545 prescribes:
+/// - fall-back (ambiguous) times resolve to the first occurrence
+/// - spring-forward (nonexistent) times are shifted by the length of the gap
+pub fn resolve_local_datetime<Tz: TimeZone>(tz: &Tz, naive: NaiveDateTime) -> Option<DateTime<Tz>> {
+    match tz.from_local_datetime(&naive) {
+        LocalResult::Single(dt) => Some(dt),
+        LocalResult::Ambiguous(earliest, _) => Some(earliest),
+        LocalResult::None => {
+            // Interpret using the offset in effect before the gap
+            let before = naive - chrono::Duration::hours(1);
+            let offset_before = tz.offset_from_local_datetime(&before).earliest()?;
+            let utc = naive - offset_before.fix();
+            Some(tz.from_utc_datetime(&utc))
+        }
+    }
+}
+
+/// Last day a timed event from `start` to `end` is listed on: the day it starts unless it lasts
+/// more than a day, so a 23:00-00:30 meeting stays on its evening. One ending at midnight
+/// stops the day before.
+pub fn last_covered_day(start: NaiveDateTime, end: NaiveDateTime) -> NaiveDate {
+    match end.time() == NaiveTime::MIN {
+        _ if end - start <= chrono::Duration::days(1) => start.date(),
+        true => end.date().pred_opt().unwrap_or(end.date()),
+        false => end.date(),
+    }
+}
+
+/// Move an unreadable file aside as `<name>.corrupt-<timestamp>` so a fresh one can be written
+pub fn quarantine_file(path: &Path) -> std::io::Result<PathBuf> {
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("file");
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let backup = path.with_file_name(format!("{}.corrupt-{}", file_name, timestamp));
+    std::fs::rename(path, &backup)?;
+    Ok(backup)
+}
+
+/// Build the command that opens a URL: the configured browser command if set
+/// (`{url}` is substituted, otherwise the URL is appended), else the platform opener
+pub fn url_opener(url: &str, browser_command: Option<&str>) -> Command {
+    if let Some(custom) = browser_command.filter(|c| !c.trim().is_empty()) {
+        let mut parts = custom.split_whitespace();
+        let mut command = Command::new(parts.next().unwrap_or_default());
+        let mut substituted = false;
+        for part in parts {
+            if part.contains("{url}") {
+                command.arg(part.replace("{url}", url));
+                substituted = true;
+            } else {
+                command.arg(part);
+            }
+        }
+        if !substituted {
+            command.arg(url);
+        }
+        return command;
+    }
+
+    if cfg!(target_os = "macos") {
+        let mut command = Command::new("open");
+        command.arg(url);
+        command
+    } else if cfg!(windows) {
+        // The empty argument is the window title `start` expects before the target
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", "", url]);
+        command
+    } else {
+        let mut command = Command::new("xdg-open");
+        command.arg(url);
+        command
+    }
+}
+
+/// Open a URL in the browser, detached from the terminal's process group
+pub fn open_url(url: &str, browser_command: Option<&str>) -> std::io::Result<()> {
+    let mut command = url_opener(url, browser_command);
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
+/// Show a desktop notification with notify-send, or osascript on macOS
+pub fn notify(title: &str, body: &str) -> std::io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let script = format!("display notification {:?} with title {:?}", body, title);
+        let mut command = Command::new("osascript");
+        command.args(["-e", &script]);
+        command
+    } else if cfg!(windows) {
+        return Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "no notification tool on Windows"));
+    } else {
+        let mut command = Command::new("notify-send");
+        command.args(["--app-name=calendarchy", title, body]);
+        command
+    };
+    command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).spawn()?;
+    Ok(())
+}
+
+/// Read text from the system clipboard with the platform's paste tool
+pub fn read_clipboard() -> std::io::Result<String> {
+    let tools: &[&[&str]] = if cfg!(target_os = "macos") {
+        &[&["pbpaste"]]
+    } else if cfg!(windows) {
+        &[&["powershell", "-NoProfile", "-Command", "Get-Clipboard"]]
+    } else {
+        &[&["wl-paste", "--no-newline"], &["xclip", "-selection", "clipboard", "-o"], &["xsel", "--clipboard", "--output"]]
+    };
+    let mut last_error = std::io::Error::new(std::io::ErrorKind::NotFound, "no clipboard tool found");
+    for tool in tools {
+        match Command::new(tool[0]).args(&tool[1..]).stdin(Stdio::null()).stderr(Stdio::null()).output() {
+            Ok(output) if output.status.success() => return Ok(String::from_utf8_lossy(&output.stdout).into_owned()),
+            Ok(output) => last_error = std::io::Error::other(format!("{} exited with {}", tool[0], output.status)),
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
+
+/// Render text as a QR code in half-block characters, two modules per row.
+/// Colors are inverted for dark terminals: light modules and the quiet zone are drawn filled.
+/// Returns None if the text is too long to encode.
+pub fn qr_code_lines(text: &str) -> Option<Vec<String>> {
+    let code = QrCode::new(text.as_bytes()).ok()?;
+    let image = code
+        .render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .build();
+    Some(image.lines().map(String::from).collect())
+}
+
+/// First and last day of the month of `date`
+pub fn month_bounds(date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let first = date.with_day(1).unwrap_or(date);
+    let next = (first + Duration::days(32)).with_day(1).unwrap_or(first);
+    (first, next - Duration::days(1))
+}
+
+/// Number of days in the month of `date`
+pub fn days_in_month(date: NaiveDate) -> u32 {
+    month_bounds(date).1.day()
+}
+
+/// Format a duration in minutes compactly, e.g. 45 -> "45m", 90 -> "1h30", 120 -> "2h"
+pub fn format_duration(minutes: i64) -> String {
+    let (hours, mins) = (minutes / 60, minutes % 60);
+    match (hours, mins) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h{:02}", h, m),
+    }
+}
+
+/// Normalize a provider color ("#RRGGBB" or Apple's "#RRGGBBAA") to lowercase "#rrggbb"
+pub fn normalize_hex_color(color: &str) -> Option<String> {
+    let hex = color.trim().strip_prefix('#')?;
+    if !matches!(hex.len(), 6 | 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(format!("#{}", hex[..6].to_lowercase()))
+}
+
+/// Sort order for attendee status (lower = first)
+pub fn status_sort_order(status: &AttendeeStatus) -> u8 {
+    match status {
+        AttendeeStatus::Organizer => 0,
+        AttendeeStatus::Accepted => 1,
+        AttendeeStatus::Tentative => 2,
+        AttendeeStatus::NeedsAction => 3,
+        AttendeeStatus::Declined => 4,
+    }
+}
+
+/// Sort attendees by status (accepted first, declined last), then by name
+pub fn sort_attendees(attendees: &mut [DisplayAttendee]) {
+    attendees.sort_by(|a, b| {
+        let status_cmp = status_sort_order(&a.status).cmp(&status_sort_order(&b.status));
+        if status_cmp != std::cmp::Ordering::Equal {
+            status_cmp
+        } else {
+            a.name.cmp(&b.name)
+        }
+    });
+}
+
+/// Extract a display name from an email address
+/// e.g., "john.smith@example.com" -> "John Smith"
+///       "jsmith@example.com" -> "Jsmith"
+pub fn name_from_email(email: &str) -> String {
+    // Get the part before @
+    let local = email.split('@').next().unwrap_or(email);
+
+    // Split by common separators (., _, -)
+    let parts: Vec<&str> = local.split(['.', '_', '-']).collect();
+
+    // Capitalize each part and join with space
+    parts
+        .iter()
+        .map(|p| {
+            let mut chars = p.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Host/path fragments of known meeting providers
+const MEETING_PATTERNS: &[&str] = &[
+    "zoom.us/j/",
+    "zoom.us/my/",
+    "zoom.us/w/",
+    "meet.google.com/",
+    "teams.microsoft.com/",
+    "teams.live.com/meet/",
+    "webex.com/meet/",
+    "webex.com/join/",
+    "webex.com/wbxmjs/",
+    "webex.com/webappng/",
+    "webex.com/m/",
+    "/j.php?mtid=",
+    "meet.jit.si/",
+    "whereby.com/",
+    "bluejeans.com/",
+    "meet.goto.com/",
+    "gotomeeting.com/join/",
+    "gotomeet.me/",
+];
+
+/// Path segments that usually mean a meeting room on an unknown host
+const GENERIC_MEETING_SEGMENTS: &[&str] = &["/meeting/", "/meetings/", "/meet/", "/join/"];
+
+/// User-defined meeting URL patterns from config, checked alongside the built-ins
+#[derive(Debug, Clone, Default)]
+pub struct MeetingPatterns(Vec<Regex>);
+
+impl MeetingPatterns {
+    /// Compile the configured patterns. Each is a case-insensitive regex, so a plain
+    /// domain like "meet.corp.example" works as-is.
+    pub fn new(patterns: &[String]) -> Result<Self, String> {
+        patterns
+            .iter()
+            .map(|p| {
+                RegexBuilder::new(p)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|e| format!("meeting pattern \"{}\": {}", p, e))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+/// Check if a URL belongs to a known or user-configured meeting provider
+pub fn is_meeting_url(url: &str, custom: &MeetingPatterns) -> bool {
+    let lower = url.to_lowercase();
+    MEETING_PATTERNS.iter().any(|p| lower.contains(p)) || custom.0.iter().any(|re| re.is_match(url))
+}
+
+/// Check if a URL looks like a meeting room on a host we don't know about
+fn is_generic_meeting_url(url: &str) -> bool {
+    let url = url.to_lowercase();
+    GENERIC_MEETING_SEGMENTS.iter().any(|segment| {
+        url.find(segment)
+            .is_some_and(|pos| url[pos + segment.len()..].chars().next().is_some_and(|c| c.is_ascii_alphanumeric()))
+    })
+}
+
+/// All https:// URLs in text, cut at whitespace, quotes and angle brackets,
+/// without trailing punctuation
+fn find_urls(text: &str) -> impl Iterator<Item = &str> {
+    text.match_indices("https://").map(move |(start, _)| {
+        let url_part = &text[start..];
+        let end = url_part
+            .find(|c: char| c.is_whitespace() || c == '"' || c == '\'' || c == '>' || c == '<')
+            .unwrap_or(url_part.len());
+        url_part[..end].trim_end_matches(['.', ',', ';', ')', ']'])
+    })
+}
+
+/// Extract a meeting URL (Zoom, Meet, Teams, Webex, Jitsi, Whereby, BlueJeans, GoTo) from text.
+/// Known providers win over generic "/meeting/"-style links.
+pub fn extract_meeting_url(text: &str, custom: &MeetingPatterns) -> Option<String> {
+    find_urls(text)
+        .find(|url| is_meeting_url(url, custom))
+        .or_else(|| find_urls(text).find(|url| is_generic_meeting_url(url)))
+        .map(String::from)
+}
+
+/// Build a mailto: URI addressed to all recipients with the given subject and, unless
+/// empty, body
+pub fn mailto_uri(recipients: &[&str], subject: &str, body: &str) -> String {
+    let mut uri = format!("mailto:{}?subject={}", recipients.join(","), percent_encode(subject));
+    if !body.is_empty() {
+        uri.push_str(&format!("&body={}", percent_encode(body)));
+    }
+    uri
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Link to an event in Google Calendar's web UI; `eid` is the event and calendar id, base64-encoded
+pub fn google_event_url(calendar_id: &str, event_id: &str) -> String {
+    let eid = URL_SAFE_NO_PAD.encode(format!("{} {}", event_id, calendar_id));
+    format!("https://calendar.google.com/calendar/event?eid={}", eid)
+}
+
+/// Build a dialable tel: URI, appending the PIN as DTMF after two pauses
+pub fn tel_uri(number: &str, pin: Option<&str>) -> String {
+    let number = number.trim().trim_start_matches("tel:");
+    let digits: String = number.chars().filter(|c| c.is_ascii_digit() || matches!(c, '+' | ',' | '#' | '*')).collect();
+    let pin: Option<String> = pin.map(|p| p.chars().filter(char::is_ascii_digit).collect());
+    match pin.filter(|p| !p.is_empty()) {
+        Some(pin) if !digits.contains(',') => format!("tel:{},,{}#", digits, pin),
+        _ => format!("tel:{}", digits),
+    }
+}
+
+/// Extract a phone dial-in from text: a tel: link, or a one-tap number like "+1 301 715 8592,,81234567890#"
+pub fn extract_dial_in(text: &str) -> Option<String> {
+    let text = text.replace("%20", " ");
+    let is_dial_char = |c: char| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | ' ' | '(' | ')' | ',' | '#' | '*');
+
+    if let Some(pos) = text.find("tel:") {
+        let rest = &text[pos + 4..];
+        let end = rest.find(|c: char| !is_dial_char(c)).unwrap_or(rest.len());
+        let number = rest[..end].trim().trim_end_matches(['.', ')']);
+        if number.chars().filter(char::is_ascii_digit).count() >= 6 {
+            return Some(tel_uri(number, None));
+        }
+    }
+
+    text.match_indices('+').find_map(|(pos, _)| {
+        let rest = &text[pos..];
+        let end = rest[1..].find(|c: char| !is_dial_char(c) || c == '+').map_or(rest.len(), |i| i + 1);
+        let candidate = rest[..end].trim();
+        (candidate.contains(",,") && candidate[1..].starts_with(|c: char| c.is_ascii_digit()))
+            .then(|| tel_uri(candidate, None))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_resolve_local_datetime_regular() {
+        let tz = chrono_tz::Europe::Sofia;
+        let dt = resolve_local_datetime(&tz, naive("2026-01-08 20:00")).unwrap();
+        assert_eq!(dt.naive_utc(), naive("2026-01-08 18:00"));
+    }
+
+    #[test]
+    fn test_resolve_local_datetime_spring_forward_gap() {
+        // Sofia jumps from 03:00 EET to 04:00 EEST on 2026-03-29
+        let tz = chrono_tz::Europe::Sofia;
+        let dt = resolve_local_datetime(&tz, naive("2026-03-29 03:30")).unwrap();
+        assert_eq!(dt.naive_utc(), naive("2026-03-29 01:30"));
+        assert_eq!(dt.naive_local(), naive("2026-03-29 04:30"));
+
+        // Times after the jump use the summer offset
+        let after = resolve_local_datetime(&tz, naive("2026-03-29 10:00")).unwrap();
+        assert_eq!(after.naive_utc(), naive("2026-03-29 07:00"));
+    }
+
+    #[test]
+    fn test_resolve_local_datetime_fall_back_ambiguous() {
+        // Sofia repeats 03:00-04:00 on 2026-10-25; the first occurrence (EEST) wins
+        let tz = chrono_tz::Europe::Sofia;
+        let dt = resolve_local_datetime(&tz, naive("2026-10-25 03:30")).unwrap();
+        assert_eq!(dt.naive_utc(), naive("2026-10-25 00:30"));
+
+        // Times after the repeat use the winter offset
+        let after = resolve_local_datetime(&tz, naive("2026-10-25 10:00")).unwrap();
+        assert_eq!(after.naive_utc(), naive("2026-10-25 08:00"));
+    }
+
+    #[test]
+    fn test_quarantine_file() {
+        let dir = std::env::temp_dir().join(format!("calendarchy-quarantine-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("events.json");
+        std::fs::write(&path, "{not json").unwrap();
+
+        let backup = quarantine_file(&path).unwrap();
+        assert!(!path.exists());
+        assert!(backup.file_name().unwrap().to_str().unwrap().starts_with("events.json.corrupt-"));
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "{not json");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_url_opener_custom_command() {
+        let url = "https://meet.google.com/abc";
+        let args = |c: &Command| c.get_args().map(|a| a.to_string_lossy().to_string()).collect::<Vec<_>>();
+
+        let command = url_opener(url, Some("firefox --new-window"));
+        assert_eq!(command.get_program(), "firefox");
+        assert_eq!(args(&command), vec!["--new-window", url]);
+
+        let command = url_opener(url, Some("chromium --app={url} --incognito"));
+        assert_eq!(args(&command), vec![format!("--app={}", url), "--incognito".to_string()]);
+
+        // A blank override falls back to the platform opener
+        assert_eq!(args(&url_opener(url, Some("  "))).last().map(String::as_str), Some(url));
+    }
+
+    #[test]
+    fn test_qr_code_lines() {
+        let lines = qr_code_lines("https://meet.google.com/abc-defg-hij").unwrap();
+        // Version 3 (29 modules) plus a 4-module quiet zone on each side
+        assert_eq!(lines[0].chars().count(), 37);
+        assert_eq!(lines.len(), 19);
+        assert!(lines[0].chars().all(|c| c == '\u{2588}'));
+    }
+
+    #[test]
+    fn test_mailto_uri() {
+        assert_eq!(
+            mailto_uri(&["ana@example.com", "bo@example.com"], "Sprint review & demo", ""),
+            "mailto:ana@example.com,bo@example.com?subject=Sprint%20review%20%26%20demo"
+        );
+        assert_eq!(
+            mailto_uri(&["ana@example.com"], "Standup", "Hi,\nlater?"),
+            "mailto:ana@example.com?subject=Standup&body=Hi%2C%0Alater%3F"
+        );
+    }
+
+    #[test]
+    fn test_google_event_url() {
+        assert_eq!(
+            google_event_url("ana@example.com", "abc123"),
+            "https://calendar.google.com/calendar/event?eid=YWJjMTIzIGFuYUBleGFtcGxlLmNvbQ"
+        );
+    }
+
+    #[test]
+    fn test_tel_uri() {
+        assert_eq!(tel_uri("+1 301-715-8592", Some("812 345#")), "tel:+13017158592,,812345#");
+        assert_eq!(tel_uri("tel:+44-20-1234-5678", None), "tel:+442012345678");
+        // A number that already carries DTMF is left alone
+        assert_eq!(tel_uri("+13017158592,,81234567890#", Some("1")), "tel:+13017158592,,81234567890#");
+    }
+
+    #[test]
+    fn test_extract_dial_in() {
+        let zoom = "Meeting ID: 812 3456 7890\nOne tap mobile\n+13017158592,,81234567890# US (Washington DC)\n+13126266799,,81234567890# US";
+        assert_eq!(extract_dial_in(zoom), Some("tel:+13017158592,,81234567890#".to_string()));
+
+        let teams = "Call in (audio only)\n<tel:+359%202%20123%204567,,456789#> Bulgaria, Sofia";
+        assert_eq!(extract_dial_in(teams), Some("tel:+35921234567,,456789#".to_string()));
+
+        let one_tap = "Join by phone\n+1 301 715 8592,,81234567890# US";
+        assert_eq!(extract_dial_in(one_tap), Some("tel:+13017158592,,81234567890#".to_string()));
+
+        assert_eq!(extract_dial_in("Call me at tel:+1-555-010-9999."), Some("tel:+15550109999".to_string()));
+        assert_eq!(extract_dial_in("Budget +5% this quarter"), None);
+    }
+
+    #[test]
+    fn test_month_bounds() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(month_bounds(date(2026, 2, 14)), (date(2026, 2, 1), date(2026, 2, 28)));
+        assert_eq!(month_bounds(date(2026, 12, 31)), (date(2026, 12, 1), date(2026, 12, 31)));
+        assert_eq!(days_in_month(date(2028, 2, 1)), 29);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(45), "45m");
+        assert_eq!(format_duration(60), "1h");
+        assert_eq!(format_duration(90), "1h30");
+        assert_eq!(format_duration(125), "2h05");
+    }
+
+    #[test]
+    fn test_normalize_hex_color() {
+        assert_eq!(normalize_hex_color("#A4BDFC"), Some("#a4bdfc".to_string()));
+        assert_eq!(normalize_hex_color("#FF2968FF"), Some("#ff2968".to_string()));
+        assert_eq!(normalize_hex_color("turquoise"), None);
+        assert_eq!(normalize_hex_color("#12345"), None);
+    }
+
+    fn is_meeting_url_builtin(url: &str) -> bool {
+        is_meeting_url(url, &MeetingPatterns::default())
+    }
+
+    fn extract_builtin(text: &str) -> Option<String> {
+        extract_meeting_url(text, &MeetingPatterns::default())
+    }
+
+    #[test]
+    fn test_is_meeting_url() {
+        assert!(is_meeting_url_builtin("https://zoom.us/j/123"));
+        assert!(is_meeting_url_builtin("https://meet.google.com/abc"));
+        assert!(is_meeting_url_builtin("https://teams.microsoft.com/l/meetup"));
+        assert!(!is_meeting_url_builtin("https://example.com"));
+    }
+
+    #[test]
+    fn test_extract_meeting_url_zoom_variants() {
+        assert_eq!(
+            extract_builtin("https://us02web.zoom.us/j/123"),
+            Some("https://us02web.zoom.us/j/123".to_string())
+        );
+        assert_eq!(
+            extract_builtin("https://us04web.zoom.us/j/456"),
+            Some("https://us04web.zoom.us/j/456".to_string())
+        );
+        // Custom corporate subdomain
+        assert_eq!(
+            extract_builtin("https://dext.zoom.us/j/98429926780?pwd=abc"),
+            Some("https://dext.zoom.us/j/98429926780?pwd=abc".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_meeting_url_with_surrounding_text() {
+        let text = "Join meeting at https://meet.google.com/abc-def-ghi and bring notes";
+        assert_eq!(
+            extract_builtin(text),
+            Some("https://meet.google.com/abc-def-ghi".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_meeting_url_other_providers() {
+        assert_eq!(
+            extract_builtin("Join: https://acme.webex.com/acme/j.php?MTID=m1234 or call in"),
+            Some("https://acme.webex.com/acme/j.php?MTID=m1234".to_string())
+        );
+        assert_eq!(
+            extract_builtin("Join Webex meeting https://acme.webex.com/meet/jdoe."),
+            Some("https://acme.webex.com/meet/jdoe".to_string())
+        );
+        assert_eq!(
+            extract_builtin("Jitsi: https://meet.jit.si/WeeklyPlanningSync"),
+            Some("https://meet.jit.si/WeeklyPlanningSync".to_string())
+        );
+        assert_eq!(
+            extract_builtin("(https://whereby.com/design-crit)"),
+            Some("https://whereby.com/design-crit".to_string())
+        );
+        assert_eq!(
+            extract_builtin("BlueJeans: https://bluejeans.com/123456789/0123"),
+            Some("https://bluejeans.com/123456789/0123".to_string())
+        );
+        assert_eq!(
+            extract_builtin("Please join my meeting from your computer.\nhttps://meet.goto.com/123456789"),
+            Some("https://meet.goto.com/123456789".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_meeting_url_from_invite_html() {
+        let teams = "<p>Microsoft Teams meeting</p><a href=\"https://teams.microsoft.com/l/meetup-join/19%3ameeting_abc%40thread.v2/0?context=%7b%7d\">Click here to join the meeting</a>";
+        assert_eq!(
+            extract_builtin(teams),
+            Some("https://teams.microsoft.com/l/meetup-join/19%3ameeting_abc%40thread.v2/0?context=%7b%7d".to_string())
+        );
+
+        // The provider link wins over an earlier unrelated URL
+        let zoom = "Agenda: https://docs.example.com/agenda\n\nJoin Zoom Meeting\nhttps://us02web.zoom.us/j/81234567890?pwd=xyz\n\nMeeting ID: 812 3456 7890";
+        assert_eq!(
+            extract_builtin(zoom),
+            Some("https://us02web.zoom.us/j/81234567890?pwd=xyz".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_meeting_url_generic_fallback() {
+        assert_eq!(
+            extract_builtin("Room: https://video.internal.example/meeting/standup-42"),
+            Some("https://video.internal.example/meeting/standup-42".to_string())
+        );
+        // A known provider is preferred over a generic link
+        assert_eq!(
+            extract_builtin("https://example.com/join/abc https://meet.google.com/abc-defg-hij"),
+            Some("https://meet.google.com/abc-defg-hij".to_string())
+        );
+        assert_eq!(extract_builtin("https://example.com/meetings/"), None);
+    }
+
+    #[test]
+    fn test_custom_meeting_patterns() {
+        let text = "Docs https://wiki.corp.example/page then https://rooms.corp.example/r/standup";
+        assert_eq!(extract_builtin(text), None);
+
+        let custom = MeetingPatterns::new(&["rooms\\.corp\\.example/r/".to_string()]).unwrap();
+        assert_eq!(extract_meeting_url(text, &custom), Some("https://rooms.corp.example/r/standup".to_string()));
+
+        let err = MeetingPatterns::new(&["(unclosed".to_string()]).unwrap_err();
+        assert!(err.starts_with("meeting pattern \"(unclosed\""));
+    }
+
+    #[test]
+    fn test_extract_meeting_url_none() {
+        assert_eq!(extract_builtin("No meeting link here"), None);
+        assert_eq!(extract_builtin("https://example.com/not-a-meeting"), None);
+    }
+
+    #[test]
+    fn test_name_from_email_with_dots() {
+        assert_eq!(name_from_email("john.smith@example.com"), "John Smith");
+    }
+
+    #[test]
+    fn test_name_from_email_with_underscore() {
+        assert_eq!(name_from_email("john_smith@example.com"), "John Smith");
+    }
+
+    #[test]
+    fn test_name_from_email_simple() {
+        assert_eq!(name_from_email("jsmith@example.com"), "Jsmith");
+    }
+
+    #[test]
+    fn test_status_sort_order() {
+        assert!(status_sort_order(&AttendeeStatus::Organizer) < status_sort_order(&AttendeeStatus::Accepted));
+        assert!(status_sort_order(&AttendeeStatus::Accepted) < status_sort_order(&AttendeeStatus::Tentative));
+        assert!(status_sort_order(&AttendeeStatus::Tentative) < status_sort_order(&AttendeeStatus::NeedsAction));
+        assert!(status_sort_order(&AttendeeStatus::NeedsAction) < status_sort_order(&AttendeeStatus::Declined));
+    }
+
+    #[test]
+    fn test_sort_attendees_by_status() {
+        let mut attendees = vec![
+            DisplayAttendee {
+                name: Some("Bob".to_string()),
+                email: "bob@example.com".to_string(),
+                status: AttendeeStatus::Declined,
+                is_self: false,
+            },
+            DisplayAttendee {
+                name: Some("Alice".to_string()),
+                email: "alice@example.com".to_string(),
+                status: AttendeeStatus::Accepted,
+                is_self: false,
+            },
+            DisplayAttendee {
+                name: Some("Charlie".to_string()),
+                email: "charlie@example.com".to_string(),
+                status: AttendeeStatus::Organizer,
+                is_self: false,
+            },
+        ];
+
+        sort_attendees(&mut attendees);
+
+        assert_eq!(attendees[0].name, Some("Charlie".to_string())); // Organizer
+        assert_eq!(attendees[1].name, Some("Alice".to_string()));   // Accepted
+        assert_eq!(attendees[2].name, Some("Bob".to_string()));     // Declined
+    }
+}
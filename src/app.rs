@@ -1,21 +1,271 @@
-use crate::auth::{GoogleAuthState, ICloudAuthState};
-use crate::cache::{DisplayEvent, EventCache};
-use crate::config::Config;
-use chrono::{Datelike, Duration, Local, NaiveDate, NaiveTime};
+use crate::auth::{CalendarEntry, GoogleAuthState, ICloudAuthState};
+use crate::brief;
+use crate::cache::{self, AgendaEntry, AttendeeStatus, DisplayAttendee, DisplayEvent, EventCache, EventId, EventSource};
+use crate::config::{self, Config, EnterAction, ProviderConfig, TimeFormatConfig};
+use crate::contacts::ContactBook;
+use crate::export;
+use crate::fetch::{FetchScheduler, Priority};
+use crate::google::{BusyPeriod, CalendarListEntry, TokenInfo};
+use crate::journal;
+use crate::logging::{HttpLog, LogEntry, LogSource, StatusFilter};
+use crate::outlook::GraphToken;
+use crate::provider::{self, CalendarProvider, CopyTarget, ProviderContext};
+use crate::quick_add;
+use crate::review::{self, WeeklyReview};
+use crate::search::{attendee_matches, event_match_type, MatchType, SearchQuery};
+use crate::snooze::SnoozeStore;
+use crate::tags::{self, TagStore};
+use crate::tasks::Tasks;
+use crate::utils;
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Instant;
 
 /// Search state for the interactive search modal
 pub struct SearchState {
     pub query: String,
+    /// `query` split into text and filters, as of the last update of the results
+    pub parsed: SearchQuery,
     pub results: Vec<SearchResult>,
     pub selected_index: usize,
     pub scroll_offset: usize,
+    /// Also match events before today (Ctrl+P)
+    pub include_past: bool,
+    /// When the query last changed; the servers are only asked once typing pauses
+    pub edited_at: Instant,
+    /// Query the servers were last asked about, and whether they're still answering
+    pub remote_query: Option<String>,
+    pub remote_pending: bool,
+    /// Events the servers matched, kept apart from the cache
+    pub remote_events: Vec<(DisplayEvent, EventSource)>,
 }
 
-/// Whether a search result matched on title or participant
+/// How long each status message stays on screen
+const STATUS_DISPLAY_TIME: std::time::Duration = std::time::Duration::from_secs(3);
+/// Maximum number of messages waiting to be shown; older ones are dropped
+const STATUS_QUEUE_LIMIT: usize = 5;
+/// Minutes between background refetches unless `refresh_interval_minutes` is configured
+const DEFAULT_REFRESH_INTERVAL_MINUTES: u64 = 5;
+/// Searches with fewer matches in the cache than this ask the servers too
+const REMOTE_SEARCH_THRESHOLD: usize = 5;
+/// Typing pause before the servers are asked about a search
+const REMOTE_SEARCH_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+/// Days before and after today the servers search
+const REMOTE_SEARCH_DAYS_BACK: i64 = 730;
+const REMOTE_SEARCH_DAYS_AHEAD: i64 = 365;
+
+/// Status bar messages shown one after another, each for its own display time
+#[derive(Default)]
+pub struct StatusQueue {
+    messages: VecDeque<String>,
+    shown_at: Option<Instant>,
+}
+
+impl StatusQueue {
+    pub fn push(&mut self, msg: String, now: Instant) {
+        // Skip repeats of the message already at the back of the queue
+        if self.messages.back() == Some(&msg) {
+            return;
+        }
+        self.messages.push_back(msg);
+        if self.messages.len() > STATUS_QUEUE_LIMIT {
+            // Keep the visible message, drop the oldest pending one
+            self.messages.remove(1);
+        }
+        if self.shown_at.is_none() {
+            self.shown_at = Some(now);
+        }
+    }
+
+    /// Drop the visible message once it has been shown long enough and start the next one
+    pub fn advance(&mut self, now: Instant) {
+        if let Some(shown_at) = self.shown_at
+            && now.duration_since(shown_at) > STATUS_DISPLAY_TIME
+        {
+            self.messages.pop_front();
+            self.shown_at = if self.messages.is_empty() { None } else { Some(now) };
+        }
+    }
+
+    pub fn current(&self) -> Option<&str> {
+        self.messages.front().map(String::as_str)
+    }
+}
+
+/// Scrollable read-only modal for reports such as diagnostics
+pub struct InfoModal {
+    pub title: String,
+    pub lines: Vec<String>,
+    pub scroll: usize,
+    /// Date range `e` exports as CSV, on the stats screen
+    pub export_range: Option<(NaiveDate, NaiveDate)>,
+}
+
+impl InfoModal {
+    pub fn new(title: impl Into<String>, lines: Vec<String>) -> Self {
+        Self { title: title.into(), lines, scroll: 0, export_range: None }
+    }
+
+    pub fn scroll_down(&mut self, amount: usize) {
+        self.scroll = (self.scroll + amount).min(self.lines.len().saturating_sub(1));
+    }
+
+    pub fn scroll_up(&mut self, amount: usize) {
+        self.scroll = self.scroll.saturating_sub(amount);
+    }
+}
+
+/// Checklist of discovered iCloud calendars; unchecked ones aren't fetched
+pub struct CalendarPicker {
+    pub calendars: Vec<CalendarEntry>,
+    pub cursor: usize,
+}
+
+impl CalendarPicker {
+    pub fn new(calendars: Vec<CalendarEntry>) -> Self {
+        Self { calendars, cursor: 0 }
+    }
+
+    pub fn move_down(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.calendars.len().saturating_sub(1));
+    }
+
+    pub fn move_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn toggle(&mut self) {
+        if let Some(calendar) = self.calendars.get_mut(self.cursor) {
+            calendar.hidden = !calendar.hidden;
+        }
+    }
+
+    /// The checklist as modal lines, the one under the cursor marked
+    pub fn to_modal(&self) -> InfoModal {
+        let lines = self.calendars.iter().enumerate()
+            .map(|(i, calendar)| {
+                format!(
+                    "{} [{}] {}",
+                    if i == self.cursor { ">" } else { " " },
+                    if calendar.hidden { " " } else { "x" },
+                    calendar.name.as_deref().unwrap_or(&calendar.url)
+                )
+            })
+            .collect();
+        let mut modal = InfoModal::new("iCloud calendars", lines);
+        // Keep the cursor in view on lists taller than the screen
+        modal.scroll = self.cursor.saturating_sub(10);
+        modal
+    }
+}
+
+/// A colleague's busy times, shaded in the week availability grid next to your own
+pub struct BusyOverlay {
+    pub email: String,
+    /// Dates asked for so far; refetched when the selected week leaves them
+    pub range: Option<(NaiveDate, NaiveDate)>,
+    busy: Vec<(NaiveDateTime, NaiveDateTime)>,
+}
+
+impl BusyOverlay {
+    pub fn new(email: impl Into<String>) -> Self {
+        Self { email: email.into(), range: None, busy: Vec::new() }
+    }
+
+    /// The months around the week starting at `week_first_day`, unless already asked for
+    pub fn missing_range(&self, week_first_day: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        let week_last_day = week_first_day + Duration::days(6);
+        match self.range {
+            Some((start, end)) if start <= week_first_day && week_last_day <= end => None,
+            _ => Some((utils::month_bounds(week_first_day).0, utils::month_bounds(week_last_day).1)),
+        }
+    }
+
+    pub fn set_busy(&mut self, periods: &[BusyPeriod]) {
+        self.busy = periods.iter()
+            .map(|p| (p.start.with_timezone(&Local).naive_local(), p.end.with_timezone(&Local).naive_local()))
+            .collect();
+    }
+
+    /// Whether any busy period overlaps the minutes `start..end` of `date`
+    pub fn is_busy(&self, date: NaiveDate, start: u32, end: u32) -> bool {
+        let at = |minutes: u32| date.and_time(NaiveTime::MIN) + Duration::minutes(minutes as i64);
+        let (slot_start, slot_end) = (at(start), at(end));
+        self.busy.iter().any(|&(busy_start, busy_end)| busy_start < slot_end && slot_start < busy_end)
+    }
+}
+
+/// Tabs of the full-screen debug view
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub enum MatchType {
-    Title,
-    Participant,
+pub enum DebugTab {
+    Log,      // HTTP request/response log
+    Failures, // Captured headers and bodies of failed requests
+    Metrics,  // Per-source request counts, error rates and latency
+}
+
+/// Full-screen debug view state
+pub struct DebugScreen {
+    pub tab: DebugTab,
+    pub scroll: usize,
+    pub source_filter: Option<LogSource>,
+    pub status_filter: StatusFilter,
+    /// Snapshot of the log while paused
+    pub paused: Option<Vec<LogEntry>>,
+}
+
+impl DebugScreen {
+    pub fn new(tab: DebugTab) -> Self {
+        Self {
+            tab,
+            scroll: 0,
+            source_filter: None,
+            status_filter: StatusFilter::All,
+            paused: None,
+        }
+    }
+
+    pub fn switch_tab(&mut self) {
+        self.tab = match self.tab {
+            DebugTab::Log => DebugTab::Failures,
+            DebugTab::Failures => DebugTab::Metrics,
+            DebugTab::Metrics => DebugTab::Log,
+        };
+        self.scroll = 0;
+    }
+
+    pub fn cycle_source_filter(&mut self) {
+        self.source_filter = match self.source_filter {
+            None => Some(LogSource::Google),
+            Some(LogSource::Google) => Some(LogSource::ICloud),
+            Some(_) => None,
+        };
+        self.scroll = 0;
+    }
+
+    pub fn cycle_status_filter(&mut self) {
+        self.status_filter = self.status_filter.next();
+        self.scroll = 0;
+    }
+
+    pub fn toggle_pause(&mut self, log: &HttpLog) {
+        self.paused = match self.paused {
+            Some(_) => None,
+            None => Some(log.entries()),
+        };
+    }
+
+    /// Log entries matching the current filters, newest first
+    pub fn visible_log_entries(&self, log: &HttpLog) -> Vec<LogEntry> {
+        let entries = match self.paused {
+            Some(ref snapshot) => snapshot.clone(),
+            None => log.entries(),
+        };
+        entries
+            .into_iter()
+            .filter(|e| e.matches(self.source_filter, self.status_filter))
+            .collect()
+    }
 }
 
 /// A single search result with its source
@@ -32,91 +282,717 @@ pub enum NavigationMode {
     Event, // Navigate between events within selected day with j/k
 }
 
-/// Which event source/panel is currently selected
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum EventSource {
-    Google,
-    ICloud,
+/// What fills the area right of the month calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ViewMode {
+    #[default]
+    Month,  // Work and Personal panels for the selected day
+    Week,   // Hour timeline of the selected week, one column per day
+    Agenda, // Events of the next days from both sources in one list
 }
 
-/// Pending action awaiting confirmation
+/// Pending action awaiting confirmation; accepts, declines and deletes go through the
+/// provider that owns the event
 #[derive(Debug, Clone)]
 pub enum PendingAction {
-    AcceptEvent { calendar_id: String, event_id: String },
-    DeclineEvent { calendar_id: String, event_id: String },
-    DeleteGoogleEvent { calendar_id: String, event_id: String },
-    DeleteICloudEvent { calendar_url: String, event_uid: String, etag: Option<String> },
+    Accept { id: EventId },
+    Decline { id: EventId },
+    Delete { id: EventId },
+    /// Offered shortly before a meeting starts
+    JoinMeeting { title: String, url: String },
+    /// Accept an invitation (the accept action of its calendar) and open its meeting link
+    /// right away
+    AcceptAndJoin { accept: Box<PendingAction>, url: String },
+    /// The same action on every marked event: title and action, or why it can't be taken
+    Bulk { action: RepeatableAction, items: Vec<(String, Result<PendingAction, String>)> },
+}
+
+impl PendingAction {
+    /// The action `.` repeats on another event, for RSVPs and deletes
+    pub fn repeatable(&self) -> Option<RepeatableAction> {
+        match self {
+            PendingAction::Accept { .. } => Some(RepeatableAction::Accept),
+            PendingAction::AcceptAndJoin { accept, .. } => accept.repeatable(),
+            PendingAction::Decline { .. } => Some(RepeatableAction::Decline),
+            PendingAction::Delete { .. } => Some(RepeatableAction::Delete),
+            PendingAction::JoinMeeting { .. } | PendingAction::Bulk { .. } => None,
+        }
+    }
+}
+
+/// Event actions that can be repeated with `.`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RepeatableAction {
+    Accept,
+    Decline,
+    Delete,
+}
+
+impl RepeatableAction {
+    pub fn verb(&self) -> &'static str {
+        match self {
+            RepeatableAction::Accept => "accept",
+            RepeatableAction::Decline => "decline",
+            RepeatableAction::Delete => "delete",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RepeatableAction::Accept => "Accept",
+            RepeatableAction::Decline => "Decline",
+            RepeatableAction::Delete => "Delete",
+        }
+    }
+}
+
+/// Calendars events can be created in with the given sign-ins, the Google one first. The
+/// Google calendar goes by its configured name, else its name in the calendar list
+/// (`listed_name`), else its id.
+pub fn calendar_targets(
+    config: &Config,
+    google_auth: &GoogleAuthState,
+    icloud_auth: &ICloudAuthState,
+    listed_name: impl FnOnce(&str) -> Option<String>,
+) -> Vec<CopyTarget> {
+    let mut targets = Vec::new();
+    if let (GoogleAuthState::Authenticated(_), Some(google)) = (google_auth, &config.google) {
+        let name = google.name.clone()
+            .or_else(|| listed_name(&google.calendar_id))
+            .unwrap_or_else(|| google.calendar_id.clone());
+        targets.push(CopyTarget::Google { calendar_id: google.calendar_id.clone(), name });
+    }
+    if let ICloudAuthState::Authenticated { calendars } = icloud_auth {
+        for calendar in calendars {
+            let name = calendar.name.clone().unwrap_or_else(|| {
+                calendar.url.trim_end_matches('/').rsplit('/').next().unwrap_or_default().to_string()
+            });
+            targets.push(CopyTarget::ICloud { calendar_url: calendar.url.clone(), name });
+        }
+    }
+    targets
 }
 
 /// Application state
 pub struct App {
     pub current_date: NaiveDate,
     pub selected_date: NaiveDate,
-    pub show_logs: bool,
     pub show_weekends: bool,
+    /// Both sources in one time-ordered panel in the month view
+    pub unified_panel: bool,
     pub events: EventCache,
     pub google_auth: GoogleAuthState,
     pub icloud_auth: ICloudAuthState,
-    pub status_message: Option<String>,
-    pub status_message_time: Option<std::time::Instant>,
+    /// Microsoft Graph tokens once signed in with `:outlook`
+    pub outlook_tokens: Option<GraphToken>,
+    /// Tokens of the `google_account` providers signed in with `:google <account>`
+    pub google_accounts: BTreeMap<String, TokenInfo>,
+    /// Accounts whose tokens are being refreshed, or couldn't be and wait for a new sign-in
+    pub google_account_refreshes: HashSet<String>,
+    pub status: StatusQueue,
     pub config: Config,
-    pub google_needs_fetch: bool,
-    pub icloud_needs_fetch: bool,
+    /// `config.meeting_patterns`, compiled
+    pub meeting_patterns: utils::MeetingPatterns,
+    pub fetches: FetchScheduler,
+    /// Set while Google tokens are being refreshed
     pub google_loading: bool,
-    pub icloud_loading: bool,
+    /// Events whose full details have been requested, so each is fetched once
+    pub details_requested: HashSet<EventId>,
+    /// Last fetch error per source, shown in the panel header until retried or resolved
+    pub google_error: Option<String>,
+    pub icloud_error: Option<String>,
     pub navigation_mode: NavigationMode,
+    pub view_mode: ViewMode,
     pub selected_source: EventSource,
     pub selected_event_index: usize,
     pub pending_action: Option<PendingAction>,
     pub search: Option<SearchState>,
+    pub debug_screen: Option<DebugScreen>,
+    /// Text typed after ':' while the command line is open
+    pub command_line: Option<String>,
+    /// Event parsed from the clipboard; keeps what the `:new` line can't hold (location,
+    /// description, meeting link)
+    pub new_event_draft: Option<DisplayEvent>,
+    pub info_modal: Option<InfoModal>,
+    pub calendar_picker: Option<CalendarPicker>,
+    pub busy_overlay: Option<BusyOverlay>,
+    pub tags: TagStore,
+    pub contacts: ContactBook,
+    /// Tags being edited for the selected event, as typed
+    pub tag_editor: Option<String>,
+    /// Last confirmed RSVP or delete, repeated on the selected event by `.`
+    pub last_action: Option<RepeatableAction>,
+    /// Session override of `confirm` from the config: always (true) or never (false) ask
+    pub confirm_override: Option<bool>,
+    /// The in-day filter (kept in `events.day_filter`) is being typed
+    pub day_filter_editing: bool,
+    /// Day-of-month digits typed in Day mode, e.g. "1" on the way to "15"
+    pub day_jump: Option<String>,
+    /// Count typed before an event navigation key, e.g. the 3 in "3j"
+    pub count: Option<usize>,
+    /// Event being moved: Day mode picks its new date
+    pub moving: Option<DisplayEvent>,
+    /// Events marked for a bulk action, from any day
+    pub marked: Vec<DisplayEvent>,
+    /// When events last arrived from a calendar, for the sync status segment
+    pub last_synced: Option<NaiveTime>,
+    /// Set while fetches fail for lack of a connection: when the last reconnect probe started
+    pub offline_since: Option<Instant>,
+    /// When the displayed months were last fetched in full, for the background refresh
+    refreshed_at: Instant,
+    /// Meetings already offered in the join prompt, so each is asked about once
+    join_prompted: HashSet<(NaiveDate, String)>,
+    /// Events already announced as time to leave for, so each alert fires once
+    leave_alerted: HashSet<(NaiveDate, String)>,
+    /// Day the app last saw as today, to journal it once the date changes
+    journal_day: NaiveDate,
+    /// Demo mode: events come from the mock provider and nothing is sent or saved
+    pub demo: bool,
+    /// HTTP traffic of this session, shared with every client the app creates
+    pub http_log: HttpLog,
+    /// What the providers keep between fetches, e.g. the iCloud mirrors
+    provider_context: ProviderContext,
+    /// Background work of this session, which quitting cancels or waits for
+    pub tasks: Tasks,
 }
 
 impl App {
     pub fn new() -> Self {
         let today = Local::now().date_naive();
         let mut events = EventCache::new();
-        events.load_from_disk();
+        let snoozed_error = match SnoozeStore::load() {
+            Ok(mut snoozed) => {
+                snoozed.prune(today);
+                events.snoozed = snoozed;
+                None
+            }
+            Err(e) => Some(e),
+        };
+        let cache_error = events.load_from_disk().err();
+        let (tags, tags_error) = match TagStore::load() {
+            Ok(tags) => (tags, None),
+            Err(e) => (TagStore::default(), Some(e)),
+        };
+        let (contacts, contacts_error) = match ContactBook::load() {
+            Ok(contacts) => (contacts, None),
+            Err(e) => (ContactBook::default(), Some(e)),
+        };
+        let http_log = HttpLog::default();
 
         let mut app = Self {
             current_date: today,
             selected_date: today,
-            show_logs: false,
             show_weekends: false,
+            unified_panel: false,
             events,
             google_auth: GoogleAuthState::NotConfigured,
             icloud_auth: ICloudAuthState::NotConfigured,
-            status_message: None,
-            status_message_time: None,
+            outlook_tokens: None,
+            google_accounts: BTreeMap::new(),
+            google_account_refreshes: HashSet::new(),
+            status: StatusQueue::default(),
             config: Config::default(),
-            google_needs_fetch: false,
-            icloud_needs_fetch: false,
+            meeting_patterns: utils::MeetingPatterns::default(),
+            fetches: FetchScheduler::default(),
             google_loading: false,
-            icloud_loading: false,
+            details_requested: HashSet::new(),
+            google_error: None,
+            icloud_error: None,
             navigation_mode: NavigationMode::Day,
+            view_mode: ViewMode::default(),
             selected_source: EventSource::Google,
             selected_event_index: 0,
             pending_action: None,
             search: None,
+            debug_screen: None,
+            command_line: None,
+            new_event_draft: None,
+            info_modal: None,
+            calendar_picker: None,
+            busy_overlay: None,
+            tags,
+            contacts,
+            tag_editor: None,
+            last_action: None,
+            confirm_override: None,
+            day_filter_editing: false,
+            day_jump: None,
+            count: None,
+            moving: None,
+            marked: Vec::new(),
+            last_synced: None,
+            offline_since: None,
+            refreshed_at: Instant::now(),
+            join_prompted: HashSet::new(),
+            leave_alerted: HashSet::new(),
+            journal_day: today,
+            demo: false,
+            provider_context: ProviderContext::new(http_log.clone()),
+            http_log,
+            tasks: Tasks::default(),
         };
 
+        for e in [cache_error, snoozed_error, tags_error, contacts_error].into_iter().flatten() {
+            app.set_status(e.to_string());
+        }
+
         app.enter_event_mode();
         app
     }
 
     pub fn set_status(&mut self, msg: impl Into<String>) {
-        self.status_message = Some(msg.into());
-        self.status_message_time = Some(std::time::Instant::now());
+        self.status.push(msg.into(), Instant::now());
     }
 
-    pub fn clear_expired_status(&mut self) {
-        if let Some(time) = self.status_message_time
-            && time.elapsed() > std::time::Duration::from_secs(3)
-        {
-            self.status_message = None;
-            self.status_message_time = None;
+    /// Open a link in the browser, reporting a failed launch in the status bar
+    pub fn open_url(&mut self, url: &str) {
+        if let Err(e) = utils::open_url(url, self.config.browser_command.as_deref()) {
+            self.set_status(format!("Failed to open {}: {}", url, e));
         }
     }
 
+    /// Offer to join a meeting starting within a minute, unless another prompt is open
+    pub fn check_join_prompt(&mut self, now: NaiveDateTime) {
+        if self.pending_action.is_some() || self.info_modal.is_some() || self.search.is_some() || self.debug_screen.is_some() {
+            return;
+        }
+        let today = now.date();
+        let events = self.events.day(today);
+        let prompted = &self.join_prompted;
+        let Some(event) = meeting_due(events.filter(|e| !prompted.contains(&(today, e.uid().to_string()))), now.time()) else {
+            return;
+        };
+        let (Some(url), title) = (event.meeting_url.clone(), event.title.clone()) else { return };
+        self.join_prompted.insert((today, event.uid().to_string()));
+        self.pending_action = Some(PendingAction::JoinMeeting { title, url });
+    }
+
+    /// Announce once that it's time to leave for an event at a physical location, in the
+    /// status bar and, when `travel.notify` is set, as a desktop notification
+    pub fn check_leave_alert(&mut self, now: NaiveDateTime) {
+        let today = now.date();
+        let alerted = &self.leave_alerted;
+        let travel = &self.config.travel;
+        let Some(event) = self.events.day(today).find(|e| {
+            e.accepted
+                && !alerted.contains(&(today, e.uid().to_string()))
+                && travel.leave_by(e).is_some_and(|leave| leave <= now)
+                && e.start_time.is_some_and(|start| now.time() < start)
+        }) else {
+            return;
+        };
+        let (uid, title, start) = (event.uid().to_string(), event.title.clone(), event.time_label(&self.config.time_format));
+        let place = event.location.clone().unwrap_or_default();
+        self.leave_alerted.insert((today, uid));
+        self.set_status(format!("Time to leave for {} at {}", title, start));
+        if self.config.travel.notify && !self.demo {
+            let _ = utils::notify(&format!("Leave now for {}", title), &format!("{} at {}", start, place));
+        }
+    }
+
+    /// Append the attended meetings of a day to its daily note, reporting the outcome in the status bar
+    pub fn write_journal(&mut self, date: NaiveDate, now: NaiveDateTime) {
+        let events = self.events.day(date);
+        let attended = journal::attended(events, now);
+        match journal::append(&self.config.journal, date, &attended, &self.config.time_format) {
+            Ok((_, 0)) => self.set_status(format!("No new meetings to journal for {}", date.format("%a %b %d"))),
+            Ok((path, added)) => self.set_status(format!("Journaled {} meeting(s) to {}", added, path.display())),
+            Err(e) => self.set_status(format!("Failed to journal: {}", e)),
+        }
+    }
+
+    /// `:export [from] [to]`: write events of a date range (the displayed month by default)
+    /// as CSV to the home directory; `:export marked` writes the marked events
+    pub fn export_csv(&mut self, arg: &str) {
+        if arg.trim() == "marked" {
+            return self.export_marked();
+        }
+        let args: Vec<&str> = arg.split_whitespace().collect();
+        match export::parse_range(&args, self.current_date) {
+            Ok((from, to)) => self.export_range(from, to),
+            Err(e) => self.set_status(e),
+        }
+    }
+
+    /// Write the events between two dates as CSV to the home directory
+    pub fn export_range(&mut self, from: NaiveDate, to: NaiveDate) {
+        let path = dirs::home_dir()
+            .unwrap_or_default()
+            .join(format!("calendarchy-{}-{}.csv", from.format("%Y%m%d"), to.format("%Y%m%d")));
+        let events = export::events_in_range(&self.events, from, to);
+        let count = events.len();
+        match std::fs::write(&path, export::to_csv(&events)) {
+            Ok(()) => self.set_status(format!("Exported {} event(s) to {}", count, path.display())),
+            Err(e) => self.set_status(format!("Failed to write {}: {}", path.display(), e)),
+        }
+    }
+
+    /// `:export marked`: write the marked events as CSV to the home directory
+    fn export_marked(&mut self) {
+        if self.marked.is_empty() {
+            return self.set_status("No marked events (press v on an event)");
+        }
+        let mut events: Vec<&DisplayEvent> = self.marked.iter().collect();
+        events.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.start_time.cmp(&b.start_time)));
+        let path = dirs::home_dir()
+            .unwrap_or_default()
+            .join(format!("calendarchy-marked-{}.csv", Local::now().format("%Y%m%d-%H%M%S")));
+        let count = events.len();
+        match std::fs::write(&path, export::to_csv(&events)) {
+            Ok(()) => self.set_status(format!("Exported {} marked event(s) to {}", count, path.display())),
+            Err(e) => self.set_status(format!("Failed to write {}: {}", path.display(), e)),
+        }
+    }
+
+    /// `:stats [from] [to]`: meeting count and time per calendar over a date range (the
+    /// displayed month by default), from where `e` exports the range as CSV
+    pub fn stats(&self, arg: &str) -> InfoModal {
+        let args: Vec<&str> = arg.split_whitespace().collect();
+        let (from, to) = match export::parse_range(&args, self.current_date) {
+            Ok(range) => range,
+            Err(e) => return InfoModal::new("Stats", vec![e]),
+        };
+        let meetings: Vec<&DisplayEvent> = export::events_in_range(&self.events, from, to)
+            .into_iter()
+            .filter(|e| review::is_meeting(e))
+            .collect();
+        let shares = review::calendar_shares(&meetings);
+        let minutes = shares.iter().map(|share| share.minutes).sum();
+
+        let mut lines = vec![format!("Meetings: {} ({})", meetings.len(), utils::format_duration(minutes))];
+        lines.push(String::new());
+        lines.push("Calendars".to_string());
+        if shares.is_empty() {
+            lines.push("  None".to_string());
+        }
+        for share in &shares {
+            lines.push(format!("  {:<20} {:>3}  {}", share.name, share.meetings, utils::format_duration(share.minutes)));
+        }
+        lines.push(String::new());
+        lines.push("e: export as CSV".to_string());
+
+        let mut modal = InfoModal::new(
+            format!("Stats {} - {}", from.format("%b %d"), to.format("%b %d, %Y")),
+            lines,
+        );
+        modal.export_range = Some((from, to));
+        modal
+    }
+
+    /// `:slots <duration> [days]`: open stretches of at least the duration within working
+    /// hours over the next days (a week unless given), between both calendars' meetings
+    pub fn find_slots(&self, arg: &str) -> InfoModal {
+        let mut words = arg.split_whitespace();
+        let minutes = words.next().and_then(review::parse_minutes);
+        let days = words.next().map_or(Some(7), |days| days.parse::<i64>().ok().filter(|&days| days > 0));
+        let (Some(minutes), Some(days)) = (minutes, days) else {
+            return InfoModal::new("Open slots", vec!["Type :slots <duration> [days], e.g. :slots 45m".to_string()]);
+        };
+
+        let slots = review::open_slots(&self.events, Local::now().naive_local(), days, minutes, &self.config.work_hours);
+        let mut lines = Vec::new();
+        if slots.is_empty() {
+            lines.push(format!("No open slot in the next {} day(s)", days));
+        }
+        let mut last_date = None;
+        for (date, start, end) in slots {
+            if last_date != Some(date) {
+                if last_date.is_some() {
+                    lines.push(String::new());
+                }
+                lines.push(date.format("%A, %B %-d").to_string());
+                last_date = Some(date);
+            }
+            lines.push(format!(
+                "  {}-{}  {}",
+                self.config.time_format.time(start),
+                self.config.time_format.time(end),
+                utils::format_duration((end - start).num_minutes())
+            ));
+        }
+        InfoModal::new(format!("Open slots for {}", utils::format_duration(minutes)), lines)
+    }
+
+    /// `:review [md]`: summarize the selected week in a modal, or write it as markdown to the home directory
+    pub fn weekly_review(&mut self, arg: &str) {
+        let start = self.config.week_start.start_of_week(self.selected_date);
+        let review = WeeklyReview::compute(&self.events, start, &self.config.work_hours);
+        match arg.trim() {
+            "" => self.info_modal = Some(InfoModal::new(review.title(), review.lines(&self.config.time_format))),
+            "md" | "markdown" => {
+                let path = dirs::home_dir()
+                    .unwrap_or_default()
+                    .join(format!("calendarchy-review-{}.md", start.format("%Y-%m-%d")));
+                match std::fs::write(&path, review.to_markdown(&self.config.time_format)) {
+                    Ok(()) => self.set_status(format!("Saved review to {}", path.display())),
+                    Err(e) => self.set_status(format!("Failed to write {}: {}", path.display(), e)),
+                }
+            }
+            other => self.set_status(format!("Unknown review format: {}", other)),
+        }
+    }
+
+    /// Journal the previous day once the date changes, when enabled
+    pub fn check_journal_rollover(&mut self, now: NaiveDateTime) {
+        let previous = self.journal_day;
+        if now.date() == previous {
+            return;
+        }
+        self.journal_day = now.date();
+        if self.config.journal.on_rollover && self.config.journal.path.is_some() && !self.demo {
+            self.write_journal(previous, now);
+        }
+    }
+
+    /// Ask to confirm an accept, decline or delete of the selected event, or of all
+    /// marked events at once
+    pub fn request_action(&mut self, action: RepeatableAction) {
+        if !self.marked.is_empty() {
+            let items = self.marked.iter().map(|e| (e.title.clone(), self.action_for(e, action))).collect();
+            self.pending_action = Some(PendingAction::Bulk { action, items });
+            return;
+        }
+        let Some(event) = self.get_selected_event() else { return };
+        match self.action_for(event, action) {
+            Ok(pending) => self.pending_action = Some(pending),
+            Err(e) => self.set_status(e),
+        }
+    }
+
+    /// The action on an event, or why it can't be taken
+    fn action_for(&self, event: &DisplayEvent, action: RepeatableAction) -> Result<PendingAction, String> {
+        match &event.id {
+            EventId::Google { account: None, .. } if !matches!(self.google_auth, GoogleAuthState::Authenticated(_)) => {
+                return Err("Not signed in to Google".to_string());
+            }
+            EventId::Google { account: Some(account), .. } if !self.google_accounts.contains_key(account) => {
+                return Err(format!("Not signed in to Google as {}; sign in with :google {}", account, account));
+            }
+            EventId::ICloud { .. } if self.config.icloud.is_none() => return Err("iCloud is not configured".to_string()),
+            EventId::Feed { .. } => return Err("Events from feeds are read-only".to_string()),
+            _ => {}
+        }
+        let id = event.id.clone();
+        Ok(match action {
+            RepeatableAction::Accept => PendingAction::Accept { id },
+            RepeatableAction::Decline => PendingAction::Decline { id },
+            RepeatableAction::Delete => PendingAction::Delete { id },
+        })
+    }
+
+    /// Mark or unmark the selected event for a bulk action
+    pub fn toggle_mark(&mut self) {
+        let Some(event) = self.get_selected_event().cloned() else { return };
+        match self.marked.iter().position(|m| m.is_same_occurrence(&event)) {
+            Some(index) => {
+                self.marked.remove(index);
+            }
+            None => self.marked.push(event),
+        }
+        self.set_status(format!("{} event(s) marked", self.marked.len()));
+    }
+
+    /// Hide the selected event on its day, on this machine only; the calendar keeps it
+    pub fn snooze_selected(&mut self) {
+        let Some(event) = self.get_selected_event().cloned() else { return };
+        self.events.snoozed.snooze(&event);
+        if let Err(e) = self.events.snoozed.save() {
+            self.set_status(format!("Failed to save hidden events: {}", e));
+        } else {
+            self.set_status(format!("Hid \"{}\" for the day (:unhide to undo)", event.title));
+        }
+        self.events.reconcile();
+
+        // Keep a selection on what's left of the day
+        let google_len = self.events.google.get(self.selected_date).len();
+        let icloud_len = self.events.icloud.get(self.selected_date).len();
+        let (len, other_len) = match self.selected_source {
+            EventSource::Google => (google_len, icloud_len),
+            EventSource::ICloud => (icloud_len, google_len),
+        };
+        if len > 0 {
+            self.selected_event_index = self.selected_event_index.min(len - 1);
+        } else if other_len > 0 {
+            self.selected_source = match self.selected_source {
+                EventSource::Google => EventSource::ICloud,
+                EventSource::ICloud => EventSource::Google,
+            };
+            self.selected_event_index = 0;
+        } else {
+            self.exit_event_mode();
+        }
+    }
+
+    /// `:unhide`: show the events hidden on the selected day again
+    pub fn unsnooze_day(&mut self) {
+        let count = self.events.snoozed.unsnooze_day(self.selected_date);
+        if let Err(e) = self.events.snoozed.save() {
+            self.set_status(format!("Failed to save hidden events: {}", e));
+            return;
+        }
+        self.events.reconcile();
+        self.set_status(format!("{} hidden event(s) shown again", count));
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+        self.set_status("Marks cleared");
+    }
+
+    /// Accept the selected invitation and join its meeting; already accepted meetings are just joined
+    pub fn request_accept_and_join(&mut self) {
+        let Some(event) = self.get_selected_event() else { return };
+        let Some(url) = event.meeting_url.clone() else {
+            return self.set_status("No meeting link");
+        };
+        if event.accepted {
+            return self.open_url(&url);
+        }
+        match self.action_for(event, RepeatableAction::Accept) {
+            Ok(accept) => self.pending_action = Some(PendingAction::AcceptAndJoin { accept: Box::new(accept), url }),
+            Err(e) => self.set_status(e),
+        }
+    }
+
+    /// Whether an action waits for y/Enter; join prompts always do
+    pub fn needs_confirmation(&self, action: &PendingAction) -> bool {
+        let Some(repeatable) = action.repeatable() else { return true };
+        if let Some(always) = self.confirm_override {
+            return always;
+        }
+        match repeatable {
+            RepeatableAction::Accept => self.config.confirm.accept,
+            RepeatableAction::Decline => self.config.confirm.decline,
+            RepeatableAction::Delete => self.config.confirm.delete,
+        }
+    }
+
+    /// Take the requested action when it should run without asking
+    pub fn take_unconfirmed_action(&mut self) -> Option<PendingAction> {
+        if self.pending_action.as_ref().is_some_and(|a| self.needs_confirmation(a)) {
+            return None;
+        }
+        self.pending_action.take()
+    }
+
+    /// `:confirm on|off|default`: override the configured confirmations for this session
+    pub fn set_confirm_override(&mut self, arg: &str) {
+        let (value, message) = match arg.trim() {
+            "on" => (Some(true), "Confirming all actions"),
+            "off" => (Some(false), "Not confirming actions"),
+            "default" | "" => (None, "Confirming actions as configured"),
+            other => return self.set_status(format!("Unknown confirm setting: {} (on, off or default)", other)),
+        };
+        self.confirm_override = value;
+        self.set_status(message);
+    }
+
+    /// Enter on the selected event: nothing, details, join or the web UI, as configured
+    pub fn activate_selected_event(&mut self) {
+        let Some(event) = self.get_selected_event() else { return };
+        match self.config.enter_action {
+            EnterAction::None => {}
+            EnterAction::Details => {
+                let modal = InfoModal::new(event.title.clone(), event_detail_lines(event, self.tags.get(event), &self.config.time_format));
+                self.info_modal = Some(modal);
+            }
+            EnterAction::Join => match event.meeting_url.clone() {
+                Some(url) => self.open_url(&url),
+                None => self.set_status("No meeting link"),
+            },
+            EnterAction::Browser => {
+                let url = match &event.id {
+                    EventId::Google { calendar_id, event_id, .. } => utils::google_event_url(calendar_id, event_id),
+                    EventId::ICloud { .. } => "https://www.icloud.com/calendar".to_string(),
+                    EventId::Feed { location, .. } => location.clone(),
+                };
+                self.open_url(&url);
+            }
+        }
+    }
+
+    /// Open a mail draft to everyone on the selected event, with its title as the subject
+    pub fn email_attendees(&mut self) {
+        let Some(event) = self.get_selected_event() else { return };
+        // Meeting rooms and other resources have no inbox worth writing to
+        let recipients: Vec<&str> = event.attendees.iter()
+            .filter(|a| !a.is_resource())
+            .map(|a| a.email.as_str())
+            .collect();
+        if recipients.is_empty() {
+            self.set_status("No attendees to email");
+            return;
+        }
+        let uri = utils::mailto_uri(&recipients, &event.title, "");
+        self.open_url(&uri);
+    }
+
+    /// `:propose`: open a mail draft to the organizer of the selected Google invitation
+    /// suggesting the first free slot of the same length from the event's day on
+    pub fn propose_new_time(&mut self) {
+        let Some(event) = self.get_selected_event() else { return };
+        if !matches!(event.id, EventId::Google { .. }) || !brief::is_pending(event) {
+            self.set_status("Only for Google invitations waiting for your answer");
+            return;
+        }
+        let Some(organizer) = event.attendees.iter().find(|a| a.status == AttendeeStatus::Organizer && !a.is_self) else {
+            self.set_status("No organizer to write to");
+            return;
+        };
+        let from = Local::now().naive_local().max(event.date.and_time(NaiveTime::MIN));
+        let minutes = event.duration_minutes().unwrap_or(30);
+        let Some((date, start, end)) = review::first_free_slot(&self.events, from, minutes, &self.config.work_hours) else {
+            self.set_status("No free slot within the next week");
+            return;
+        };
+        let body = format!(
+            "Hi,\n\nI can't make {} on {} at {}. Would {}, {}-{} work instead?\n",
+            event.title,
+            event.date.format("%A, %B %-d"),
+            event.time_label(&self.config.time_format),
+            date.format("%A, %B %-d"),
+            self.config.time_format.time(start),
+            self.config.time_format.time(end),
+        );
+        let uri = utils::mailto_uri(&[&organizer.email], &format!("New time proposed: {}", event.title), &body);
+        self.open_url(&uri);
+    }
+
+    /// Hand a tel: dial-in to the configured dialer or the system tel: handler
+    pub fn dial(&mut self, tel: &str) {
+        match utils::open_url(tel, self.config.dialer_command.as_deref()) {
+            Ok(()) => self.set_status(format!("Dialing {}", tel.trim_start_matches("tel:"))),
+            Err(e) => self.set_status(format!("Failed to dial {}: {}", tel, e)),
+        }
+    }
+
+    /// A server answered again after fetches failed offline: fetch what failed
+    pub fn reconnected(&mut self) {
+        self.offline_since = None;
+        if self.retry_failed_sources() {
+            self.set_status("Reconnected, syncing...");
+        }
+    }
+
+    /// Clear per-source errors and schedule a refetch of the failed sources.
+    /// Returns false if no source was in an error state.
+    pub fn retry_failed_sources(&mut self) -> bool {
+        let mut retried = false;
+        if self.google_error.take().is_some() {
+            self.fetch_month(EventSource::Google);
+            retried = true;
+        }
+        if self.icloud_error.take().is_some() {
+            self.fetch_month(EventSource::ICloud);
+            retried = true;
+        }
+        retried
+    }
+
+    pub fn clear_expired_status(&mut self) {
+        self.status.advance(Instant::now());
+    }
+
     pub fn next_day(&mut self) {
         self.selected_date += Duration::days(1);
         self.sync_month_if_needed();
@@ -127,14 +1003,127 @@ impl App {
         self.sync_month_if_needed();
     }
 
+    pub fn next_week(&mut self) {
+        self.selected_date += Duration::days(7);
+        self.sync_month_if_needed();
+    }
+
+    pub fn prev_week(&mut self) {
+        self.selected_date -= Duration::days(7);
+        self.sync_month_if_needed();
+    }
+
+    /// Cycle the day panels, the week timeline and the agenda
+    pub fn toggle_view_mode(&mut self) {
+        self.view_mode = match self.view_mode {
+            ViewMode::Month => ViewMode::Week,
+            ViewMode::Week => ViewMode::Agenda,
+            ViewMode::Agenda => ViewMode::Month,
+        };
+        self.fetch_view_months();
+    }
+
+    pub fn agenda_days(&self) -> u32 {
+        self.config.agenda_days.unwrap_or(cache::DEFAULT_AGENDA_DAYS)
+    }
+
+    /// Events the agenda view lists, from today
+    pub fn agenda(&self) -> Vec<AgendaEntry<'_>> {
+        self.events.agenda(Local::now().date_naive(), self.agenda_days())
+    }
+
+    /// Select the agenda entry `offset` places from the selected event, or the first that
+    /// hasn't ended yet when no agenda event is selected
+    fn move_in_agenda(&mut self, offset: isize) {
+        let now = Local::now().naive_local();
+        let agenda = self.agenda();
+        let selected = agenda.iter().position(|entry| {
+            self.navigation_mode == NavigationMode::Event
+                && entry.event.date == self.selected_date
+                && entry.source == self.selected_source
+                && entry.index == self.selected_event_index
+        });
+        let target = match selected {
+            Some(position) => position.saturating_add_signed(offset).min(agenda.len().saturating_sub(1)),
+            None => agenda
+                .iter()
+                .position(|entry| {
+                    let end = entry.event.end_time.or(entry.event.start_time);
+                    entry.event.date > now.date() || end.is_none_or(|end| entry.event.date.and_time(end) > now)
+                })
+                .unwrap_or(0),
+        };
+        let Some(entry) = agenda.get(target) else {
+            let days = self.agenda_days();
+            self.set_status(format!("No events in the next {} days", days));
+            return;
+        };
+        let (date, source, index) = (entry.event.date, entry.source, entry.index);
+        if date != self.selected_date {
+            self.clear_day_filter();
+        }
+        self.selected_date = date;
+        self.selected_source = source;
+        self.selected_event_index = index;
+        self.navigation_mode = NavigationMode::Event;
+        self.sync_month_if_needed();
+    }
+
+    /// Add a digit to the day being typed, jumping once no further digit could follow
+    /// (e.g. "4", or "1" then "5"); "0" can't start a day
+    pub fn push_day_jump_digit(&mut self, digit: char) {
+        let mut input = self.day_jump.take().unwrap_or_default();
+        if input.is_empty() && digit == '0' {
+            return;
+        }
+        input.push(digit);
+        let days = utils::days_in_month(self.current_date);
+        let day: u32 = input.parse().unwrap_or(0);
+        if input.len() >= 2 || day * 10 > days {
+            self.jump_to_day(&input);
+        } else {
+            self.day_jump = Some(input);
+        }
+    }
+
+    /// Select a day of the displayed month, as typed
+    pub fn jump_to_day(&mut self, input: &str) {
+        self.day_jump = None;
+        match input.parse().ok().and_then(|day| self.current_date.with_day(day)) {
+            Some(date) => self.selected_date = date,
+            None => self.set_status(format!("No day {} in {}", input, self.current_date.format("%B"))),
+        }
+    }
+
+    /// Select a clicked day, back in Day mode
+    pub fn select_day(&mut self, date: NaiveDate) {
+        if self.navigation_mode == NavigationMode::Event {
+            self.exit_event_mode();
+        }
+        self.selected_date = date;
+        self.sync_month_if_needed();
+    }
+
+    /// Select a clicked event in Event mode
+    pub fn select_event(&mut self, date: NaiveDate, source: EventSource, index: usize) {
+        if date != self.selected_date {
+            self.clear_day_filter();
+        }
+        self.selected_date = date;
+        self.selected_source = source;
+        self.selected_event_index = index;
+        self.navigation_mode = NavigationMode::Event;
+        self.sync_month_if_needed();
+    }
+
     fn sync_month_if_needed(&mut self) {
         if self.selected_date.month() != self.current_date.month()
             || self.selected_date.year() != self.current_date.year()
         {
             self.current_date = self.selected_date.with_day(1).unwrap();
-            self.google_needs_fetch = true;
-            self.icloud_needs_fetch = true;
+            self.fetch_months();
         }
+        self.fetch_view_months();
     }
 
     pub fn goto_today(&mut self) {
@@ -144,9 +1133,9 @@ impl App {
         self.current_date = today;
         self.selected_date = today;
         if month_changed {
-            self.google_needs_fetch = true;
-            self.icloud_needs_fetch = true;
+            self.fetch_months();
         }
+        self.fetch_view_months();
     }
 
     pub fn goto_now(&mut self) {
@@ -154,21 +1143,122 @@ impl App {
         self.enter_event_mode();
     }
 
-    pub fn month_range(&self) -> (NaiveDate, NaiveDate) {
-        let first = self.current_date.with_day(1).unwrap();
-        let last = if self.current_date.month() == 12 {
-            NaiveDate::from_ymd_opt(self.current_date.year() + 1, 1, 1)
-                .unwrap()
-                - Duration::days(1)
-        } else {
-            NaiveDate::from_ymd_opt(self.current_date.year(), self.current_date.month() + 1, 1)
-                .unwrap()
-                - Duration::days(1)
+    /// Queue the displayed month of a panel, and the months either side of it shown in
+    /// the calendar column ahead of navigation; months already in the cache are skipped
+    /// when their turn comes
+    pub fn fetch_month(&mut self, source: EventSource) {
+        let (first, last) = utils::month_bounds(self.current_date);
+        self.fetches.request(source, self.current_date, Priority::Visible);
+        self.fetches.request(source, last + Duration::days(1), Priority::Prefetch);
+        self.fetches.request(source, first - Duration::days(1), Priority::Prefetch);
+    }
+
+    /// Queue the months the week timeline or the agenda shows besides the displayed one
+    fn fetch_view_months(&mut self) {
+        let (first, last) = match self.view_mode {
+            ViewMode::Month => return,
+            ViewMode::Week => {
+                let first = self.config.week_start.start_of_week(self.selected_date);
+                (first, first + Duration::days(6))
+            }
+            ViewMode::Agenda => {
+                let today = Local::now().date_naive();
+                (today, today + Duration::days(self.agenda_days().saturating_sub(1) as i64))
+            }
         };
-        (first, last)
+        let mut month = first.with_day(1).unwrap_or(first);
+        while month <= last {
+            if month != self.current_date.with_day(1).unwrap_or(self.current_date) {
+                self.fetches.request(EventSource::Google, month, Priority::Visible);
+                self.fetches.request(EventSource::ICloud, month, Priority::Visible);
+            }
+            month = utils::month_bounds(month).1 + Duration::days(1);
+        }
     }
 
-    pub fn get_current_source_events(&self) -> &[DisplayEvent] {
+    /// Queue the displayed month of both panels
+    pub fn fetch_months(&mut self) {
+        self.fetch_month(EventSource::Google);
+        self.fetch_month(EventSource::ICloud);
+    }
+
+    /// `:overlay <email>`: shade a colleague's busy times in the availability grid;
+    /// without an email, remove the overlay
+    pub fn set_busy_overlay(&mut self, email: &str) {
+        if email.is_empty() {
+            self.busy_overlay = None;
+            self.set_status("Overlay removed");
+        } else if !matches!(self.google_auth, GoogleAuthState::Authenticated(_)) {
+            self.set_status("Connect Google first (press g)");
+        } else {
+            self.busy_overlay = Some(BusyOverlay::new(email));
+        }
+    }
+
+    /// Open the checklist of the connected iCloud account's calendars
+    pub fn open_calendar_picker(&mut self) {
+        match self.icloud_auth {
+            ICloudAuthState::Authenticated { ref calendars } => {
+                self.calendar_picker = Some(CalendarPicker::new(calendars.clone()));
+            }
+            _ => self.set_status("Connect iCloud first (press i)"),
+        }
+    }
+
+    /// Keep the calendars checked in the picker: saved for next time and refetched now
+    pub fn apply_calendar_picker(&mut self) {
+        let Some(picker) = self.calendar_picker.take() else { return };
+        let stored: Vec<config::StoredCalendar> = picker.calendars.iter().map(CalendarEntry::to_stored).collect();
+        if let Err(e) = config::save_icloud_tokens(&stored) {
+            self.set_status(format!("Failed to save calendars: {}", e));
+            return;
+        }
+        let shown = picker.calendars.iter().filter(|c| !c.hidden).count();
+        self.set_status(format!("Showing {} of {} iCloud calendar(s)", shown, picker.calendars.len()));
+        self.icloud_auth = ICloudAuthState::Authenticated { calendars: picker.calendars };
+        self.reload_events();
+    }
+
+    /// Drop all cached events and fetch the displayed month again
+    pub fn reload_events(&mut self) {
+        self.events.clear();
+        self.details_requested.clear();
+        self.fetches.invalidate();
+        self.provider_context.refresh();
+        self.fetch_months();
+        self.refreshed_at = Instant::now();
+    }
+
+    /// Refetch the displayed months in the background once the refresh interval has passed.
+    /// Events stay on screen until the new ones arrive; offline, the reconnect probe takes over.
+    pub fn refresh_if_due(&mut self, now: Instant) {
+        let minutes = self.config.refresh_interval_minutes.unwrap_or(DEFAULT_REFRESH_INTERVAL_MINUTES);
+        if minutes == 0
+            || now.duration_since(self.refreshed_at) < std::time::Duration::from_secs(minutes * 60)
+            || self.offline_since.is_some()
+            || self.fetches.has_queued()
+        {
+            return;
+        }
+        self.refreshed_at = now;
+        self.provider_context.refresh();
+        self.events.google.mark_stale();
+        self.events.icloud.mark_stale();
+        self.fetch_months();
+        self.fetch_view_months();
+    }
+
+    /// Select the event with `id` again after its day was refetched, or the nearest one
+    /// still there when it's gone
+    pub fn reselect(&mut self, id: &EventId) {
+        let events = self.get_current_source_events();
+        match events.iter().position(|e| &e.id == id) {
+            Some(index) => self.selected_event_index = index,
+            None => self.selected_event_index = self.selected_event_index.min(events.len().saturating_sub(1)),
+        }
+    }
+
+    pub fn get_current_source_events(&self) -> &[Arc<DisplayEvent>] {
         match self.selected_source {
             EventSource::Google => self.events.google.get(self.selected_date),
             EventSource::ICloud => self.events.icloud.get(self.selected_date),
@@ -177,13 +1267,17 @@ impl App {
 
     pub fn get_selected_event(&self) -> Option<&DisplayEvent> {
         if self.navigation_mode == NavigationMode::Event {
-            self.get_current_source_events().get(self.selected_event_index)
+            self.get_current_source_events().get(self.selected_event_index).map(Arc::as_ref)
         } else {
             None
         }
     }
 
     pub fn enter_event_mode(&mut self) {
+        if self.view_mode == ViewMode::Agenda {
+            self.move_in_agenda(0);
+            return;
+        }
         let google_events = self.events.google.get(self.selected_date);
         let icloud_events = self.events.icloud.get(self.selected_date);
 
@@ -216,9 +1310,7 @@ impl App {
 
             match (google_next, icloud_next) {
                 (Some((g_idx, _)), Some((i_idx, _))) => {
-                    let g_time = &google_events[g_idx].time_str;
-                    let i_time = &icloud_events[i_idx].time_str;
-                    if g_time <= i_time {
+                    if google_events[g_idx].start_time <= icloud_events[i_idx].start_time {
                         self.selected_source = EventSource::Google;
                         self.selected_event_index = g_idx;
                     } else {
@@ -241,7 +1333,10 @@ impl App {
             }
         }
 
-        if !google_events.is_empty() {
+        // The merged list starts with the earliest event of either calendar
+        if self.unified_panel && self.view_mode == ViewMode::Month {
+            self.select_merged_day_end(false);
+        } else if !google_events.is_empty() {
             self.selected_source = EventSource::Google;
             self.selected_event_index = 0;
         } else {
@@ -251,12 +1346,74 @@ impl App {
     }
 
     pub fn exit_event_mode(&mut self) {
+        self.clear_day_filter();
         self.navigation_mode = NavigationMode::Day;
         self.selected_source = EventSource::Google;
         self.selected_event_index = 0;
     }
 
+    /// Pick up the selected event to move it: the calendar goes back to Day mode to choose
+    /// the target date, keeping the event's time of day
+    pub fn start_move(&mut self) {
+        let Some(event) = self.get_selected_event().cloned() else { return };
+        if !event.is_organizer {
+            self.set_status("Only events you organize can be moved");
+            return;
+        }
+        self.set_status(format!("Moving \"{}\": pick a day, Enter to move, Esc to cancel", event.title));
+        self.moving = Some(event);
+        self.exit_event_mode();
+    }
+
+    pub fn cancel_move(&mut self) {
+        self.moving = None;
+        self.set_status("Move cancelled");
+    }
+
+    /// The event being moved, rescheduled to the selected date
+    pub fn take_move(&mut self) -> Option<DisplayEvent> {
+        if self.moving.as_ref()?.date == self.selected_date {
+            self.set_status("Pick another day to move the event to");
+            return None;
+        }
+        let mut event = self.moving.take()?;
+        event.move_to(self.selected_date);
+        Some(event)
+    }
+
+    /// Start typing a filter narrowing the selected day's events by title or attendee
+    pub fn open_day_filter(&mut self) {
+        self.events.day_filter = Some((self.selected_date, String::new()));
+        self.day_filter_editing = true;
+    }
+
+    /// Apply the filter text as typed, selecting the first event still shown
+    pub fn set_day_filter(&mut self, text: &str) {
+        self.events.day_filter = Some((self.selected_date, text.to_lowercase()));
+        self.events.reconcile();
+        let google_empty = self.events.google.get(self.selected_date).is_empty();
+        self.selected_source = if google_empty { EventSource::ICloud } else { EventSource::Google };
+        self.selected_event_index = 0;
+    }
+
+    pub fn clear_day_filter(&mut self) {
+        self.day_filter_editing = false;
+        if self.events.day_filter.take().is_some() {
+            self.events.reconcile();
+        }
+    }
+
     pub fn next_event(&mut self) {
+        if self.view_mode == ViewMode::Agenda {
+            self.move_in_agenda(1);
+            return;
+        }
+        if self.unified_panel && self.view_mode == ViewMode::Month {
+            if !self.move_in_merged_day(1) && self.navigate_to_next_day_with_events() {
+                self.select_merged_day_end(false);
+            }
+            return;
+        }
         let current_events = self.get_current_source_events();
 
         if self.selected_event_index < current_events.len().saturating_sub(1) {
@@ -275,6 +1432,16 @@ impl App {
     }
 
     pub fn prev_event(&mut self) {
+        if self.view_mode == ViewMode::Agenda {
+            self.move_in_agenda(-1);
+            return;
+        }
+        if self.unified_panel && self.view_mode == ViewMode::Month {
+            if !self.move_in_merged_day(-1) && self.navigate_to_prev_day_with_events() {
+                self.select_merged_day_end(true);
+            }
+            return;
+        }
         if self.selected_event_index > 0 {
             self.selected_event_index -= 1;
         } else if self.selected_source == EventSource::ICloud {
@@ -290,7 +1457,29 @@ impl App {
         }
     }
 
-    fn navigate_to_next_day_with_events(&mut self) {
+    /// Step `offset` places through the selected day's events of both sources in time order,
+    /// as the unified panel lists them; false when that steps off the day
+    fn move_in_merged_day(&mut self, offset: isize) -> bool {
+        let day = self.events.agenda(self.selected_date, 1);
+        let position = day.iter().position(|e| e.source == self.selected_source && e.index == self.selected_event_index);
+        let Some(entry) = position.and_then(|p| p.checked_add_signed(offset)).and_then(|p| day.get(p)) else {
+            return false;
+        };
+        (self.selected_source, self.selected_event_index) = (entry.source, entry.index);
+        true
+    }
+
+    /// Select the selected day's first or last event in time order
+    fn select_merged_day_end(&mut self, last: bool) {
+        let day = self.events.agenda(self.selected_date, 1);
+        if let Some(entry) = if last { day.last() } else { day.first() } {
+            (self.selected_source, self.selected_event_index) = (entry.source, entry.index);
+        }
+    }
+
+    fn navigate_to_next_day_with_events(&mut self) -> bool {
+        // The in-day filter only applies to the day it was typed on
+        self.clear_day_filter();
         let mut check_date = self.selected_date + Duration::days(1);
         let limit = self.selected_date + Duration::days(90);
 
@@ -308,13 +1497,16 @@ impl App {
                     self.selected_source = EventSource::ICloud;
                     self.selected_event_index = 0;
                 }
-                return;
+                return true;
             }
             check_date += Duration::days(1);
         }
+        false
     }
 
-    fn navigate_to_prev_day_with_events(&mut self) {
+    fn navigate_to_prev_day_with_events(&mut self) -> bool {
+        // The in-day filter only applies to the day it was typed on
+        self.clear_day_filter();
         let mut check_date = self.selected_date - Duration::days(1);
         let limit = self.selected_date - Duration::days(90);
 
@@ -333,10 +1525,11 @@ impl App {
                     self.selected_source = EventSource::Google;
                     self.selected_event_index = google_events.len().saturating_sub(1);
                 }
-                return;
+                return true;
             }
             check_date -= Duration::days(1);
         }
+        false
     }
 
     pub fn next_month(&mut self) {
@@ -347,6 +1540,8 @@ impl App {
         };
         self.current_date = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
         self.selected_date = self.current_date;
+        self.fetch_months();
+        self.fetch_view_months();
     }
 
     pub fn prev_month(&mut self) {
@@ -357,14 +1552,22 @@ impl App {
         };
         self.current_date = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
         self.selected_date = self.current_date;
+        self.fetch_months();
+        self.fetch_view_months();
     }
 
     pub fn open_search(&mut self) {
         self.search = Some(SearchState {
             query: String::new(),
+            parsed: SearchQuery::default(),
             results: Vec::new(),
             selected_index: 0,
             scroll_offset: 0,
+            include_past: false,
+            edited_at: Instant::now(),
+            remote_query: None,
+            remote_pending: false,
+            remote_events: Vec::new(),
         });
     }
 
@@ -372,22 +1575,255 @@ impl App {
         self.search = None;
     }
 
+    /// Open the debug screen on the given tab, or close it if already showing that tab
+    pub fn toggle_debug_screen(&mut self, tab: DebugTab) {
+        match self.debug_screen {
+            Some(ref debug) if debug.tab == tab => self.debug_screen = None,
+            Some(ref mut debug) => {
+                debug.tab = tab;
+                debug.scroll = 0;
+            }
+            None => self.debug_screen = Some(DebugScreen::new(tab)),
+        }
+    }
+
+    /// Start editing the selected event's tags, prefilled with the current ones
+    pub fn open_tag_editor(&mut self) {
+        if !self.marked.is_empty() {
+            // Tags typed for marked events are added to the tags they already have
+            self.tag_editor = Some(String::new());
+        } else if let Some(event) = self.get_selected_event() {
+            let current = tags::format_tags(self.tags.get(event));
+            self.tag_editor = Some(if current.is_empty() { current } else { current + " " });
+        }
+    }
+
+    /// Apply the tag editor input to the selected event and persist it
+    pub fn save_tag_editor(&mut self) {
+        let Some(input) = self.tag_editor.take() else { return };
+        if self.marked.is_empty() {
+            let Some(event) = self.get_selected_event().cloned() else { return };
+            self.tags.set(&event, tags::parse_tags(&input));
+        } else {
+            let added = tags::parse_tags(&input);
+            for event in std::mem::take(&mut self.marked) {
+                let mut tags = self.tags.get(&event).to_vec();
+                for tag in &added {
+                    if !tags.contains(tag) {
+                        tags.push(tag.clone());
+                    }
+                }
+                self.tags.set(&event, tags);
+            }
+            self.set_status(format!("Tagged marked events with {}", tags::format_tags(&added)));
+        }
+        if self.demo {
+            return;
+        }
+        if let Err(e) = self.tags.save() {
+            self.set_status(format!("Failed to save tags: {}", e));
+        }
+    }
+
+    /// The configured backends ready to fetch from and send changes to
+    pub fn providers(&self) -> Vec<Box<dyn CalendarProvider>> {
+        provider::registry(
+            &self.config,
+            &self.google_auth,
+            &self.icloud_auth,
+            self.outlook_tokens.as_ref(),
+            &self.google_accounts,
+            &self.provider_context,
+            self.demo,
+        )
+    }
+
+    /// Every connected calendar events can be created in, Google first
+    pub fn calendars(&self) -> Vec<CopyTarget> {
+        // Fetched events carry their calendar's name from the calendar list
+        let listed_name = |id: &str| {
+            self.events.google.all_events().find_map(|event| match &event.id {
+                EventId::Google { calendar_id, calendar_name, account: None, .. } if calendar_id == id => calendar_name.clone(),
+                _ => None,
+            })
+        };
+        calendar_targets(&self.config, &self.google_auth, &self.icloud_auth, listed_name)
+    }
+
+    /// Calendars the selected event can be copied to: every connected calendar but its own
+    pub fn copy_targets(&self) -> Vec<CopyTarget> {
+        let Some(event) = self.get_selected_event() else { return vec![] };
+        self.calendars().into_iter()
+            .filter(|target| match (target, &event.id) {
+                (CopyTarget::Google { calendar_id, .. }, EventId::Google { calendar_id: own, account: None, .. }) => calendar_id != own,
+                (CopyTarget::ICloud { calendar_url, .. }, EventId::ICloud { calendar_url: own, .. }) => calendar_url != own,
+                _ => true,
+            })
+            .collect()
+    }
+
+    /// Parse the clipboard (an ICS invite or a line like "Thu 15:00 Design review") and
+    /// prefill the command line with an editable `:new` command for it
+    pub fn paste_event(&mut self) {
+        let text = match utils::read_clipboard() {
+            Ok(text) => text,
+            Err(e) => {
+                self.set_status(format!("Failed to read clipboard: {}", e));
+                return;
+            }
+        };
+        match quick_add::parse_pasted(&text, Local::now().date_naive(), &self.meeting_patterns) {
+            Ok(event) => {
+                self.command_line = Some(format!("new {}", quick_add::to_line(&event)));
+                self.new_event_draft = Some(event);
+                self.set_status("Edit and press Enter to create");
+            }
+            Err(e) => self.set_status(e),
+        }
+    }
+
+    /// Agenda of cached events carrying the tag, or an overview of all tags when none is given
+    pub fn tag_agenda(&self, tag: &str) -> InfoModal {
+        let tag = tag.trim().trim_start_matches('#');
+        if tag.is_empty() {
+            let lines = self.tags.counts().into_iter()
+                .map(|(tag, count)| format!("#{:<20} {}", tag, count))
+                .collect::<Vec<_>>();
+            let lines = if lines.is_empty() { vec!["No tags yet (press # on an event)".to_string()] } else { lines };
+            return InfoModal::new("Tags", lines);
+        }
+
+        let mut events: Vec<&DisplayEvent> = self.events.google.all_events()
+            .chain(self.events.icloud.all_events())
+            .filter(|e| self.tags.has_tag(e, tag))
+            .collect();
+        events.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.start_time.cmp(&b.start_time)));
+
+        let lines = if events.is_empty() {
+            vec![format!("No loaded events tagged #{}", tag)]
+        } else {
+            events.iter()
+                .map(|e| format!("{}  {:<7} {}", e.date.format("%a %Y-%m-%d"), e.time_label(&self.config.time_format), e.title))
+                .collect()
+        };
+        InfoModal::new(format!("#{}", tag.to_lowercase()), lines)
+    }
+
+    /// `:shared`: other people's calendars the Google account can read, and how to add them
+    pub fn shared_calendars(&self, calendars: &[CalendarListEntry]) -> InfoModal {
+        let added: Vec<String> = self.config.providers().into_iter()
+            .filter_map(|p| match p {
+                ProviderConfig::GoogleShared { calendar_id, .. } => Some(calendar_id),
+                _ => None,
+            })
+            .collect();
+        let shared: Vec<&CalendarListEntry> = calendars.iter().filter(|c| c.is_shared_read_only()).collect();
+        if shared.is_empty() {
+            return InfoModal::new("Shared calendars", vec!["No calendars are shared with this account".to_string()]);
+        }
+        let mut lines: Vec<String> = shared.iter()
+            .map(|c| {
+                let mark = if added.contains(&c.id) { "\u{2713}" } else { " " };
+                format!("{} {} ({}) {}", mark, c.name(), c.access_role, c.id)
+            })
+            .collect();
+        lines.push(String::new());
+        lines.push("Add one to \"providers\" in config.json:".to_string());
+        lines.push(format!("{{\"type\": \"google_shared\", \"calendar_id\": \"{}\"}}", shared[0].id));
+        InfoModal::new("Shared calendars", lines)
+    }
+
+    /// `:calendars google`: the account's own calendars, which of them are shown, and how
+    /// to pick them
+    pub fn google_calendars(&self, calendars: &[CalendarListEntry]) -> InfoModal {
+        let shown = self.config.google.as_ref()
+            .map(|c| c.shown_calendar_ids())
+            .unwrap_or_else(|| vec!["primary".to_string()]);
+        let own: Vec<&CalendarListEntry> = calendars.iter().filter(|c| !c.is_shared_read_only()).collect();
+        let mut lines: Vec<String> = own.iter()
+            .map(|c| {
+                let mark = if shown.iter().any(|id| c.has_id(id)) { "\u{2713}" } else { " " };
+                format!("{} {} {}", mark, c.name(), c.id)
+            })
+            .collect();
+        lines.push(String::new());
+        lines.push("Pick them with \"calendar_ids\" in the \"google\" section of config.json:".to_string());
+        let ids: Vec<String> = own.iter().map(|c| format!("\"{}\"", c.id)).collect();
+        lines.push(format!("\"calendar_ids\": [{}]", ids.join(", ")));
+        InfoModal::new("Google calendars", lines)
+    }
+
+    /// `:person <name or email>`: how often and how long you've met someone across the
+    /// loaded events, and the meetings still ahead with them
+    pub fn person_stats(&self, query: &str, today: NaiveDate) -> InfoModal {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return InfoModal::new("Person", vec!["Type :person <name or email>".to_string()]);
+        }
+
+        let events: Vec<&DisplayEvent> = self.events.google.all_events().chain(self.events.icloud.all_events()).collect();
+        let mut people: Vec<&DisplayAttendee> = Vec::new();
+        for attendee in events.iter().flat_map(|e| &e.attendees) {
+            if !attendee.is_self
+                && attendee_matches(attendee, &query)
+                && !people.iter().any(|p| p.email.eq_ignore_ascii_case(&attendee.email))
+            {
+                people.push(attendee);
+            }
+        }
+        let person = match people.as_slice() {
+            [] => return InfoModal::new("Person", vec![format!("No loaded meetings with \"{}\"", query)]),
+            [person] => *person,
+            _ => {
+                let mut lines = vec![format!("\"{}\" matches several people:", query)];
+                lines.extend(people.iter().map(|p| format!("  {} <{}>", p.name.as_deref().unwrap_or(&p.email), p.email)));
+                return InfoModal::new("Person", lines);
+            }
+        };
+
+        let mut together: Vec<&DisplayEvent> = events.into_iter()
+            .filter(|e| e.attendees.iter().any(|a| a.email.eq_ignore_ascii_case(&person.email)))
+            .collect();
+        together.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.start_time.cmp(&b.start_time)));
+        let (past, upcoming): (Vec<&DisplayEvent>, Vec<&DisplayEvent>) = together.into_iter().partition(|e| e.date < today);
+        let minutes: i64 = past.iter().filter_map(|e| e.duration_minutes()).sum();
+
+        let mut lines = vec![
+            person.email.clone(),
+            String::new(),
+            format!("Met {} time(s), {} in total", past.len(), utils::format_duration(minutes)),
+        ];
+        if let (Some(first), Some(last)) = (past.first(), past.last()) {
+            lines.push(format!("  first {}, last {}", first.date.format("%Y-%m-%d"), last.date.format("%Y-%m-%d")));
+            lines.push(format!("  {} on average", utils::format_duration(minutes / past.len() as i64)));
+        }
+        lines.push(String::new());
+        lines.push(format!("Upcoming ({})", upcoming.len()));
+        lines.extend(upcoming.iter().map(|e| {
+            format!("  {}  {:<7} {}", e.date.format("%a %Y-%m-%d"), e.time_label(&self.config.time_format), e.title)
+        }));
+        InfoModal::new(person.name.as_deref().unwrap_or(&person.email), lines)
+    }
+
     pub fn update_search_results(&mut self) {
         let search = match self.search.as_ref() {
             Some(s) => s,
             None => return,
         };
 
-        let query_lower = search.query.to_lowercase();
+        let parsed = SearchQuery::parse(&search.query);
         let mut results: Vec<SearchResult> = Vec::new();
         let today = Local::now().date_naive();
+        // Dates given in the query replace the default of upcoming events only
+        let include_past = search.include_past || parsed.has_date_range();
+        let in_range = |event: &DisplayEvent, source| (include_past || event.date >= today) && parsed.filters_match(event, source);
 
-        if !query_lower.is_empty() {
+        if !parsed.is_empty() {
             let matched_events = self.events.google.all_events().map(|e| (e, EventSource::Google))
                 .chain(self.events.icloud.all_events().map(|e| (e, EventSource::ICloud)));
             for (event, source) in matched_events {
-                if event.date >= today
-                    && let Some(match_type) = event_match_type(event, &query_lower)
+                if in_range(event, source)
+                    && let Some(match_type) = event_match_type(event, &parsed.text)
                 {
                     results.push(SearchResult {
                         event: event.clone(),
@@ -396,16 +1832,35 @@ impl App {
                     });
                 }
             }
+
+            // Server matches the cache doesn't hold; those for the query as typed may have
+            // matched on a description or location
+            let cached: HashSet<(EventId, NaiveDate)> = results.iter().map(|r| (r.event.id.clone(), r.event.date)).collect();
+            let searched = search.remote_query.as_deref() == Some(parsed.text.as_str());
+            for (event, source) in &search.remote_events {
+                if in_range(event, *source)
+                    && !cached.contains(&(event.id.clone(), event.date))
+                    && let Some(match_type) = event_match_type(event, &parsed.text).or(searched.then_some(MatchType::Title))
+                {
+                    results.push(SearchResult {
+                        event: event.clone(),
+                        source: *source,
+                        match_type,
+                    });
+                }
+            }
+
             results.sort_by(|a, b| {
-                let a_title = a.event.title.to_lowercase().contains(&query_lower);
-                let b_title = b.event.title.to_lowercase().contains(&query_lower);
+                let a_title = a.match_type == MatchType::Title;
+                let b_title = b.match_type == MatchType::Title;
                 b_title.cmp(&a_title)
                     .then_with(|| a.event.date.cmp(&b.event.date))
-                    .then_with(|| a.event.time_str.cmp(&b.event.time_str))
+                    .then_with(|| a.event.start_time.cmp(&b.event.start_time))
             });
         }
 
         if let Some(ref mut search) = self.search {
+            search.parsed = parsed;
             search.results = results;
             if search.selected_index >= search.results.len() {
                 search.selected_index = search.results.len().saturating_sub(1);
@@ -417,6 +1872,37 @@ impl App {
         }
     }
 
+    /// The query and date range to ask the servers about once typing has paused on a
+    /// search the cache barely answers; marks the query as asked
+    pub fn take_remote_search(&mut self) -> Option<(String, NaiveDate, NaiveDate)> {
+        let search = self.search.as_mut()?;
+        // Filters are applied to what the servers return
+        let query = search.parsed.text.as_str();
+        if query.chars().count() < 3
+            || search.results.len() >= REMOTE_SEARCH_THRESHOLD
+            || search.edited_at.elapsed() < REMOTE_SEARCH_DELAY
+            || search.remote_query.as_deref() == Some(query)
+        {
+            return None;
+        }
+        search.remote_query = Some(query.to_string());
+        search.remote_pending = true;
+        let today = Local::now().date_naive();
+        Some((query.to_string(), today - Duration::days(REMOTE_SEARCH_DAYS_BACK), today + Duration::days(REMOTE_SEARCH_DAYS_AHEAD)))
+    }
+
+    /// Add what the servers matched for `query` to the results, unless the search has
+    /// since moved on to another query
+    pub fn apply_remote_search(&mut self, query: &str, events: Vec<(DisplayEvent, EventSource)>) {
+        let Some(search) = self.search.as_mut() else { return };
+        if search.remote_query.as_deref() != Some(query) {
+            return;
+        }
+        search.remote_pending = false;
+        search.remote_events = events;
+        self.update_search_results();
+    }
+
     pub fn select_search_result(&mut self) {
         let (date, source, event_title) = match self.search.as_ref() {
             Some(s) => {
@@ -434,8 +1920,7 @@ impl App {
         self.selected_date = date;
         if month_changed {
             self.current_date = date.with_day(1).unwrap();
-            self.google_needs_fetch = true;
-            self.icloud_needs_fetch = true;
+            self.fetch_months();
         }
 
         // Enter event mode on the correct source/index
@@ -454,73 +1939,63 @@ impl App {
     }
 }
 
-/// Check if an event matches the search query (case-insensitive)
-#[cfg(test)]
-fn event_matches_query(event: &DisplayEvent, query_lower: &str) -> bool {
-    event_match_type(event, query_lower).is_some()
-}
-
-/// Determine how an event matches the search query, returning the match type.
-/// Title matches take priority over participant matches.
-pub fn event_match_type(event: &DisplayEvent, query_lower: &str) -> Option<MatchType> {
-    if event.title.to_lowercase().contains(query_lower) {
-        return Some(MatchType::Title);
+/// Everything known about an event, one fact per line, for the details modal
+fn event_detail_lines(event: &DisplayEvent, tags: &[String], format: &TimeFormatConfig) -> Vec<String> {
+    let mut when = format!("{} {}", event.date.format("%a %Y-%m-%d"), event.time_range_label(format));
+    if let Some(end) = event.end_date {
+        when.push_str(&format!(" until {}", end.format("%a %Y-%m-%d")));
     }
-    for attendee in &event.attendees {
-        if let Some(ref name) = attendee.name
-            && name.to_lowercase().contains(query_lower)
-        {
-            return Some(MatchType::Participant);
-        }
-        if attendee.email.to_lowercase().contains(query_lower) {
-            return Some(MatchType::Participant);
+    let mut lines = vec![when, format!("Calendar: {}", event.calendar_label())];
+    if let Some(location) = &event.location {
+        lines.push(format!("Location: {}", location));
+    }
+    if !event.rooms.is_empty() {
+        lines.push(format!("Room: {}", event.rooms.join(", ")));
+    }
+    if let Some(url) = &event.meeting_url {
+        lines.push(format!("Link: {}", url));
+    }
+    if let Some(tel) = &event.dial_in {
+        lines.push(format!("Dial-in: {}", tel.trim_start_matches("tel:")));
+    }
+    if !tags.is_empty() {
+        lines.push(format!("Tags: {}", tags::format_tags(tags)));
+    }
+    if !event.attendees.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("Attendees ({})", event.attendees.len()));
+        for attendee in &event.attendees {
+            match &attendee.name {
+                Some(name) => lines.push(format!("  {} {} <{}>", attendee.status.icon(), name, attendee.email)),
+                None => lines.push(format!("  {} {}", attendee.status.icon(), attendee.email)),
+            }
         }
     }
-    None
+    if let Some(description) = &event.description {
+        lines.push(String::new());
+        lines.extend(description.lines().map(String::from));
+    }
+    lines
 }
 
 /// Find current or next event in a list, returns (index, is_current)
-fn find_current_or_next_event(events: &[DisplayEvent], current_time: NaiveTime) -> Option<(usize, bool)> {
+fn find_current_or_next_event(events: &[Arc<DisplayEvent>], current_time: NaiveTime) -> Option<(usize, bool)> {
     let mut best_current: Option<(usize, NaiveTime)> = None;
     let mut first_next: Option<usize> = None;
 
     for (i, event) in events.iter().enumerate() {
-        if event.time_str == "All day" {
-            continue;
-        }
+        let Some(event_time) = event.start_time.filter(|_| !event.in_all_day_strip()) else { continue };
 
-        let parts: Vec<&str> = event.time_str.split(':').collect();
-        if parts.len() != 2 {
-            continue;
-        }
-        let hour: u32 = match parts[0].parse() {
-            Ok(h) => h,
-            Err(_) => continue,
-        };
-        let minute: u32 = match parts[1].parse() {
-            Ok(m) => m,
-            Err(_) => continue,
-        };
-        let event_time = match NaiveTime::from_hms_opt(hour, minute, 0) {
-            Some(t) => t,
-            None => continue,
-        };
-
-        if let Some(ref end_str) = event.end_time_str {
-            let end_parts: Vec<&str> = end_str.split(':').collect();
-            if end_parts.len() == 2
-                && let (Ok(eh), Ok(em)) = (end_parts[0].parse::<u32>(), end_parts[1].parse::<u32>())
-                && let Some(end_time) = NaiveTime::from_hms_opt(eh, em, 0)
-                && event_time <= current_time
-                && current_time < end_time
-            {
-                match best_current {
-                    None => best_current = Some((i, event_time)),
-                    Some((_, best_time)) if event_time > best_time => {
-                        best_current = Some((i, event_time));
-                    }
-                    _ => {}
+        if let Some(end_time) = event.end_time
+            && event_time <= current_time
+            && current_time < end_time
+        {
+            match best_current {
+                None => best_current = Some((i, event_time)),
+                Some((_, best_time)) if event_time > best_time => {
+                    best_current = Some((i, event_time));
                 }
+                _ => {}
             }
         }
 
@@ -536,135 +2011,156 @@ fn find_current_or_next_event(events: &[DisplayEvent], current_time: NaiveTime)
     }
 }
 
+/// First accepted event with a meeting link that starts within the next minute
+fn meeting_due<'a>(events: impl IntoIterator<Item = &'a DisplayEvent>, now: NaiveTime) -> Option<&'a DisplayEvent> {
+    events.into_iter().find(|e| {
+        e.accepted
+            && e.meeting_url.is_some()
+            && e.start_time.is_some_and(|start| start >= now && start - now <= Duration::minutes(1))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cache::{DisplayAttendee, AttendeeStatus, EventId};
-
-    fn make_event_with_attendees(title: &str, attendees: Vec<DisplayAttendee>) -> DisplayEvent {
-        DisplayEvent {
-            id: EventId::Google { calendar_id: "test".to_string(), event_id: "test-id".to_string(), calendar_name: None },
-            title: title.to_string(),
-            time_str: "10:00".to_string(),
-            end_time_str: None,
-            date: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
-            accepted: true,
-            is_organizer: false,
-            is_free: false,
-            meeting_url: None,
-            description: None,
-            location: None,
-            attendees,
-        }
-    }
-
-    #[test]
-    fn test_event_matches_query_title() {
-        let event = make_event_with_attendees("Sprint Planning", vec![]);
-        assert!(event_matches_query(&event, "sprint"));
-        assert!(event_matches_query(&event, "planning"));
-    }
+    use crate::cache::EventId;
 
     #[test]
-    fn test_event_matches_query_attendee_name() {
-        let event = make_event_with_attendees("Meeting", vec![
-            DisplayAttendee {
-                name: Some("Alice Johnson".to_string()),
-                email: "alice@example.com".to_string(),
-                status: AttendeeStatus::Accepted,
-            },
-        ]);
-        assert!(event_matches_query(&event, "alice"));
-        assert!(event_matches_query(&event, "johnson"));
+    fn test_status_queue_shows_messages_sequentially() {
+        let start = Instant::now();
+        let mut queue = StatusQueue::default();
+        queue.push("Connected to Google Calendar!".to_string(), start);
+        queue.push("Connected to 2 iCloud calendar(s)!".to_string(), start);
+        assert_eq!(queue.current(), Some("Connected to Google Calendar!"));
+
+        // First message still within its display time
+        queue.advance(start + std::time::Duration::from_secs(1));
+        assert_eq!(queue.current(), Some("Connected to Google Calendar!"));
+
+        // Second message gets its own full display time
+        let second_shown = start + std::time::Duration::from_secs(4);
+        queue.advance(second_shown);
+        assert_eq!(queue.current(), Some("Connected to 2 iCloud calendar(s)!"));
+        queue.advance(second_shown + std::time::Duration::from_secs(2));
+        assert_eq!(queue.current(), Some("Connected to 2 iCloud calendar(s)!"));
+        queue.advance(second_shown + std::time::Duration::from_secs(4));
+        assert_eq!(queue.current(), None);
     }
 
     #[test]
-    fn test_event_matches_query_attendee_email() {
-        let event = make_event_with_attendees("Meeting", vec![
-            DisplayAttendee {
-                name: None,
-                email: "bob@company.org".to_string(),
-                status: AttendeeStatus::Accepted,
-            },
-        ]);
-        assert!(event_matches_query(&event, "bob@company"));
-        assert!(event_matches_query(&event, "company.org"));
+    fn test_status_queue_skips_repeats_and_caps_length() {
+        let now = Instant::now();
+        let mut queue = StatusQueue::default();
+        queue.push("Refreshing...".to_string(), now);
+        queue.push("Refreshing...".to_string(), now);
+        assert_eq!(queue.messages.len(), 1);
+
+        for i in 0..10 {
+            queue.push(format!("msg {}", i), now);
+        }
+        assert_eq!(queue.messages.len(), STATUS_QUEUE_LIMIT);
+        assert_eq!(queue.current(), Some("Refreshing..."));
+        assert_eq!(queue.messages.back().map(String::as_str), Some("msg 9"));
     }
 
-    #[test]
-    fn test_event_matches_query_case_insensitive() {
-        let event = make_event_with_attendees("Team Standup", vec![
-            DisplayAttendee {
-                name: Some("Charlie Brown".to_string()),
-                email: "Charlie@Example.COM".to_string(),
-                status: AttendeeStatus::Accepted,
-            },
-        ]);
-        assert!(event_matches_query(&event, "team standup"));
-        assert!(event_matches_query(&event, "charlie brown"));
-        assert!(event_matches_query(&event, "charlie@example.com"));
+    fn make_event_with_attendees(title: &str, attendees: Vec<DisplayAttendee>) -> DisplayEvent {
+        DisplayEvent {
+            id: EventId::Google { calendar_id: "test".to_string(), event_id: "test-id".to_string(), calendar_name: None, account: None },
+            title: title.to_string(),
+            start_time: NaiveTime::from_hms_opt(10, 0, 0),
+            date: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+            attendees,
+            ..Default::default()
+        }
     }
 
     #[test]
-    fn test_event_match_type_title() {
-        let event = make_event_with_attendees("Sprint Planning", vec![
-            DisplayAttendee {
-                name: Some("Alice".to_string()),
-                email: "alice@example.com".to_string(),
-                status: AttendeeStatus::Accepted,
-            },
-        ]);
-        assert_eq!(event_match_type(&event, "sprint"), Some(MatchType::Title));
+    fn test_busy_overlay_ranges_and_slots() {
+        let date = |d: u32| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let mut overlay = BusyOverlay::new("boss@example.com");
+        // A week spanning two months asks for both
+        let april = NaiveDate::from_ymd_opt(2026, 4, 30).unwrap();
+        assert_eq!(overlay.missing_range(date(30)), Some((date(1), april)));
+        overlay.range = overlay.missing_range(date(30));
+        assert_eq!(overlay.missing_range(date(2)), None);
+
+        let at = |d: u32, h: u32| date(d).and_hms_opt(h, 0, 0).unwrap().and_local_timezone(Local).unwrap().to_utc();
+        overlay.set_busy(&[BusyPeriod { start: at(2, 10), end: at(2, 11) }]);
+        assert!(overlay.is_busy(date(2), 10 * 60 + 30, 11 * 60));
+        assert!(!overlay.is_busy(date(2), 11 * 60, 11 * 60 + 30));
+        assert!(!overlay.is_busy(date(3), 10 * 60, 10 * 60 + 30));
     }
 
     #[test]
-    fn test_event_match_type_participant() {
-        let event = make_event_with_attendees("Sprint Planning", vec![
-            DisplayAttendee {
-                name: Some("Alice Johnson".to_string()),
-                email: "alice@example.com".to_string(),
-                status: AttendeeStatus::Accepted,
-            },
-        ]);
-        assert_eq!(event_match_type(&event, "alice"), Some(MatchType::Participant));
+    fn test_calendar_picker_toggles_under_cursor() {
+        let entry = |name: &str| CalendarEntry {
+            url: format!("https://caldav.icloud.com/{}/", name),
+            name: Some(name.to_string()),
+            color: None,
+            hidden: false,
+        };
+        let mut picker = CalendarPicker::new(vec![entry("Home"), entry("Work")]);
+        picker.move_down();
+        picker.move_down();
+        picker.toggle();
+        assert!(!picker.calendars[0].hidden);
+        assert!(picker.calendars[1].hidden);
+        assert_eq!(picker.to_modal().lines, vec!["  [x] Home", "> [ ] Work"]);
     }
 
     #[test]
-    fn test_event_match_type_title_takes_priority() {
-        // "Alice" appears in both title and attendees — title wins
-        let event = make_event_with_attendees("Meeting with Alice", vec![
-            DisplayAttendee {
-                name: Some("Alice Johnson".to_string()),
-                email: "alice@example.com".to_string(),
-                status: AttendeeStatus::Accepted,
-            },
-        ]);
-        assert_eq!(event_match_type(&event, "alice"), Some(MatchType::Title));
+    fn test_pending_action_repeatable() {
+        let google = EventId::Google { calendar_id: "c".to_string(), event_id: "e".to_string(), calendar_name: None, account: None };
+        let icloud = EventId::ICloud { calendar_url: "u".to_string(), event_uid: "e".to_string(), etag: None, calendar_name: None, occurrence: None };
+        let accept = PendingAction::Accept { id: google };
+        let delete = PendingAction::Delete { id: icloud.clone() };
+        let join = PendingAction::JoinMeeting { title: "Standup".to_string(), url: "https://meet.google.com/abc".to_string() };
+        assert_eq!(accept.repeatable(), Some(RepeatableAction::Accept));
+        assert_eq!(delete.repeatable(), Some(RepeatableAction::Delete));
+        assert_eq!(join.repeatable(), None);
+        let decline = PendingAction::Decline { id: icloud };
+        assert_eq!(decline.repeatable(), Some(RepeatableAction::Decline));
+        let accept_and_join = PendingAction::AcceptAndJoin {
+            accept: Box::new(accept),
+            url: "https://meet.google.com/abc".to_string(),
+        };
+        assert_eq!(accept_and_join.repeatable(), Some(RepeatableAction::Accept));
     }
 
     #[test]
-    fn test_event_match_type_no_match() {
-        let event = make_event_with_attendees("Sprint Planning", vec![
-            DisplayAttendee {
-                name: Some("Alice".to_string()),
-                email: "alice@example.com".to_string(),
-                status: AttendeeStatus::Accepted,
-            },
-        ]);
-        assert_eq!(event_match_type(&event, "bob"), None);
+    fn test_google_target_name() {
+        let tokens = TokenInfo {
+            access_token: "token".to_string(),
+            refresh_token: None,
+            expires_at: chrono::Utc::now() + Duration::hours(1),
+            token_type: "Bearer".to_string(),
+        };
+        let google = GoogleAuthState::Authenticated(tokens);
+        let mut config: Config = serde_json::from_str(r#"{"google": {"client_id": "id", "client_secret": "secret"}}"#).unwrap();
+        let names = |config: &Config, listed: Option<&str>| -> Vec<String> {
+            calendar_targets(config, &google, &ICloudAuthState::NotConfigured, |_| listed.map(String::from))
+                .iter()
+                .map(|target| target.name().to_string())
+                .collect()
+        };
+        assert_eq!(names(&config, None), vec!["primary"]);
+        assert_eq!(names(&config, Some("jane@example.com")), vec!["jane@example.com"]);
+        config.google.as_mut().unwrap().name = Some("Work".to_string());
+        assert_eq!(names(&config, Some("jane@example.com")), vec!["Work"]);
     }
 
     #[test]
-    fn test_event_matches_query_no_match() {
-        let event = make_event_with_attendees("Sprint Planning", vec![
-            DisplayAttendee {
-                name: Some("Alice".to_string()),
-                email: "alice@example.com".to_string(),
-                status: AttendeeStatus::Accepted,
-            },
-        ]);
-        assert!(!event_matches_query(&event, "retro"));
-        assert!(!event_matches_query(&event, "bob"));
-        assert!(!event_matches_query(&event, "xyz"));
+    fn test_meeting_due_within_a_minute() {
+        let mut standup = make_event_with_attendees("Standup", vec![]);
+        standup.meeting_url = Some("https://meet.google.com/abc".to_string());
+        let mut declined = standup.clone();
+        declined.accepted = false;
+        let no_link = make_event_with_attendees("Focus", vec![]);
+        let at = |h, m, s| NaiveTime::from_hms_opt(h, m, s).unwrap();
+
+        assert_eq!(meeting_due([&no_link, &standup], at(9, 59, 0)).map(|e| e.title.as_str()), Some("Standup"));
+        assert!(meeting_due([&standup], at(9, 58, 59)).is_none());
+        assert!(meeting_due([&standup], at(10, 0, 1)).is_none());
+        assert!(meeting_due([&declined], at(9, 59, 30)).is_none());
     }
 }
@@ -0,0 +1,131 @@
+//! Command-line argument parsing
+
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(name = "calendarchy", version, about = "Google and iCloud calendars in the terminal")]
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+    /// Show generated demo events instead of real calendars
+    #[arg(long)]
+    demo: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// What to do on startup
+#[derive(Debug, PartialEq, Subcommand)]
+pub enum Command {
+    /// Start the interactive calendar, optionally with generated demo events
+    #[command(skip)]
+    Run { demo: bool },
+    /// Check config, tokens, connectivity, cache and terminal, then exit
+    Doctor,
+    /// Open the link of the current or next meeting, then exit
+    Join,
+    /// Print a summary of today's agenda, free time and pending invitations
+    Brief,
+    /// Print cached events as CSV (dates as YYYY-MM-DD; defaults to this month)
+    Export {
+        #[arg(value_name = "DATE", num_args = 0..=2)]
+        dates: Vec<String>,
+    },
+    /// Print the current or next accepted event, e.g. for a tmux or waybar status line
+    Next {
+        /// Template for an upcoming event; placeholders: {title}, {rel}, {time}, {end}
+        #[arg(long, default_value = "{time} {title} (in {rel})")]
+        format: String,
+        /// Template for the event in progress (same placeholders)
+        #[arg(long, default_value = "{time} {title} (now)")]
+        now_format: String,
+    },
+    /// Create every event of an .ics file in a signed-in calendar, reporting each
+    Import {
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+        /// Calendar to create the events in, by name (defaults to the first signed-in one)
+        #[arg(long)]
+        calendar: Option<String>,
+    },
+    /// Print the next days' events of both panels in time order
+    Agenda {
+        /// Days to list, starting today (defaults to agenda_days from the config, or 14)
+        #[arg(long)]
+        days: Option<u32>,
+        /// Print a JSON array instead of text
+        #[arg(long)]
+        json: bool,
+        /// Fetch the days from the calendars into the cache first
+        #[arg(long)]
+        refresh: bool,
+    },
+}
+
+/// Parse command-line arguments (without the program name); help and version requests
+/// come back as errors that print them on `exit()`
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, clap::Error> {
+    let cli = Cli::try_parse_from(std::iter::once("calendarchy".to_string()).chain(args))?;
+    Ok(cli.command.unwrap_or(Command::Run { demo: cli.demo }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::error::ErrorKind;
+
+    fn parse(args: &[&str]) -> Result<Command, ErrorKind> {
+        parse_args(args.iter().map(|s| s.to_string())).map_err(|e| e.kind())
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse(&[]), Ok(Command::Run { demo: false }));
+        assert_eq!(parse(&["--demo"]), Ok(Command::Run { demo: true }));
+        assert!(parse(&["--demo", "doctor"]).is_err());
+        assert_eq!(parse(&["doctor"]), Ok(Command::Doctor));
+        assert_eq!(parse(&["--help"]), Err(ErrorKind::DisplayHelp));
+        assert_eq!(parse(&["-V"]), Err(ErrorKind::DisplayVersion));
+        assert!(parse(&["--bogus"]).is_err());
+        assert!(parse(&["doctor", "doctor"]).is_err());
+        assert_eq!(parse(&["join"]), Ok(Command::Join));
+        assert!(parse(&["join", "--demo"]).is_err());
+        assert_eq!(parse(&["brief"]), Ok(Command::Brief));
+        assert_eq!(parse(&["export"]), Ok(Command::Export { dates: vec![] }));
+        assert_eq!(
+            parse(&["export", "2026-02-01", "2026-02-28"]),
+            Ok(Command::Export { dates: vec!["2026-02-01".to_string(), "2026-02-28".to_string()] })
+        );
+        assert!(parse(&["export", "--demo"]).is_err());
+        assert!(parse(&["export", "2026-02-01", "2026-02-02", "2026-02-03"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_next_args() {
+        assert_eq!(
+            parse(&["next"]),
+            Ok(Command::Next { format: "{time} {title} (in {rel})".to_string(), now_format: "{time} {title} (now)".to_string() })
+        );
+        assert!(matches!(parse(&["next", "--format", "{title}"]), Ok(Command::Next { format, .. }) if format == "{title}"));
+    }
+
+    #[test]
+    fn test_parse_agenda_args() {
+        assert_eq!(parse(&["agenda"]), Ok(Command::Agenda { days: None, json: false, refresh: false }));
+        assert_eq!(
+            parse(&["agenda", "--days", "7", "--json", "--refresh"]),
+            Ok(Command::Agenda { days: Some(7), json: true, refresh: true })
+        );
+        assert!(parse(&["agenda", "--days", "soon"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_import_args() {
+        assert_eq!(
+            parse(&["import", "offsite.ics", "--calendar", "work"]),
+            Ok(Command::Import { file: PathBuf::from("offsite.ics"), calendar: Some("work".to_string()) })
+        );
+        assert_eq!(parse(&["import", "offsite.ics"]), Ok(Command::Import { file: PathBuf::from("offsite.ics"), calendar: None }));
+        assert!(parse(&["import"]).is_err());
+    }
+}
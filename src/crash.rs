@@ -0,0 +1,119 @@
+//! Panic handling: restore the terminal and write a crash report for bug reports
+
+use crate::logging::HttpLog;
+use crossterm::{cursor, event::DisableMouseCapture, execute, terminal::{disable_raw_mode, LeaveAlternateScreen}};
+use std::backtrace::Backtrace;
+use std::fs;
+use std::io::stdout;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Recent user actions, included in crash reports
+static RECENT_ACTIONS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+const MAX_ACTIONS: usize = 30;
+const MAX_REPORT_LOGS: usize = 50;
+
+/// Remember a user action for crash reports
+pub fn record_action(action: impl Into<String>) {
+    if let Ok(mut actions) = RECENT_ACTIONS.lock() {
+        let timestamp = chrono::Local::now().format("%H:%M:%S");
+        actions.push(format!("[{}] {}", timestamp, action.into()));
+        if actions.len() > MAX_ACTIONS {
+            actions.remove(0);
+        }
+    }
+}
+
+/// Install a panic hook that restores the terminal, writes a crash report
+/// to the cache dir and prints its path; the report includes the tail of `log`
+pub fn install_panic_hook(log: HttpLog) {
+    std::panic::set_hook(Box::new(move |info| {
+        // Restore the terminal first so the message is readable
+        let _ = disable_raw_mode();
+        let _ = execute!(stdout(), DisableMouseCapture, LeaveAlternateScreen, cursor::Show);
+
+        let report = build_report(info, &Backtrace::force_capture(), &log);
+        match write_report(&report) {
+            Ok(path) => {
+                eprintln!("calendarchy crashed: {}", panic_message(info));
+                eprintln!("A crash report was written to {}", path.display());
+                eprintln!("Please attach it when filing an issue.");
+            }
+            Err(_) => eprintln!("{}", report),
+        }
+    }));
+}
+
+fn panic_message(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    match info.location() {
+        Some(loc) => format!("{} at {}:{}", message, loc.file(), loc.line()),
+        None => message,
+    }
+}
+
+fn build_report(info: &PanicHookInfo, backtrace: &Backtrace, log: &HttpLog) -> String {
+    let actions = RECENT_ACTIONS.lock().map(|a| a.clone()).unwrap_or_default();
+    let logs: Vec<String> = log.entries()
+        .iter()
+        .take(MAX_REPORT_LOGS)
+        .rev()
+        .map(|e| e.to_line())
+        .collect();
+    format_report(&panic_message(info), &backtrace.to_string(), &actions, &logs)
+}
+
+fn format_report(message: &str, backtrace: &str, actions: &[String], logs: &[String]) -> String {
+    let mut report = format!(
+        "calendarchy {} crash report ({})\n\nPanic: {}\n\n== Backtrace ==\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+        message,
+        backtrace
+    );
+    report.push_str("\n== Recent actions ==\n");
+    for action in actions {
+        report.push_str(action);
+        report.push('\n');
+    }
+    report.push_str("\n== Recent HTTP log ==\n");
+    for line in logs {
+        report.push_str(line);
+        report.push('\n');
+    }
+    report
+}
+
+fn write_report(report: &str) -> std::io::Result<PathBuf> {
+    let dir = dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("calendarchy");
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("crash-{}.txt", chrono::Local::now().format("%Y%m%d-%H%M%S")));
+    fs::write(&path, report)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_report_sections() {
+        let actions = vec!["[10:00:00] key Char('j')".to_string()];
+        let logs = vec!["[10:00:01] GET https://www.googleapis.com/calendar/v3".to_string()];
+        let report = format_report("boom at src/ui.rs:1", "0: main", &actions, &logs);
+
+        assert!(report.contains("Panic: boom at src/ui.rs:1"));
+        assert!(report.contains("== Backtrace ==\n0: main"));
+        assert!(report.contains("== Recent actions ==\n[10:00:00] key Char('j')"));
+        assert!(report.contains("== Recent HTTP log ==\n[10:00:01] GET"));
+    }
+}
@@ -0,0 +1,236 @@
+//! Built-in diagnostics (`calendarchy doctor` and the `:doctor` command)
+
+use crate::cache::EventCache;
+use crate::config::{Config, StoredTokens};
+use crate::google::calendar::CALENDAR_API_BASE;
+use crate::icloud::CALDAV_SERVER;
+use std::io::IsTerminal;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// Result of a single diagnostic check
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { name, status, detail: detail.into() }
+    }
+
+    /// One-line summary, e.g. "[ok]   Config: 2 source(s) configured"
+    pub fn to_line(&self) -> String {
+        let tag = match self.status {
+            CheckStatus::Ok => "[ok]  ",
+            CheckStatus::Warn => "[warn]",
+            CheckStatus::Fail => "[FAIL]",
+        };
+        format!("{} {}: {}", tag, self.name, self.detail)
+    }
+}
+
+/// Run all checks. Connectivity checks use a short timeout.
+pub async fn run_checks() -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    let config = check_config(&mut checks);
+    let tokens = load_stored_tokens();
+    checks.push(check_google_tokens(config.as_ref(), &tokens));
+    checks.push(check_icloud_tokens(config.as_ref(), &tokens));
+    checks.push(check_connectivity("Google API", CALENDAR_API_BASE).await);
+    checks.push(check_connectivity("iCloud CalDAV", CALDAV_SERVER).await);
+    checks.push(check_cache());
+    checks.push(check_terminal());
+
+    checks
+}
+
+/// True if any check failed
+pub fn has_failures(checks: &[Check]) -> bool {
+    checks.iter().any(|c| c.status == CheckStatus::Fail)
+}
+
+fn check_config(checks: &mut Vec<Check>) -> Option<Config> {
+    let path = Config::config_path();
+    if !path.exists() {
+        checks.push(Check::new(
+            "Config",
+            CheckStatus::Fail,
+            format!("{} not found; create it with a \"google\" and/or \"icloud\" section", path.display()),
+        ));
+        return None;
+    }
+
+    match Config::load() {
+        Ok(config) => {
+            let sources = config.google.is_some() as usize + config.icloud.is_some() as usize;
+            let status = if sources == 0 { CheckStatus::Warn } else { CheckStatus::Ok };
+            checks.push(Check::new("Config", status, format!("{} source(s) configured in {}", sources, path.display())));
+            Some(config)
+        }
+        Err(e) => {
+            checks.push(Check::new("Config", CheckStatus::Fail, format!("{} is invalid: {}", path.display(), e)));
+            None
+        }
+    }
+}
+
+/// Read tokens.json without side effects (a corrupt file is reported, not moved)
+fn load_stored_tokens() -> Result<Option<StoredTokens>, String> {
+    let path = Config::token_path();
+    let Ok(content) = std::fs::read_to_string(&path) else { return Ok(None) };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("{} is unreadable: {}", path.display(), e))
+}
+
+fn check_google_tokens(config: Option<&Config>, tokens: &Result<Option<StoredTokens>, String>) -> Check {
+    if config.is_none_or(|c| c.google.is_none()) {
+        return Check::new("Google auth", CheckStatus::Warn, "not configured");
+    }
+    let tokens = match tokens {
+        Ok(tokens) => tokens.as_ref().and_then(|t| t.google.as_ref()),
+        Err(e) => return Check::new("Google auth", CheckStatus::Fail, e.clone()),
+    };
+    let Some(google) = tokens else {
+        return Check::new("Google auth", CheckStatus::Warn, "not signed in; press g in the app");
+    };
+
+    let token = &google.tokens;
+    if !token.is_expired() {
+        let expires = token.expires_at.with_timezone(&chrono::Local).format("%H:%M");
+        Check::new("Google auth", CheckStatus::Ok, format!("access token valid until {}", expires))
+    } else if token.refresh_token.is_some() {
+        Check::new("Google auth", CheckStatus::Ok, "access token expired; will refresh on start")
+    } else {
+        Check::new("Google auth", CheckStatus::Fail, "token expired without refresh token; press g to sign in again")
+    }
+}
+
+fn check_icloud_tokens(config: Option<&Config>, tokens: &Result<Option<StoredTokens>, String>) -> Check {
+    if config.is_none_or(|c| c.icloud.is_none()) {
+        return Check::new("iCloud auth", CheckStatus::Warn, "not configured");
+    }
+    let icloud = match tokens {
+        Ok(tokens) => tokens.as_ref().and_then(|t| t.icloud.as_ref()),
+        Err(e) => return Check::new("iCloud auth", CheckStatus::Fail, e.clone()),
+    };
+    let count = icloud.map(|i| i.calendars.len().max(i.calendar_urls.len())).unwrap_or(0);
+    if count == 0 {
+        Check::new("iCloud auth", CheckStatus::Warn, "no calendars discovered; press i in the app")
+    } else {
+        Check::new("iCloud auth", CheckStatus::Ok, format!("{} calendar(s) discovered", count))
+    }
+}
+
+async fn check_connectivity(name: &'static str, url: &str) -> Check {
+    let client = match reqwest::Client::builder().timeout(Duration::from_secs(5)).build() {
+        Ok(client) => client,
+        Err(e) => return Check::new(name, CheckStatus::Fail, e.to_string()),
+    };
+
+    let started = Instant::now();
+    match client.get(url).send().await {
+        // Any HTTP response (even 401/404) means the server is reachable
+        Ok(response) => Check::new(
+            name,
+            CheckStatus::Ok,
+            format!("reachable (HTTP {}, {}ms)", response.status().as_u16(), started.elapsed().as_millis()),
+        ),
+        Err(e) => Check::new(name, CheckStatus::Fail, format!("unreachable: {}; check network/proxy", e)),
+    }
+}
+
+fn check_cache() -> Check {
+    match EventCache::inspect_disk() {
+        Ok(None) => Check::new("Cache", CheckStatus::Ok, "no cache yet; created after the first fetch"),
+        Ok(Some(count)) => Check::new("Cache", CheckStatus::Ok, format!("{} cached event(s)", count)),
+        Err(e) => Check::new("Cache", CheckStatus::Warn, format!("{}; it will be moved aside on next start", e)),
+    }
+}
+
+fn check_terminal() -> Check {
+    if !std::io::stdout().is_terminal() {
+        return Check::new("Terminal", CheckStatus::Warn, "stdout is not a terminal");
+    }
+
+    let (width, height) = crossterm::terminal::size().unwrap_or((0, 0));
+    let truecolor = std::env::var("COLORTERM").is_ok_and(|v| v == "truecolor" || v == "24bit");
+    let mut detail = format!("{}x{}", width, height);
+    let mut status = CheckStatus::Ok;
+    if width < 80 || height < 24 {
+        detail.push_str(", smaller than 80x24; panels may be hidden");
+        status = CheckStatus::Warn;
+    }
+    if truecolor {
+        detail.push_str(", truecolor");
+    } else {
+        detail.push_str(", no truecolor (COLORTERM unset); availability colors may look off");
+        status = CheckStatus::Warn;
+    }
+    Check::new("Terminal", status, detail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{GoogleConfig, GoogleTokens};
+    use crate::google::TokenInfo;
+    use chrono::Utc;
+
+    fn google_config() -> Config {
+        Config {
+            google: Some(GoogleConfig {
+                client_id: "id".to_string(),
+                client_secret: "secret".to_string(),
+                calendar_id: "primary".to_string(),
+                calendar_ids: Vec::new(),
+                quick_add: false,
+                name: None,
+            }),
+            ..Config::default()
+        }
+    }
+
+    fn stored(expires_in_minutes: i64, refresh: bool) -> Result<Option<StoredTokens>, String> {
+        Ok(Some(StoredTokens {
+            google: Some(GoogleTokens {
+                tokens: TokenInfo {
+                    access_token: "token".to_string(),
+                    refresh_token: refresh.then(|| "refresh".to_string()),
+                    expires_at: Utc::now() + chrono::Duration::minutes(expires_in_minutes),
+                    token_type: "Bearer".to_string(),
+                },
+                stored_at: Utc::now(),
+            }),
+            ..StoredTokens::default()
+        }))
+    }
+
+    #[test]
+    fn test_check_google_tokens() {
+        let config = google_config();
+        assert_eq!(check_google_tokens(None, &Ok(None)).status, CheckStatus::Warn);
+        assert_eq!(check_google_tokens(Some(&config), &Ok(None)).status, CheckStatus::Warn);
+        assert_eq!(check_google_tokens(Some(&config), &stored(60, false)).status, CheckStatus::Ok);
+        assert_eq!(check_google_tokens(Some(&config), &stored(-60, true)).status, CheckStatus::Ok);
+        assert_eq!(check_google_tokens(Some(&config), &stored(-60, false)).status, CheckStatus::Fail);
+        assert_eq!(check_google_tokens(Some(&config), &Err("bad".to_string())).status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn test_check_to_line() {
+        let check = Check::new("Cache", CheckStatus::Fail, "broken");
+        assert_eq!(check.to_line(), "[FAIL] Cache: broken");
+        assert!(has_failures(&[check]));
+    }
+}
@@ -0,0 +1,222 @@
+//! Month fetch scheduling: one queue for both panels that dedupes requests, starts the
+//! most urgent first and caps how many fetches run at once
+
+use crate::cache::{DisplayEvent, EventSource};
+use chrono::{Datelike, NaiveDate};
+
+/// Months fetched at the same time, across both panels
+const MAX_RUNNING: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// A neighbouring month, fetched ahead of navigation
+    Prefetch,
+    /// The month on screen
+    Visible,
+}
+
+/// A month of one panel to fetch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FetchRequest {
+    pub source: EventSource,
+    pub month: NaiveDate, // First day of the month
+    pub priority: Priority,
+}
+
+/// Reported by fetch tasks; `generation` tells results that predate a reload apart
+pub enum FetchMessage {
+    /// `done` of the month's providers have answered
+    Progress { source: EventSource, month: NaiveDate, generation: u64, done: usize },
+    /// Every provider has answered; `events` is None when all of them failed, and `offline`
+    /// tells that some couldn't reach their server at all
+    Finished {
+        source: EventSource,
+        month: NaiveDate,
+        generation: u64,
+        events: Option<Vec<DisplayEvent>>,
+        errors: Vec<String>,
+        offline: bool,
+    },
+}
+
+struct Running {
+    source: EventSource,
+    month: NaiveDate,
+    generation: u64,
+    done: usize,
+    total: usize,
+}
+
+#[derive(Default)]
+pub struct FetchScheduler {
+    queue: Vec<FetchRequest>,
+    running: Vec<Running>,
+    generation: u64,
+}
+
+impl FetchScheduler {
+    /// Queue a month, or raise the priority of the same month already waiting
+    pub fn request(&mut self, source: EventSource, month: NaiveDate, priority: Priority) {
+        let month = month.with_day(1).unwrap_or(month);
+        match self.queue.iter_mut().find(|r| r.source == source && r.month == month) {
+            Some(queued) => queued.priority = queued.priority.max(priority),
+            None => self.queue.push(FetchRequest { source, month, priority }),
+        }
+    }
+
+    pub fn has_queued(&self) -> bool {
+        !self.queue.is_empty()
+    }
+
+    /// Start queued months while there are free slots. `start` spawns the fetch and returns
+    /// how many providers it asked, or None when there was nothing to fetch.
+    pub fn pump(&mut self, mut start: impl FnMut(FetchRequest, u64) -> Option<usize>) {
+        while self.running.len() < MAX_RUNNING {
+            let Some(index) = self.next_index() else { break };
+            let request = self.queue.remove(index);
+            if let Some(total) = start(request, self.generation) {
+                self.running.push(Running {
+                    source: request.source,
+                    month: request.month,
+                    generation: self.generation,
+                    done: 0,
+                    total,
+                });
+            }
+        }
+    }
+
+    /// Highest priority first, oldest first among equals; a month already being fetched waits
+    fn next_index(&self) -> Option<usize> {
+        self.queue
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| !self.running.iter().any(|run| {
+                run.source == r.source && run.month == r.month && run.generation == self.generation
+            }))
+            .rev()
+            .max_by_key(|(_, r)| r.priority)
+            .map(|(index, _)| index)
+    }
+
+    pub fn progress(&mut self, source: EventSource, month: NaiveDate, generation: u64, done: usize) {
+        if let Some(run) = self.find_running(source, month, generation) {
+            run.done = done;
+        }
+    }
+
+    /// Free the fetch's slot; false when its results are stale and should be dropped
+    pub fn finish(&mut self, source: EventSource, month: NaiveDate, generation: u64) -> bool {
+        self.running.retain(|run| !(run.source == source && run.month == month && run.generation == generation));
+        generation == self.generation
+    }
+
+    /// Disown running fetches, e.g. after the cache was cleared; their results are dropped
+    /// and the same months can be fetched again right away
+    pub fn invalidate(&mut self) {
+        self.generation += 1;
+    }
+
+    pub fn is_loading(&self, source: EventSource) -> bool {
+        self.current().any(|run| run.source == source)
+    }
+
+    /// Providers answered and asked so far over all current fetches
+    pub fn progress_summary(&self) -> Option<(usize, usize)> {
+        self.current()
+            .map(|run| (run.done, run.total))
+            .reduce(|(done, total), (d, t)| (done + d, total + t))
+    }
+
+    fn current(&self) -> impl Iterator<Item = &Running> {
+        self.running.iter().filter(|run| run.generation == self.generation)
+    }
+
+    fn find_running(&mut self, source: EventSource, month: NaiveDate, generation: u64) -> Option<&mut Running> {
+        self.running
+            .iter_mut()
+            .find(|run| run.source == source && run.month == month && run.generation == generation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    /// Start everything that fits, recording what was started
+    fn pump_all(scheduler: &mut FetchScheduler) -> Vec<(EventSource, NaiveDate)> {
+        let mut started = Vec::new();
+        scheduler.pump(|request, _| {
+            started.push((request.source, request.month));
+            Some(1)
+        });
+        started
+    }
+
+    #[test]
+    fn test_requests_are_deduped_and_prioritized() {
+        let mut scheduler = FetchScheduler::default();
+        scheduler.request(EventSource::Google, date(2026, 4, 1), Priority::Prefetch);
+        scheduler.request(EventSource::ICloud, date(2026, 4, 10), Priority::Prefetch);
+        scheduler.request(EventSource::Google, date(2026, 3, 15), Priority::Visible);
+        scheduler.request(EventSource::ICloud, date(2026, 4, 20), Priority::Visible);
+
+        // The iCloud April request was raised to visible and kept its place in line
+        assert_eq!(pump_all(&mut scheduler), vec![
+            (EventSource::ICloud, date(2026, 4, 1)),
+            (EventSource::Google, date(2026, 3, 1)),
+        ]);
+        assert!(scheduler.has_queued());
+
+        // A slot frees up for the prefetch once a fetch finishes
+        assert!(scheduler.finish(EventSource::Google, date(2026, 3, 1), 0));
+        assert_eq!(pump_all(&mut scheduler), vec![(EventSource::Google, date(2026, 4, 1))]);
+        assert!(!scheduler.has_queued());
+    }
+
+    #[test]
+    fn test_running_month_waits_for_its_fetch() {
+        let mut scheduler = FetchScheduler::default();
+        scheduler.request(EventSource::Google, date(2026, 3, 1), Priority::Visible);
+        pump_all(&mut scheduler);
+        scheduler.request(EventSource::Google, date(2026, 3, 1), Priority::Visible);
+        assert!(pump_all(&mut scheduler).is_empty());
+
+        scheduler.finish(EventSource::Google, date(2026, 3, 1), 0);
+        assert_eq!(pump_all(&mut scheduler).len(), 1);
+    }
+
+    #[test]
+    fn test_skipped_requests_take_no_slot() {
+        let mut scheduler = FetchScheduler::default();
+        scheduler.request(EventSource::Google, date(2026, 3, 1), Priority::Visible);
+        scheduler.request(EventSource::ICloud, date(2026, 3, 1), Priority::Visible);
+        scheduler.pump(|_, _| None);
+        assert!(!scheduler.has_queued());
+        assert!(!scheduler.is_loading(EventSource::Google));
+    }
+
+    #[test]
+    fn test_invalidate_drops_stale_results() {
+        let mut scheduler = FetchScheduler::default();
+        scheduler.request(EventSource::Google, date(2026, 3, 1), Priority::Visible);
+        scheduler.pump(|_, _| Some(3));
+        scheduler.progress(EventSource::Google, date(2026, 3, 1), 0, 1);
+        assert!(scheduler.is_loading(EventSource::Google));
+        assert_eq!(scheduler.progress_summary(), Some((1, 3)));
+
+        scheduler.invalidate();
+        assert!(!scheduler.is_loading(EventSource::Google));
+        assert_eq!(scheduler.progress_summary(), None);
+
+        // The same month can be refetched while the stale fetch is still out
+        scheduler.request(EventSource::Google, date(2026, 3, 1), Priority::Visible);
+        assert_eq!(pump_all(&mut scheduler).len(), 1);
+        assert!(!scheduler.finish(EventSource::Google, date(2026, 3, 1), 0));
+        assert!(scheduler.finish(EventSource::Google, date(2026, 3, 1), 1));
+    }
+}
@@ -1,34 +1,50 @@
 mod app;
-mod auth;
-mod cache;
-mod config;
-mod conversion;
-mod error;
-mod google;
-mod icloud;
-mod logging;
+mod cli;
+mod crash;
+mod doctor;
+mod fetch;
+mod tasks;
 mod ui;
-mod utils;
 
-use app::{App, NavigationMode, PendingAction};
+use calendarchy_core::{
+    auth, brief, cache, config, contacts, conversion, error, export, google, holidays, icloud, journal,
+    logging, mute, outlook, provider, quick_add, review, search, snooze, tags, utils,
+};
+
+use app::{App, DebugTab, InfoModal, NavigationMode, PendingAction, RepeatableAction, ViewMode};
 use auth::{CalendarEntry, GoogleAuthState, ICloudAuthState};
-use cache::{DisplayEvent, EventId};
-use conversion::{google_event_to_display, icloud_event_to_display};
-use chrono::{DateTime, NaiveDate, Utc};
-use config::Config;
+use cache::{DisplayEvent, EventCache, EventDetails, EventId, EventSource};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use config::{Config, ProviderConfig, TimeFormatConfig};
+use fetch::{FetchMessage, FetchRequest};
 use crossterm::{
     cursor,
-    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use google::{CalendarClient, GoogleAuth, TokenInfo};
-use icloud::{CalDavClient, ICalEvent, ICloudAuth};
+use icloud::{CalDavClient, CardDavClient, ICloudAuth};
+use outlook::{GraphToken, OutlookAuth};
+use provider::{CalendarProvider, CopyTarget, ProviderContext, WriteFuture};
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+use std::collections::BTreeMap;
 use std::io::stdout;
-use std::os::unix::process::CommandExt;
-use std::time::Duration as StdDuration;
+use std::path::{Path, PathBuf};
+use std::time::{Duration as StdDuration, Instant};
+use tasks::Tasks;
 use tokio::sync::mpsc;
 
+/// How often to check whether a calendar server answers again after fetches failed offline
+const RECONNECT_PROBE_INTERVAL: StdDuration = StdDuration::from_secs(15);
+
+/// Title of the modal showing the Outlook device code, closed once the sign-in ends
+const OUTLOOK_SIGN_IN: &str = "Outlook sign-in";
+
+/// Title of the modal showing the device code of another Google account
+const GOOGLE_ACCOUNT_SIGN_IN: &str = "Google sign-in";
+
 /// Messages from async tasks to main loop
 enum AsyncMessage {
     // Google messages
@@ -39,42 +55,1088 @@ enum AsyncMessage {
         expires_at: DateTime<Utc>,
     },
     GoogleToken(TokenInfo),
-    GoogleAuthPending,
     GoogleAuthError(String),
-    GoogleEvents(Vec<google::CalendarEvent>, NaiveDate, String, Option<String>), // events, month_date, calendar_id, calendar_name
-    GoogleFetchError(String),
     GoogleTokenRefreshed(TokenInfo),
     GoogleRefreshFailed(String),
 
+    // Other Google accounts
+    GoogleAccountDeviceCode { account: String, user_code: String, verification_url: String },
+    GoogleAccountToken { account: String, tokens: TokenInfo },
+    GoogleAccountTokenRefreshed { account: String, tokens: TokenInfo },
+    GoogleAccountAuthError { account: String, message: String },
+
+    // Outlook messages
+    OutlookDeviceCode { user_code: String, verification_uri: String },
+    OutlookToken(GraphToken),
+    OutlookAuthError(String),
+
     // iCloud messages
     ICloudDiscovered { calendars: Vec<CalendarEntry> },
     ICloudDiscoveryError(String),
-    ICloudEvents(Vec<(ICalEvent, Option<String>)>, NaiveDate), // Events with calendar name
-    ICloudFetchError(String),
+    ICloudCredentialsRejected { status: u16, body: String },
+
+    // Month fetches
+    Fetch(FetchMessage),
+    Reconnected,
+    EventDetails(EventId, EventDetails),
+    EventDetailsError(String),
 
     // Event action messages
     EventActionSuccess(String), // Success message
     EventActionError(String),   // Error message
+    BulkActionDone(RepeatableAction, Vec<(String, Result<(), String>)>), // Outcome per event title
+    Imported { calendar: String, results: Vec<(String, Result<(), String>)> },
+
+    // Contacts
+    Contacts(Vec<String>), // Raw vCards
+    ContactsError(String),
+
+    // Holiday overlay
+    Holidays(Vec<(NaiveDate, String)>),
+    HolidaysError(String),
+
+    // Diagnostics
+    DoctorReport(Vec<doctor::Check>),
+
+    // Google calendar list
+    GoogleCalendars { calendars: Vec<google::CalendarListEntry>, shared: bool },
+    GoogleCalendarsError(String),
+
+    // Colleague's free/busy overlay
+    FreeBusy { email: String, busy: Vec<google::BusyPeriod> },
+    FreeBusyError { email: String, error: String },
+
+    // Server-side search
+    SearchResults { query: String, events: Vec<(DisplayEvent, EventSource)> },
+}
+
+/// Config and the event cache from disk, filtered as the calendar would show them
+fn load_cached_events() -> Result<(Config, EventCache), String> {
+    let config = Config::load().map_err(|e| format!("Config error: {}", e))?;
+    let mut events = EventCache::new();
+    events.dedup = config.dedup.clone();
+    events.hidden_kinds = config.hide_event_types.clone();
+    events.mute = mute::MuteFilter::new(&config.mute).map_err(|e| format!("Config error: {}", e))?;
+    config.time_format.validate().map_err(|e| format!("Config error: {}", e))?;
+    events.snoozed = snooze::SnoozeStore::load().map_err(|e| e.to_string())?;
+    events.load_from_disk().map_err(|e| e.to_string())?;
+    Ok((config, events))
+}
+
+/// `calendarchy join`: open the current or next meeting link from the event cache
+fn join_next_meeting() -> Result<String, String> {
+    let (config, events) = load_cached_events()?;
+    let now = Local::now();
+    let next = ui::find_next_event(&events, now.date_naive(), now.time(), |e| e.meeting_url.is_some())
+        .ok_or("No upcoming meeting with a link in the cache")?;
+    let url = next.event.meeting_url.as_deref().unwrap_or_default();
+    utils::open_url(url, config.browser_command.as_deref())
+        .map_err(|e| format!("Failed to open {}: {}", url, e))?;
+    Ok(format!("Joining {} ({}): {}", next.event.title, next.event.time_label(&config.time_format), url))
+}
+
+/// `calendarchy next`: the current or next accepted event from the cache, empty when there
+/// is none so a status bar shows nothing
+fn next_event_line(format: &str, now_format: &str) -> Result<String, String> {
+    let (config, events) = load_cached_events()?;
+    let now = Local::now();
+    let Some(next) = ui::find_next_event(&events, now.date_naive(), now.time(), |_| true) else {
+        return Ok(String::new());
+    };
+    let template = if next.is_current || next.minutes_until <= 0 { now_format } else { format };
+    Ok(ui::fill_countdown_template(template, &next, &config.time_format, usize::MAX))
+}
+
+/// `calendarchy agenda`: the next days of both panels from the cache, as text or JSON
+async fn print_agenda(days: Option<u32>, json: bool, refresh: bool) -> Result<String, String> {
+    let (config, mut events) = load_cached_events()?;
+    let days = days.or(config.agenda_days).unwrap_or(cache::DEFAULT_AGENDA_DAYS).max(1);
+    let today = Local::now().date_naive();
+    if refresh {
+        let last = today + chrono::Duration::days(days as i64 - 1);
+        // Stale events are still printed when a calendar can't be reached
+        for error in refresh_cache(&config, &mut events, today, last).await {
+            eprintln!("{}", error);
+        }
+    }
+    let agenda = events.agenda(today, days);
+    Ok(if json {
+        format!("{}\n", export::agenda_to_json(&agenda))
+    } else if agenda.is_empty() {
+        format!("No events in the next {} days\n", days)
+    } else {
+        export::agenda_to_text(&agenda, &config.time_format)
+    })
+}
+
+/// The saved Google sign-in, refreshed (and saved again) when expired
+async fn load_google_auth(config: &Config, log: &logging::HttpLog) -> Result<GoogleAuthState, String> {
+    let (Some(google_config), Ok(Some(tokens))) = (&config.google, config::load_google_tokens()) else {
+        return Ok(GoogleAuthState::NotAuthenticated);
+    };
+    if !tokens.is_expired() {
+        return Ok(GoogleAuthState::Authenticated(tokens));
+    }
+    let Some(refresh_token) = tokens.refresh_token else { return Ok(GoogleAuthState::NotAuthenticated) };
+    match GoogleAuth::new(google_config.clone(), log.clone()).refresh_token(&refresh_token).await {
+        Ok(tokens) => {
+            let _ = config::save_google_tokens(&tokens);
+            Ok(GoogleAuthState::Authenticated(tokens))
+        }
+        Err(e) => Err(format!("Google: token refresh failed: {}", e)),
+    }
+}
+
+/// The iCloud calendars saved at the last discovery
+fn load_icloud_auth(config: &Config) -> ICloudAuthState {
+    match config::load_icloud_tokens() {
+        Ok(Some(tokens)) if config.icloud.is_some() => ICloudAuthState::Authenticated { calendars: CalendarEntry::from_tokens(tokens) },
+        _ => ICloudAuthState::NotAuthenticated,
+    }
+}
+
+/// `calendarchy import`: create every event of an .ics file in a signed-in calendar, the
+/// first one unless `calendar` names another; returns the outcome per event title
+async fn import_file(path: &Path, calendar: Option<&str>) -> Result<(String, Vec<(String, Result<(), String>)>), String> {
+    let config = Config::load().map_err(|e| format!("Config error: {}", e))?;
+    let meeting_patterns = utils::MeetingPatterns::new(&config.meeting_patterns).map_err(|e| format!("Config error: {}", e))?;
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let (events, repeating) = quick_add::parse_ics(&text, &meeting_patterns);
+    if events.is_empty() && repeating.is_empty() {
+        return Err(format!("No events in {}", path.display()));
+    }
+
+    let log = logging::HttpLog::default();
+    let google = load_google_auth(&config, &log).await.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        GoogleAuthState::NotAuthenticated
+    });
+    let listed_name = match (&google, &config.google) {
+        (GoogleAuthState::Authenticated(tokens), Some(google_config)) => CalendarClient::new(log.clone())
+            .get_calendar_name(tokens, &google_config.calendar_id)
+            .await
+            .ok()
+            .flatten(),
+        _ => None,
+    };
+    let icloud = load_icloud_auth(&config);
+    let targets = app::calendar_targets(&config, &google, &icloud, |_| listed_name);
+    let target = match calendar.map(str::to_lowercase) {
+        Some(wanted) => targets.iter().find(|t| t.name().to_lowercase().starts_with(&wanted)),
+        None => targets.first(),
+    };
+    let Some(target) = target else {
+        let names: Vec<&str> = targets.iter().map(CopyTarget::name).collect();
+        return Err(if names.is_empty() {
+            "No signed-in calendars; connect one in the app first".to_string()
+        } else {
+            format!("No calendar named {}; choose one of: {}", calendar.unwrap_or_default(), names.join(", "))
+        });
+    };
+
+    let providers = provider::registry(&config, &google, &icloud, None, &BTreeMap::new(), &ProviderContext::new(log), false);
+    Ok((target.name().to_string(), import_events(events, repeating, target, &providers).await))
+}
+
+/// One line per event title with how the change to it went
+fn outcome_lines(results: &[(String, Result<(), String>)]) -> Vec<String> {
+    results.iter().map(|(title, result)| match result {
+        Ok(()) => format!("✓ {}", title),
+        Err(e) => format!("✗ {}: {}", title, e),
+    }).collect()
+}
+
+/// Create events in a calendar one after another, with the outcome per event title; the
+/// `repeating` ones left out of the file are listed as not imported, and events with guests
+/// say that those weren't invited
+async fn import_events(
+    events: Vec<DisplayEvent>,
+    repeating: Vec<String>,
+    target: &CopyTarget,
+    providers: &[Box<dyn CalendarProvider>],
+) -> Vec<(String, Result<(), String>)> {
+    let mut results: Vec<(String, Result<(), String>)> = repeating
+        .into_iter()
+        .map(|title| (title, Err("repeating events are not imported".to_string())))
+        .collect();
+    for event in events {
+        let result = send_write(provider::write_with(providers, |p| p.create(target, &event))).await;
+        // Created events carry no guest list, so nobody is invited by an import
+        let title = if event.attendees.iter().any(|a| !a.is_self) {
+            format!("{} (guests not invited)", event.title)
+        } else {
+            event.title
+        };
+        results.push((title, result));
+    }
+    results
+}
+
+/// Wait for a change to go through, or explain that no connected calendar takes it
+async fn send_write(write: Option<WriteFuture>) -> Result<(), String> {
+    match write {
+        Some(write) => write.await.map_err(|e| e.to_string()),
+        None => Err("No connected calendar can make this change".to_string()),
+    }
+}
+
+/// Fetch the months from `from` to `to` of every signed-in calendar into the cache and save
+/// it, refreshing expired tokens on the way; returns what failed
+async fn refresh_cache(config: &Config, events: &mut EventCache, from: NaiveDate, to: NaiveDate) -> Vec<String> {
+    let log = logging::HttpLog::default();
+    let mut errors = Vec::new();
+
+    let google = load_google_auth(config, &log).await.unwrap_or_else(|e| {
+        errors.push(e);
+        GoogleAuthState::NotAuthenticated
+    });
+
+    let mut outlook = None;
+    if let (Some(outlook_config), Ok(Some(tokens))) = (&config.outlook, config::load_outlook_tokens()) {
+        if !tokens.is_expired() {
+            outlook = Some(tokens);
+        } else if let Some(refresh_token) = tokens.refresh_token {
+            match OutlookAuth::new(outlook_config.clone(), log.clone()).refresh_token(&refresh_token).await {
+                Ok(tokens) => {
+                    let _ = config::save_outlook_tokens(&tokens);
+                    outlook = Some(tokens);
+                }
+                Err(e) => errors.push(format!("Outlook: token refresh failed: {}", e)),
+            }
+        }
+    }
+
+    let mut google_accounts = config::load_google_account_tokens().unwrap_or_default();
+    if let Some(google_config) = &config.google {
+        for (account, tokens) in &mut google_accounts {
+            let Some(refresh_token) = tokens.refresh_token.clone().filter(|_| tokens.is_expired()) else { continue };
+            match GoogleAuth::new(google_config.clone(), log.clone()).refresh_token(&refresh_token).await {
+                Ok(new_tokens) => {
+                    let _ = config::save_google_account_tokens(account, &new_tokens);
+                    *tokens = new_tokens;
+                }
+                Err(e) => errors.push(format!("Google ({}): token refresh failed: {}", account, e)),
+            }
+        }
+    }
+    google_accounts.retain(|_, tokens| !tokens.is_expired());
+
+    let icloud = load_icloud_auth(config);
+
+    let providers = provider::registry(config, &google, &icloud, outlook.as_ref(), &google_accounts, &ProviderContext::new(log.clone()), false);
+    let mut month = utils::month_bounds(from).0;
+    while month <= to {
+        let (start, end) = utils::month_bounds(month);
+        for source in [EventSource::Google, EventSource::ICloud] {
+            let mut fetched = Vec::new();
+            let mut fetched_any = false;
+            for provider in providers.iter().filter(|p| p.source() == source) {
+                match provider.fetch_range(start, end).await {
+                    Ok(month_events) => {
+                        fetched.extend(month_events);
+                        fetched_any = true;
+                    }
+                    Err(e) => errors.push(format!("{}: {}", provider.name(), e)),
+                }
+            }
+            // Keep the cached month when every provider failed
+            if fetched_any {
+                events.store(source, fetched, month);
+            }
+        }
+        month = end + chrono::Duration::days(1);
+    }
+    events.save_to_disk();
+    errors
+}
+
+/// `:copy <calendar>`: create a copy of the selected event in another connected calendar.
+/// Without a matching calendar name, lists the available ones.
+fn copy_selected_event(app: &mut App, target_name: &str, tx: &mpsc::Sender<AsyncMessage>) {
+    let Some(event) = app.get_selected_event().cloned() else {
+        app.set_status("Select an event to copy");
+        return;
+    };
+    let targets = app.copy_targets();
+    let wanted = target_name.trim().to_lowercase();
+    let Some(target) = targets.iter().find(|t| !wanted.is_empty() && t.name().to_lowercase().starts_with(&wanted)) else {
+        let mut lines: Vec<String> = targets.iter().map(|t| format!("  {}", t.name())).collect();
+        if lines.is_empty() {
+            lines.push("No other connected calendars".to_string());
+        } else {
+            lines.insert(0, "Type :copy <calendar> with one of:".to_string());
+        }
+        app.info_modal = Some(InfoModal::new(format!("Copy \"{}\"", event.title), lines));
+        return;
+    };
+    if app.demo {
+        app.set_status("Demo mode: changes are not sent");
+        return;
+    }
+
+    create_in_calendar(app, &event, target, format!("Copied to {}", target.name()), "copy", tx);
+    app.set_status("Copying event...");
+}
+
+/// Create a new event from a `:new [@calendar] <day> [start[-end]] <title>` line, filling in
+/// the details of a pasted invite, or from a free-form `:add [@calendar] <text>` line
+/// (`natural`); without a calendar it goes to the first connected one
+fn create_new_event(app: &mut App, arg: &str, natural: bool, tx: &mpsc::Sender<AsyncMessage>) {
+    let draft = app.new_event_draft.take();
+    let (target_name, text) = match arg.trim().strip_prefix('@') {
+        Some(rest) => {
+            let (name, text) = rest.split_once(' ').unwrap_or((rest, ""));
+            (Some(name.to_lowercase()), text)
+        }
+        None => (None, arg),
+    };
+    let today = Local::now().date_naive();
+    let (parsed, usage) = if natural {
+        (quick_add::parse_natural(text, today), ":add [@calendar] <title> [day] [time] [at <place>]")
+    } else {
+        (quick_add::parse_line(text, today), ":new [@calendar] <day> [hh:mm[-hh:mm]] <title>")
+    };
+    let mut event = match parsed {
+        Ok(event) => event,
+        Err(e) => {
+            app.set_status(format!("{} (use {})", e, usage));
+            return;
+        }
+    };
+    if let Some(draft) = draft {
+        event.location = draft.location;
+        event.description = draft.description;
+        event.meeting_url = draft.meeting_url;
+    }
+
+    let calendars = app.calendars();
+    let target = match &target_name {
+        Some(wanted) => calendars.iter().find(|t| t.name().to_lowercase().starts_with(wanted)),
+        None => calendars.first(),
+    };
+    let Some(target) = target else {
+        let mut lines: Vec<String> = calendars.iter().map(|t| format!("  {}", t.name())).collect();
+        if lines.is_empty() {
+            lines.push("No connected calendars".to_string());
+        } else {
+            lines.insert(0, format!("Type :{} @<calendar> ... with one of:", if natural { "add" } else { "new" }));
+        }
+        app.info_modal = Some(InfoModal::new(format!("New \"{}\"", event.title), lines));
+        return;
+    };
+    if app.demo {
+        app.set_status("Demo mode: changes are not sent");
+        return;
+    }
+
+    let google_parses = natural && app.config.google.as_ref().is_some_and(|c| c.quick_add);
+    match target {
+        CopyTarget::Google { .. } if google_parses => {
+            let write = provider::write_with(&app.providers(), |p| p.quick_add(target, text));
+            let done = format!("Created in {}", target.name());
+            let tx = tx.clone();
+            app.tasks.spawn(async move {
+                let message = match send_write(write).await {
+                    Ok(()) => AsyncMessage::EventActionSuccess(done),
+                    Err(e) => AsyncMessage::EventActionError(format!("Failed to create: {}", e)),
+                };
+                let _ = tx.send(message).await;
+            });
+        }
+        _ => create_in_calendar(app, &event, target, format!("Created in {}", target.name()), "create", tx),
+    }
+    app.set_status("Creating event...");
+}
+
+/// Send a new event to a calendar; `verb` names the operation in the failure message
+fn create_in_calendar(
+    app: &App,
+    event: &DisplayEvent,
+    target: &CopyTarget,
+    done: String,
+    verb: &'static str,
+    tx: &mpsc::Sender<AsyncMessage>,
+) {
+    let write = provider::write_with(&app.providers(), |p| p.create(target, event));
+    let tx = tx.clone();
+    app.tasks.spawn(async move {
+        let message = match send_write(write).await {
+            Ok(()) => AsyncMessage::EventActionSuccess(done),
+            Err(e) => AsyncMessage::EventActionError(format!("Failed to {}: {}", verb, e)),
+        };
+        let _ = tx.send(message).await;
+    });
+}
+
+/// `:import [@calendar] <file.ics>`: create every event of an .ics file in a connected
+/// calendar, the first one unless named, and list how each went
+fn import_ics_file(app: &mut App, arg: &str, tx: &mpsc::Sender<AsyncMessage>) {
+    let (target_name, path) = match arg.trim().strip_prefix('@') {
+        Some(rest) => {
+            let (name, path) = rest.split_once(' ').unwrap_or((rest, ""));
+            (Some(name.to_lowercase()), path.trim())
+        }
+        None => (None, arg.trim()),
+    };
+    if path.is_empty() {
+        app.set_status("Use :import [@calendar] <file.ics>");
+        return;
+    }
+    let path = match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+        None => PathBuf::from(path),
+    };
+    let (events, repeating) = match std::fs::read_to_string(&path) {
+        Ok(text) => quick_add::parse_ics(&text, &app.meeting_patterns),
+        Err(e) => {
+            app.set_status(format!("Failed to read {}: {}", path.display(), e));
+            return;
+        }
+    };
+    if events.is_empty() && repeating.is_empty() {
+        app.set_status(format!("No events in {}", path.display()));
+        return;
+    }
+
+    let calendars = app.calendars();
+    let target = match &target_name {
+        Some(wanted) => calendars.iter().find(|t| t.name().to_lowercase().starts_with(wanted)),
+        None => calendars.first(),
+    };
+    let Some(target) = target else {
+        let mut lines: Vec<String> = calendars.iter().map(|t| format!("  {}", t.name())).collect();
+        if lines.is_empty() {
+            lines.push("No connected calendars".to_string());
+        } else {
+            lines.insert(0, "Type :import @<calendar> <file.ics> with one of:".to_string());
+        }
+        app.info_modal = Some(InfoModal::new(format!("Import {} event(s)", events.len()), lines));
+        return;
+    };
+    if app.demo {
+        app.set_status("Demo mode: changes are not sent");
+        return;
+    }
+
+    app.set_status(format!("Importing {} event(s) into {}...", events.len(), target.name()));
+    let providers = app.providers();
+    let (target, tx) = (target.clone(), tx.clone());
+    app.tasks.spawn(async move {
+        let results = import_events(events, repeating, &target, &providers).await;
+        let _ = tx.send(AsyncMessage::Imported { calendar: target.name().to_string(), results }).await;
+    });
+}
+
+/// Clicks select days and events, or join the selected meeting; the wheel moves months.
+/// Ignored while a prompt or modal is waiting for keys
+fn handle_mouse(app: &mut App, hits: &ui::HitMap, mouse: MouseEvent) {
+    if app.search.is_some()
+        || app.debug_screen.is_some()
+        || app.pending_action.is_some()
+        || app.info_modal.is_some()
+        || app.calendar_picker.is_some()
+        || app.command_line.is_some()
+        || app.tag_editor.is_some()
+        || app.day_filter_editing
+        || app.day_jump.is_some()
+        || app.moving.is_some()
+    {
+        return;
+    }
+    match mouse.kind {
+        MouseEventKind::ScrollDown => app.next_month(),
+        MouseEventKind::ScrollUp => app.prev_month(),
+        MouseEventKind::Down(MouseButton::Left) => match hits.at(mouse.column, mouse.row) {
+            Some(ui::Hit::Day(date)) => app.select_day(*date),
+            Some(ui::Hit::Event { date, source, index }) => app.select_event(*date, *source, *index),
+            Some(ui::Hit::Join) => {
+                if let Some(url) = app.get_selected_event().and_then(|e| e.meeting_url.clone()) {
+                    app.open_url(&url);
+                }
+            }
+            None => {}
+        },
+        _ => {}
+    }
+}
+
+/// Fetch the Google calendar list for `:shared` (others' calendars) or `:calendars google`
+fn list_google_calendars(app: &mut App, tx: &mpsc::Sender<AsyncMessage>, shared: bool) {
+    let GoogleAuthState::Authenticated(ref tokens) = app.google_auth else {
+        app.set_status("Connect Google first (press g)");
+        return;
+    };
+    let tokens = tokens.clone();
+    let client = CalendarClient::new(app.http_log.clone());
+    let tx = tx.clone();
+    app.tasks.spawn_cancellable(async move {
+        let message = match client.list_calendars(&tokens).await {
+            Ok(calendars) => AsyncMessage::GoogleCalendars { calendars, shared },
+            Err(e) => AsyncMessage::GoogleCalendarsError(e.to_string()),
+        };
+        let _ = tx.send(message).await;
+    });
+    app.set_status("Listing calendars...");
+}
+
+/// `:outlook`: sign in to Microsoft 365 with a device code, shown until the sign-in completes
+fn sign_in_outlook(app: &mut App, tx: &mpsc::Sender<AsyncMessage>) {
+    let Some(ref outlook_config) = app.config.outlook else {
+        app.set_status(format!("Add an \"outlook\" section to {}", Config::config_path().display()));
+        return;
+    };
+    let auth = OutlookAuth::new(outlook_config.clone(), app.http_log.clone());
+    let tx = tx.clone();
+    app.tasks.spawn_cancellable(async move {
+        let resp = match auth.request_device_code().await {
+            Ok(resp) => resp,
+            Err(e) => {
+                let _ = tx.send(AsyncMessage::OutlookAuthError(e.to_string())).await;
+                return;
+            }
+        };
+        let expires_at = Utc::now() + chrono::Duration::seconds(resp.expires_in as i64);
+        let _ = tx.send(AsyncMessage::OutlookDeviceCode {
+            user_code: resp.user_code,
+            verification_uri: resp.verification_uri,
+        }).await;
+        let interval = StdDuration::from_secs(resp.interval);
+        let message = match auth.wait_for_token(&resp.device_code, interval, expires_at).await {
+            Ok(tokens) => AsyncMessage::OutlookToken(tokens),
+            Err(e) => AsyncMessage::OutlookAuthError(e.to_string()),
+        };
+        let _ = tx.send(message).await;
+    });
+    app.set_status("Requesting an Outlook sign-in code...");
+}
+
+/// `:google <account>`: sign in to another Google account with a device code, for the
+/// `google_account` provider of that name
+fn sign_in_google_account(app: &mut App, tx: &mpsc::Sender<AsyncMessage>, account: &str) {
+    let Some(ref google_config) = app.config.google else {
+        app.set_status(format!("Add a \"google\" section to {}", Config::config_path().display()));
+        return;
+    };
+    let configured = app.config.providers().iter()
+        .any(|p| matches!(p, ProviderConfig::GoogleAccount { account: a, .. } if a == account));
+    if !configured {
+        app.set_status(format!("Add {{\"type\": \"google_account\", \"account\": \"{}\"}} to \"providers\" first", account));
+        return;
+    }
+    let auth = GoogleAuth::new(google_config.clone(), app.http_log.clone());
+    let account = account.to_string();
+    let tx = tx.clone();
+    app.tasks.spawn_cancellable(async move {
+        let resp = match auth.request_device_code().await {
+            Ok(resp) => resp,
+            Err(e) => {
+                let _ = tx.send(AsyncMessage::GoogleAccountAuthError { account, message: e.to_string() }).await;
+                return;
+            }
+        };
+        let expires_at = Utc::now() + chrono::Duration::seconds(resp.expires_in as i64);
+        let _ = tx.send(AsyncMessage::GoogleAccountDeviceCode {
+            account: account.clone(),
+            user_code: resp.user_code,
+            verification_url: resp.verification_url,
+        }).await;
+        let interval = StdDuration::from_secs(resp.interval);
+        let message = match auth.wait_for_token(&resp.device_code, interval, expires_at).await {
+            Ok(tokens) => AsyncMessage::GoogleAccountToken { account, tokens },
+            Err(e) => AsyncMessage::GoogleAccountAuthError { account, message: e.to_string() },
+        };
+        let _ = tx.send(message).await;
+    });
+    app.set_status("Requesting a Google sign-in code...");
+}
+
+/// Send a moved event's new times through the provider that owns it
+fn move_event(app: &mut App, event: DisplayEvent, tx: &mpsc::Sender<AsyncMessage>) {
+    if app.demo {
+        app.set_status("Demo mode: changes are not sent");
+        return;
+    }
+    if matches!(event.id, EventId::Feed { .. }) {
+        app.set_status("Events from feeds are read-only");
+        return;
+    }
+    let write = provider::write_with(&app.providers(), |p| p.reschedule(&event));
+    let done = format!("Moved \"{}\" to {}", event.title, event.date.format("%a %b %d"));
+    let tx = tx.clone();
+    app.tasks.spawn(async move {
+        let message = match send_write(write).await {
+            Ok(()) => AsyncMessage::EventActionSuccess(done),
+            Err(e) => AsyncMessage::EventActionError(format!("Failed to move: {}", e)),
+        };
+        let _ = tx.send(message).await;
+    });
+    app.set_status("Moving event...");
+}
+
+/// List the iCloud account's calendars with their names and colors
+fn discover_icloud(tasks: &Tasks, client: CalDavClient, tx: &mpsc::Sender<AsyncMessage>) {
+    let tx = tx.clone();
+    tasks.spawn_cancellable(async move {
+        let message = match client.discover_calendars().await {
+            Ok(discovered) if discovered.is_empty() => AsyncMessage::ICloudDiscoveryError("No calendars found".to_string()),
+            Ok(discovered) => AsyncMessage::ICloudDiscovered {
+                calendars: discovered
+                    .into_iter()
+                    .map(|c| CalendarEntry { url: c.url, name: c.name, color: c.color, hidden: false })
+                    .collect(),
+            },
+            Err(error::CalendarchyError::ICloudCredentials { status, body }) => {
+                AsyncMessage::ICloudCredentialsRejected { status, body }
+            }
+            Err(e) => AsyncMessage::ICloudDiscoveryError(e.to_string()),
+        };
+        let _ = tx.send(message).await;
+    });
+}
+
+/// Fetch the full details of the selected event when its month was listed without them
+fn fetch_selected_details(app: &mut App, tx: &mpsc::Sender<AsyncMessage>) {
+    let Some(id) = app.get_selected_event().filter(|e| e.partial).map(|e| e.id.clone()) else { return };
+    let EventId::Google { ref calendar_id, ref event_id, ref account, .. } = id else { return };
+    // Events of another Google account are read with its own tokens
+    let tokens = match (account, &app.google_auth) {
+        (Some(account), _) => app.google_accounts.get(account),
+        (None, GoogleAuthState::Authenticated(tokens)) => Some(tokens),
+        (None, _) => None,
+    };
+    let Some(tokens) = tokens.cloned() else { return };
+    if !app.details_requested.insert(id.clone()) {
+        return;
+    }
+    let client = CalendarClient::new(app.http_log.clone());
+    let (calendar_id, event_id, account) = (calendar_id.clone(), event_id.clone(), account.clone());
+    let meeting_patterns = app.meeting_patterns.clone();
+    let tx = tx.clone();
+    app.tasks.spawn_cancellable(async move {
+        let message = match client.get_event(&tokens, &calendar_id, &event_id).await {
+            Ok(event) => match conversion::google_event_to_display(event, calendar_id, None, account, &meeting_patterns) {
+                Some(full) => AsyncMessage::EventDetails(id, EventDetails::of(full)),
+                None => return,
+            },
+            Err(e) => AsyncMessage::EventDetailsError(e.to_string()),
+        };
+        let _ = tx.send(message).await;
+    });
+}
+
+/// Fetch the overlaid colleague's busy times once the selected week leaves what was fetched
+fn fetch_busy_overlay(app: &mut App, tx: &mpsc::Sender<AsyncMessage>) {
+    let GoogleAuthState::Authenticated(ref tokens) = app.google_auth else { return };
+    let Some(ref mut overlay) = app.busy_overlay else { return };
+    let week_first_day = app.config.week_start.start_of_week(app.selected_date);
+    let Some((start, end)) = overlay.missing_range(week_first_day) else { return };
+    overlay.range = Some((start, end));
+    let client = CalendarClient::new(app.http_log.clone());
+    let (tokens, email) = (tokens.clone(), overlay.email.clone());
+    let tx = tx.clone();
+    app.tasks.spawn_cancellable(async move {
+        let message = match client.free_busy(&tokens, &email, start, end).await {
+            Ok(busy) => AsyncMessage::FreeBusy { email, busy },
+            Err(e) => AsyncMessage::FreeBusyError { email, error: e.to_string() },
+        };
+        let _ = tx.send(message).await;
+    });
+}
+
+/// Ask the servers about a search the cached months barely answer
+fn search_servers(app: &mut App, tx: &mpsc::Sender<AsyncMessage>) {
+    let Some((query, start, end)) = app.take_remote_search() else { return };
+    let providers = app.providers();
+    let searches: Vec<(EventSource, provider::FetchFuture)> = providers.iter()
+        .filter_map(|p| Some((p.source(), p.search(&query, start, end)?)))
+        .collect();
+    if searches.is_empty() {
+        app.apply_remote_search(&query, Vec::new());
+        return;
+    }
+    let tx = tx.clone();
+    app.tasks.spawn_cancellable(async move {
+        let mut events = Vec::new();
+        for (source, search) in searches {
+            // A server that fails to answer leaves the cached and other servers' matches
+            if let Ok(found) = search.await {
+                events.extend(found.into_iter().map(|e| (e, source)));
+            }
+        }
+        let _ = tx.send(AsyncMessage::SearchResults { query, events }).await;
+    });
+}
+
+/// Start queued month fetches from the providers configured for their panel
+fn pump_fetches(app: &mut App, tx: &mpsc::Sender<AsyncMessage>) {
+    if !app.fetches.has_queued() {
+        return;
+    }
+    let providers = app.providers();
+    let (events, tasks) = (&app.events, &app.tasks);
+    app.fetches.pump(|request, generation| {
+        if events.has_month(request.source, request.month) {
+            return None;
+        }
+        let (start, end) = utils::month_bounds(request.month);
+        let fetches: Vec<(String, provider::FetchFuture)> = providers.iter()
+            .filter(|p| p.source() == request.source)
+            .map(|p| (p.name().to_string(), p.fetch_range(start, end)))
+            .collect();
+        if fetches.is_empty() {
+            return None;
+        }
+        let total = fetches.len();
+        tasks.spawn_cancellable(run_fetch(request, generation, fetches, tx.clone()));
+        Some(total)
+    });
+}
+
+/// Ask a month's providers one after another, reporting progress as each answers
+async fn run_fetch(
+    request: FetchRequest,
+    generation: u64,
+    fetches: Vec<(String, provider::FetchFuture)>,
+    tx: mpsc::Sender<AsyncMessage>,
+) {
+    let FetchRequest { source, month, .. } = request;
+    let mut events = Vec::new();
+    let mut fetched_any = false;
+    let mut errors = Vec::new();
+    let mut offline = false;
+    for (done, (name, fetch)) in fetches.into_iter().enumerate() {
+        match fetch.await {
+            Ok(fetched) => {
+                events.extend(fetched);
+                fetched_any = true;
+            }
+            // Discovery explains refused credentials, so point there
+            Err(e @ error::CalendarchyError::ICloudCredentials { .. }) => {
+                errors.push(format!("{}: {}; press i for help", name, e));
+            }
+            Err(e) => {
+                offline |= matches!(&e, error::CalendarchyError::Network(e) if e.is_connect() || e.is_timeout());
+                errors.push(format!("{}: {}", name, e));
+            }
+        }
+        let _ = tx.send(AsyncMessage::Fetch(FetchMessage::Progress { source, month, generation, done: done + 1 })).await;
+    }
+    // Keep what the working providers returned; a failing feed shouldn't blank the panel
+    let events = fetched_any.then_some(events);
+    let _ = tx.send(AsyncMessage::Fetch(FetchMessage::Finished { source, month, generation, events, errors, offline })).await;
+}
+
+/// Refresh the Google tokens about to expire, the main account's and the other accounts',
+/// so fetches and actions keep working through a long session
+fn refresh_google_tokens(app: &mut App, tx: &mpsc::Sender<AsyncMessage>) {
+    if app.demo {
+        return;
+    }
+    if let GoogleAuthState::Authenticated(ref tokens) = app.google_auth
+        && tokens.is_expired()
+        && !app.google_loading
+        && let Some(refresh_token) = tokens.refresh_token.clone()
+    {
+        app.google_loading = true;
+        refresh_google(app, refresh_token, tx);
+    }
+    let expiring: Vec<(String, String)> = app.google_accounts.iter()
+        .filter(|(account, tokens)| tokens.is_expired() && !app.google_account_refreshes.contains(*account))
+        .filter_map(|(account, tokens)| Some((account.clone(), tokens.refresh_token.clone()?)))
+        .collect();
+    for (account, refresh_token) in expiring {
+        refresh_google_account(app, account, refresh_token, tx);
+    }
+}
+
+/// Trade the main Google account's refresh token for new tokens in the background
+fn refresh_google(app: &App, refresh_token: String, tx: &mpsc::Sender<AsyncMessage>) {
+    let Some(ref google_config) = app.config.google else { return };
+    let auth = GoogleAuth::new(google_config.clone(), app.http_log.clone());
+    let tx = tx.clone();
+    app.tasks.spawn_cancellable(async move {
+        let message = match auth.refresh_token(&refresh_token).await {
+            Ok(new_tokens) => AsyncMessage::GoogleTokenRefreshed(new_tokens),
+            Err(e) => AsyncMessage::GoogleRefreshFailed(e.to_string()),
+        };
+        let _ = tx.send(message).await;
+    });
+}
+
+/// Trade another Google account's refresh token for new tokens in the background. The
+/// account stays marked until it answers, or after a failure until it signs in again.
+fn refresh_google_account(app: &mut App, account: String, refresh_token: String, tx: &mpsc::Sender<AsyncMessage>) {
+    let Some(ref google_config) = app.config.google else { return };
+    let auth = GoogleAuth::new(google_config.clone(), app.http_log.clone());
+    app.google_account_refreshes.insert(account.clone());
+    let tx = tx.clone();
+    app.tasks.spawn_cancellable(async move {
+        let message = match auth.refresh_token(&refresh_token).await {
+            Ok(tokens) => AsyncMessage::GoogleAccountTokenRefreshed { account, tokens },
+            Err(e) => AsyncMessage::GoogleAccountAuthError {
+                message: format!("Token refresh failed: {}; sign in again with :google {}", e, account),
+                account,
+            },
+        };
+        let _ = tx.send(message).await;
+    });
+}
+
+/// While offline, check every so often whether a calendar server answers again
+fn probe_connectivity(app: &mut App, tx: &mpsc::Sender<AsyncMessage>) {
+    let Some(probed) = app.offline_since else { return };
+    if probed.elapsed() < RECONNECT_PROBE_INTERVAL {
+        return;
+    }
+    app.offline_since = Some(Instant::now());
+    let url = if app.google_error.is_some() { google::calendar::CALENDAR_API_BASE } else { icloud::CALDAV_SERVER };
+    let tx = tx.clone();
+    app.tasks.spawn_cancellable(async move {
+        let Ok(client) = reqwest::Client::builder().timeout(StdDuration::from_secs(5)).build() else { return };
+        // Any HTTP response, even an error status, means the server is reachable
+        if client.get(url).send().await.is_ok() {
+            let _ = tx.send(AsyncMessage::Reconnected).await;
+        }
+    });
+}
+
+/// Ask to confirm an action on the selected event, or run it right away when its type
+/// doesn't need confirmation
+fn request_action(app: &mut App, action: RepeatableAction, tx: &mpsc::Sender<AsyncMessage>) {
+    app.request_action(action);
+    if let Some(action) = app.take_unconfirmed_action() {
+        perform_action(app, action, tx);
+    }
+}
+
+/// Carry out a confirmed (or confirmation-free) event action
+fn perform_action(app: &mut App, action: PendingAction, tx: &mpsc::Sender<AsyncMessage>) {
+    if app.demo && !matches!(action, PendingAction::JoinMeeting { .. }) {
+        app.set_status("Demo mode: changes are not sent");
+        return;
+    }
+    if let Some(repeatable) = action.repeatable() {
+        app.last_action = Some(repeatable);
+    }
+    if let PendingAction::AcceptAndJoin { url, .. } = &action {
+        app.open_url(url);
+    }
+    let providers = app.providers();
+    let tx = tx.clone();
+    match action {
+        PendingAction::JoinMeeting { url, .. } => {
+            app.open_url(&url);
+        }
+        PendingAction::Bulk { action, items } => {
+            app.last_action = Some(action);
+            app.marked.clear();
+            app.set_status(format!("Sending {} to {} event(s)...", action.verb(), items.len()));
+            let writes: Vec<_> = items.into_iter()
+                .map(|(title, item)| (title, item.map(|single| action_write(&providers, &single))))
+                .collect();
+            let shutdown = app.tasks.shutdown_token();
+            app.tasks.spawn(async move {
+                let mut results = Vec::new();
+                for (title, write) in writes {
+                    // Quitting stops between events rather than in the middle of one
+                    if shutdown.is_cancelled() {
+                        break;
+                    }
+                    let result = match write {
+                        Ok(write) => send_write(write).await,
+                        Err(e) => Err(e),
+                    };
+                    results.push((title, result));
+                }
+                let _ = tx.send(AsyncMessage::BulkActionDone(action, results)).await;
+            });
+        }
+        action => {
+            let (progress, done, verb) = match action {
+                PendingAction::Accept { .. } | PendingAction::AcceptAndJoin { .. } => ("Accepting event...", "Event accepted", "accept"),
+                PendingAction::Decline { .. } => ("Declining event...", "Event declined", "decline"),
+                _ => ("Deleting event...", "Event deleted", "delete"),
+            };
+            let write = action_write(&providers, &action);
+            app.tasks.spawn(async move {
+                let message = match send_write(write).await {
+                    Ok(()) => AsyncMessage::EventActionSuccess(done.to_string()),
+                    Err(e) => AsyncMessage::EventActionError(format!("Failed to {}: {}", verb, e)),
+                };
+                let _ = tx.send(message).await;
+            });
+            app.set_status(progress);
+        }
+    }
+}
+
+/// The accept, decline or delete of one event, from the provider that owns it
+fn action_write(providers: &[Box<dyn CalendarProvider>], action: &PendingAction) -> Option<WriteFuture> {
+    match action {
+        PendingAction::Accept { id } => provider::write_with(providers, |p| p.respond(id, true)),
+        PendingAction::Decline { id } => provider::write_with(providers, |p| p.respond(id, false)),
+        PendingAction::Delete { id } => provider::write_with(providers, |p| p.delete(id)),
+        // Accept-and-join sends the accept of the event's calendar
+        PendingAction::AcceptAndJoin { accept, .. } => action_write(providers, accept),
+        PendingAction::JoinMeeting { .. } | PendingAction::Bulk { .. } => None,
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let demo = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(cli::Command::Run { demo }) => demo,
+        Ok(cli::Command::Doctor) => {
+            let checks = doctor::run_checks().await;
+            for check in &checks {
+                println!("{}", check.to_line());
+            }
+            if doctor::has_failures(&checks) {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Ok(cli::Command::Join) => {
+            match join_next_meeting() {
+                Ok(message) => println!("{}", message),
+                Err(message) => {
+                    eprintln!("{}", message);
+                    std::process::exit(1);
+                }
+            }
+            return Ok(());
+        }
+        Ok(cli::Command::Brief) => {
+            match load_cached_events() {
+                Ok((config, events)) => {
+                    let lines = brief::compose(&events, Local::now().date_naive(), &config.work_hours, &config.time_format);
+                    println!("{}", lines.join("\n"));
+                }
+                Err(message) => {
+                    eprintln!("{}", message);
+                    std::process::exit(1);
+                }
+            }
+            return Ok(());
+        }
+        Ok(cli::Command::Export { dates }) => {
+            let dates: Vec<&str> = dates.iter().map(String::as_str).collect();
+            let result = export::parse_range(&dates, Local::now().date_naive())
+                .and_then(|(from, to)| load_cached_events().map(|(_, events)| export::to_csv(&export::events_in_range(&events, from, to))));
+            match result {
+                Ok(csv) => print!("{}", csv),
+                Err(message) => {
+                    eprintln!("{}", message);
+                    std::process::exit(1);
+                }
+            }
+            return Ok(());
+        }
+        Ok(cli::Command::Next { format, now_format }) => {
+            match next_event_line(&format, &now_format) {
+                Ok(line) => println!("{}", line),
+                Err(message) => {
+                    eprintln!("{}", message);
+                    std::process::exit(1);
+                }
+            }
+            return Ok(());
+        }
+        Ok(cli::Command::Import { file, calendar }) => {
+            match import_file(&file, calendar.as_deref()).await {
+                Ok((calendar, results)) => {
+                    for line in outcome_lines(&results) {
+                        println!("{}", line);
+                    }
+                    let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+                    println!("Imported {} of {} event(s) into {}", results.len() - failed, results.len(), calendar);
+                    if failed > 0 {
+                        std::process::exit(1);
+                    }
+                }
+                Err(message) => {
+                    eprintln!("{}", message);
+                    std::process::exit(1);
+                }
+            }
+            return Ok(());
+        }
+        Ok(cli::Command::Agenda { days, json, refresh }) => {
+            match print_agenda(days, json, refresh).await {
+                Ok(output) => print!("{}", output),
+                Err(message) => {
+                    eprintln!("{}", message);
+                    std::process::exit(1);
+                }
+            }
+            return Ok(());
+        }
+        // Also prints --help and --version
+        Err(e) => e.exit(),
+    };
+
     let mut app = App::new();
 
-    // Load config
-    app.config = Config::load().unwrap_or_default();
+    // Load config (demo mode runs without config, tokens or the disk cache)
+    if demo {
+        app.demo = true;
+        app.events = EventCache::new();
+        app.tags = tags::TagStore::default();
+        app.contacts = contacts::ContactBook::default();
+        app.set_status("Demo mode: showing generated events");
+    } else {
+        app.config = match Config::load() {
+            Ok(config) => config,
+            Err(e) => {
+                app.set_status(format!("Config error: {}", e));
+                Config::default()
+            }
+        };
+    }
+    app.unified_panel = app.config.unified_panel;
+    app.http_log.set_verbose(app.config.verbose_http);
+    app.http_log.set_capacity(app.config.http_log_capacity.unwrap_or(logging::DEFAULT_LOG_CAPACITY));
+    app.events.dedup = app.config.dedup.clone();
+    app.events.hidden_kinds = app.config.hide_event_types.clone();
+    match mute::MuteFilter::new(&app.config.mute) {
+        Ok(filter) => app.events.mute = filter,
+        Err(e) => app.set_status(format!("Config error: {}", e)),
+    }
+    match utils::MeetingPatterns::new(&app.config.meeting_patterns) {
+        Ok(patterns) => app.meeting_patterns = patterns,
+        Err(e) => app.set_status(format!("Config error: {}", e)),
+    }
+    // A time format that can't be written falls back to the default rather than failing each frame
+    if let Err(e) = app.config.time_format.validate() {
+        app.config.time_format = TimeFormatConfig::default();
+        app.set_status(format!("Config error: {}", e));
+    }
+    app.events.reconcile();
+
+    // Local vCards resolve attendee names right away
+    if let Some(dir) = app.config.contacts.vcard_dir.clone()
+        && let Err(e) = app.contacts.add_vcard_dir(&dir) {
+            app.set_status(format!("Failed to read contacts from {}: {}", dir.display(), e));
+        }
+    let contacts = &app.contacts;
+    app.events.update_events(|e| contacts.resolve_names(e));
 
     // Initialize auth states based on config
     // Track if we need to refresh Google token
     let mut google_needs_refresh: Option<String> = None;
+    // Calendars saved by older versions have no names; discovery fills them in
+    let mut icloud_needs_names = false;
 
     if app.config.google.is_some() {
         app.google_auth = GoogleAuthState::NotAuthenticated;
         // Try to load saved Google tokens
-        if let Ok(Some(tokens)) = config::load_google_tokens() {
+        let saved_tokens = config::load_google_tokens().unwrap_or_else(|e| {
+            app.set_status(e.to_string());
+            None
+        });
+        if let Some(tokens) = saved_tokens {
             if !tokens.is_expired() {
                 app.google_auth = GoogleAuthState::Authenticated(tokens);
-                app.google_needs_fetch = true;
             } else if let Some(ref refresh_token) = tokens.refresh_token {
                 // Token expired but we have a refresh token - will refresh after channel is created
                 google_needs_refresh = Some(refresh_token.clone());
@@ -86,25 +1148,48 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     if app.config.icloud.is_some() {
         app.icloud_auth = ICloudAuthState::NotAuthenticated;
         // Try to load saved iCloud discovery info
-        if let Ok(Some(icloud_tokens)) = config::load_icloud_tokens() {
-            // Use new calendars field if available, fall back to legacy calendar_urls
-            let calendars: Vec<CalendarEntry> = if !icloud_tokens.calendars.is_empty() {
-                icloud_tokens.calendars.into_iter()
-                    .map(|c| CalendarEntry { url: c.url, name: c.name })
-                    .collect()
-            } else {
-                icloud_tokens.calendar_urls.into_iter()
-                    .map(|url| CalendarEntry { url, name: None })
-                    .collect()
-            };
+        let saved_tokens = config::load_icloud_tokens().unwrap_or_else(|e| {
+            app.set_status(e.to_string());
+            None
+        });
+        if let Some(icloud_tokens) = saved_tokens {
+            let calendars = CalendarEntry::from_tokens(icloud_tokens);
             if !calendars.is_empty() {
+                icloud_needs_names = calendars.iter().any(|c| c.name.is_none());
                 app.icloud_auth = ICloudAuthState::Authenticated { calendars };
-                app.icloud_needs_fetch = true;
+            }
+        }
+    }
+    // Track if we need to refresh the Outlook token
+    let mut outlook_needs_refresh: Option<String> = None;
+    if app.config.outlook.is_some() {
+        let saved_tokens = config::load_outlook_tokens().unwrap_or_else(|e| {
+            app.set_status(e.to_string());
+            None
+        });
+        if let Some(tokens) = saved_tokens {
+            if !tokens.is_expired() {
+                app.outlook_tokens = Some(tokens);
+            } else {
+                outlook_needs_refresh = tokens.refresh_token;
             }
         }
     }
 
-    if app.config.google.is_none() && app.config.icloud.is_none() {
+    // Other Google accounts whose tokens expired are refreshed once the channel exists
+    let mut google_accounts_needing_refresh = Vec::new();
+    for (account, tokens) in config::load_google_account_tokens().unwrap_or_default() {
+        if !tokens.is_expired() {
+            app.google_accounts.insert(account, tokens);
+        } else if let Some(refresh_token) = tokens.refresh_token {
+            google_accounts_needing_refresh.push((account, refresh_token));
+        }
+    }
+
+    // Feeds are ready right away; accounts still signing in queue their month once connected
+    app.fetch_months();
+
+    if !app.demo && app.config.google.is_none() && app.config.icloud.is_none() && app.config.outlook.is_none() {
         app.set_status("No calendars configured. Edit ~/.config/calendarchy/config.json");
     }
 
@@ -112,31 +1197,95 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (tx, mut rx) = mpsc::channel::<AsyncMessage>(32);
 
     // Spawn Google token refresh if needed
-    if let Some(refresh_token) = google_needs_refresh
-        && let Some(ref google_config) = app.config.google {
-            let auth = GoogleAuth::new(google_config.clone());
+    if let Some(refresh_token) = google_needs_refresh {
+        refresh_google(&app, refresh_token, &tx);
+    }
+    if !app.demo {
+        for (account, refresh_token) in google_accounts_needing_refresh {
+            refresh_google_account(&mut app, account, refresh_token, &tx);
+        }
+    }
+
+    // Spawn Outlook token refresh if needed
+    if let Some(refresh_token) = outlook_needs_refresh
+        && !app.demo
+        && let Some(ref outlook_config) = app.config.outlook {
+            let auth = OutlookAuth::new(outlook_config.clone(), app.http_log.clone());
+            let tx = tx.clone();
+            app.tasks.spawn_cancellable(async move {
+                let message = match auth.refresh_token(&refresh_token).await {
+                    Ok(new_tokens) => AsyncMessage::OutlookToken(new_tokens),
+                    Err(e) => AsyncMessage::OutlookAuthError(format!("Token refresh failed: {}; sign in again with :outlook", e)),
+                };
+                let _ = tx.send(message).await;
+            });
+        }
+
+    // Look up the names of calendars saved without them, keeping the saved ones meanwhile
+    if icloud_needs_names
+        && !app.demo
+        && let Some(ref icloud_config) = app.config.icloud {
+            discover_icloud(&app.tasks, CalDavClient::new(ICloudAuth::new(icloud_config.clone()), app.http_log.clone()), &tx);
+        }
+
+    // Fetch the iCloud address book for attendee names
+    if !app.demo
+        && app.config.contacts.icloud
+        && let Some(ref icloud_config) = app.config.icloud {
+            let client = CardDavClient::new(ICloudAuth::new(icloud_config.clone()), app.http_log.clone());
+            let tx = tx.clone();
+            app.tasks.spawn_cancellable(async move {
+                match client.fetch_vcards().await {
+                    Ok(vcards) => {
+                        let _ = tx.send(AsyncMessage::Contacts(vcards)).await;
+                    }
+                    Err(e) => {
+                        let _ = tx.send(AsyncMessage::ContactsError(e.to_string())).await;
+                    }
+                }
+            });
+        }
+
+    // Fetch the public holiday overlay
+    if !app.demo
+        && let Some(region) = app.config.holidays.clone() {
+            let log = app.http_log.clone();
             let tx = tx.clone();
-            tokio::spawn(async move {
-                match auth.refresh_token(&refresh_token).await {
-                    Ok(new_tokens) => {
-                        let _ = tx.send(AsyncMessage::GoogleTokenRefreshed(new_tokens)).await;
+            app.tasks.spawn_cancellable(async move {
+                match holidays::fetch(&region, &log).await {
+                    Ok(holidays) => {
+                        let _ = tx.send(AsyncMessage::Holidays(holidays)).await;
                     }
                     Err(e) => {
-                        let _ = tx.send(AsyncMessage::GoogleRefreshFailed(e.to_string())).await;
+                        let _ = tx.send(AsyncMessage::HolidaysError(e.to_string())).await;
                     }
                 }
             });
         }
 
+    // Restore the terminal and write a crash report if anything panics
+    crash::install_panic_hook(app.http_log.clone());
+
     // Enable raw mode and enter alternate screen
     enable_raw_mode()?;
-    execute!(stdout(), EnterAlternateScreen, cursor::Hide)?;
+    execute!(stdout(), EnterAlternateScreen, EnableMouseCapture, cursor::Hide)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    let mut hits = ui::HitMap::default();
 
     // Main loop
     loop {
         // Clear expired status messages
         app.clear_expired_status();
 
+        // Offer to join meetings that are about to start
+        app.check_join_prompt(Local::now().naive_local());
+
+        // Say when it's time to set off for events somewhere in person
+        app.check_leave_alert(Local::now().naive_local());
+
+        // Append yesterday's meetings to its daily note after midnight
+        app.check_journal_rollover(Local::now().naive_local());
+
         // Render
         let render_state = ui::RenderState {
             current_date: app.current_date,
@@ -144,83 +1293,46 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             events: &app.events,
             google_auth: &app.google_auth,
             icloud_auth: &app.icloud_auth,
-            status_message: app.status_message.as_deref(),
-            google_loading: app.google_loading,
-            icloud_loading: app.icloud_loading,
+            status_message: app.status.current(),
+            google_loading: app.google_loading || app.fetches.is_loading(EventSource::Google),
+            icloud_loading: app.fetches.is_loading(EventSource::ICloud),
+            sync_progress: app.fetches.progress_summary(),
+            google_error: app.google_error.as_deref(),
+            icloud_error: app.icloud_error.as_deref(),
             navigation_mode: app.navigation_mode,
+            view_mode: app.view_mode,
             selected_source: app.selected_source,
             selected_event_index: app.selected_event_index,
-            show_logs: app.show_logs,
             show_weekends: app.show_weekends,
+            unified_panel: app.unified_panel,
             pending_action: app.pending_action.as_ref(),
             search: app.search.as_ref(),
+            debug_screen: app.debug_screen.as_ref(),
+            command_line: app.command_line.as_deref(),
+            info_modal: app.info_modal.as_ref(),
+            calendar_picker: app.calendar_picker.as_ref(),
+            busy_overlay: app.busy_overlay.as_ref(),
+            tags: &app.tags,
+            tag_editor: app.tag_editor.as_deref(),
+            day_filter_editing: app.day_filter_editing,
+            day_jump: app.day_jump.as_deref(),
+            moving: app.moving.as_ref(),
+            marked: &app.marked,
+            last_synced: app.last_synced,
+            config: &app.config,
+            http_log: &app.http_log,
         };
-        ui::render(&render_state);
-
-        // Check if we need to fetch Google events
-        if app.google_needs_fetch {
-            if let GoogleAuthState::Authenticated(ref tokens) = app.google_auth {
-                let (start, end) = app.month_range();
-                if !app.events.google.has_month(start) {
-                    let tokens = tokens.clone();
-                    let calendar_id = app.config.google.as_ref()
-                        .map(|c| c.calendar_id.clone())
-                        .unwrap_or_else(|| "primary".to_string());
-                    let tx = tx.clone();
-
-                    app.google_loading = true;
-                    let calendar_id_clone = calendar_id.clone();
-                    tokio::spawn(async move {
-                        let client = CalendarClient::new();
-                        // Get calendar display name
-                        let calendar_name = client.get_calendar_name(&tokens, &calendar_id).await.ok().flatten();
-                        match client.list_events(&tokens, &calendar_id, start, end).await {
-                            Ok(events) => {
-                                let _ = tx.send(AsyncMessage::GoogleEvents(events, start, calendar_id_clone, calendar_name)).await;
-                            }
-                            Err(e) => {
-                                let _ = tx.send(AsyncMessage::GoogleFetchError(e.to_string())).await;
-                            }
-                        }
-                    });
-                }
-            }
-            app.google_needs_fetch = false;
-        }
-
-        // Check if we need to fetch iCloud events
-        if app.icloud_needs_fetch {
-            if let ICloudAuthState::Authenticated { ref calendars } = app.icloud_auth {
-                let (start, end) = app.month_range();
-                if !app.events.icloud.has_month(start)
-                    && let Some(ref icloud_config) = app.config.icloud {
-                        let auth = ICloudAuth::new(icloud_config.clone());
-                        let client = CalDavClient::new(auth);
-                        let calendars = calendars.clone();
-                        let tx = tx.clone();
-
-                        app.icloud_loading = true;
-                        tokio::spawn(async move {
-                            let mut all_events: Vec<(ICalEvent, Option<String>)> = Vec::new();
-                            for cal in &calendars {
-                                match client.fetch_events(&cal.url, start, end).await {
-                                    Ok(events) => {
-                                        for e in events {
-                                            all_events.push((e, cal.name.clone()));
-                                        }
-                                    }
-                                    Err(e) => {
-                                        let _ = tx.send(AsyncMessage::ICloudFetchError(e.to_string())).await;
-                                        return;
-                                    }
-                                }
-                            }
-                            let _ = tx.send(AsyncMessage::ICloudEvents(all_events, start)).await;
-                        });
-                    }
-            }
-            app.icloud_needs_fetch = false;
-        }
+        let render_started = std::time::Instant::now();
+        terminal.draw(|frame| hits = ui::render(frame, &render_state))?;
+        app.http_log.record_render(render_started.elapsed());
+
+        app.refresh_if_due(Instant::now());
+        pump_fetches(&mut app, &tx);
+        search_servers(&mut app, &tx);
+        fetch_selected_details(&mut app, &tx);
+        fetch_busy_overlay(&mut app, &tx);
+        probe_connectivity(&mut app, &tx);
+        refresh_google_tokens(&mut app, &tx);
 
         // Handle async messages (non-blocking)
         while let Ok(msg) = rx.try_recv() {
@@ -242,145 +1354,299 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 AsyncMessage::GoogleToken(tokens) => {
                     let _ = config::save_google_tokens(&tokens);
                     app.google_auth = GoogleAuthState::Authenticated(tokens);
-                    app.google_needs_fetch = true;
-                    app.set_status("Connected to Google Calendar!");
-                }
-                AsyncMessage::GoogleAuthPending => {}
-                AsyncMessage::GoogleAuthError(msg) => {
-                    app.google_auth = GoogleAuthState::Error(msg);
-                }
-                AsyncMessage::GoogleEvents(events, month_date, calendar_id, calendar_name) => {
-                    let display_events: Vec<DisplayEvent> = events
-                        .into_iter()
-                        .filter_map(|e| google_event_to_display(e, calendar_id.clone(), calendar_name.clone()))
-                        .collect();
-                    app.events.google.store(display_events, month_date);
-                    app.events.save_to_disk();
-                    app.google_loading = false;
+                    app.fetch_month(EventSource::Google);
+                    app.set_status("Connected to Google Calendar!");
                 }
-                AsyncMessage::GoogleFetchError(msg) => {
-                    app.set_status(format!("Google: {}", msg));
-                    app.google_loading = false;
+                AsyncMessage::GoogleAuthError(msg) => {
+                    app.google_auth = GoogleAuthState::Error(msg);
                 }
                 AsyncMessage::GoogleTokenRefreshed(tokens) => {
                     let _ = config::save_google_tokens(&tokens);
                     app.google_auth = GoogleAuthState::Authenticated(tokens);
-                    app.google_needs_fetch = true;
+                    app.fetch_month(EventSource::Google);
                     app.google_loading = false;
                 }
                 AsyncMessage::GoogleRefreshFailed(msg) => {
                     app.google_auth = GoogleAuthState::NotAuthenticated;
-                    app.set_status(format!("Token refresh failed: {}", msg));
+                    app.google_error = Some(format!("Token refresh failed: {}", msg));
                     app.google_loading = false;
                 }
 
+                // Other Google accounts
+                AsyncMessage::GoogleAccountDeviceCode { account, user_code, verification_url } => {
+                    app.info_modal = Some(InfoModal::new(GOOGLE_ACCOUNT_SIGN_IN, vec![
+                        format!("Sign in as {}:", account),
+                        format!("open {}", verification_url),
+                        format!("and enter the code {}", user_code),
+                        String::new(),
+                        "Events show up once the sign-in is approved.".to_string(),
+                    ]));
+                }
+                AsyncMessage::GoogleAccountToken { account, tokens } => {
+                    let _ = config::save_google_account_tokens(&account, &tokens);
+                    app.google_account_refreshes.remove(&account);
+                    let signed_in = app.google_accounts.insert(account.clone(), tokens).is_none();
+                    if app.info_modal.as_ref().is_some_and(|m| m.title == GOOGLE_ACCOUNT_SIGN_IN) {
+                        app.info_modal = None;
+                    }
+                    app.reload_events();
+                    if signed_in {
+                        app.set_status(format!("Connected to Google as {}!", account));
+                    }
+                }
+                AsyncMessage::GoogleAccountTokenRefreshed { account, tokens } => {
+                    let _ = config::save_google_account_tokens(&account, &tokens);
+                    app.google_account_refreshes.remove(&account);
+                    // Accounts whose tokens had expired by startup weren't fetched yet
+                    if app.google_accounts.insert(account, tokens).is_none() {
+                        app.reload_events();
+                    }
+                }
+                AsyncMessage::GoogleAccountAuthError { account, message } => {
+                    if app.info_modal.as_ref().is_some_and(|m| m.title == GOOGLE_ACCOUNT_SIGN_IN) {
+                        app.info_modal = None;
+                    }
+                    app.set_status(format!("Google ({}): {}", account, message));
+                }
+
+                // Outlook messages
+                AsyncMessage::OutlookDeviceCode { user_code, verification_uri } => {
+                    app.info_modal = Some(InfoModal::new(OUTLOOK_SIGN_IN, vec![
+                        format!("Open {}", verification_uri),
+                        format!("and enter the code {}", user_code),
+                        String::new(),
+                        "Events show up once the sign-in is approved.".to_string(),
+                    ]));
+                }
+                AsyncMessage::OutlookToken(tokens) => {
+                    let _ = config::save_outlook_tokens(&tokens);
+                    let signed_in = app.outlook_tokens.is_none();
+                    app.outlook_tokens = Some(tokens);
+                    if app.info_modal.as_ref().is_some_and(|m| m.title == OUTLOOK_SIGN_IN) {
+                        app.info_modal = None;
+                    }
+                    app.reload_events();
+                    if signed_in {
+                        app.set_status("Connected to Outlook!");
+                    }
+                }
+                AsyncMessage::OutlookAuthError(msg) => {
+                    if app.info_modal.as_ref().is_some_and(|m| m.title == OUTLOOK_SIGN_IN) {
+                        app.info_modal = None;
+                    }
+                    app.set_status(format!("Outlook: {}", msg));
+                }
+
                 // iCloud messages
-                AsyncMessage::ICloudDiscovered { calendars } => {
-                    let stored: Vec<config::StoredCalendar> = calendars.iter()
-                        .map(|c| config::StoredCalendar { url: c.url.clone(), name: c.name.clone() })
-                        .collect();
+                AsyncMessage::ICloudDiscovered { mut calendars } => {
+                    // Keep calendars unchecked in the picker unchecked
+                    let picked = matches!(app.icloud_auth, ICloudAuthState::Discovering { .. });
+                    let hidden = app.icloud_auth.hidden_calendars();
+                    for calendar in &mut calendars {
+                        calendar.hidden = hidden.contains(&calendar.url);
+                    }
+                    let stored: Vec<config::StoredCalendar> = calendars.iter().map(CalendarEntry::to_stored).collect();
                     let _ = config::save_icloud_tokens(&stored);
+                    // Name the calendars of events already on screen
+                    app.events.update_events(|event| {
+                        if let EventId::ICloud { calendar_url, calendar_name, .. } = &mut event.id
+                            && let Some(calendar) = calendars.iter().find(|c| c.url == *calendar_url) {
+                                *calendar_name = calendar.name.clone();
+                            }
+                    });
                     let count = calendars.len();
                     app.icloud_auth = ICloudAuthState::Authenticated { calendars };
-                    app.icloud_needs_fetch = true;
+                    app.fetch_month(EventSource::ICloud);
                     app.set_status(format!("Connected to {} iCloud calendar(s)!", count));
+                    // Discovery the user asked for ends with choosing what to show
+                    if picked {
+                        app.open_calendar_picker();
+                    }
+                }
+                AsyncMessage::ICloudCredentialsRejected { status, body } => {
+                    let mut lines = app.config.icloud.as_ref()
+                        .map(|c| icloud::credential_guidance(c, status, &body))
+                        .unwrap_or_default();
+                    lines.push(String::new());
+                    lines.push(format!("Fix {}, then press i to retry.", Config::config_path().display()));
+                    if !matches!(app.icloud_auth, ICloudAuthState::Authenticated { .. }) {
+                        app.icloud_auth = ICloudAuthState::Error(format!("iCloud refused the sign-in (HTTP {})", status));
+                    }
+                    app.info_modal = Some(InfoModal::new("iCloud sign-in", lines));
                 }
                 AsyncMessage::ICloudDiscoveryError(msg) => {
-                    app.icloud_auth = ICloudAuthState::Error(msg);
-                }
-                AsyncMessage::ICloudEvents(events, month_date) => {
-                    let display_events: Vec<DisplayEvent> = events
-                        .into_iter()
-                        .map(|(e, calendar_name)| icloud_event_to_display(e, calendar_name))
-                        .collect();
-                    app.events.icloud.store(display_events, month_date);
-                    app.events.save_to_disk();
-                    app.icloud_loading = false;
+                    // A background refresh of calendar names keeps the saved calendars working
+                    if matches!(app.icloud_auth, ICloudAuthState::Authenticated { .. }) {
+                        app.set_status(format!("Could not refresh iCloud calendars: {}", msg));
+                    } else {
+                        app.icloud_auth = ICloudAuthState::Error(msg);
+                    }
+                }
+                // Month fetches
+                AsyncMessage::Fetch(FetchMessage::Progress { source, month, generation, done }) => {
+                    app.fetches.progress(source, month, generation, done);
+                }
+                AsyncMessage::Fetch(FetchMessage::Finished { source, month, generation, events, errors, offline }) => {
+                    // Results of fetches started before a reload are dropped
+                    if app.fetches.finish(source, month, generation) {
+                        let fetched = events.is_some();
+                        if let Some(mut events) = events {
+                            events.iter_mut().for_each(|e| app.contacts.resolve_names(e));
+                            // Keep the selection on the same event, wherever the refetch put it
+                            let selected = app.get_selected_event().map(|e| e.id.clone());
+                            app.events.store(source, events, month);
+                            if let Some(id) = selected {
+                                app.reselect(&id);
+                            }
+                            if !app.demo {
+                                app.events.save_to_disk();
+                            }
+                            app.last_synced = Some(Local::now().time());
+                        }
+                        let error = (!errors.is_empty()).then(|| errors.join("; "));
+                        match source {
+                            EventSource::Google => app.google_error = error,
+                            EventSource::ICloud => app.icloud_error = error,
+                        }
+                        // The first answer after going offline also refetches the other panel
+                        if offline {
+                            app.offline_since.get_or_insert_with(Instant::now);
+                        } else if fetched && app.offline_since.is_some() {
+                            app.reconnected();
+                        }
+                    }
                 }
-                AsyncMessage::ICloudFetchError(msg) => {
-                    app.set_status(format!("iCloud: {}", msg));
-                    app.icloud_loading = false;
+                AsyncMessage::Reconnected => {
+                    if app.offline_since.is_some() {
+                        app.reconnected();
+                    }
                 }
 
                 // Event action messages
                 AsyncMessage::EventActionSuccess(msg) => {
                     app.set_status(msg);
-                    // Refresh events to reflect the change
-                    app.events.clear();
-                    app.google_needs_fetch = true;
-                    app.icloud_needs_fetch = true;
-                    // Exit event mode after action
-                    app.exit_event_mode();
+                    // Refresh events to reflect the change, staying on the same position
+                    // so the next event can be handled right away (e.g. with `.`)
+                    app.reload_events();
                 }
                 AsyncMessage::EventActionError(msg) => {
                     app.set_status(msg);
                 }
-            }
-        }
+                AsyncMessage::BulkActionDone(action, results) => {
+                    let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+                    app.info_modal = Some(InfoModal::new(
+                        format!("{} {} event(s)", action.label(), results.len()),
+                        outcome_lines(&results),
+                    ));
+                    app.set_status(format!("{} of {} succeeded", results.len() - failed, results.len()));
+                    app.reload_events();
+                }
+                AsyncMessage::Imported { calendar, results } => {
+                    let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+                    app.info_modal = Some(InfoModal::new(format!("Import into {}", calendar), outcome_lines(&results)));
+                    app.set_status(format!("Imported {} of {} event(s)", results.len() - failed, results.len()));
+                    app.reload_events();
+                }
 
-        // Poll for Google device code if awaiting
-        if let GoogleAuthState::AwaitingUserCode { ref device_code, expires_at, .. } = app.google_auth
-            && Utc::now() < expires_at
-                && let Some(ref google_config) = app.config.google {
-                    let auth = GoogleAuth::new(google_config.clone());
-                    let device_code = device_code.clone();
-                    let tx = tx.clone();
+                // Contacts
+                AsyncMessage::Contacts(vcards) => {
+                    for vcard in &vcards {
+                        app.contacts.add_vcards(vcard);
+                    }
+                    app.contacts.save();
+                    let contacts = &app.contacts;
+                    app.events.update_events(|e| contacts.resolve_names(e));
+                }
+                AsyncMessage::ContactsError(msg) => {
+                    app.set_status(format!("Failed to load contacts: {}", msg));
+                }
 
-                    tokio::spawn(async move {
-                        tokio::time::sleep(StdDuration::from_secs(5)).await;
-                        match auth.poll_for_token(&device_code).await {
-                            Ok(google::auth::PollResult::Success(tokens)) => {
-                                let _ = tx.send(AsyncMessage::GoogleToken(tokens)).await;
-                            }
-                            Ok(google::auth::PollResult::Pending) => {
-                                let _ = tx.send(AsyncMessage::GoogleAuthPending).await;
-                            }
-                            Ok(google::auth::PollResult::Denied) => {
-                                let _ = tx.send(AsyncMessage::GoogleAuthError("Access denied".to_string())).await;
-                            }
-                            Ok(google::auth::PollResult::Expired) => {
-                                let _ = tx.send(AsyncMessage::GoogleAuthError("Code expired".to_string())).await;
-                            }
-                            Ok(google::auth::PollResult::SlowDown) => {
-                                let _ = tx.send(AsyncMessage::GoogleAuthPending).await;
-                            }
-                            Err(e) => {
-                                let _ = tx.send(AsyncMessage::GoogleAuthError(e.to_string())).await;
-                            }
-                        }
+                AsyncMessage::EventDetails(id, details) => {
+                    app.events.set_details(id, details);
+                    app.events.save_to_disk();
+                }
+                AsyncMessage::EventDetailsError(msg) => {
+                    app.set_status(format!("Failed to load event details: {}", msg));
+                }
+
+                // Holiday overlay
+                AsyncMessage::Holidays(holidays) => {
+                    app.events.set_holidays(holidays);
+                    app.events.save_to_disk();
+                }
+                AsyncMessage::HolidaysError(msg) => {
+                    app.set_status(format!("Failed to load holidays: {}", msg));
+                }
+
+                // Diagnostics
+                AsyncMessage::DoctorReport(checks) => {
+                    let lines = checks.iter().map(|c| c.to_line()).collect();
+                    app.info_modal = Some(InfoModal::new("Doctor", lines));
+                }
+                AsyncMessage::GoogleCalendars { calendars, shared } => {
+                    app.info_modal = Some(if shared {
+                        app.shared_calendars(&calendars)
+                    } else {
+                        app.google_calendars(&calendars)
                     });
                 }
+                AsyncMessage::GoogleCalendarsError(msg) => {
+                    app.set_status(format!("Failed to list calendars: {}", msg));
+                }
+                AsyncMessage::FreeBusy { email, busy } => {
+                    // Answers for an overlay that was since replaced are dropped
+                    if let Some(ref mut overlay) = app.busy_overlay
+                        && overlay.email == email {
+                            overlay.set_busy(&busy);
+                        }
+                }
+                AsyncMessage::FreeBusyError { email, error } => {
+                    if app.busy_overlay.as_ref().is_some_and(|o| o.email == email) {
+                        app.busy_overlay = None;
+                        app.set_status(error);
+                    }
+                }
+                AsyncMessage::SearchResults { query, events } => {
+                    app.apply_remote_search(&query, events);
+                }
+            }
+        }
 
         // Handle input events with timeout
         if event::poll(StdDuration::from_millis(100))? {
             match event::read()? {
-                Event::Resize(_, _) => {
-                    // Clear screen on resize - next loop iteration will re-render
-                    execute!(stdout(), Clear(ClearType::All)).ok();
-                }
+                // The next draw resizes the buffers and repaints the whole screen
+                Event::Resize(_, _) => {}
+                Event::Mouse(mouse) => handle_mouse(&mut app, &hits, mouse),
                 Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                    crash::record_action(format!("key {:?} {:?} ({:?} mode)", key_event.code, key_event.modifiers, app.navigation_mode));
                     // Handle search mode input first
                     if app.search.is_some() {
                         match key_event.code {
                             KeyCode::Esc => {
                                 app.close_search();
-                                execute!(stdout(), Clear(ClearType::All)).ok();
                             }
                             KeyCode::Enter => {
                                 app.select_search_result();
-                                execute!(stdout(), Clear(ClearType::All)).ok();
                             }
                             KeyCode::Backspace => {
                                 if let Some(ref mut search) = app.search {
                                     search.query.pop();
+                                    search.edited_at = Instant::now();
                                 }
                                 app.update_search_results();
                             }
-                            KeyCode::Down | KeyCode::Tab => {
+                            KeyCode::Char('p') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
                                 if let Some(ref mut search) = app.search {
-                                    if !search.results.is_empty() {
-                                        search.selected_index = (search.selected_index + 1).min(search.results.len() - 1);
-                                    }
+                                    search.include_past = !search.include_past;
+                                }
+                                app.update_search_results();
+                            }
+                            KeyCode::Down | KeyCode::Tab => {
+                                if let Some(ref mut search) = app.search
+                                    && !search.results.is_empty()
+                                {
+                                    search.selected_index = (search.selected_index + 1).min(search.results.len() - 1);
                                 }
                             }
                             KeyCode::Up | KeyCode::BackTab => {
@@ -391,6 +1657,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             KeyCode::Char(c) => {
                                 if let Some(ref mut search) = app.search {
                                     search.query.push(c);
+                                    search.edited_at = Instant::now();
                                 }
                                 app.update_search_results();
                             }
@@ -399,86 +1666,222 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         continue;
                     }
 
-                    // Handle pending confirmation first
-                    if let Some(action) = app.pending_action.take() {
+                    // Handle debug screen input
+                    if let Some(ref mut debug) = app.debug_screen {
+                        match (key_event.code, key_event.modifiers) {
+                            (KeyCode::Char('j') | KeyCode::Char('й') | KeyCode::Down, _) => {
+                                debug.scroll += 1;
+                            }
+                            (KeyCode::Char('k') | KeyCode::Char('к') | KeyCode::Up, _) => {
+                                debug.scroll = debug.scroll.saturating_sub(1);
+                            }
+                            (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
+                                debug.scroll += 10;
+                            }
+                            (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
+                                debug.scroll = debug.scroll.saturating_sub(10);
+                            }
+                            (KeyCode::Char('v') | KeyCode::Char('ж'), _) => {
+                                let verbose = !app.http_log.is_verbose();
+                                app.http_log.set_verbose(verbose);
+                                app.set_status(if verbose { "Verbose HTTP capture on" } else { "Verbose HTTP capture off" });
+                            }
+                            (KeyCode::Tab, _) => {
+                                debug.switch_tab();
+                            }
+                            (KeyCode::Char('s') | KeyCode::Char('с'), _) => {
+                                debug.cycle_source_filter();
+                            }
+                            (KeyCode::Char('c') | KeyCode::Char('ц'), _) => {
+                                debug.cycle_status_filter();
+                            }
+                            (KeyCode::Char('p') | KeyCode::Char('п'), _) => {
+                                debug.toggle_pause(&app.http_log);
+                            }
+                            (KeyCode::Char('e') | KeyCode::Char('е'), _) => {
+                                match app.http_log.export_debug_report() {
+                                    Ok(path) => app.set_status(format!("Debug report written to {}", path.display())),
+                                    Err(e) => app.set_status(format!("Failed to write debug report: {}", e)),
+                                }
+                            }
+                            (KeyCode::Esc | KeyCode::Char('D') | KeyCode::Char('V') | KeyCode::Char('q') | KeyCode::Char('я'), _) => {
+                                app.debug_screen = None;
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    // Handle calendar picker input
+                    if let Some(ref mut picker) = app.calendar_picker {
                         match key_event.code {
-                            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
-                                // Execute the confirmed action
-                                match action {
-                                    PendingAction::AcceptEvent { calendar_id, event_id } => {
-                                        if let GoogleAuthState::Authenticated(ref tokens) = app.google_auth {
-                                            let tokens = tokens.clone();
-                                            let tx = tx.clone();
-                                            tokio::spawn(async move {
-                                                let client = CalendarClient::new();
-                                                match client.respond_to_event(&tokens, &calendar_id, &event_id, "accepted").await {
-                                                    Ok(()) => {
-                                                        let _ = tx.send(AsyncMessage::EventActionSuccess("Event accepted".to_string())).await;
-                                                    }
-                                                    Err(e) => {
-                                                        let _ = tx.send(AsyncMessage::EventActionError(format!("Failed to accept: {}", e))).await;
-                                                    }
-                                                }
-                                            });
-                                            app.set_status("Accepting event...");
-                                        }
+                            KeyCode::Char('j') | KeyCode::Char('й') | KeyCode::Down => picker.move_down(),
+                            KeyCode::Char('k') | KeyCode::Char('к') | KeyCode::Up => picker.move_up(),
+                            KeyCode::Char(' ') => picker.toggle(),
+                            KeyCode::Enter => {
+                                app.apply_calendar_picker();
+                            }
+                            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('я') => {
+                                app.calendar_picker = None;
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    // Handle info modal input
+                    if let Some(ref mut modal) = app.info_modal {
+                        match key_event.code {
+                            KeyCode::Char('j') | KeyCode::Char('й') | KeyCode::Down => modal.scroll_down(1),
+                            KeyCode::Char('k') | KeyCode::Char('к') | KeyCode::Up => modal.scroll_up(1),
+                            KeyCode::Char('e') | KeyCode::Char('е') if modal.export_range.is_some() => {
+                                if let Some((from, to)) = modal.export_range {
+                                    app.info_modal = None;
+                                    app.export_range(from, to);
+                                }
+                            }
+                            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('я') => {
+                                app.info_modal = None;
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    // Handle command line input
+                    if let Some(ref mut command) = app.command_line {
+                        match key_event.code {
+                            KeyCode::Esc => {
+                                app.command_line = None;
+                                app.new_event_draft = None;
+                            }
+                            KeyCode::Backspace if command.is_empty() => {
+                                app.command_line = None;
+                                app.new_event_draft = None;
+                            }
+                            KeyCode::Backspace => {
+                                command.pop();
+                            }
+                            KeyCode::Char(c) => {
+                                command.push(c);
+                            }
+                            KeyCode::Enter => {
+                                let command = app.command_line.take().unwrap_or_default();
+                                let command = command.trim();
+                                let (name, arg) = command.split_once(' ').unwrap_or((command, ""));
+                                match name {
+                                    "" => {}
+                                    "doctor" => {
+                                        let tx = tx.clone();
+                                        app.tasks.spawn_cancellable(async move {
+                                            let checks = doctor::run_checks().await;
+                                            let _ = tx.send(AsyncMessage::DoctorReport(checks)).await;
+                                        });
+                                        app.set_status("Running diagnostics...");
                                     }
-                                    PendingAction::DeclineEvent { calendar_id, event_id } => {
-                                        if let GoogleAuthState::Authenticated(ref tokens) = app.google_auth {
-                                            let tokens = tokens.clone();
-                                            let tx = tx.clone();
-                                            tokio::spawn(async move {
-                                                let client = CalendarClient::new();
-                                                match client.respond_to_event(&tokens, &calendar_id, &event_id, "declined").await {
-                                                    Ok(()) => {
-                                                        let _ = tx.send(AsyncMessage::EventActionSuccess("Event declined".to_string())).await;
-                                                    }
-                                                    Err(e) => {
-                                                        let _ = tx.send(AsyncMessage::EventActionError(format!("Failed to decline: {}", e))).await;
-                                                    }
-                                                }
-                                            });
-                                            app.set_status("Declining event...");
-                                        }
+                                    "tag" | "tags" => {
+                                        app.info_modal = Some(app.tag_agenda(arg));
                                     }
-                                    PendingAction::DeleteGoogleEvent { calendar_id, event_id } => {
-                                        if let GoogleAuthState::Authenticated(ref tokens) = app.google_auth {
-                                            let tokens = tokens.clone();
-                                            let tx = tx.clone();
-                                            tokio::spawn(async move {
-                                                let client = CalendarClient::new();
-                                                match client.delete_event(&tokens, &calendar_id, &event_id).await {
-                                                    Ok(()) => {
-                                                        let _ = tx.send(AsyncMessage::EventActionSuccess("Event deleted".to_string())).await;
-                                                    }
-                                                    Err(e) => {
-                                                        let _ = tx.send(AsyncMessage::EventActionError(format!("Failed to delete: {}", e))).await;
-                                                    }
-                                                }
-                                            });
-                                            app.set_status("Deleting event...");
-                                        }
+                                    "copy" => {
+                                        copy_selected_event(&mut app, arg, &tx);
                                     }
-                                    PendingAction::DeleteICloudEvent { calendar_url, event_uid, etag } => {
-                                        if let Some(ref icloud_config) = app.config.icloud {
-                                            let auth = ICloudAuth::new(icloud_config.clone());
-                                            let client = CalDavClient::new(auth);
-                                            let tx = tx.clone();
-                                            tokio::spawn(async move {
-                                                match client.delete_event(&calendar_url, &event_uid, etag.as_deref()).await {
-                                                    Ok(()) => {
-                                                        let _ = tx.send(AsyncMessage::EventActionSuccess("Event deleted".to_string())).await;
-                                                    }
-                                                    Err(e) => {
-                                                        let _ = tx.send(AsyncMessage::EventActionError(format!("Failed to delete: {}", e))).await;
-                                                    }
-                                                }
-                                            });
-                                            app.set_status("Deleting event...");
-                                        }
+                                    "unmark" => {
+                                        app.clear_marks();
+                                    }
+                                    "new" => {
+                                        create_new_event(&mut app, arg, false, &tx);
+                                    }
+                                    "add" => {
+                                        create_new_event(&mut app, arg, true, &tx);
+                                    }
+                                    "confirm" => {
+                                        app.set_confirm_override(arg);
+                                    }
+                                    "brief" => {
+                                        let lines = brief::compose(&app.events, app.selected_date, &app.config.work_hours, &app.config.time_format);
+                                        app.info_modal = Some(InfoModal::new("Briefing", lines));
+                                    }
+                                    "export" => {
+                                        app.export_csv(arg);
+                                    }
+                                    "stats" => {
+                                        app.info_modal = Some(app.stats(arg));
+                                    }
+                                    "person" => {
+                                        app.info_modal = Some(app.person_stats(arg, Local::now().date_naive()));
+                                    }
+                                    "slots" => {
+                                        app.info_modal = Some(app.find_slots(arg));
+                                    }
+                                    "review" => {
+                                        app.weekly_review(arg);
+                                    }
+                                    "calendars" if arg == "google" => {
+                                        list_google_calendars(&mut app, &tx, false);
+                                    }
+                                    "calendars" => {
+                                        app.open_calendar_picker();
+                                    }
+                                    "unhide" => {
+                                        app.unsnooze_day();
+                                    }
+                                    "overlay" => {
+                                        app.set_busy_overlay(arg);
+                                    }
+                                    "shared" => {
+                                        list_google_calendars(&mut app, &tx, true);
+                                    }
+                                    "outlook" => {
+                                        sign_in_outlook(&mut app, &tx);
+                                    }
+                                    "google" => {
+                                        sign_in_google_account(&mut app, &tx, arg);
+                                    }
+                                    "import" => {
+                                        import_ics_file(&mut app, arg, &tx);
+                                    }
+                                    "propose" => {
+                                        app.propose_new_time();
+                                    }
+                                    "journal" => {
+                                        app.write_journal(app.selected_date, Local::now().naive_local());
+                                    }
+                                    other => {
+                                        app.set_status(format!("Unknown command: {}", other));
                                     }
                                 }
                             }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    // Handle tag editor input
+                    if let Some(ref mut input) = app.tag_editor {
+                        match key_event.code {
+                            KeyCode::Esc => {
+                                app.tag_editor = None;
+                            }
+                            KeyCode::Backspace => {
+                                input.pop();
+                            }
+                            KeyCode::Char(c) => {
+                                input.push(c);
+                            }
+                            KeyCode::Enter => {
+                                app.save_tag_editor();
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    // Handle pending confirmation first
+                    if let Some(action) = app.pending_action.take() {
+                        match key_event.code {
+                            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                                perform_action(&mut app, action, &tx);
+                            }
                             KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                                 // Cancel - action already taken from pending_action
                                 app.set_status("Cancelled");
@@ -491,14 +1894,44 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         continue;
                     }
 
+                    // Handle in-day filter input: Enter keeps the filter, Esc drops it
+                    if app.day_filter_editing {
+                        let mut text = app.events.day_filter.as_ref().map(|(_, q)| q.clone()).unwrap_or_default();
+                        match key_event.code {
+                            KeyCode::Esc => app.clear_day_filter(),
+                            KeyCode::Enter => app.day_filter_editing = false,
+                            KeyCode::Backspace => {
+                                text.pop();
+                                app.set_day_filter(&text);
+                            }
+                            KeyCode::Char(c) => {
+                                text.push(c);
+                                app.set_day_filter(&text);
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
                     // Handle Event navigation mode
                     if app.navigation_mode == NavigationMode::Event {
+                        // Any key but a digit ends a count prefix
+                        let pending_count = app.count.take();
+                        let count = pending_count.unwrap_or(1);
                         match (key_event.code, key_event.modifiers) {
+                            (KeyCode::Char(c @ '0'..='9'), _) if c != '0' || pending_count.is_some() => {
+                                let digit = c.to_digit(10).unwrap_or(0) as usize;
+                                app.count = Some((pending_count.unwrap_or(0) * 10 + digit).min(999));
+                            }
                             (KeyCode::Char('j') | KeyCode::Char('й') | KeyCode::Down, _) => {
-                                app.next_event();
+                                for _ in 0..count {
+                                    app.next_event();
+                                }
                             }
                             (KeyCode::Char('k') | KeyCode::Char('к') | KeyCode::Up, _) => {
-                                app.prev_event();
+                                for _ in 0..count {
+                                    app.prev_event();
+                                }
                             }
                             (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
                                 // Scroll down 10 events
@@ -512,93 +1945,128 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     app.prev_event();
                                 }
                             }
+                            (KeyCode::Enter, _) => {
+                                app.activate_selected_event();
+                            }
                             (KeyCode::Char('J'), _) => {
                                 // Join meeting
-                                if let Some(event) = app.get_selected_event()
-                                    && let Some(ref url) = event.meeting_url {
-                                        let _ = std::process::Command::new("xdg-open")
-                                            .arg(url)
-                                            .process_group(0)
-                                            .spawn();
-                                    }
+                                if let Some(url) = app.get_selected_event().and_then(|e| e.meeting_url.clone()) {
+                                    app.open_url(&url);
+                                }
                             }
-                            (KeyCode::Char('a') | KeyCode::Char('а'), _) => {
-                                // Accept event (Google only) - set pending action
-                                if let Some(event) = app.get_selected_event() {
-                                    if let EventId::Google { calendar_id, event_id, .. } = event.id.clone() {
-                                        if matches!(app.google_auth, GoogleAuthState::Authenticated(_)) {
-                                            app.pending_action = Some(PendingAction::AcceptEvent { calendar_id, event_id });
-                                        }
-                                    } else {
-                                        app.set_status("Accept not supported for iCloud");
-                                    }
+                            (KeyCode::Char('m') | KeyCode::Char('м'), _) => {
+                                app.email_attendees();
+                            }
+                            (KeyCode::Char('P'), _) => {
+                                // Dial in by phone, PIN included
+                                if let Some(tel) = app.get_selected_event().and_then(|e| e.dial_in.clone()) {
+                                    app.dial(&tel);
                                 }
                             }
-                            (KeyCode::Char('d') | KeyCode::Char('д'), m) if !m.contains(KeyModifiers::CONTROL) => {
-                                // Decline event (Google only) - set pending action
-                                if let Some(event) = app.get_selected_event() {
-                                    if let EventId::Google { calendar_id, event_id, .. } = event.id.clone() {
-                                        if matches!(app.google_auth, GoogleAuthState::Authenticated(_)) {
-                                            app.pending_action = Some(PendingAction::DeclineEvent { calendar_id, event_id });
+                            (KeyCode::Char('Q'), _) => {
+                                // Show the meeting link as a QR code for joining from a phone
+                                if let Some(url) = app.get_selected_event().and_then(|e| e.meeting_url.clone()) {
+                                    match utils::qr_code_lines(&url) {
+                                        Some(mut lines) => {
+                                            lines.push(String::new());
+                                            lines.push(url);
+                                            app.info_modal = Some(InfoModal::new("Meeting QR", lines));
                                         }
-                                    } else {
-                                        app.set_status("Decline not supported for iCloud");
+                                        None => app.set_status("Meeting link too long for a QR code"),
                                     }
                                 }
                             }
+                            (KeyCode::Char('a') | KeyCode::Char('а'), _) => {
+                                // Accept event - set pending action
+                                request_action(&mut app, RepeatableAction::Accept, &tx);
+                            }
+                            (KeyCode::Char('A'), _) => {
+                                // Accept the invitation and join the call in one go
+                                app.request_accept_and_join();
+                                if let Some(action) = app.take_unconfirmed_action() {
+                                    perform_action(&mut app, action, &tx);
+                                }
+                            }
+                            (KeyCode::Char('d') | KeyCode::Char('д'), m) if !m.contains(KeyModifiers::CONTROL) => {
+                                // Decline event - set pending action
+                                request_action(&mut app, RepeatableAction::Decline, &tx);
+                            }
                             (KeyCode::Char('x') | KeyCode::Char('ь'), _) => {
                                 // Delete event - set pending action
-                                if let Some(event) = app.get_selected_event() {
-                                    match event.id.clone() {
-                                        EventId::Google { calendar_id, event_id, .. } => {
-                                            if matches!(app.google_auth, GoogleAuthState::Authenticated(_)) {
-                                                app.pending_action = Some(PendingAction::DeleteGoogleEvent { calendar_id, event_id });
-                                            }
-                                        }
-                                        EventId::ICloud { calendar_url, event_uid, etag, .. } => {
-                                            if app.config.icloud.is_some() {
-                                                app.pending_action = Some(PendingAction::DeleteICloudEvent { calendar_url, event_uid, etag });
-                                            }
-                                        }
-                                    }
+                                request_action(&mut app, RepeatableAction::Delete, &tx);
+                            }
+                            (KeyCode::Char('.'), _) => {
+                                // Repeat the last accept/decline/delete on the selected event
+                                match app.last_action {
+                                    Some(action) => request_action(&mut app, action, &tx),
+                                    None => app.set_status("Nothing to repeat"),
                                 }
                             }
+                            (KeyCode::Char('#'), _) => {
+                                app.open_tag_editor();
+                            }
+                            (KeyCode::Char('v') | KeyCode::Char('ж'), _) => {
+                                // Mark for a bulk decline, delete, tag or export
+                                app.toggle_mark();
+                            }
+                            (KeyCode::Char('h') | KeyCode::Char('х'), _) => {
+                                // Hide for the day, here only; ":unhide" brings it back
+                                app.snooze_selected();
+                            }
+                            (KeyCode::Char('M'), _) => {
+                                // Move to another day, picked in Day mode
+                                app.start_move();
+                            }
+                            (KeyCode::Char('c'), _) => {
+                                // Copy to another calendar: ":copy <calendar>"
+                                app.command_line = Some("copy ".to_string());
+                            }
                             (KeyCode::Char('t') | KeyCode::Char('т'), _) => {
                                 app.goto_today();
                             }
                             (KeyCode::Char('r') | KeyCode::Char('р'), _) => {
-                                app.events.clear();
-                                app.google_needs_fetch = true;
-                                app.icloud_needs_fetch = true;
+                                app.reload_events();
                                 app.set_status("Refreshing...");
                             }
+                            (KeyCode::Char('R') | KeyCode::Char('Р'), _) => {
+                                let status = if app.retry_failed_sources() { "Retrying..." } else { "Nothing to retry" };
+                                app.set_status(status);
+                            }
                             (KeyCode::Char('n') | KeyCode::Char('н'), _) => {
                                 app.goto_now();
                             }
+                            (KeyCode::Char('/'), _) => {
+                                app.open_day_filter();
+                            }
+                            (KeyCode::Esc, _) if app.events.day_filter.is_some() => {
+                                app.clear_day_filter();
+                            }
                             (KeyCode::Esc, _) => {
                                 app.exit_event_mode();
                             }
                             (KeyCode::Char('D'), _) => {
-                                app.show_logs = !app.show_logs;
+                                app.toggle_debug_screen(DebugTab::Log);
+                            }
+                            (KeyCode::Char('V'), _) => {
+                                app.toggle_debug_screen(DebugTab::Failures);
                             }
                             (KeyCode::Char('f') | KeyCode::Char('ф'), _) => {
                                 app.open_search();
                             }
+                            (KeyCode::Char(':'), _) => {
+                                app.command_line = Some(String::new());
+                            }
                             (KeyCode::Char('w') | KeyCode::Char('ц'), _) => {
                                 app.show_weekends = !app.show_weekends;
-                                execute!(stdout(), Clear(ClearType::All)).ok();
                             }
-                            (KeyCode::Char('1'), _) => {
-                                let _ = std::process::Command::new("xdg-open")
-                                    .arg("https://calendar.google.com")
-                                    .process_group(0)
-                                    .spawn();
+                            (KeyCode::Char('u') | KeyCode::Char('у'), _) => {
+                                app.unified_panel = !app.unified_panel;
+                            }
+                            (KeyCode::Char('!'), _) => {
+                                app.open_url("https://calendar.google.com");
                             }
-                            (KeyCode::Char('2'), _) => {
-                                let _ = std::process::Command::new("xdg-open")
-                                    .arg("https://www.icloud.com/calendar")
-                                    .process_group(0)
-                                    .spawn();
+                            (KeyCode::Char('@'), _) => {
+                                app.open_url("https://www.icloud.com/calendar");
                             }
                             (KeyCode::Char('q') | KeyCode::Char('я'), _) => {
                                 break;
@@ -609,15 +2077,77 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
 
 
+                    // Day-of-month being typed: Enter jumps, Esc cancels, other keys act as usual
+                    if let Some(input) = app.day_jump.clone() {
+                        let handled = match key_event.code {
+                            KeyCode::Char(c) if c.is_ascii_digit() => {
+                                app.push_day_jump_digit(c);
+                                true
+                            }
+                            KeyCode::Enter => {
+                                app.jump_to_day(&input);
+                                true
+                            }
+                            KeyCode::Esc | KeyCode::Backspace => {
+                                app.day_jump = None;
+                                true
+                            }
+                            _ => {
+                                app.day_jump = None;
+                                false
+                            }
+                        };
+                        if handled {
+                            continue;
+                        }
+                    }
+
+                    // Moving an event: navigate as usual, Enter moves it to the selected day
+                    if app.moving.is_some() {
+                        match key_event.code {
+                            KeyCode::Enter => {
+                                if let Some(event) = app.take_move() {
+                                    move_event(&mut app, event, &tx);
+                                }
+                                continue;
+                            }
+                            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('я') => {
+                                app.cancel_move();
+                                continue;
+                            }
+                            _ => {}
+                        }
+                    }
+
                     // Day navigation mode (default)
                     match (key_event.code, key_event.modifiers) {
-                        // Navigation keys (with Bulgarian Phonetic equivalents)
-                        (KeyCode::Char('j') | KeyCode::Char('й') | KeyCode::Down, _) => {
+                        (KeyCode::Char(c), _) if c.is_ascii_digit() => {
+                            // Jump to a day of the displayed month, e.g. "15"
+                            app.push_day_jump_digit(c);
+                        }
+                        // Navigation keys (with Bulgarian Phonetic equivalents); the week
+                        // timeline moves across days with h/l and between weeks with j/k,
+                        // the agenda selects its next event
+                        (KeyCode::Char('j') | KeyCode::Char('й') | KeyCode::Down, _) => match app.view_mode {
+                            ViewMode::Month => app.next_day(),
+                            ViewMode::Week => app.next_week(),
+                            ViewMode::Agenda => app.enter_event_mode(),
+                        },
+                        (KeyCode::Char('k') | KeyCode::Char('к') | KeyCode::Up, _) => match app.view_mode {
+                            ViewMode::Month => app.prev_day(),
+                            ViewMode::Week => app.prev_week(),
+                            ViewMode::Agenda => app.enter_event_mode(),
+                        },
+                        (KeyCode::Char('l') | KeyCode::Char('л') | KeyCode::Right, _) if app.view_mode == ViewMode::Week => {
                             app.next_day();
                         }
-                        (KeyCode::Char('k') | KeyCode::Char('к') | KeyCode::Up, _) => {
+                        (KeyCode::Char('h') | KeyCode::Char('х') | KeyCode::Left, _) if app.view_mode == ViewMode::Week => {
                             app.prev_day();
                         }
+                        (KeyCode::Tab, _) => {
+                            // Cycle the day panels, the week timeline and the agenda
+                            app.toggle_view_mode();
+                        }
                         (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
                             app.next_month();
                         }
@@ -631,56 +2161,88 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             app.goto_today();
                         }
                         (KeyCode::Char('r') | KeyCode::Char('р'), _) => {
-                            app.events.clear();
-                            app.google_needs_fetch = true;
-                            app.icloud_needs_fetch = true;
+                            app.reload_events();
                             app.set_status("Refreshing...");
                         }
+                        (KeyCode::Char('R') | KeyCode::Char('Р'), _) => {
+                            // Retry sources that failed to fetch
+                            let status = if app.retry_failed_sources() { "Retrying..." } else { "Nothing to retry" };
+                            app.set_status(status);
+                        }
                         (KeyCode::Char('n') | KeyCode::Char('н'), _) => {
                             app.goto_now();
                         }
                         (KeyCode::Char('D'), _) => {
-                            // Toggle HTTP request logs display
-                            app.show_logs = !app.show_logs;
+                            // Full-screen HTTP request log
+                            app.toggle_debug_screen(DebugTab::Log);
+                        }
+                        (KeyCode::Char('V'), _) => {
+                            // Full-screen view of captured HTTP failures
+                            app.toggle_debug_screen(DebugTab::Failures);
                         }
                         (KeyCode::Char('f') | KeyCode::Char('ф'), _) => {
                             app.open_search();
                         }
+                        (KeyCode::Char(':'), _) => {
+                            // Open the command line (e.g. ":doctor")
+                            app.command_line = Some(String::new());
+                        }
+                        (KeyCode::Char('p') | KeyCode::Char('п'), _) => {
+                            // Prefill ":new" from an invite or "Thu 15:00 ..." line on the clipboard
+                            app.paste_event();
+                        }
+                        (KeyCode::Char('A'), _) => {
+                            // Quick add: ":add Lunch with Maria tomorrow 12:30-13:15 at Bistro"
+                            app.command_line = Some("add ".to_string());
+                        }
+                        (KeyCode::Char('J'), _) => {
+                            // Join the current or next meeting without navigating to it
+                            let now = Local::now();
+                            let next = ui::find_next_event(&app.events, now.date_naive(), now.time(), |e| e.meeting_url.is_some());
+                            match next.and_then(|n| n.event.meeting_url.clone()) {
+                                Some(url) => app.open_url(&url),
+                                None => app.set_status("No upcoming meeting with a link"),
+                            }
+                        }
                         (KeyCode::Char('w') | KeyCode::Char('ц'), _) => {
                             // Toggle weekend visibility
                             app.show_weekends = !app.show_weekends;
-                            execute!(stdout(), Clear(ClearType::All)).ok();
                         }
-                        (KeyCode::Char('1'), _) => {
-                            let _ = std::process::Command::new("xdg-open")
-                                .arg("https://calendar.google.com")
-                                .process_group(0)
-                                .spawn();
+                        (KeyCode::Char('u') | KeyCode::Char('у'), _) => {
+                            // One time-ordered panel for both sources, or Work above Personal
+                            app.unified_panel = !app.unified_panel;
+                        }
+                        (KeyCode::Char('!'), _) => {
+                            app.open_url("https://calendar.google.com");
                         }
-                        (KeyCode::Char('2'), _) => {
-                            let _ = std::process::Command::new("xdg-open")
-                                .arg("https://www.icloud.com/calendar")
-                                .process_group(0)
-                                .spawn();
+                        (KeyCode::Char('@'), _) => {
+                            app.open_url("https://www.icloud.com/calendar");
                         }
                         (KeyCode::Char('g') | KeyCode::Char('г'), _) => {
-                            // Start Google auth flow (only if not already authenticated)
-                            if matches!(app.google_auth, GoogleAuthState::Authenticated(_)) {
-                                // Already authenticated, ignore
+                            // Start Google auth flow (only if not already authenticated or waiting)
+                            if matches!(app.google_auth, GoogleAuthState::Authenticated(_) | GoogleAuthState::AwaitingUserCode { .. }) {
+                                // Already authenticated or polling, ignore
                             } else if let Some(ref google_config) = app.config.google {
-                                let auth = GoogleAuth::new(google_config.clone());
+                                let auth = GoogleAuth::new(google_config.clone(), app.http_log.clone());
                                 let tx = tx.clone();
 
-                                tokio::spawn(async move {
+                                app.tasks.spawn_cancellable(async move {
                                     match auth.request_device_code().await {
                                         Ok(resp) => {
                                             let expires_at = Utc::now() + chrono::Duration::seconds(resp.expires_in as i64);
                                             let _ = tx.send(AsyncMessage::GoogleDeviceCode {
                                                 user_code: resp.user_code,
                                                 verification_url: resp.verification_url,
-                                                device_code: resp.device_code,
+                                                device_code: resp.device_code.clone(),
                                                 expires_at,
                                             }).await;
+                                            // The one polling task for this code, at the pace Google asks for
+                                            let interval = StdDuration::from_secs(resp.interval);
+                                            let message = match auth.wait_for_token(&resp.device_code, interval, expires_at).await {
+                                                Ok(tokens) => AsyncMessage::GoogleToken(tokens),
+                                                Err(e) => AsyncMessage::GoogleAuthError(e.to_string()),
+                                            };
+                                            let _ = tx.send(message).await;
                                         }
                                         Err(e) => {
                                             let _ = tx.send(AsyncMessage::GoogleAuthError(e.to_string())).await;
@@ -690,33 +2252,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             }
                         }
                         (KeyCode::Char('i') | KeyCode::Char('и'), _) => {
-                            // Start iCloud discovery (re-run to refresh calendar names)
+                            // Start iCloud discovery (re-run to refresh calendar names). The
+                            // credentials are re-read so a fixed config works without a restart.
+                            if !app.demo {
+                                match Config::load() {
+                                    Ok(config) => app.config.icloud = config.icloud,
+                                    Err(e) => app.set_status(format!("Config error: {}", e)),
+                                }
+                            }
                             if let Some(ref icloud_config) = app.config.icloud {
-                                app.icloud_auth = ICloudAuthState::Discovering;
-                                let auth = ICloudAuth::new(icloud_config.clone());
-                                let client = CalDavClient::new(auth);
-                                let tx = tx.clone();
-
-                                tokio::spawn(async move {
-                                    match client.discover_calendars().await {
-                                        Ok(discovered) => {
-                                            let calendars: Vec<CalendarEntry> = discovered
-                                                .into_iter()
-                                                .map(|c| CalendarEntry { url: c.url, name: c.name })
-                                                .collect();
-                                            if calendars.is_empty() {
-                                                let _ = tx.send(AsyncMessage::ICloudDiscoveryError(
-                                                    "No calendars found".to_string()
-                                                )).await;
-                                            } else {
-                                                let _ = tx.send(AsyncMessage::ICloudDiscovered { calendars }).await;
-                                            }
-                                        }
-                                        Err(e) => {
-                                            let _ = tx.send(AsyncMessage::ICloudDiscoveryError(e.to_string())).await;
-                                        }
-                                    }
-                                });
+                                let hidden = app.icloud_auth.hidden_calendars();
+                                app.icloud_auth = ICloudAuthState::Discovering { hidden };
+                                discover_icloud(&app.tasks, CalDavClient::new(ICloudAuth::new(icloud_config.clone()), app.http_log.clone()), &tx);
                             }
                         }
                         (KeyCode::Char('q') | KeyCode::Char('я') | KeyCode::Esc, _) => {
@@ -730,9 +2277,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    // Cleanup
+    // Cleanup: stop fetches and give changes in flight a moment to land first
+    let finished = app.tasks.shutdown(tasks::SHUTDOWN_TIMEOUT).await;
     disable_raw_mode()?;
-    execute!(stdout(), LeaveAlternateScreen, cursor::Show)?;
+    execute!(stdout(), DisableMouseCapture, LeaveAlternateScreen, cursor::Show)?;
+    if !finished {
+        eprintln!("Quit while changes were still being sent; check your calendar for their outcome");
+    }
 
     Ok(())
 }
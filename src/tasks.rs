@@ -0,0 +1,49 @@
+//! Background task bookkeeping, so quitting stops reads right away but lets changes
+//! that are already on their way to a calendar finish
+
+use std::future::Future;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+
+/// How long quitting waits for in-flight changes before giving up on them
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The app's background tasks and the token that tells them it is quitting; clones share both
+#[derive(Clone, Default)]
+pub struct Tasks {
+    tracker: TaskTracker,
+    shutdown: CancellationToken,
+}
+
+impl Tasks {
+    /// Spawn a task that is dropped at its next await point on quit: fetches, auth, lookups
+    pub fn spawn_cancellable(&self, task: impl Future<Output = ()> + Send + 'static) {
+        let token = self.shutdown.clone();
+        self.tracker.spawn(async move {
+            tokio::select! {
+                _ = token.cancelled() => {}
+                _ = task => {}
+            }
+        });
+    }
+
+    /// Spawn a task that quitting waits for, e.g. an RSVP or delete. Long-running ones should
+    /// check [`Tasks::shutdown_token`] between steps so they stop at a clean boundary.
+    pub fn spawn(&self, task: impl Future<Output = ()> + Send + 'static) {
+        self.tracker.spawn(task);
+    }
+
+    /// Cancelled once the app starts shutting down
+    pub fn shutdown_token(&self) -> CancellationToken {
+        self.shutdown.clone()
+    }
+
+    /// Cancel reads and wait up to `timeout` for the remaining tasks; false if some were
+    /// still running when time ran out
+    pub async fn shutdown(&self, timeout: Duration) -> bool {
+        self.shutdown.cancel();
+        self.tracker.close();
+        tokio::time::timeout(timeout, self.tracker.wait()).await.is_ok()
+    }
+}
@@ -1,42 +1,34 @@
-use crate::app::{EventSource, MatchType, NavigationMode, PendingAction, SearchState};
+use crate::app::{BusyOverlay, CalendarPicker, DebugScreen, DebugTab, InfoModal, NavigationMode, PendingAction, SearchState, ViewMode};
 use crate::auth::{AuthDisplay, GoogleAuthState, ICloudAuthState};
-use crate::cache::{AttendeeStatus, DisplayEvent, EventCache, EventId};
-use crate::logging::get_recent_logs;
-use chrono::{Datelike, Duration, Local, NaiveDate, NaiveTime, Timelike};
-use crossterm::{
-    cursor,
-    execute,
-    style::{Attribute, Color, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
-    terminal::{self, Clear, ClearType},
-};
+use crate::brief;
+use crate::cache::{self, AgendaEntry, AttendeeStatus, DisplayEvent, EventCache, EventId, EventKind, EventSource};
+use crate::config::{Config, CountdownConfig, StatusSegment, TimeFormatConfig, TravelConfig, WeekStart};
+use crate::logging::{format_capture, HttpLog, LogSource};
+use crate::search::MatchType;
+use crate::tags::{format_tags, TagStore};
+use crate::utils::{days_in_month, format_duration};
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+use crossterm::style::Color;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Position, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, List, ListItem, ListState, Padding, Paragraph, StatefulWidget, Widget};
+use ratatui::Frame;
 use std::collections::HashSet;
-use std::io::{stdout, Write};
-use std::sync::Mutex;
+use std::sync::Arc;
 
 const CALENDAR_WIDTH_WITH_WEEKENDS: u16 = 23;
 const CALENDAR_WIDTH_NO_WEEKENDS: u16 = 19;
 const MIN_PANEL_WIDTH: u16 = 25;
+const DURATION_MIN_PANEL_WIDTH: u16 = 40;
+/// Title width kept before an event row gives room to its attendees' replies
+const RESPONSES_MIN_TITLE_WIDTH: usize = 16;
 
 fn calendar_width(show_weekends: bool) -> u16 {
     if show_weekends { CALENDAR_WIDTH_WITH_WEEKENDS } else { CALENDAR_WIDTH_NO_WEEKENDS }
 }
 
-// Track previous render state to avoid unnecessary clearing
-#[derive(Default)]
-struct PrevRenderState {
-    selected_date: Option<NaiveDate>,
-    selected_source: Option<EventSource>,
-    selected_event_index: Option<usize>,
-    navigation_mode: Option<NavigationMode>,
-}
-
-static PREV_STATE: Mutex<PrevRenderState> = Mutex::new(PrevRenderState {
-    selected_date: None,
-    selected_source: None,
-    selected_event_index: None,
-    navigation_mode: None,
-});
-
 // Semantic color constants
 mod colors {
     use crossterm::style::Color;
@@ -44,76 +36,222 @@ mod colors {
     // Calendar sources
     pub const GOOGLE_ACCENT: Color = Color::Blue;
     pub const ICLOUD_ACCENT: Color = Color::Magenta;
+    pub const HOLIDAY: Color = Color::DarkYellow;
+    pub const OUT_OF_OFFICE: Color = Color::DarkRed;
+    pub const WORKING_LOCATION: Color = Color::DarkGreen;
+    pub const LOW_ATTENDANCE: Color = Color::DarkYellow;
+    pub const MARKED: Color = Color::Cyan;
 
     // Event states
     pub const CURRENT_EVENT: Color = Color::Green;
     pub const NEXT_EVENT: Color = Color::Yellow;
     pub const PAST_EVENT: Color = Color::DarkGrey;
     pub const FREE_EVENT: Color = Color::DarkGrey;
+    pub const FOCUS_TIME: Color = Color::DarkCyan;
     pub const SELECTED: Color = Color::Cyan;
 
     // UI elements
     pub const HEADER: Color = Color::Cyan;
     pub const SEPARATOR: Color = Color::DarkGrey;
+    pub const ALL_DAY_STRIP: Color = Color::Rgb { r: 40, g: 40, b: 52 };
 
     // Details panel
     pub const TITLE: Color = Color::White;
     pub const TIME: Color = Color::White;
     pub const LOCATION: Color = Color::Yellow;
     pub const ACTION: Color = Color::Green;
+    pub const TAG: Color = Color::Magenta;
 
     // Overlap indicator
     pub const OVERLAP_EVENT: Color = Color::Red;
 
     // Week availability
-    pub const BUSY_BLOCK: Color = Color::Blue;
     pub const FREE_BLOCK: Color = Color::Rgb { r: 200, g: 200, b: 200 };
+    pub const COLLEAGUE_BUSY: Color = Color::Rgb { r: 120, g: 120, b: 120 };
 
     // Status bar
     pub const LOG_TEXT: Color = Color::DarkCyan;
     pub const STATUS_MESSAGE: Color = Color::Yellow;
 }
 
-// Terminal write helpers
-fn draw_separator(out: &mut impl Write, x: u16, y: u16, width: u16) {
-    execute!(out, cursor::MoveTo(x, y)).unwrap();
-    execute!(out, SetForegroundColor(colors::SEPARATOR)).unwrap();
-    for _ in 0..width.min(40) {
-        print!("\u{2500}");
+/// What a mouse click on a drawn region selects
+#[derive(Debug, Clone, PartialEq)]
+pub enum Hit {
+    Day(NaiveDate),
+    Event { date: NaiveDate, source: EventSource, index: usize },
+    /// The selected event's `[J] Join` action
+    Join,
+}
+
+/// Clickable regions of the last frame; later ones are drawn over earlier ones
+#[derive(Debug, Default)]
+pub struct HitMap(Vec<(Rect, Hit)>);
+
+impl HitMap {
+    pub fn at(&self, column: u16, row: u16) -> Option<&Hit> {
+        let position = Position::new(column, row);
+        self.0.iter().rev().find(|(area, _)| area.contains(position)).map(|(_, hit)| hit)
     }
-    execute!(out, ResetColor).unwrap();
 }
 
-fn draw_section_header(out: &mut impl Write, x: u16, y: u16, label: &str, width: usize) {
-    execute!(out, cursor::MoveTo(x, y)).unwrap();
-    execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-    print!("\u{2500} {} ", label);
-    let remaining = width.saturating_sub(label.len() + 3);
-    for _ in 0..remaining {
-        print!("\u{2500}");
+/// Cursor-style drawing into a frame's buffer: text goes where the last `move_to` left
+/// off, in the current colors; cells past the edge of the buffer are dropped
+struct Screen<'a> {
+    buf: &'a mut Buffer,
+    x: u16,
+    y: u16,
+    style: Style,
+    hits: HitMap,
+}
+
+impl<'a> Screen<'a> {
+    fn new(buf: &'a mut Buffer) -> Self {
+        Self { buf, x: 0, y: 0, style: Style::default(), hits: HitMap::default() }
+    }
+
+    /// Make the next `width` cells from the cursor clickable
+    fn hit(&mut self, width: u16, hit: Hit) {
+        self.hits.0.push((Rect::new(self.x, self.y, width, 1), hit));
+    }
+
+    fn move_to(&mut self, x: u16, y: u16) {
+        self.x = x;
+        self.y = y;
+    }
+
+    fn fg(&mut self, color: Color) {
+        self.style = self.style.fg(color.into());
+    }
+
+    fn bg(&mut self, color: Color) {
+        self.style = self.style.bg(color.into());
+    }
+
+    fn bold(&mut self) {
+        self.style = self.style.add_modifier(Modifier::BOLD);
+    }
+
+    fn reverse(&mut self) {
+        self.style = self.style.add_modifier(Modifier::REVERSED);
+    }
+
+    fn reset_color(&mut self) {
+        self.style.fg = None;
+        self.style.bg = None;
+    }
+
+    fn reset_attributes(&mut self) {
+        self.style = Style::default();
+    }
+
+    fn print(&mut self, text: &str) {
+        let area = self.buf.area;
+        if self.y >= area.bottom() || self.x >= area.right() {
+            return;
+        }
+        let width = (area.right() - self.x) as usize;
+        let (x, _) = self.buf.set_stringn(self.x, self.y, text, width, self.style);
+        self.x = x;
+    }
+
+    /// Draw a widget into `area`, clipped to the frame
+    fn render(&mut self, widget: impl Widget, area: Rect) {
+        widget.render(area.intersection(self.buf.area), self.buf);
+    }
+
+    fn render_stateful<W: StatefulWidget>(&mut self, widget: W, area: Rect, state: &mut W::State) {
+        widget.render(area.intersection(self.buf.area), self.buf, state);
     }
-    execute!(out, ResetColor).unwrap();
+
+    /// Blank `area` and draw a bordered box around it, for modals over the views; returns
+    /// the inside, kept a column clear of the borders
+    fn modal_box(&mut self, area: Rect, title: &str) -> Rect {
+        let area = area.intersection(self.buf.area);
+        // Nothing under a modal takes clicks
+        self.hits.0.retain(|(hit_area, _)| !hit_area.intersects(area));
+        Clear.render(area, self.buf);
+        let mut block = Block::bordered()
+            .border_style(fg(colors::HEADER))
+            .padding(Padding::horizontal(1));
+        if !title.is_empty() {
+            block = block.title(format!(" {} ", title));
+        }
+        let inner = block.inner(area);
+        block.render(area, self.buf);
+        inner
+    }
+}
+
+/// Style of text in a terminal color
+fn fg(color: Color) -> Style {
+    Style::default().fg(color.into())
+}
+
+/// A `width` by `height` area in the middle of `area`
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [column] = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center).areas(area);
+    let [centered] = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center).areas(column);
+    centered
+}
+
+/// Horizontal rule under a column header
+fn separator(width: u16) -> Line<'static> {
+    Line::styled("\u{2500}".repeat(width.min(40) as usize), fg(colors::SEPARATOR))
+}
+
+/// Rule with a label leading it: "─ Titles ─────"
+fn section_header(label: &str, width: usize) -> Line<'static> {
+    let rule = "\u{2500}".repeat(width.saturating_sub(label.len() + 3));
+    Line::styled(format!("\u{2500} {} {}", label, rule), fg(Color::DarkGrey))
+}
+
+// Terminal write helpers
+fn draw_separator(out: &mut Screen, x: u16, y: u16, width: u16) {
+    out.render(Paragraph::new(separator(width)), Rect::new(x, y, width, 1));
 }
 
 pub struct RenderState<'a> {
     pub current_date: NaiveDate,
     pub selected_date: NaiveDate,
-    pub show_logs: bool,
     pub show_weekends: bool,
+    pub unified_panel: bool,
     pub events: &'a EventCache,
     pub google_auth: &'a GoogleAuthState,
     pub icloud_auth: &'a ICloudAuthState,
     pub status_message: Option<&'a str>,
     pub google_loading: bool,
     pub icloud_loading: bool,
+    /// Providers answered and asked over the running fetches
+    pub sync_progress: Option<(usize, usize)>,
+    pub google_error: Option<&'a str>,
+    pub icloud_error: Option<&'a str>,
     // Two-level navigation state
     pub navigation_mode: NavigationMode,
+    pub view_mode: ViewMode,
     pub selected_source: EventSource,
     pub selected_event_index: usize,
     // Confirmation state
     pub pending_action: Option<&'a PendingAction>,
     // Search state
     pub search: Option<&'a SearchState>,
+    pub debug_screen: Option<&'a DebugScreen>,
+    pub command_line: Option<&'a str>,
+    pub info_modal: Option<&'a InfoModal>,
+    pub calendar_picker: Option<&'a CalendarPicker>,
+    pub busy_overlay: Option<&'a BusyOverlay>,
+    pub tags: &'a TagStore,
+    pub tag_editor: Option<&'a str>,
+    /// The in-day filter in `events.day_filter` is being typed
+    pub day_filter_editing: bool,
+    /// Day-of-month digits typed in Day mode
+    pub day_jump: Option<&'a str>,
+    /// Event being moved to the selected day
+    pub moving: Option<&'a DisplayEvent>,
+    /// Events marked for a bulk action
+    pub marked: &'a [DisplayEvent],
+    pub last_synced: Option<NaiveTime>,
+    pub config: &'a Config,
+    pub http_log: &'a HttpLog,
 }
 
 /// Information about an upcoming event for the countdown display
@@ -123,28 +261,26 @@ pub struct NextEventInfo<'a> {
     pub minutes_until: i64,    // Minutes until start (negative if already started)
 }
 
-/// Find the next upcoming event across all sources
-fn find_next_event<'a>(events: &'a EventCache, today: NaiveDate, current_time: NaiveTime) -> Option<NextEventInfo<'a>> {
+/// Find the current or next accepted event across all sources, among events passing `filter`
+pub fn find_next_event<'a>(
+    events: &'a EventCache,
+    today: NaiveDate,
+    current_time: NaiveTime,
+    filter: impl Fn(&DisplayEvent) -> bool,
+) -> Option<NextEventInfo<'a>> {
     // Check today's events first
-    let all_today: Vec<&DisplayEvent> = events.google.get(today).iter()
-        .chain(events.icloud.get(today).iter())
-        .filter(|e| e.accepted) // Only show accepted events
+    let all_today: Vec<&DisplayEvent> = events.day(today)
+        .filter(|e| e.accepted && filter(e)) // Only show accepted events
         .collect();
 
     // Find current or next event today
     for event in &all_today {
-        if event.time_str == "All day" {
-            continue;
-        }
-
-        let Some(start_time) = parse_event_time(&event.time_str) else {
+        let Some(start_time) = event.start_time.filter(|_| !event.in_all_day_strip()) else {
             continue;
         };
 
         // Calculate end time
-        let end_time = event.end_time_str.as_ref()
-            .and_then(|s| parse_event_time(s))
-            .unwrap_or_else(|| start_time + chrono::Duration::hours(1));
+        let end_time = event.end_time.unwrap_or_else(|| start_time + chrono::Duration::hours(1));
 
         if current_time < end_time {
             // This event hasn't ended yet
@@ -162,13 +298,12 @@ fn find_next_event<'a>(events: &'a EventCache, today: NaiveDate, current_time: N
     // Check future days (up to 7 days ahead)
     for days_ahead in 1..=7 {
         let check_date = today + Duration::days(days_ahead);
-        let future_events: Vec<&DisplayEvent> = events.google.get(check_date).iter()
-            .chain(events.icloud.get(check_date).iter())
-            .filter(|e| e.accepted && e.time_str != "All day")
+        let future_events: Vec<&DisplayEvent> = events.day(check_date)
+            .filter(|e| e.accepted && !e.in_all_day_strip() && filter(e))
             .collect();
 
         if let Some(event) = future_events.first()
-            && let Some(start_time) = parse_event_time(&event.time_str)
+            && let Some(start_time) = event.start_time
         {
             // Calculate minutes from now until the event
             // Remaining today + full days + time into target day
@@ -188,111 +323,256 @@ fn find_next_event<'a>(events: &'a EventCache, today: NaiveDate, current_time: N
     None
 }
 
-/// Format the countdown string for display
-fn format_countdown(info: &NextEventInfo, max_title_len: usize) -> String {
-    let title = truncate_str(&info.event.title, max_title_len);
-
-    if info.is_current || info.minutes_until <= 0 {
-        format!("Now: {}", title)
-    } else if info.minutes_until < 60 {
-        format!("Next: {} in {}m", title, info.minutes_until)
-    } else if info.minutes_until < 24 * 60 {
-        let hours = info.minutes_until / 60;
-        let mins = info.minutes_until % 60;
-        if mins > 0 {
-            format!("Next: {} in {}h {}m", title, hours, mins)
-        } else {
-            format!("Next: {} in {}h", title, hours)
-        }
+/// Format minutes until an event as a short relative string, e.g. "45m", "1h 30m", "2d 3h"
+fn format_relative(minutes: i64) -> String {
+    if minutes < 60 {
+        format!("{}m", minutes.max(0))
+    } else if minutes < 24 * 60 {
+        let hours = minutes / 60;
+        let mins = minutes % 60;
+        if mins > 0 { format!("{}h {}m", hours, mins) } else { format!("{}h", hours) }
     } else {
-        let days = info.minutes_until / (24 * 60);
-        let hours = (info.minutes_until % (24 * 60)) / 60;
-        if hours > 0 {
-            format!("Next: {} in {}d {}h", title, days, hours)
-        } else {
-            format!("Next: {} in {}d", title, days)
-        }
+        let days = minutes / (24 * 60);
+        let hours = (minutes % (24 * 60)) / 60;
+        if hours > 0 { format!("{}d {}h", days, hours) } else { format!("{}d", days) }
     }
 }
 
-pub fn render(state: &RenderState) {
-    let mut out = stdout();
-    let today = Local::now().date_naive();
+/// Format the countdown string for display using the configured templates.
+/// Returns None when the countdown is disabled or the event is outside the display window.
+fn format_countdown(
+    info: &NextEventInfo,
+    config: &CountdownConfig,
+    format: &TimeFormatConfig,
+    max_title_len: usize,
+) -> Option<String> {
+    if !config.enabled {
+        return None;
+    }
 
-    // Get terminal size
-    let (term_width, term_height) = terminal::size().unwrap_or((80, 24));
+    let is_now = info.is_current || info.minutes_until <= 0;
+    if !is_now && config.within_minutes.is_some_and(|limit| info.minutes_until > limit) {
+        return None;
+    }
 
-    // When search modal is active, skip redrawing underlying content to avoid flicker
-    if let Some(search) = state.search {
-        render_search_modal(&mut out, search, term_width, term_height);
-    } else {
-        // Move to home position instead of clearing (alternate screen handles buffer)
-        execute!(out, cursor::MoveTo(0, 0)).unwrap();
-
-        // Month view handles both normal and day timeline modes
-        render_month_view(&mut out, state, today, term_width, term_height);
-
-        // Render HTTP logs if enabled
-        let log_height = if state.show_logs { 8 } else { 0 };
-        if state.show_logs {
-            let logs = get_recent_logs(log_height as usize);
-            let log_start_row = term_height.saturating_sub(2 + log_height);
-
-            execute!(out, SetForegroundColor(colors::LOG_TEXT)).unwrap();
-            for (i, log) in logs.iter().rev().enumerate() {
-                let row = log_start_row + i as u16;
-                if row < term_height.saturating_sub(2) {
-                    execute!(out, cursor::MoveTo(0, row)).unwrap();
-                    print!(" {}", truncate_str(log, term_width as usize - 2));
+    let template = if is_now { &config.now_format } else { &config.format };
+    Some(fill_countdown_template(template, info, format, max_title_len))
+}
+
+/// Replace a countdown template's {title}, {rel}, {time} and {end} placeholders
+pub fn fill_countdown_template(template: &str, info: &NextEventInfo, format: &TimeFormatConfig, max_title_len: usize) -> String {
+    template
+        .replace("{title}", &truncate_str(&info.event.title, max_title_len))
+        .replace("{rel}", &format_relative(info.minutes_until))
+        .replace("{time}", &info.event.time_label(format))
+        .replace("{end}", &info.event.end_time.map(|end| format.time(end)).unwrap_or_default())
+}
+
+/// " · leave by 13:40" (or " · leave now") for an upcoming event at a physical location
+/// whose leave time is today, and whether that's within a quarter of an hour
+fn leave_hint(info: &NextEventInfo, travel: &TravelConfig, format: &TimeFormatConfig, now: NaiveDateTime) -> Option<(String, bool)> {
+    if info.is_current {
+        return None;
+    }
+    let leave = travel.leave_by(info.event).filter(|leave| leave.date() == now.date())?;
+    let hint = match leave <= now {
+        true => " \u{b7} leave now".to_string(),
+        false => format!(" \u{b7} leave by {}", format.time(leave.time())),
+    };
+    Some((hint, leave - now <= Duration::minutes(15)))
+}
+
+/// Display color for an attendee's response
+fn attendee_status_color(status: &AttendeeStatus) -> Color {
+    match status {
+        AttendeeStatus::Accepted => Color::Green,
+        AttendeeStatus::Organizer => Color::Blue,
+        AttendeeStatus::Declined => Color::Red,
+        AttendeeStatus::Tentative => Color::Yellow,
+        AttendeeStatus::NeedsAction => Color::DarkGrey,
+    }
+}
+
+/// Text and color of each configured status bar segment with something to show
+fn status_segments(state: &RenderState, today: NaiveDate, now: NaiveTime, width: usize) -> Vec<(String, Color)> {
+    let mut segments = Vec::new();
+    for segment in &state.config.status_bar.0 {
+        match segment {
+            StatusSegment::Countdown => {
+                let countdown = &state.config.countdown;
+                let work_hours = &state.config.work_hours;
+                let in_work_hours = |e: &DisplayEvent| e.start_time.is_some_and(|start| work_hours.contains(e.date, start));
+                if let Some(next_info) = find_next_event(state.events, today, now, |e| !countdown.work_hours_only || in_work_hours(e))
+                    && let Some(mut countdown) = format_countdown(&next_info, &state.config.countdown, &state.config.time_format, 30)
+                {
+                    let leave = leave_hint(&next_info, &state.config.travel, &state.config.time_format, today.and_time(now));
+                    if let Some((hint, _)) = &leave {
+                        countdown.push_str(hint);
+                    }
+                    let color = if next_info.is_current {
+                        colors::CURRENT_EVENT
+                    } else if next_info.minutes_until <= 15 || leave.is_some_and(|(_, soon)| soon) {
+                        colors::NEXT_EVENT
+                    } else {
+                        Color::White
+                    };
+                    segments.push((countdown, color));
+                }
+            }
+            StatusSegment::SelectedDate => {
+                segments.push((state.selected_date.format("%a %b %-d, %Y").to_string(), Color::White));
+            }
+            StatusSegment::Sync => {
+                let failed: Vec<&str> = [("Work", state.google_error), ("Personal", state.icloud_error)]
+                    .into_iter()
+                    .filter_map(|(name, error)| error.map(|_| name))
+                    .collect();
+                if let Some((done, total)) = state.sync_progress.filter(|(_, total)| *total > 1) {
+                    segments.push((format!("Syncing {}/{}...", done, total), Color::DarkGrey));
+                } else if state.google_loading || state.icloud_loading {
+                    segments.push(("Syncing...".to_string(), Color::DarkGrey));
+                } else if !failed.is_empty() {
+                    segments.push((format!("Sync failed: {}", failed.join(", ")), colors::OVERLAP_EVENT));
+                } else if let Some(time) = state.last_synced {
+                    segments.push((format!("Synced {}", state.config.time_format.time(time)), Color::DarkGrey));
+                }
+            }
+            StatusSegment::Pending => {
+                let pending = state.events.google.all_events()
+                    .chain(state.events.icloud.all_events())
+                    .filter(|e| e.date >= today && brief::is_pending(e))
+                    .count();
+                if pending > 0 {
+                    segments.push((format!("{} pending", pending), colors::NEXT_EVENT));
                 }
             }
-            execute!(out, ResetColor).unwrap();
+            StatusSegment::Clock => {
+                segments.push((state.config.time_format.time(now), Color::White));
+            }
+        }
+    }
+    // Drop trailing segments that don't fit rather than wrapping the bar
+    let mut used = 1;
+    segments.retain(|(text, _)| {
+        used += text.chars().count() + 3;
+        used <= width
+    });
+    segments
+}
+
+/// Draw a frame; ratatui diffs it against the previous one, so only changed cells reach the terminal.
+/// Returns where the clickable parts of the frame ended up
+pub fn render(frame: &mut Frame, state: &RenderState) -> HitMap {
+    let today = Local::now().date_naive();
+    let area = frame.area();
+    let (term_width, term_height) = (area.width, area.height);
+    let mut out = Screen::new(frame.buffer_mut());
+    // Everything but the status bar and the controls under it
+    let [main_area, _] = Layout::vertical([Constraint::Fill(1), Constraint::Length(2)]).areas(area);
+
+    if let Some(debug) = state.debug_screen {
+        render_debug_screen(&mut out, debug, state.http_log, main_area);
+    } else {
+        match state.view_mode {
+            // Month view handles both normal and day timeline modes
+            ViewMode::Month => render_month_view(&mut out, state, today, main_area),
+            ViewMode::Week => render_week_view(&mut out, state, today, term_width, term_height),
+            ViewMode::Agenda => render_agenda_view(&mut out, state, today, term_width, term_height),
         }
 
         // Render confirmation modal if there's a pending action
         if let Some(action) = state.pending_action {
-            render_confirmation_modal(&mut out, action, term_width, term_height);
+            render_confirmation_modal(&mut out, action, area);
+        }
+
+        if let Some(modal) = state.info_modal {
+            render_info_modal(&mut out, modal, area);
+        }
+
+        if let Some(picker) = state.calendar_picker {
+            render_info_modal(&mut out, &picker.to_modal(), area);
+        }
+
+        if let Some(search) = state.search {
+            render_search_modal(&mut out, search, state.config, area);
         }
     }
 
     // Render status bar at bottom
     let status_row = term_height.saturating_sub(2);
-    execute!(out, cursor::MoveTo(0, status_row)).unwrap();
-
-    if let Some(msg) = state.status_message {
-        execute!(out, SetForegroundColor(colors::STATUS_MESSAGE)).unwrap();
-        print!(" {}", truncate_str(msg, term_width as usize - 2));
-        execute!(out, ResetColor).unwrap();
+    out.move_to(0, status_row);
+
+    if let Some(command) = state.command_line {
+        out.print(&format!(":{}_", command));
+    } else if let Some(input) = state.tag_editor {
+        out.print(&format!(" Tags: {}_", input));
+    } else if let Some((_, query)) = state.events.day_filter.as_ref().filter(|_| state.day_filter_editing) {
+        out.print(&format!(" /{}_", query));
+    } else if let Some(input) = state.day_jump {
+        out.print(&format!(" Day: {}_", input));
+    } else if let Some(msg) = state.status_message {
+        out.fg(colors::STATUS_MESSAGE);
+        out.print(&format!(" {}", truncate_str(msg, term_width as usize - 2)));
+        out.reset_color();
+    } else if let Some(event) = state.moving {
+        out.fg(colors::STATUS_MESSAGE);
+        let line = format!("Move \"{}\" to {}", event.title, state.selected_date.format("%a %b %d"));
+        out.print(&format!(" {}", truncate_str(&line, term_width as usize - 2)));
+        out.reset_color();
+    } else if let Some((_, query)) = &state.events.day_filter {
+        out.fg(Color::DarkGrey);
+        out.print(&format!(" /{}", query));
+        out.reset_color();
     } else {
-        // Show countdown to next event when no status message
-        let current_time = Local::now().time();
-        if let Some(next_info) = find_next_event(state.events, today, current_time) {
-            let countdown = format_countdown(&next_info, 30);
-            if next_info.is_current {
-                execute!(out, SetForegroundColor(colors::CURRENT_EVENT)).unwrap();
-            } else if next_info.minutes_until <= 15 {
-                execute!(out, SetForegroundColor(colors::NEXT_EVENT)).unwrap();
-            } else {
-                execute!(out, SetForegroundColor(Color::White)).unwrap();
+        // Configured segments when no status message, separated by dim dots
+        let now = Local::now();
+        let segments = status_segments(state, now.date_naive(), now.time(), term_width as usize);
+        out.print(" ");
+        for (i, (text, color)) in segments.iter().enumerate() {
+            if i > 0 {
+                out.fg(Color::DarkGrey);
+                out.print(" \u{b7} ");
             }
-            print!(" {}", countdown);
-            execute!(out, ResetColor).unwrap();
+            out.fg(*color);
+            out.print(text);
         }
+        out.reset_color();
     }
 
     // Render controls based on current mode
-    execute!(out, cursor::MoveTo(0, term_height.saturating_sub(1))).unwrap();
-    execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
+    out.move_to(0, term_height.saturating_sub(1));
+    out.fg(Color::DarkGrey);
 
     let controls = if state.pending_action.is_some() {
         // Confirmation mode controls
         " y/Enter:confirm n/Esc:cancel".to_string()
+    } else if state.calendar_picker.is_some() {
+        " jk:move Space:toggle Enter:save Esc:cancel".to_string()
+    } else if state.info_modal.is_some() {
+        " jk:scroll Esc:close".to_string()
+    } else if state.day_filter_editing {
+        " type to filter Enter:keep Esc:clear".to_string()
+    } else if state.day_jump.is_some() {
+        " 0-9:day Enter:go Esc:cancel".to_string()
+    } else if state.moving.is_some() {
+        " jk:day ^d/^u:month 0-9:day t:today Enter:move here Esc:cancel".to_string()
+    } else if let Some(debug) = state.debug_screen {
+        match debug.tab {
+            DebugTab::Log => " jk:scroll ^d/^u:page s:source c:status p:pause Tab:failures e:export Esc:back".to_string(),
+            DebugTab::Failures => " jk:scroll ^d/^u:page v:verbose Tab:metrics e:export Esc:back".to_string(),
+            DebugTab::Metrics => " Tab:log e:export Esc:back".to_string(),
+        }
     } else if state.navigation_mode == NavigationMode::Event {
         // Event navigation mode controls
-        " jk:nav ^d/^u:scroll f:find n:now t:today r:refresh Esc:back q:quit".to_string()
+        " jk:nav ^d/^u:scroll .:repeat /:filter v:mark h:hide M:move f:find #:tags n:now t:today r:refresh Esc:back q:quit".to_string()
     } else {
         // Day navigation mode controls
-        let mut c = String::from(" jk:day ^d/^u:month f:find n:now t:today r:refresh Enter:events");
+        let mut c = String::from(match state.view_mode {
+            ViewMode::Month => " jk:day Tab:week u:merge",
+            ViewMode::Week => " hl:day jk:week Tab:agenda",
+            ViewMode::Agenda => " jk:select Tab:month",
+        });
+        c.push_str(" ^d/^u:month f:find n:now t:today J:join A:add p:paste r:refresh Enter:events");
         if !state.google_auth.is_authenticated() {
             c.push_str(" g:work");
         }
@@ -302,13 +582,13 @@ pub fn render(state: &RenderState) {
         c.push_str(" q:quit");
         c
     };
-    print!("{}", controls);
-    execute!(out, ResetColor).unwrap();
+    out.print(&controls);
+    out.reset_color();
 
-    out.flush().unwrap();
+    out.hits
 }
 
-fn render_month_view(out: &mut impl Write, state: &RenderState, today: NaiveDate, term_width: u16, term_height: u16) {
+fn render_month_view(out: &mut Screen, state: &RenderState, today: NaiveDate, area: Rect) {
     let now = Local::now();
     let current_time = now.time();
     let is_today = state.selected_date == today;
@@ -323,369 +603,932 @@ fn render_month_view(out: &mut impl Write, state: &RenderState, today: NaiveDate
     let cal_width = calendar_width(state.show_weekends);
 
     if in_event_mode {
-        let available = term_width.saturating_sub(cal_width + 2);
+        let available = area.width.saturating_sub(cal_width + 2);
         // Details panel: fixed width or 1/3 of available
         details_panel_width = (available / 3).clamp(MIN_PANEL_WIDTH, 40);
         events_panel_width = available.saturating_sub(details_panel_width + 1);
     } else {
-        events_panel_width = term_width.saturating_sub(cal_width + 1);
+        events_panel_width = area.width.saturating_sub(cal_width + 1);
         details_panel_width = 0;
     }
-
-    // Reserve 2 rows for column headers
-    let header_rows = 2u16;
+    let [_, events_area, _, details_area] = Layout::horizontal([
+        Constraint::Length(cal_width + 1),
+        Constraint::Length(events_panel_width),
+        Constraint::Length(1),
+        Constraint::Length(details_panel_width),
+    ])
+    .areas(area);
+
+    // Public holidays and out-of-office banners get a row each under the column header
+    let holidays = state.events.holidays(state.selected_date);
+    let banners = state.events.banners(state.selected_date);
+    let [header_area, separator_area, all_day_area, panels_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length((holidays.len() + banners.len()) as u16),
+        Constraint::Fill(1),
+    ])
+    .areas(events_area);
 
     // Render calendar on left
-    render_calendar(out, state.current_date, state.selected_date, today, state.events, state.google_loading || state.icloud_loading, state.show_weekends);
-
-    // Check if we need to clear (only when state changes)
-    let needs_clear = {
-        let prev = PREV_STATE.lock().unwrap();
-        prev.selected_date != Some(state.selected_date)
-            || prev.selected_source != Some(state.selected_source)
-            || prev.selected_event_index != Some(state.selected_event_index)
-            || prev.navigation_mode != Some(state.navigation_mode)
-    };
+    render_calendar(out, state, today, area.height);
 
     // Render event panels in the middle
     if events_panel_width >= MIN_PANEL_WIDTH {
-        let events_x = cal_width + 1;
-
-        // Clear the events panel area only when content changes
-        if needs_clear {
-            for row in 0..term_height.saturating_sub(2) {
-                execute!(out, cursor::MoveTo(events_x, row), Clear(ClearType::UntilNewLine)).unwrap();
+        // Events column header: selected date
+        let mut header = format!(
+            "{} \u{b7} {}",
+            state.selected_date.format("%a"),
+            state.config.week_start.week_label(state.selected_date)
+        );
+        if let Some(label) = relative_day_label(state.selected_date, today) {
+            header = format!("{} \u{b7} {}", label, header);
+        }
+        let mut header_line = vec![Span::styled(
+            truncate_str(&header, events_panel_width as usize),
+            fg(colors::HEADER).add_modifier(Modifier::BOLD),
+        )];
+
+        // Working location badge, e.g. "@Home", when it fits after the header
+        if let Some(location) = state.events.working_location(state.selected_date) {
+            let badge = format!(" @{}", location);
+            let used = header.chars().count() + badge.chars().count();
+            if used <= events_panel_width as usize {
+                header_line.push(Span::styled(badge, fg(colors::WORKING_LOCATION)));
             }
         }
-
-        // Events column header: selected date
-        execute!(out, cursor::MoveTo(events_x, 0)).unwrap();
-        execute!(out, SetForegroundColor(colors::HEADER), SetAttribute(Attribute::Bold)).unwrap();
-        print!("{}", state.selected_date.format("%a %b %d"));
-        execute!(out, ResetColor, SetAttribute(Attribute::Reset)).unwrap();
-
-        // Separator line
-        draw_separator(out, events_x, 1, events_panel_width);
+        out.render(Paragraph::new(Line::from(header_line)), header_area);
+        out.render(Paragraph::new(separator(events_panel_width)), separator_area);
+
+        // Public holidays as subdued all-day entries, then out-of-office banners across the day
+        let width = events_panel_width as usize;
+        let holiday_lines = holidays
+            .iter()
+            .map(|name| Line::styled(truncate_str(&format!("  All day  {}", name), width), fg(Color::DarkGrey)));
+        let banner_lines = banners.iter().map(|event| {
+            let when = if event.is_all_day() { String::new() } else { format!(" {}", event.time_range_label(&state.config.time_format)) };
+            let line = format!("  {:>7}  {}{}", "OOO", event.title, when);
+            Line::styled(truncate_str(&line, width), fg(colors::OUT_OF_OFFICE))
+        });
+        out.render(Paragraph::new(holiday_lines.chain(banner_lines).collect::<Vec<_>>()), all_day_area);
 
         let google_events = state.events.google.get(state.selected_date);
         let icloud_events = state.events.icloud.get(state.selected_date);
         let is_past_day = state.selected_date < today;
         let (google_overlaps, icloud_overlaps) = compute_overlapping_events(google_events, icloud_events);
-
-        // Selection info for highlighting
-        let google_selected = if in_event_mode && state.selected_source == EventSource::Google {
-            Some(state.selected_event_index)
-        } else {
-            None
-        };
-        let icloud_selected = if in_event_mode && state.selected_source == EventSource::ICloud {
-            Some(state.selected_event_index)
-        } else {
-            None
-        };
-
-        // Render Work (Google) panel
-        render_event_panel(
-            out,
-            events_x,
-            header_rows,
-            events_panel_width,
-            "Work",
-            google_events,
-            state.google_loading,
-            colors::GOOGLE_ACCENT,
+        let day = PanelDay {
+            date: state.selected_date,
+            time_format: &state.config.time_format,
             is_today,
             is_past_day,
             current_time,
-            google_selected,
-            &google_overlaps,
-        );
+            marked: state.marked,
+        };
 
-        // Calculate Personal panel position: after Work header (1) + events + spacing (1)
-        let work_panel_rows = 1 + google_events.len().max(1) as u16;
-        let personal_y = header_rows + work_panel_rows + 1;
-
-        // Render Personal (iCloud) panel below
-        render_event_panel(
-            out,
-            events_x,
-            personal_y,
-            events_panel_width,
-            "Personal",
-            icloud_events,
-            state.icloud_loading,
-            colors::ICLOUD_ACCENT,
-            is_today,
-            is_past_day,
-            current_time,
-            icloud_selected,
-            &icloud_overlaps,
-        );
+        if state.unified_panel {
+            // Both sources in one list in time order, each event in its calendar's accent
+            let agenda = state.events.agenda(state.selected_date, 1);
+            let events: Vec<Arc<DisplayEvent>> = agenda
+                .iter()
+                .map(|entry| match entry.source {
+                    EventSource::Google => google_events[entry.index].clone(),
+                    EventSource::ICloud => icloud_events[entry.index].clone(),
+                })
+                .collect();
+            let selected = agenda.iter().position(|entry| {
+                in_event_mode && entry.source == state.selected_source && entry.index == state.selected_event_index
+            });
+            let overlaps: HashSet<usize> = agenda
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| match entry.source {
+                    EventSource::Google => google_overlaps.contains(&entry.index),
+                    EventSource::ICloud => icloud_overlaps.contains(&entry.index),
+                })
+                .map(|(i, _)| i)
+                .collect();
+            render_event_panel(
+                out,
+                panels_area,
+                day,
+                EventPanel {
+                    title: "Events",
+                    events: &events,
+                    is_loading: state.google_loading || state.icloud_loading,
+                    error: state.google_error.or(state.icloud_error),
+                    accent_color: colors::HEADER,
+                    event_color: &|e| e.color.as_deref().and_then(parse_color).or(Some(event_accent(state.config, e))),
+                    selected_index: selected,
+                    overlapping_indices: &overlaps,
+                },
+            );
+            let rows = agenda.iter().map(|entry| (entry.source, entry.index));
+            hit_event_rows(out, panel_rows(panels_area), state.selected_date, rows);
+        } else {
+            // Selection info for highlighting
+            let google_selected = if in_event_mode && state.selected_source == EventSource::Google {
+                Some(state.selected_event_index)
+            } else {
+                None
+            };
+            let icloud_selected = if in_event_mode && state.selected_source == EventSource::ICloud {
+                Some(state.selected_event_index)
+            } else {
+                None
+            };
+
+            // Work panel: its header and events (or the empty note), a blank row, then Personal
+            let [work_area, _, personal_area] = Layout::vertical([
+                Constraint::Length(1 + google_events.len().max(1) as u16),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ])
+            .areas(panels_area);
+
+            // Render Work (Google) panel
+            render_event_panel(
+                out,
+                work_area,
+                day,
+                EventPanel {
+                    title: "Work",
+                    events: google_events,
+                    is_loading: state.google_loading,
+                    error: state.google_error,
+                    accent_color: source_accent(state.config, EventSource::Google),
+                    event_color: &|e| e.color.as_deref().and_then(parse_color),
+                    selected_index: google_selected,
+                    overlapping_indices: &google_overlaps,
+                },
+            );
+            let rows = (0..google_events.len()).map(|index| (EventSource::Google, index));
+            hit_event_rows(out, panel_rows(work_area), state.selected_date, rows);
+
+            // Render Personal (iCloud) panel below
+            render_event_panel(
+                out,
+                personal_area,
+                day,
+                EventPanel {
+                    title: "Personal",
+                    events: icloud_events,
+                    is_loading: state.icloud_loading,
+                    error: state.icloud_error,
+                    accent_color: source_accent(state.config, EventSource::ICloud),
+                    event_color: &|e| e.color.as_deref().and_then(parse_color),
+                    selected_index: icloud_selected,
+                    overlapping_indices: &icloud_overlaps,
+                },
+            );
+            let rows = (0..icloud_events.len()).map(|index| (EventSource::ICloud, index));
+            hit_event_rows(out, panel_rows(personal_area), state.selected_date, rows);
+        }
     }
 
     // Render details panel on the right when in Event mode
     if in_event_mode && details_panel_width >= MIN_PANEL_WIDTH {
-        let details_x = cal_width + events_panel_width + 2;
-        let details_height = term_height.saturating_sub(3);
+        render_selected_details(out, state, details_area);
+    }
+}
+
+/// The event rows of a panel drawn in `area`, under its header
+fn panel_rows(area: Rect) -> Rect {
+    let [_, rows] = Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+    rows
+}
+
+/// Make the rows of `area` select the events listed on them on click
+fn hit_event_rows(out: &mut Screen, area: Rect, date: NaiveDate, rows: impl IntoIterator<Item = (EventSource, usize)>) {
+    for (row, (source, index)) in rows.into_iter().take(area.height as usize).enumerate() {
+        out.move_to(area.x, area.y + row as u16);
+        out.hit(area.width, Hit::Event { date, source, index });
+    }
+}
 
-        // Clear the details panel area only when content changes
-        if needs_clear {
-            for row in 0..term_height.saturating_sub(2) {
-                execute!(out, cursor::MoveTo(details_x, row), Clear(ClearType::UntilNewLine)).unwrap();
+/// Details of the selected event in `area`
+fn render_selected_details(out: &mut Screen, state: &RenderState, area: Rect) {
+    let selected_event = match state.selected_source {
+        EventSource::Google => state.events.google.get(state.selected_date).get(state.selected_event_index),
+        EventSource::ICloud => state.events.icloud.get(state.selected_date).get(state.selected_event_index),
+    }
+    .map(Arc::as_ref);
+
+    render_event_details_column(out, state, area, selected_event);
+}
+
+/// A timed event placed on the week timeline
+struct WeekBlock<'a> {
+    event: &'a DisplayEvent,
+    source: EventSource,
+    index: usize,
+    start: u32,
+    end: u32,
+    lane: usize,
+    lanes: usize,
+}
+
+/// Timed events of a day from both sources, with their lanes
+fn week_blocks(events: &EventCache, date: NaiveDate) -> Vec<WeekBlock<'_>> {
+    let mut blocks = Vec::new();
+    for (source, list) in [(EventSource::Google, events.google.get(date)), (EventSource::ICloud, events.icloud.get(date))] {
+        for (index, event) in list.iter().enumerate() {
+            if let Some((start, end)) = event_minutes(event) {
+                // Events running past midnight end with the day
+                let end = if end <= start { 24 * 60 } else { end };
+                blocks.push(WeekBlock { event, source, index, start, end, lane: 0, lanes: 1 });
             }
         }
+    }
+    let ranges: Vec<(u32, u32)> = blocks.iter().map(|b| (b.start, b.end)).collect();
+    for (block, (lane, lanes)) in blocks.iter_mut().zip(assign_lanes(&ranges)) {
+        block.lane = lane;
+        block.lanes = lanes;
+    }
+    blocks
+}
 
-        // Get the selected event
-        let selected_event = match state.selected_source {
-            EventSource::Google => state.events.google.get(state.selected_date).get(state.selected_event_index),
-            EventSource::ICloud => state.events.icloud.get(state.selected_date).get(state.selected_event_index),
+/// Side-by-side lanes for time ranges: (lane, lanes in its group of overlapping ranges)
+/// per range. Each range takes the first lane that is free when it starts.
+fn assign_lanes(ranges: &[(u32, u32)]) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..ranges.len()).collect();
+    order.sort_by_key(|&i| (ranges[i].0, std::cmp::Reverse(ranges[i].1)));
+
+    let mut lanes = vec![(0, 1); ranges.len()];
+    let mut lane_ends: Vec<u32> = Vec::new();
+    let mut group: Vec<usize> = Vec::new();
+    let mut group_end = 0;
+    for i in order {
+        let (start, end) = ranges[i];
+        // A range starting after everything in the group has ended opens a new group
+        if !group.is_empty() && start >= group_end {
+            for &j in &group {
+                lanes[j].1 = lane_ends.len();
+            }
+            group.clear();
+            lane_ends.clear();
+        }
+        let lane = match lane_ends.iter().position(|&lane_end| lane_end <= start) {
+            Some(lane) => {
+                lane_ends[lane] = end;
+                lane
+            }
+            None => {
+                lane_ends.push(end);
+                lane_ends.len() - 1
+            }
         };
+        lanes[i].0 = lane;
+        group_end = if group.is_empty() { end } else { group_end.max(end) };
+        group.push(i);
+    }
+    for &j in &group {
+        lanes[j].1 = lane_ends.len();
+    }
+    lanes
+}
+
+/// Week timeline right of the calendar: a column per day, hours down the side and timed
+/// events as blocks, side by side where they overlap
+fn render_week_view(
+    out: &mut Screen,
+    state: &RenderState,
+    today: NaiveDate,
+    term_width: u16,
+    term_height: u16,
+) {
+    const LABEL_WIDTH: usize = 6;
+    const FIRST_TIMELINE_ROW: u16 = 4;
+
+    let now = Local::now().time();
+    let now_minutes = now.hour() * 60 + now.minute();
+    let in_event_mode = state.navigation_mode == NavigationMode::Event;
+    let cal_width = calendar_width(state.show_weekends);
+
+    // Event mode keeps the details column on the right, as in month view
+    let available = term_width.saturating_sub(cal_width + 1);
+    let (timeline_width, details_width) = if in_event_mode {
+        let details = (available / 3).clamp(MIN_PANEL_WIDTH, 40);
+        (available.saturating_sub(details + 1), details)
+    } else {
+        (available, 0)
+    };
+
+    render_calendar(out, state, today, term_height.saturating_sub(2));
+
+    let x = cal_width + 1;
+    let columns = week_columns(state.config.week_start, state.show_weekends);
+    let week_first_day = state.config.week_start.start_of_week(state.selected_date);
+    let column_width = (timeline_width as usize).saturating_sub(LABEL_WIDTH) / columns.len();
+    // The last cell of each column is a gap before the next day
+    let inner = column_width.saturating_sub(1);
+
+    if timeline_width >= MIN_PANEL_WIDTH && inner >= 2 {
+
+        let days: Vec<NaiveDate> = columns.iter().map(|&(offset, _)| week_first_day + Duration::days(offset as i64)).collect();
+        let blocks: Vec<Vec<WeekBlock>> = days.iter().map(|&date| week_blocks(state.events, date)).collect();
+
+        // Header: the selected week
+        out.move_to(x, 0);
+        out.fg(colors::HEADER);
+        out.bold();
+        let header = format!("Week \u{b7} {}", state.config.week_start.week_label(state.selected_date));
+        out.print(&truncate_str(&header, timeline_width as usize));
+        out.reset_color();
+        out.reset_attributes();
+
+        // Day headers: the selected day reversed, today in the header color
+        out.move_to(x, 1);
+        out.print(&format!("{:width$}", "", width = LABEL_WIDTH));
+        for &date in &days {
+            let label = truncate_str(&format!("{} {}", date.format("%a"), date.day()), inner);
+            let color = if date == today {
+                colors::HEADER
+            } else if date < today {
+                colors::PAST_EVENT
+            } else {
+                colors::TITLE
+            };
+            out.hit(inner as u16, Hit::Day(date));
+            out.fg(color);
+            if date == state.selected_date {
+                out.reverse();
+            }
+            out.print(&format!("{:width$}", label, width = inner));
+            out.reset_color();
+            out.reset_attributes();
+            out.print(" ");
+        }
 
-        render_event_details_column(out, details_x, 0, details_panel_width, details_height, selected_event);
+        // All-day row: a holiday, else the first all-day event, with a count of the rest
+        out.move_to(x, 2);
+        out.fg(Color::DarkGrey);
+        out.print(&format!("{:width$}", "", width = LABEL_WIDTH));
+        for &date in &days {
+            let holidays = state.events.holidays(date);
+            let all_day: Vec<&DisplayEvent> = state.events.day(date).filter(|e| e.in_all_day_strip()).collect();
+            let (text, color) = match (holidays.first(), all_day.first()) {
+                (Some(name), _) => (name.clone(), colors::HOLIDAY),
+                (None, Some(event)) => (event.title.clone(), event_accent(state.config, event)),
+                (None, None) => (String::new(), Color::DarkGrey),
+            };
+            let count = holidays.len() + all_day.len();
+            let text = if count > 1 { format!("+{} {}", count - 1, text) } else { text };
+            out.fg(color);
+            out.print(&format!("{:width$} ", truncate_str(&text, inner), width = inner));
+        }
+        out.reset_color();
+
+        out.move_to(x, 3);
+        out.fg(colors::SEPARATOR);
+        out.print(&"\u{2500}".repeat(LABEL_WIDTH + column_width * days.len()));
+        out.reset_color();
+
+        // Working hours, widened to the week's events, then cut to the rows there are
+        let rows = term_height.saturating_sub(FIRST_TIMELINE_ROW + 2) as u32;
+        let first_hour = blocks.iter().flatten().map(|b| b.start / 60).min().unwrap_or(8).min(8);
+        let last_hour = blocks.iter().flatten().map(|b| b.end.div_ceil(60)).max().unwrap_or(20).max(20);
+        let hours = (last_hour - first_hour).min(rows).max(1);
+        let rows_per_hour = (rows / hours).clamp(1, 4);
+        let slot = 60 / rows_per_hour;
+        let window_start = first_hour * 60;
+
+        for row in 0..hours * rows_per_hour {
+            let slot_start = window_start + row * slot;
+            let slot_end = slot_start + slot;
+            out.move_to(x, FIRST_TIMELINE_ROW + row as u16);
+
+            // Hour labels; the current time's row is marked in a week with today in it
+            let is_now_row = days.contains(&today) && (slot_start..slot_end).contains(&now_minutes);
+            let label = if row % rows_per_hour == 0 {
+                truncate_str(&state.config.time_format.hour(slot_start / 60), LABEL_WIDTH - 1)
+            } else if is_now_row {
+                "  now".to_string()
+            } else {
+                String::new()
+            };
+            let color = if is_now_row { colors::OVERLAP_EVENT } else { Color::DarkGrey };
+            out.fg(color);
+            out.print(&format!("{:width$}", label, width = LABEL_WIDTH));
+            out.reset_color();
+
+            for (day, &date) in blocks.iter().zip(&days) {
+                // The block, and the character of it, drawn in each cell of the column
+                let mut cells: Vec<Option<(usize, char)>> = vec![None; inner];
+                for (b, block) in day.iter().enumerate() {
+                    if block.start >= slot_end || block.end <= slot_start {
+                        continue;
+                    }
+                    let lane_width = (inner / block.lanes).max(1);
+                    let left = block.lane * lane_width;
+                    if left >= inner {
+                        continue;
+                    }
+                    let width = if block.lane + 1 == block.lanes { inner - left } else { lane_width };
+
+                    // Title on the block's first row, its times on the second
+                    let first_row_at = block.start.max(window_start);
+                    let text = if (slot_start..slot_end).contains(&first_row_at) {
+                        block.event.title.clone()
+                    } else if (slot_start.saturating_sub(slot)..slot_start).contains(&first_row_at) {
+                        block.event.time_range_label(&state.config.time_format)
+                    } else {
+                        String::new()
+                    };
+                    let mut chars = text.chars();
+                    for cell in &mut cells[left..left + width] {
+                        *cell = Some((b, chars.next().unwrap_or(' ')));
+                    }
+                }
+
+                let mut drawn = None;
+                for cell in cells {
+                    let Some((b, ch)) = cell else {
+                        if drawn.take().is_some() {
+                            out.reset_color();
+                        }
+                        out.print(" ");
+                        continue;
+                    };
+                    if drawn != Some(b) {
+                        let block = &day[b];
+                        let selected = in_event_mode
+                            && date == state.selected_date
+                            && block.source == state.selected_source
+                            && block.index == state.selected_event_index;
+                        let accent = event_accent(state.config, block.event);
+                        let past = date < today || (date == today && block.end <= now_minutes);
+                        let accent = if past { dim_color(accent) } else { accent };
+                        out.reset_color();
+                        if selected {
+                            out.bg(colors::SELECTED);
+                            out.fg(Color::Black);
+                        } else if block.event.is_free || !block.event.accepted {
+                            // Time that isn't blocked shows as text in the accent, not a filled block
+                            out.fg(accent);
+                        } else {
+                            out.bg(accent);
+                            out.fg(Color::Black);
+                        }
+                        drawn = Some(b);
+                    }
+                    out.hit(1, Hit::Event { date, source: day[b].source, index: day[b].index });
+                    out.print(&ch.to_string());
+                }
+                out.reset_color();
+                out.print(" ");
+            }
+        }
     }
 
-    // Update previous state
-    {
-        let mut prev = PREV_STATE.lock().unwrap();
-        prev.selected_date = Some(state.selected_date);
-        prev.selected_source = Some(state.selected_source);
-        prev.selected_event_index = Some(state.selected_event_index);
-        prev.navigation_mode = Some(state.navigation_mode);
+    if in_event_mode && details_width >= MIN_PANEL_WIDTH {
+        render_selected_details(out, state, Rect::new(x + timeline_width + 1, 0, details_width, term_height.saturating_sub(2)));
     }
 }
 
-fn render_calendar(
-    out: &mut impl Write,
-    current_date: NaiveDate,
-    selected_date: NaiveDate,
+/// A row of the agenda list
+enum AgendaLine<'a> {
+    Day(NaiveDate),
+    Event(&'a AgendaEntry<'a>),
+    Blank,
+}
+
+/// Agenda right of the calendar: the next days' events of both sources in one list under
+/// day headings, scrolled to keep the selected event in view
+fn render_agenda_view(
+    out: &mut Screen,
+    state: &RenderState,
     today: NaiveDate,
-    events: &EventCache,
-    is_loading: bool,
-    show_weekends: bool,
+    term_width: u16,
+    term_height: u16,
 ) {
-    execute!(out, cursor::MoveTo(0, 0)).unwrap();
+    let now = Local::now().naive_local();
+    let in_event_mode = state.navigation_mode == NavigationMode::Event;
+    let cal_width = calendar_width(state.show_weekends);
+
+    // Event mode keeps the details column on the right, as in month view
+    let available = term_width.saturating_sub(cal_width + 1);
+    let (list_width, details_width) = if in_event_mode {
+        let details = (available / 3).clamp(MIN_PANEL_WIDTH, 40);
+        (available.saturating_sub(details + 1), details)
+    } else {
+        (available, 0)
+    };
+
+    render_calendar(out, state, today, term_height.saturating_sub(2));
+
+    let x = cal_width + 1;
+    if list_width >= MIN_PANEL_WIDTH {
+        let width = list_width as usize;
+        let days = state.config.agenda_days.unwrap_or(cache::DEFAULT_AGENDA_DAYS);
+        let agenda = state.events.agenda(today, days);
+
+        out.move_to(x, 0);
+        out.fg(colors::HEADER);
+        out.bold();
+        let header = format!("Agenda \u{b7} next {} days", days);
+        out.print(&format!("{:width$}", truncate_str(&header, width), width = width));
+        out.reset_color();
+        out.reset_attributes();
+        draw_separator(out, x, 1, list_width);
+
+        // Day headings and their events, remembering which line holds the selection
+        let mut lines: Vec<AgendaLine> = Vec::new();
+        let mut selected_line = None;
+        let mut last_date = None;
+        for entry in &agenda {
+            if last_date != Some(entry.event.date) {
+                if last_date.is_some() {
+                    lines.push(AgendaLine::Blank);
+                }
+                lines.push(AgendaLine::Day(entry.event.date));
+                last_date = Some(entry.event.date);
+            }
+            if in_event_mode
+                && entry.event.date == state.selected_date
+                && entry.source == state.selected_source
+                && entry.index == state.selected_event_index
+            {
+                selected_line = Some(lines.len());
+            }
+            lines.push(AgendaLine::Event(entry));
+        }
+
+        let rows = term_height.saturating_sub(4) as usize;
+        let offset = selected_line.map_or(0, |line| (line + 1).saturating_sub(rows));
+
+        for row in 0..rows {
+            out.move_to(x, 2 + row as u16);
+            let event = match lines.get(offset + row) {
+                Some(AgendaLine::Event(entry)) => {
+                    out.hit(width as u16, Hit::Event { date: entry.event.date, source: entry.source, index: entry.index });
+                    entry.event
+                }
+                Some(AgendaLine::Day(date)) => {
+                    // Day heading, e.g. "Tomorrow · Tue Mar 17"
+                    let mut heading = date.format("%a %b %d").to_string();
+                    if let Some(label) = relative_day_label(*date, today) {
+                        heading = format!("{} \u{b7} {}", label, heading);
+                    }
+                    out.fg(colors::HEADER);
+                    out.print(&format!("{:width$}", truncate_str(&heading, width), width = width));
+                    out.reset_color();
+                    continue;
+                }
+                None if row == 0 => {
+                    out.fg(Color::DarkGrey);
+                    out.print(&format!("{:width$}", "  No events", width = width));
+                    out.reset_color();
+                    continue;
+                }
+                Some(AgendaLine::Blank) | None => {
+                    out.print(&format!("{:width$}", "", width = width));
+                    continue;
+                }
+            };
+
+            let is_selected = selected_line == Some(offset + row);
+            let end = event.end_time.or(event.start_time);
+            let is_past = event.date < today || end.is_some_and(|end| event.date == today && event.date.and_time(end) <= now);
+            let color = if is_selected {
+                colors::SELECTED
+            } else if is_past || !event.accepted {
+                colors::PAST_EVENT
+            } else if event.is_free {
+                colors::FREE_EVENT
+            } else {
+                Color::Reset
+            };
+
+            // Selection indicator, or a bullet in the calendar's accent
+            if is_selected {
+                out.fg(colors::SELECTED);
+                out.print("\u{25B6}");
+            } else if state.marked.iter().any(|m| m.is_same_occurrence(event)) {
+                out.fg(colors::MARKED);
+                out.print("+");
+            } else {
+                let accent = event_accent(state.config, event);
+                out.fg(if is_past { dim_color(accent) } else { accent });
+                out.print("\u{2022}");
+            }
+
+            let when = event.time_range_label(&state.config.time_format);
+            out.fg(color);
+            if is_selected {
+                out.bold();
+            }
+            let text = format!(" {:>11}  {}", when, event.title);
+            out.print(&format!("{:width$}", truncate_str(&text, width.saturating_sub(1)), width = width.saturating_sub(1)));
+            out.reset_color();
+            out.reset_attributes();
+        }
+    }
+
+    if in_event_mode && details_width >= MIN_PANEL_WIDTH {
+        render_selected_details(out, state, Rect::new(x + list_width + 1, 0, details_width, term_height.saturating_sub(2)));
+    }
+}
+
+fn render_calendar(out: &mut Screen, state: &RenderState, today: NaiveDate, height: u16) {
+    let current_date = state.current_date;
+    let selected_date = state.selected_date;
+    let show_weekends = state.show_weekends;
+    let week_start = state.config.week_start;
+    let is_loading = state.google_loading || state.icloud_loading;
+
+    out.move_to(0, 0);
 
     // Month header
-    execute!(
-        out,
-        SetForegroundColor(Color::Cyan),
-        SetAttribute(Attribute::Bold)
-    )
-    .unwrap();
+    out.fg(Color::Cyan);
+    out.bold();
 
     let cal_width = calendar_width(show_weekends);
     let loading_indicator = if is_loading { " *" } else { "" };
-    let header = format!(
-        "{} {}{}",
+    let mut header = format!(
+        "{} {}",
         current_date.format("%B").to_string().to_uppercase(),
         current_date.year(),
-        loading_indicator
     );
-    print!("{}", truncate_str(&header, cal_width as usize));
-    execute!(out, ResetColor, SetAttribute(Attribute::Reset)).unwrap();
+    // Week number of the selected date, when it fits
+    let week = format!(" W{:02}", week_start.week_number(selected_date));
+    if header.len() + week.len() + loading_indicator.len() < cal_width as usize {
+        header.push_str(&week);
+    }
+    header.push_str(loading_indicator);
+    out.print(&truncate_str(&header, cal_width as usize));
+    out.reset_color();
+    out.reset_attributes();
 
     // Separator line
     draw_separator(out, 0, 1, cal_width - 1);
 
     // Weekday header
-    execute!(out, cursor::MoveTo(0, 2)).unwrap();
-    execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-    if show_weekends {
-        print!("Mo Tu We Th Fr Sa Su");
-    } else {
-        print!("Mo Tu We Th Fr");
-    }
-    execute!(out, ResetColor).unwrap();
+    let columns = week_columns(week_start, show_weekends);
+    out.move_to(0, 2);
+    out.fg(Color::DarkGrey);
+    let labels: Vec<String> = columns.iter()
+        .map(|(_, weekday)| weekday.to_string()[..2].to_string())
+        .collect();
+    out.print(&labels.join(" "));
+    out.reset_color();
 
-    // Calendar grid
+    // Calendar grid, between the previous and next months when the column has room for them
     let first_day = current_date.with_day(1).unwrap();
-    let start_weekday = first_day.weekday().num_days_from_monday();
-    let days_in_month = days_in_month(current_date);
-    let cols = if show_weekends { 7 } else { 5 };
-
-    for row in 0..6 {
-        execute!(out, cursor::MoveTo(0, 3 + row as u16)).unwrap();
+    let previous_month = (first_day - Duration::days(1)).with_day(1).unwrap();
+    let next_month = first_day + Duration::days(days_in_month(current_date) as i64);
+    let availability_rows = state.config.work_hours.grid_hours(columns.iter().map(|&(_, weekday)| weekday)).len() as u16 + 3;
+    let mini_rows = mini_month_rows(previous_month, week_start) + mini_month_rows(next_month, week_start);
+    let show_mini_months = 3 + mini_rows + 6 + 1 + availability_rows <= height;
 
-        for col in 0..cols {
-            let cell = row * 7 + col; // Always use 7-day weeks for calculation
-            if cell < start_weekday || cell >= start_weekday + days_in_month {
-                print!("   ");
+    let mut row = 3;
+    if show_mini_months {
+        row = render_mini_month(out, state, today, previous_month, &columns, row);
+    }
+    let start_offset = week_start.days_from_start(first_day.weekday());
+    let days_in_month = days_in_month(current_date);
+    for week in 0..6 {
+        out.move_to(0, row + week as u16);
+        for &(col, weekday) in &columns {
+            let cell = week * 7 + col; // Always use 7-day weeks for calculation
+            if cell < start_offset || cell >= start_offset + days_in_month {
+                out.print("   ");
             } else {
-                let day = cell - start_weekday + 1;
-                let date = first_day.with_day(day).unwrap();
-                let is_today = date == today;
-                let is_selected = date == selected_date;
-                let is_weekend = col >= 5;
-                let has_events = events.has_events(date);
-
-                if is_selected {
-                    execute!(
-                        out,
-                        SetForegroundColor(Color::Black),
-                        SetAttribute(Attribute::Reverse)
-                    )
-                    .unwrap();
-                } else if is_today {
-                    execute!(
-                        out,
-                        SetForegroundColor(Color::Green),
-                        SetAttribute(Attribute::Bold)
-                    )
-                    .unwrap();
-                } else if is_weekend && show_weekends {
-                    execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-                }
+                let date = first_day.with_day(cell - start_offset + 1).unwrap();
+                render_day_cell(out, state, today, date, weekday, false);
+            }
+        }
+    }
+    row += 6;
+    if show_mini_months {
+        row = render_mini_month(out, state, today, next_month, &columns, row);
+    }
 
-                if has_events && !is_selected {
-                    print!("{:2}\u{2022}", day);
-                } else {
-                    print!("{:2} ", day);
-                }
+    // Render week availability below the calendar grid
+    render_week_availability(out, state.config, state.events, state.busy_overlay, selected_date, &columns, row + 1);
+}
 
-                execute!(out, ResetColor, SetAttribute(Attribute::Reset)).unwrap();
+/// Rows a mini month takes: its name and the weeks it spans
+fn mini_month_rows(month: NaiveDate, week_start: WeekStart) -> u16 {
+    let cells = week_start.days_from_start(month.weekday()) + days_in_month(month);
+    1 + cells.div_ceil(7) as u16
+}
+
+/// A neighbouring month in subdued colors from `row`, for moving across month
+/// boundaries with the mouse; returns the row below it
+fn render_mini_month(out: &mut Screen, state: &RenderState, today: NaiveDate, month: NaiveDate, columns: &[(u32, Weekday)], row: u16) -> u16 {
+    let cal_width = calendar_width(state.show_weekends);
+    out.move_to(0, row);
+    out.fg(colors::SEPARATOR);
+    out.print(&truncate_str(&format!("\u{2500} {} \u{2500}", month.format("%B")), cal_width as usize));
+    out.reset_color();
+
+    let start_offset = state.config.week_start.days_from_start(month.weekday());
+    let days = days_in_month(month);
+    let weeks = (start_offset + days).div_ceil(7);
+    for week in 0..weeks {
+        out.move_to(0, row + 1 + week as u16);
+        for &(col, weekday) in columns {
+            let cell = week * 7 + col;
+            if cell < start_offset || cell >= start_offset + days {
+                out.print("   ");
+            } else {
+                let date = month.with_day(cell - start_offset + 1).unwrap();
+                render_day_cell(out, state, today, date, weekday, true);
             }
         }
     }
+    row + 1 + weeks as u16
+}
 
-    // Render week availability below the calendar grid
-    render_week_availability(out, events, selected_date, show_weekends);
+/// A clickable day of the calendar grid, with a dot when it has events; `muted` days
+/// belong to a neighbouring month
+fn render_day_cell(out: &mut Screen, state: &RenderState, today: NaiveDate, date: NaiveDate, weekday: Weekday, muted: bool) {
+    let events = state.events;
+    let is_selected = date == state.selected_date;
+    let is_weekend = matches!(weekday, Weekday::Sat | Weekday::Sun);
+    let has_events = events.has_events(date);
+    let is_holiday = !events.holidays(date).is_empty();
+    out.hit(3, Hit::Day(date));
+
+    if is_selected {
+        out.fg(Color::Black);
+        out.reverse();
+    } else if date == today {
+        out.fg(Color::Green);
+        out.bold();
+    } else if is_holiday {
+        out.fg(colors::HOLIDAY);
+    } else if muted || (is_weekend && state.show_weekends) {
+        out.fg(Color::DarkGrey);
+    }
+
+    if has_events && !is_selected {
+        out.print(&format!("{:2}", date.day()));
+        // Dot in the accent of the day's first calendar
+        if let Some(first) = events.day(date).next() {
+            out.fg(event_accent(state.config, first));
+        }
+        out.print("\u{2022}");
+    } else {
+        out.print(&format!("{:2} ", date.day()));
+    }
+
+    out.reset_color();
+    out.reset_attributes();
 }
 
 /// Parse an event's time range into (start_minutes, end_minutes) from midnight.
 /// Returns None for all-day, free, or unaccepted events (not time-blocking).
 fn parse_event_range(event: &DisplayEvent) -> Option<(u32, u32)> {
-    if event.time_str == "All day" || event.is_free || !event.accepted {
+    if event.is_free || !event.accepted {
         return None;
     }
+    event_minutes(event)
+}
 
-    let start_time = parse_event_time(&event.time_str)?;
+/// Minutes from midnight a timed event covers, whatever its response or transparency;
+/// None for events of the all-day strip
+fn event_minutes(event: &DisplayEvent) -> Option<(u32, u32)> {
+    if event.in_all_day_strip() {
+        return None;
+    }
+    let start_time = event.start_time?;
     let event_start = start_time.hour() * 60 + start_time.minute();
 
-    let event_end = if let Some(ref end_str) = event.end_time_str {
-        if end_str == "All day" {
-            return None;
+    let event_end = match event.end_time {
+        Some(t) => {
+            let mins = t.hour() * 60 + t.minute();
+            if mins == 0 { 24 * 60 } else { mins }
         }
-        parse_event_time(end_str)
-            .map(|t| {
-                let mins = t.hour() * 60 + t.minute();
-                if mins == 0 { 24 * 60 } else { mins }
-            })
-            .unwrap_or(event_start + 60)
-    } else {
-        event_start + 60
+        None => event_start + 60,
     };
 
     Some((event_start, event_end))
 }
 
+/// Groups of time-blocking events that overlap one another, from one sort-and-sweep over
+/// a day's events of both sources; each event in a group overlaps at least one other
+fn conflict_groups(google_events: &[Arc<DisplayEvent>], icloud_events: &[Arc<DisplayEvent>]) -> Vec<Vec<(EventSource, usize)>> {
+    let mut ranges: Vec<((u32, u32), EventSource, usize)> = google_events
+        .iter()
+        .enumerate()
+        .map(|(i, e)| (e, EventSource::Google, i))
+        .chain(icloud_events.iter().enumerate().map(|(i, e)| (e, EventSource::ICloud, i)))
+        .filter_map(|(e, source, i)| parse_event_range(e).map(|range| (range, source, i)))
+        .collect();
+    ranges.sort_by_key(|&(range, ..)| range);
+
+    let mut groups = Vec::new();
+    let mut group: Vec<(EventSource, usize)> = Vec::new();
+    let mut group_end = 0;
+    for ((start, end), source, index) in ranges {
+        if start >= group_end {
+            if group.len() > 1 {
+                groups.push(std::mem::take(&mut group));
+            }
+            group.clear();
+        }
+        group_end = if group.is_empty() { end } else { group_end.max(end) };
+        group.push((source, index));
+    }
+    if group.len() > 1 {
+        groups.push(group);
+    }
+    groups
+}
+
 /// Detect overlapping events across two source panels.
 /// Returns sets of indices into google_events and icloud_events that overlap with any other event.
 fn compute_overlapping_events(
-    google_events: &[DisplayEvent],
-    icloud_events: &[DisplayEvent],
+    google_events: &[Arc<DisplayEvent>],
+    icloud_events: &[Arc<DisplayEvent>],
 ) -> (HashSet<usize>, HashSet<usize>) {
     let mut google_overlaps = HashSet::new();
     let mut icloud_overlaps = HashSet::new();
-
-    // Parse ranges once
-    let google_ranges: Vec<Option<(u32, u32)>> = google_events.iter().map(parse_event_range).collect();
-    let icloud_ranges: Vec<Option<(u32, u32)>> = icloud_events.iter().map(parse_event_range).collect();
-
-    // Check within Google events
-    for i in 0..google_ranges.len() {
-        for j in (i + 1)..google_ranges.len() {
-            if let (Some((s_a, e_a)), Some((s_b, e_b))) = (google_ranges[i], google_ranges[j]) {
-                if s_a < e_b && s_b < e_a {
-                    google_overlaps.insert(i);
-                    google_overlaps.insert(j);
-                }
-            }
-        }
-    }
-
-    // Check within iCloud events
-    for i in 0..icloud_ranges.len() {
-        for j in (i + 1)..icloud_ranges.len() {
-            if let (Some((s_a, e_a)), Some((s_b, e_b))) = (icloud_ranges[i], icloud_ranges[j]) {
-                if s_a < e_b && s_b < e_a {
-                    icloud_overlaps.insert(i);
-                    icloud_overlaps.insert(j);
-                }
-            }
-        }
-    }
-
-    // Check cross-source overlaps
-    for (gi, g_range) in google_ranges.iter().enumerate() {
-        for (ii, i_range) in icloud_ranges.iter().enumerate() {
-            if let (Some((s_a, e_a)), Some((s_b, e_b))) = (g_range, i_range) {
-                if s_a < e_b && s_b < e_a {
-                    google_overlaps.insert(gi);
-                    icloud_overlaps.insert(ii);
-                }
-            }
-        }
+    for (source, index) in conflict_groups(google_events, icloud_events).into_iter().flatten() {
+        match source {
+            EventSource::Google => google_overlaps.insert(index),
+            EventSource::ICloud => icloud_overlaps.insert(index),
+        };
     }
-
     (google_overlaps, icloud_overlaps)
 }
 
 /// Count how many time-blocking events cover a given slot (across both sources).
-fn count_slot_events(google_events: &[DisplayEvent], icloud_events: &[DisplayEvent], slot_start: u32, slot_end: u32) -> usize {
-    google_events.iter().chain(icloud_events.iter())
-        .filter_map(parse_event_range)
-        .filter(|(es, ee)| slot_start < *ee && slot_end > *es)
-        .count()
+fn slot_events<'a>(google_events: &'a [Arc<DisplayEvent>], icloud_events: &'a [Arc<DisplayEvent>], slot_start: u32, slot_end: u32) -> Vec<&'a DisplayEvent> {
+    google_events.iter().chain(icloud_events.iter()).map(Arc::as_ref)
+        .filter(|e| parse_event_range(e).is_some_and(|(es, ee)| slot_start < ee && slot_end > es))
+        .collect()
 }
 
-/// Get the Monday of the week containing the given date
-fn get_week_monday(date: NaiveDate) -> NaiveDate {
-    let weekday = date.weekday().num_days_from_monday();
-    date - Duration::days(weekday as i64)
+/// Visible calendar columns as (offset from start of week, weekday),
+/// skipping Saturday and Sunday when weekends are hidden
+fn week_columns(week_start: WeekStart, show_weekends: bool) -> Vec<(u32, Weekday)> {
+    let mut weekday = week_start.first_weekday();
+    let mut columns = Vec::with_capacity(7);
+    for offset in 0..7 {
+        if show_weekends || !matches!(weekday, Weekday::Sat | Weekday::Sun) {
+            columns.push((offset, weekday));
+        }
+        weekday = weekday.succ();
+    }
+    columns
 }
 
 /// Render week availability grid below the calendar
 fn render_week_availability(
-    out: &mut impl Write,
+    out: &mut Screen,
+    config: &Config,
     events: &EventCache,
+    overlay: Option<&BusyOverlay>,
     selected_date: NaiveDate,
-    show_weekends: bool,
+    columns: &[(u32, Weekday)],
+    start_row: u16,
 ) {
-    let start_row = 10u16; // Below the calendar grid
-    let monday = get_week_monday(selected_date);
+    let week_first_day = config.week_start.start_of_week(selected_date);
     let today = Local::now().date_naive();
     let current_minutes = {
         let now = Local::now().time();
         now.hour() * 60 + now.minute()
     };
-    let num_days = if show_weekends { 7 } else { 5 };
 
     // Header row
-    execute!(out, cursor::MoveTo(0, start_row)).unwrap();
-    execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-    if show_weekends {
-        print!("    M  T  W  T  F  S  S");
-    } else {
-        print!("    M  T  W  T  F");
+    out.move_to(0, start_row);
+    out.fg(Color::DarkGrey);
+    out.print("  ");
+    for &(day_offset, weekday) in columns {
+        // Lowercase initial of the working location (e.g. "h" for Home) before the weekday letter
+        let date = week_first_day + Duration::days(day_offset as i64);
+        match events.working_location(date).and_then(|l| l.chars().next()) {
+            Some(initial) => {
+                out.print(" ");
+                out.fg(colors::WORKING_LOCATION);
+                out.print(&initial.to_lowercase().to_string());
+                out.fg(Color::DarkGrey);
+            }
+            None => out.print("  "),
+        }
+        out.print(&weekday.to_string()[..1]);
     }
-    execute!(out, ResetColor).unwrap();
+    out.reset_color();
 
-    // Render each hour row (8am - 7pm = 12 rows)
+    // An hour row per hour of the week's working hours, with one either side
     // Each cell shows 30-min resolution using half-blocks
-    for hour_offset in 0..12u32 {
-        let hour = 8 + hour_offset;
+    let hours = config.work_hours.grid_hours(columns.iter().map(|&(_, weekday)| weekday));
+    let legend_row = start_row + 1 + hours.len() as u16;
+    for (hour_offset, hour) in hours.enumerate() {
         let row = start_row + 1 + hour_offset as u16;
 
-        execute!(out, cursor::MoveTo(0, row)).unwrap();
+        out.move_to(0, row);
 
         // Hour label
-        execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-        print!("{:2} ", hour);
-        execute!(out, ResetColor).unwrap();
+        out.fg(Color::DarkGrey);
+        out.print(&format!("{} ", config.time_format.short_hour(hour)));
+        out.reset_color();
 
         // Check each weekday
-        for day_offset in 0..num_days as i64 {
-            let date = monday + Duration::days(day_offset);
+        for &(day_offset, _) in columns {
+            let date = week_first_day + Duration::days(day_offset as i64);
 
             // Get events for this date from both sources
             let google_events = events.google.get(date);
@@ -697,113 +1540,220 @@ fn render_week_availability(
             let slot2_start = hour * 60 + 30;  // :30
             let slot2_end = (hour + 1) * 60;   // :00 next hour
 
-            let first_half_count = count_slot_events(google_events, icloud_events, slot1_start, slot1_end);
-            let second_half_count = count_slot_events(google_events, icloud_events, slot2_start, slot2_end);
+            let first_half = slot_events(google_events, icloud_events, slot1_start, slot1_end);
+            let second_half = slot_events(google_events, icloud_events, slot2_start, slot2_end);
 
-            let first_half_busy = first_half_count > 0;
-            let second_half_busy = second_half_count > 0;
+            let first_half_busy = !first_half.is_empty();
+            let second_half_busy = !second_half.is_empty();
 
             let is_past_day = date < today;
             let first_half_past = is_past_day || (date == today && current_minutes >= slot1_end);
             let second_half_past = is_past_day || (date == today && current_minutes >= slot2_end);
 
-            let dim = |color: Color| -> Color {
-                match color {
-                    Color::Blue => Color::Rgb { r: 90, g: 90, b: 170 },
-                    Color::Red => Color::Rgb { r: 170, g: 75, b: 75 },
-                    Color::Rgb { r: 200, g: 200, b: 200 } => Color::Rgb { r: 150, g: 150, b: 150 },
-                    other => other,
-                }
+            // A single busy event takes its calendar's accent; overlaps are red
+            let color_for = |slot: &[&DisplayEvent], past: bool| -> Color {
+                let c = if slot.len() >= 2 { colors::OVERLAP_EVENT } else { event_accent(config, slot[0]) };
+                if past { dim_color(c) } else { c }
             };
 
-            let color_for = |count: usize, past: bool| -> Color {
-                let c = if count >= 2 { colors::OVERLAP_EVENT } else { colors::BUSY_BLOCK };
-                if past { dim(c) } else { c }
+            // Free time of yours the overlaid colleague is busy in takes the darker shade
+            let colleague_busy = |start: u32, end: u32| overlay.is_some_and(|o| o.is_busy(date, start, end));
+            let first_half_colleague = colleague_busy(slot1_start, slot1_end);
+            let second_half_colleague = colleague_busy(slot2_start, slot2_end);
+            let free_color = |past: bool, colleague: bool| -> Color {
+                let c = if colleague { colors::COLLEAGUE_BUSY } else { colors::FREE_BLOCK };
+                if past { dim_color(c) } else { c }
             };
 
-            let free_color = |past: bool| -> Color {
-                if past { dim(colors::FREE_BLOCK) } else { colors::FREE_BLOCK }
-            };
+            // Hours blocked only by focus time are hatched instead of solid
+            let focus_only = (first_half_busy || second_half_busy)
+                && first_half.iter().chain(&second_half).all(|e| e.kind == EventKind::FocusTime);
 
             // Vertical half-blocks: top = first 30 min, bottom = second 30 min
             // ▀ draws top with fg, bottom with bg
             match (first_half_busy, second_half_busy) {
+                _ if focus_only => {
+                    let (slot, past) = if first_half_busy { (&first_half, first_half_past) } else { (&second_half, second_half_past) };
+                    let colleague = first_half_colleague || second_half_colleague;
+                    out.fg(color_for(slot, past));
+                    out.bg(free_color(past, colleague));
+                    out.print("▒▒");
+                }
                 (true, true) => {
-                    let top = color_for(first_half_count, first_half_past);
-                    let bot = color_for(second_half_count, second_half_past);
+                    let top = color_for(&first_half, first_half_past);
+                    let bot = color_for(&second_half, second_half_past);
                     if top == bot {
-                        execute!(out, SetForegroundColor(top)).unwrap();
-                        print!("██");
+                        out.fg(top);
+                        out.print("██");
                     } else {
-                        execute!(out, SetForegroundColor(top), SetBackgroundColor(bot)).unwrap();
-                        print!("▀▀");
+                        out.fg(top);
+                        out.bg(bot);
+                        out.print("▀▀");
                     }
                 }
                 (true, false) => {
-                    execute!(out, SetForegroundColor(color_for(first_half_count, first_half_past)), SetBackgroundColor(free_color(second_half_past))).unwrap();
-                    print!("▀▀");
+                    out.fg(color_for(&first_half, first_half_past));
+                    out.bg(free_color(second_half_past, second_half_colleague));
+                    out.print("▀▀");
                 }
                 (false, true) => {
-                    execute!(out, SetForegroundColor(free_color(first_half_past)), SetBackgroundColor(color_for(second_half_count, second_half_past))).unwrap();
-                    print!("▀▀");
+                    out.fg(free_color(first_half_past, first_half_colleague));
+                    out.bg(color_for(&second_half, second_half_past));
+                    out.print("▀▀");
                 }
                 (false, false) => {
-                    execute!(out, SetForegroundColor(free_color(first_half_past))).unwrap();
-                    print!("██");
+                    let top = free_color(first_half_past, first_half_colleague);
+                    let bot = free_color(second_half_past, second_half_colleague);
+                    if top == bot {
+                        out.fg(top);
+                        out.print("██");
+                    } else {
+                        out.fg(top);
+                        out.bg(bot);
+                        out.print("▀▀");
+                    }
                 }
             }
-            execute!(out, ResetColor).unwrap();
-            print!(" ");
+            out.reset_color();
+            out.print(" ");
         }
-        execute!(out, ResetColor).unwrap();
+        out.reset_color();
+    }
+
+    // Legend for the overlay
+    let grid_width = 3 + columns.len() * 3;
+    let legend = overlay.map(|o| format!("   {}", o.email)).unwrap_or_default();
+    out.move_to(0, legend_row);
+    out.fg(colors::COLLEAGUE_BUSY);
+    out.print(&format!("{:<width$}", truncate_str(&legend, grid_width), width = grid_width));
+    if overlay.is_some() {
+        out.move_to(1, legend_row);
+        out.print("\u{2588}");
+    }
+    out.reset_color();
+    // Conflicts across the shown days, under the legend
+    let conflicts: usize = columns
+        .iter()
+        .map(|&(day_offset, _)| week_first_day + Duration::days(day_offset as i64))
+        .map(|date| conflict_groups(events.google.get(date), events.icloud.get(date)).len())
+        .sum();
+    if conflicts > 0 {
+        out.move_to(0, legend_row + 1);
+        out.fg(colors::OVERLAP_EVENT);
+        let line = format!(" ! {} conflict{} this week", conflicts, if conflicts == 1 { "" } else { "s" });
+        out.print(&truncate_str(&line, grid_width));
+        out.reset_color();
     }
 }
 
-/// Render event panel with title and events
-fn render_event_panel(
-    out: &mut impl Write,
-    x: u16,
-    y: u16,
-    width: u16,
-    title: &str,
-    events: &[DisplayEvent],
-    is_loading: bool,
-    accent_color: Color,
+/// Accent color of a source, from config or the built-in default
+fn source_accent(config: &Config, source: EventSource) -> Color {
+    let (configured, default) = match source {
+        EventSource::Google => (&config.colors.google, colors::GOOGLE_ACCENT),
+        EventSource::ICloud => (&config.colors.icloud, colors::ICLOUD_ACCENT),
+    };
+    configured.as_deref().and_then(parse_color).unwrap_or(default)
+}
+
+/// Accent color of an event's calendar, falling back to its source accent
+fn event_accent(config: &Config, event: &DisplayEvent) -> Color {
+    let (calendar_name, source) = match &event.id {
+        EventId::Google { calendar_name, .. } => (calendar_name, EventSource::Google),
+        EventId::ICloud { calendar_name, .. } => (calendar_name, EventSource::ICloud),
+        // Feeds don't record their panel; they take the personal accent unless named in colors.calendars
+        EventId::Feed { calendar_name, .. } => (calendar_name, EventSource::ICloud),
+    };
+    calendar_name.as_ref()
+        .and_then(|name| config.colors.calendars.get(name))
+        .and_then(|c| parse_color(c))
+        .unwrap_or_else(|| source_accent(config, source))
+}
+
+/// Dimmed variant of a color for past time slots
+fn dim_color(color: Color) -> Color {
+    match color {
+        Color::Blue => Color::Rgb { r: 90, g: 90, b: 170 },
+        Color::Red => Color::Rgb { r: 170, g: 75, b: 75 },
+        Color::Magenta => Color::DarkMagenta,
+        Color::Cyan => Color::DarkCyan,
+        Color::Green => Color::DarkGreen,
+        Color::Yellow => Color::DarkYellow,
+        Color::Rgb { r, g, b } => Color::Rgb { r: r / 4 * 3, g: g / 4 * 3, b: b / 4 * 3 },
+        other => other,
+    }
+}
+
+/// Parse a color name ("blue", "dark_cyan") or "#rrggbb" into a terminal color
+fn parse_color(value: &str) -> Option<Color> {
+    let Some(hex) = value.strip_prefix('#') else {
+        return Color::try_from(value).ok();
+    };
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some(Color::Rgb { r: channel(0)?, g: channel(2)?, b: channel(4)? })
+}
+
+/// The day the event panels show
+#[derive(Clone, Copy)]
+struct PanelDay<'a> {
+    date: NaiveDate,
+    time_format: &'a TimeFormatConfig,
     is_today: bool,
     is_past_day: bool,
     current_time: NaiveTime,
+    marked: &'a [DisplayEvent],
+}
+
+/// One titled list of events for the shown day
+struct EventPanel<'a> {
+    title: &'a str,
+    events: &'a [Arc<DisplayEvent>],
+    is_loading: bool,
+    error: Option<&'a str>,
+    accent_color: Color,
+    event_color: &'a dyn Fn(&DisplayEvent) -> Option<Color>,
     selected_index: Option<usize>,
-    overlapping_indices: &HashSet<usize>,
-) {
+    overlapping_indices: &'a HashSet<usize>,
+}
+
+/// Render an event panel into `area`: a header row with the title, then a row per event
+fn render_event_panel(out: &mut Screen, area: Rect, day: PanelDay, panel: EventPanel) {
+    let PanelDay { date, time_format, is_today, is_past_day, current_time, marked } = day;
+    let EventPanel { title, events, is_loading, error, accent_color, event_color, selected_index, overlapping_indices } = panel;
+    let width = area.width;
+    let [header_area, list_area] = Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+
     // Panel header: ─ Title ─────────
-    execute!(out, cursor::MoveTo(x, y)).unwrap();
-    execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-    print!("\u{2500} ");
-    execute!(out, SetForegroundColor(accent_color)).unwrap();
     let loading_str = if is_loading { "*" } else { "" };
-    print!("{}{}", title, loading_str);
-    execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-    print!(" ");
-    let remaining = width.saturating_sub(title.len() as u16 + 4 + loading_str.len() as u16);
-    for _ in 0..remaining.min(40) {
-        print!("\u{2500}");
-    }
-    execute!(out, ResetColor).unwrap();
-
-    let content_start = y + 1;
+    let mut header = vec![
+        Span::styled("\u{2500} ", fg(Color::DarkGrey)),
+        Span::styled(format!("{}{}", title, loading_str), fg(accent_color)),
+    ];
+    let mut header_len = title.len() + loading_str.len() + 4;
+    if let Some(error) = error {
+        // Error banner with retry hint: "─ Work ✗ Calendar API error (R:retry) ──"
+        let first_line = error.lines().next().unwrap_or_default();
+        let hint = " (R:retry)";
+        let max_len = (width as usize).saturating_sub(header_len + hint.len() + 4);
+        let banner = format!(" \u{2717} {}{}", truncate_str(first_line, max_len), hint);
+        header_len += banner.chars().count();
+        header.push(Span::styled(banner, fg(colors::OVERLAP_EVENT)));
+    }
+    let remaining = width.saturating_sub(header_len as u16).min(40) as usize;
+    header.push(Span::styled(format!(" {}", "\u{2500}".repeat(remaining)), fg(Color::DarkGrey)));
+    out.render(Paragraph::new(Line::from(header)), header_area);
 
     if events.is_empty() {
-        execute!(out, cursor::MoveTo(x, content_start)).unwrap();
-        execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-        if is_loading {
-            print!("Loading...");
-        } else {
-            print!("No events");
-        }
-        execute!(out, ResetColor).unwrap();
+        let empty = if is_loading { "Loading..." } else { "No events" };
+        out.render(Paragraph::new(Span::styled(empty, fg(Color::DarkGrey))), list_area);
         return;
     }
 
+    // Durations only when the panel leaves enough room for titles
+    let show_durations = width >= DURATION_MIN_PANEL_WIDTH;
+
     // Find current and next event indices
     let (current_event_idx, next_event_idx) = if is_today {
         find_current_and_next_events(events, current_time)
@@ -811,9 +1761,7 @@ fn render_event_panel(
         (None, None)
     };
 
-    for (i, event) in events.iter().enumerate() {
-        execute!(out, cursor::MoveTo(x, content_start + i as u16)).unwrap();
-
+    let items = events.iter().enumerate().map(|(i, event)| {
         let is_selected = selected_index == Some(i);
         let is_current = current_event_idx == Some(i);
         let is_next = next_event_idx == Some(i);
@@ -821,10 +1769,11 @@ fn render_event_panel(
         let is_unaccepted = !event.accepted;
         let is_free_event = event.is_free;
         let is_overlapping = overlapping_indices.contains(&i);
+        let provider_color = event_color(event);
 
         // Choose color based on event status
         // Priority: Selected > Past/Unaccepted > Free > Overlap (Red) > Current (Green) > Next (Yellow) > Default
-        let event_color = if is_selected {
+        let color = if is_selected {
             colors::SELECTED
         } else if is_past_day || is_unaccepted || is_past_event {
             colors::PAST_EVENT
@@ -836,263 +1785,267 @@ fn render_event_panel(
             colors::CURRENT_EVENT
         } else if is_next {
             colors::NEXT_EVENT
+        } else if event.kind == EventKind::FocusTime {
+            colors::FOCUS_TIME
         } else {
-            Color::Reset
+            provider_color.unwrap_or(Color::Reset)
         };
+        let mut style = fg(color);
+        if is_selected || ((is_current || is_next) && !is_unaccepted && !is_free_event) {
+            style = style.add_modifier(Modifier::BOLD);
+        }
 
         // Selection indicator
-        if is_selected {
-            execute!(out, SetForegroundColor(Color::Cyan)).unwrap();
-            print!("\u{25B6}"); // Right-pointing triangle
+        let indicator = if is_selected {
+            Span::styled("\u{25B6}", fg(Color::Cyan)) // Right-pointing triangle
+        } else if marked.iter().any(|m| m.is_same_occurrence(event)) {
+            Span::styled("+", fg(colors::MARKED))
         } else if is_overlapping && !is_past_day && !is_unaccepted && !is_free_event && !is_past_event {
-            execute!(out, SetForegroundColor(colors::OVERLAP_EVENT)).unwrap();
-            print!("!");
+            Span::styled("!", fg(colors::OVERLAP_EVENT))
         } else if is_current && !is_unaccepted && !is_free_event {
-            execute!(out, SetForegroundColor(Color::Green)).unwrap();
-            print!("\u{25CF}"); // Filled circle
+            Span::styled("\u{25CF}", fg(Color::Green)) // Filled circle
         } else if is_next && !is_unaccepted && !is_free_event {
-            execute!(out, SetForegroundColor(Color::Yellow)).unwrap();
-            print!("\u{25CB}"); // Empty circle
+            Span::styled("\u{25CB}", fg(Color::Yellow)) // Empty circle
+        } else if let Some(color) = provider_color
+            && !is_past_day && !is_unaccepted && !is_free_event && !is_past_event
+        {
+            Span::styled("\u{2022}", fg(color)) // Bullet in the provider's event color
         } else {
-            print!(" ");
-        }
+            Span::raw(" ")
+        };
 
-        // Time
-        execute!(out, SetForegroundColor(event_color)).unwrap();
-        if is_selected || ((is_current || is_next) && !is_unaccepted && !is_free_event) {
-            execute!(out, SetAttribute(Attribute::Bold)).unwrap();
+        let mut spans = vec![indicator, Span::styled(format!("{:>7} ", strip_time_label(event, date, time_format)), style)];
+        let mut used_width = 10;
+        if show_durations {
+            let duration = event.duration_minutes().map(format_duration).unwrap_or_default();
+            let column = if duration.is_empty() { format!("{:7} ", "") } else { format!("\u{b7} {:<5} ", duration) };
+            spans.push(Span::styled(column, fg(Color::DarkGrey)));
+            used_width += 8;
         }
-        print!("{:>7} ", event.time_str);
-        execute!(out, ResetColor, SetAttribute(Attribute::Reset)).unwrap();
 
         // Title
-        execute!(out, SetForegroundColor(event_color)).unwrap();
-        if is_selected || ((is_current || is_next) && !is_unaccepted && !is_free_event) {
-            execute!(out, SetAttribute(Attribute::Bold)).unwrap();
+        let title_width = width.saturating_sub(used_width) as usize;
+        // Flag upcoming meetings most attendees have declined, shortened on narrow panels
+        let marker = if is_past_day || is_past_event || !event.mostly_declined() {
+            ""
+        } else if title_width >= 40 {
+            " \u{26A0} mostly declined"
+        } else {
+            " \u{26A0}"
+        };
+        let span = event.day_of_span(date).map(|day| format!(" {}", day)).unwrap_or_default();
+        let title_width = title_width.saturating_sub(marker.chars().count() + span.len());
+        // Replies of the invited, "5✓ 1? 2✗", when the title keeps some room
+        let responses: Vec<(String, AttendeeStatus)> = event.response_counts()
+            .map(|(accepted, awaiting, declined)| {
+                [(accepted, AttendeeStatus::Accepted), (awaiting, AttendeeStatus::NeedsAction), (declined, AttendeeStatus::Declined)]
+                    .into_iter()
+                    .filter(|&(count, _)| count > 0)
+                    .map(|(count, status)| (format!(" {}{}", count, status.icon()), status))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let responses_width: usize = responses.iter().map(|(part, _)| part.chars().count()).sum();
+        let (responses, responses_width) = if title_width >= responses_width + RESPONSES_MIN_TITLE_WIDTH {
+            (responses, responses_width)
+        } else {
+            (Vec::new(), 0)
+        };
+        spans.push(Span::styled(truncate_str(&event.title, title_width - responses_width), style));
+        if !span.is_empty() {
+            spans.push(Span::styled(span, fg(Color::DarkGrey)));
+        }
+        for (part, status) in responses {
+            let color = if is_past_day || is_past_event { Color::DarkGrey } else { attendee_status_color(&status) };
+            spans.push(Span::styled(part, fg(color)));
+        }
+        if !marker.is_empty() {
+            spans.push(Span::styled(marker, fg(colors::LOW_ATTENDANCE)));
         }
-        let title_width = width.saturating_sub(10) as usize;
-        print!("{}", truncate_str(&event.title, title_width));
-        execute!(out, ResetColor, SetAttribute(Attribute::Reset)).unwrap();
-    }
-}
-
-/// Render event details in a column
-fn render_event_details_column(
-    out: &mut impl Write,
-    x: u16,
-    y: u16,
-    width: u16,
-    height: u16,
-    event: Option<&DisplayEvent>,
-) {
-    // Header
-    execute!(out, cursor::MoveTo(x, y)).unwrap();
-    execute!(out, SetForegroundColor(colors::HEADER), SetAttribute(Attribute::Bold)).unwrap();
-    print!("Details");
-    execute!(out, ResetColor, SetAttribute(Attribute::Reset)).unwrap();
 
-    // Separator line
-    draw_separator(out, x, y + 1, width);
+        // All-day and multi-day events lead the list on a band of their own
+        let item = ListItem::new(Line::from(spans));
+        if event.in_all_day_strip() {
+            item.style(Style::default().bg(colors::ALL_DAY_STRIP.into()))
+        } else {
+            item
+        }
+    });
+    out.render(List::new(items), list_area);
+}
 
-    let content_x = x;
-    let content_width = width as usize;
-    let mut current_row = y + 2;
+/// Render event details in a column: the event's facts, the actions it offers, then its
+/// participants as far as they fit
+fn render_event_details_column(out: &mut Screen, state: &RenderState, area: Rect, event: Option<&DisplayEvent>) {
+    let [header_area, separator_area, body] =
+        Layout::vertical([Constraint::Length(1), Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+    out.render(
+        Paragraph::new("Details").style(fg(colors::HEADER).add_modifier(Modifier::BOLD)),
+        header_area,
+    );
+    out.render(Paragraph::new(separator(area.width)), separator_area);
 
     let Some(event) = event else {
-        execute!(out, cursor::MoveTo(content_x, current_row)).unwrap();
-        execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-        print!("No event selected");
-        execute!(out, ResetColor).unwrap();
+        out.render(Paragraph::new(Span::styled("No event selected", fg(Color::DarkGrey))), body);
         return;
     };
+    let content_width = area.width as usize;
+    let tags = state.tags.get(event);
+    let mut lines = Vec::new();
 
-    // Title
-    execute!(out, cursor::MoveTo(content_x, current_row)).unwrap();
-    execute!(out, SetForegroundColor(colors::TITLE), SetAttribute(Attribute::Bold)).unwrap();
-    print!("{}", truncate_str(&event.title, content_width));
-    execute!(out, ResetColor, SetAttribute(Attribute::Reset)).unwrap();
-    current_row += 1;
+    lines.push(Line::styled(
+        truncate_str(&event.title, content_width),
+        fg(colors::TITLE).add_modifier(Modifier::BOLD),
+    ));
 
     // Time
-    execute!(out, cursor::MoveTo(content_x, current_row)).unwrap();
-    execute!(out, SetForegroundColor(colors::TIME)).unwrap();
-    if let Some(ref end) = event.end_time_str {
-        print!("\u{1F552} {} - {}", event.time_str, end);
+    if let (Some(start), Some(end)) = (event.start_time, event.end_time) {
+        let mut time = vec![Span::styled(format!("\u{1F552} {} - {}", state.config.time_format.time(start), state.config.time_format.time(end)), fg(colors::TIME))];
+        if let Some(minutes) = event.duration_minutes() {
+            time.push(Span::styled(format!(" ({})", format_duration(minutes)), fg(Color::DarkGrey)));
+        }
+        lines.push(Line::from(time));
     } else {
-        print!("\u{1F552} {}", event.time_str);
+        lines.push(Line::styled(format!("\u{1F552} {}", event.time_label(&state.config.time_format)), fg(colors::TIME)));
     }
-    execute!(out, ResetColor).unwrap();
-    current_row += 1;
 
-    // Location
     if let Some(ref loc) = event.location
-        && !loc.is_empty() && current_row < y + height - 3 {
-            execute!(out, cursor::MoveTo(content_x, current_row)).unwrap();
-            execute!(out, SetForegroundColor(colors::LOCATION)).unwrap();
-            print!("\u{1F4CD} {}", truncate_str(loc, content_width.saturating_sub(3)));
-            execute!(out, ResetColor).unwrap();
-            current_row += 1;
-        }
+        && !loc.is_empty()
+    {
+        lines.push(Line::styled(format!("\u{1F4CD} {}", truncate_str(loc, content_width.saturating_sub(3))), fg(colors::LOCATION)));
+    }
 
-    // Calendar source
-    if current_row < y + height - 3 {
-        execute!(out, cursor::MoveTo(content_x, current_row)).unwrap();
-        execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-        match &event.id {
-            EventId::Google { calendar_name, .. } => {
-                if let Some(name) = calendar_name {
-                    print!("Google - {}", name);
-                } else {
-                    print!("Google");
-                }
-            }
-            EventId::ICloud { calendar_name, .. } => {
-                if let Some(name) = calendar_name {
-                    print!("iCloud - {}", name);
-                } else {
-                    print!("iCloud");
-                }
-            }
-        }
-        execute!(out, ResetColor).unwrap();
-        current_row += 1;
+    // When to set off, for events somewhere in person
+    if let Some(leave) = state.config.travel.leave_by(event) {
+        let travel = (event.date.and_time(event.start_time.unwrap_or_default()) - leave).num_minutes();
+        lines.push(Line::from(vec![
+            Span::styled(format!("\u{1F6B6} Leave by {}", state.config.time_format.time(leave.time())), fg(colors::TIME)),
+            Span::styled(format!(" ({} travel)", format_duration(travel)), fg(Color::DarkGrey)),
+        ]));
+    }
+
+    // Booked rooms, right under the location since that's where people go
+    if !event.rooms.is_empty() {
+        lines.push(Line::styled(
+            format!("\u{1F6AA} {}", truncate_str(&event.rooms.join(", "), content_width.saturating_sub(3))),
+            fg(colors::LOCATION).add_modifier(Modifier::BOLD),
+        ));
     }
 
-    // Actions section
-    current_row += 1; // Blank line before actions
+    // Calendar source
+    let source = match &event.id {
+        EventId::Google { calendar_name: Some(name), .. } => format!("Google - {}", name),
+        EventId::Google { .. } => "Google".to_string(),
+        EventId::ICloud { calendar_name: Some(name), .. } => format!("iCloud - {}", name),
+        EventId::ICloud { .. } => "iCloud".to_string(),
+        EventId::Feed { calendar_name, .. } => format!("Feed - {} (read-only)", calendar_name.as_deref().unwrap_or("unnamed")),
+    };
+    lines.push(Line::styled(source, fg(Color::DarkGrey)));
+
+    // Local tags
+    if !tags.is_empty() {
+        lines.push(Line::styled(truncate_str(&format_tags(tags), content_width), fg(colors::TAG)));
+    }
 
-    // Meeting link
-    if event.meeting_url.is_some() && current_row < y + height - 3 {
-        execute!(out, cursor::MoveTo(content_x, current_row)).unwrap();
-        execute!(out, SetForegroundColor(colors::ACTION)).unwrap();
-        print!("[J] Join");
-        execute!(out, ResetColor).unwrap();
-        current_row += 1;
+    // Attendee and room names arrive after the event is opened
+    if event.partial {
+        lines.push(Line::styled("Loading details...", fg(Color::DarkGrey)));
     }
 
+    // Actions, after a blank line
+    lines.push(Line::default());
+    if event.meeting_url.is_some() {
+        out.move_to(body.x, body.y + lines.len() as u16);
+        if lines.len() < body.height as usize {
+            out.hit(8, Hit::Join);
+        }
+        lines.push(Line::styled("[J] Join  [Q] QR code", fg(colors::ACTION)));
+    }
+    if let Some(ref tel) = event.dial_in {
+        let label = format!("[P] Dial {}", tel.trim_start_matches("tel:"));
+        lines.push(Line::styled(truncate_str(&label, content_width), fg(colors::ACTION)));
+    }
     // Accept/Decline (Google events only)
-    if matches!(event.id, EventId::Google { .. }) && current_row < y + height - 3 {
-        execute!(out, cursor::MoveTo(content_x, current_row)).unwrap();
-        execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-        if event.accepted {
-            print!("[d] Decline");
+    if matches!(event.id, EventId::Google { .. }) {
+        let answer = if event.accepted {
+            "[d] Decline"
+        } else if event.meeting_url.is_some() {
+            "[a] Accept  [A] Accept & join"
         } else {
-            print!("[a] Accept");
-        }
-        execute!(out, ResetColor).unwrap();
-        current_row += 1;
-    }
-
-    // Delete
-    if current_row < y + height - 3 {
-        execute!(out, cursor::MoveTo(content_x, current_row)).unwrap();
-        execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-        print!("[x] Delete");
-        execute!(out, ResetColor).unwrap();
-        current_row += 1;
-    }
-
-    // Separator
-    if current_row < y + height - 2 {
-        current_row += 1;
-    }
-
-    // Participants
-    if !event.attendees.is_empty() && current_row < y + height - 2 {
-        execute!(out, cursor::MoveTo(content_x, current_row)).unwrap();
-        execute!(out, SetForegroundColor(Color::White), SetAttribute(Attribute::Bold)).unwrap();
-        print!("Participants:");
-        execute!(out, ResetColor, SetAttribute(Attribute::Reset)).unwrap();
-        current_row += 1;
-
-        let max_row = y + height - 1;
-        for attendee in &event.attendees {
-            if current_row >= max_row {
-                execute!(out, cursor::MoveTo(content_x, current_row)).unwrap();
-                execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-                let remaining = event.attendees.len() - (current_row - y - 7) as usize;
-                if remaining > 0 {
-                    print!("  ... +{} more", remaining);
-                }
-                execute!(out, ResetColor).unwrap();
-                break;
-            }
-
-            execute!(out, cursor::MoveTo(content_x, current_row)).unwrap();
-
-            // Status icon
-            execute!(out, SetForegroundColor(attendee.status.color())).unwrap();
-            print!("  {} ", attendee.status.icon());
-            execute!(out, ResetColor).unwrap();
+            "[a] Accept"
+        };
+        lines.push(Line::styled(answer, fg(Color::DarkGrey)));
+    }
+    if !event.attendees.is_empty() {
+        lines.push(Line::styled("[m] Email attendees", fg(Color::DarkGrey)));
+    }
+    lines.push(Line::styled("[c] Copy to calendar", fg(Color::DarkGrey)));
+    lines.push(Line::styled("[x] Delete", fg(Color::DarkGrey)));
 
-            // Name or email
+    // Participants, with a count of those that don't fit on the last row
+    if !event.attendees.is_empty() {
+        lines.push(Line::default());
+        lines.push(Line::styled("Participants:", fg(Color::White).add_modifier(Modifier::BOLD)));
+        let room = (body.height as usize).saturating_sub(lines.len());
+        let shown = if event.attendees.len() > room { room.saturating_sub(1) } else { room };
+        for attendee in event.attendees.iter().take(shown) {
             let display_name = attendee.name.as_ref().unwrap_or(&attendee.email);
             let status_str = match attendee.status {
                 AttendeeStatus::Organizer => " (org)",
                 _ => "",
             };
             let name_width = content_width.saturating_sub(5 + status_str.len());
-            print!("{}{}", truncate_str(display_name, name_width), status_str);
-            current_row += 1;
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", attendee.status.icon()), fg(attendee_status_color(&attendee.status))),
+                Span::raw(format!("{}{}", truncate_str(display_name, name_width), status_str)),
+            ]));
+        }
+        if shown < event.attendees.len() && room > 0 {
+            lines.push(Line::styled(format!("  ... +{} more", event.attendees.len() - shown), fg(Color::DarkGrey)));
         }
     }
+
+    out.render(Paragraph::new(lines), body);
 }
 
-/// Parse time string like "14:30" into NaiveTime
-fn parse_event_time(time_str: &str) -> Option<NaiveTime> {
-    if time_str == "All day" {
-        return NaiveTime::from_hms_opt(0, 0, 0);
-    }
-    let parts: Vec<&str> = time_str.split(':').collect();
-    if parts.len() == 2 {
-        let hour: u32 = parts[0].parse().ok()?;
-        let minute: u32 = parts[1].parse().ok()?;
-        NaiveTime::from_hms_opt(hour, minute, 0)
-    } else {
-        None
+/// Time column of a panel row on `date`: the start on an event's first day, the end on the
+/// last day of one running over several days, and "All day" in between
+fn strip_time_label(event: &DisplayEvent, date: NaiveDate, format: &TimeFormatConfig) -> String {
+    match (event.end_time, event.end_date) {
+        _ if date == event.date => event.time_label(format),
+        (Some(end), Some(last)) if date == last && event.start_time.is_some() => format!("\u{2192}{}", format.time(end)),
+        _ => "All day".to_string(),
     }
 }
 
-/// Check if an event is in the past
+/// Check if an event is in the past; events of the all-day strip never are during the day
 fn is_event_past(event: &DisplayEvent, current_time: NaiveTime) -> bool {
-    if let Some(event_time) = parse_event_time(&event.time_str) {
-        if event.time_str == "All day" {
-            return false; // All-day events are never "past" during the day
-        }
-        event_time < current_time
-    } else {
-        false
-    }
+    !event.in_all_day_strip() && event.start_time.is_some_and(|start| start < current_time)
 }
 
 /// Find indices of current (happening now) and next upcoming event
 /// Returns (current_index, next_index)
-pub fn find_current_and_next_events(events: &[DisplayEvent], current_time: NaiveTime) -> (Option<usize>, Option<usize>) {
+pub fn find_current_and_next_events(events: &[Arc<DisplayEvent>], current_time: NaiveTime) -> (Option<usize>, Option<usize>) {
     let mut current_idx: Option<usize> = None;
     let mut next_idx: Option<usize> = None;
 
     for (i, event) in events.iter().enumerate() {
-        if let Some(event_time) = parse_event_time(&event.time_str) {
-            if event.time_str == "All day" {
-                continue; // Skip all-day events
-            }
+        // Skip the all-day strip
+        let Some(event_time) = event.start_time.filter(|_| !event.in_all_day_strip()) else { continue };
 
-            // Check if event is currently happening (started but not ended)
-            if event_time <= current_time {
-                // Check if event has ended
-                let has_ended = event.end_time_str.as_ref().map_or(false, |end_str| {
-                    parse_event_time(end_str).map_or(false, |end_time| current_time >= end_time)
-                });
+        // Check if event is currently happening (started but not ended)
+        if event_time <= current_time {
+            // Check if event has ended
+            let has_ended = event.end_time.is_some_and(|end_time| current_time >= end_time);
 
-                if !has_ended {
-                    // Event is still ongoing - it's the current candidate
-                    current_idx = Some(i);
-                }
-            } else if next_idx.is_none() {
-                // First event that hasn't started yet
-                next_idx = Some(i);
-                break; // No need to continue
+            if !has_ended {
+                // Event is still ongoing - it's the current candidate
+                current_idx = Some(i);
             }
+        } else if next_idx.is_none() {
+            // First event that hasn't started yet
+            next_idx = Some(i);
+            break; // No need to continue
         }
     }
 
@@ -1108,325 +2061,403 @@ fn truncate_str(s: &str, max_len: usize) -> String {
     }
 }
 
-/// Format a smart "when" string combining date and time based on proximity
-fn format_smart_when(date: NaiveDate, time_str: &str, today: NaiveDate) -> String {
-    let days = (date - today).num_days();
-    let is_all_day = time_str == "All day";
-
-    if days == 0 {
-        if is_all_day { "today".to_string() } else { format!("today {}", time_str) }
-    } else if days == 1 {
-        if is_all_day { "tmrw".to_string() } else { format!("tmrw {}", time_str) }
-    } else if days >= 2 && days <= 6 {
-        let weekday = date.format("%a").to_string();
-        if is_all_day { weekday } else { format!("{} {}", weekday, time_str) }
-    } else {
-        date.format("%b %d").to_string()
+/// Relative label for dates adjacent to today ("Today", "Tomorrow", "Yesterday")
+fn relative_day_label(date: NaiveDate, today: NaiveDate) -> Option<&'static str> {
+    match (date - today).num_days() {
+        0 => Some("Today"),
+        1 => Some("Tomorrow"),
+        -1 => Some("Yesterday"),
+        _ => None,
     }
 }
 
-/// Render a centered search modal
-fn render_search_modal(out: &mut impl Write, search: &SearchState, term_width: u16, term_height: u16) {
-    use crate::app::EventSource;
-    use crate::cache::EventId;
-
-    let modal_width = 60u16.min(term_width.saturating_sub(4));
-    let modal_height = (term_height * 3 / 4).max(10).min(term_height.saturating_sub(4));
-    let start_x = (term_width.saturating_sub(modal_width)) / 2;
-    let start_y = (term_height.saturating_sub(modal_height)) / 2;
+/// Format a smart "when" string combining date and time based on proximity
+fn format_smart_when(date: NaiveDate, time: Option<NaiveTime>, today: NaiveDate, format: &TimeFormatConfig) -> String {
+    let days = (date - today).num_days();
 
-    execute!(out, SetForegroundColor(colors::HEADER)).unwrap();
+    let day = if let Some(label) = relative_day_label(date, today) {
+        label.to_string()
+    } else if (2..=6).contains(&days) {
+        date.format("%a").to_string()
+    } else {
+        return date.format("%b %d").to_string();
+    };
 
-    // Top border with title
-    execute!(out, cursor::MoveTo(start_x, start_y)).unwrap();
-    print!("┌─ Search ");
-    let remaining_top = modal_width.saturating_sub(11);
-    for _ in 0..remaining_top {
-        print!("─");
+    match time {
+        Some(time) => format!("{} {}", day, format.time(time)),
+        None => day,
     }
-    print!("┐");
+}
 
-    // Empty rows
-    for row in 1..modal_height - 1 {
-        execute!(out, cursor::MoveTo(start_x, start_y + row)).unwrap();
-        print!("│");
-        for _ in 0..modal_width - 2 {
-            print!(" ");
-        }
-        print!("│");
+/// Lines shown on the failures tab: captured failures, newest first
+fn failure_lines(log: &HttpLog) -> Vec<String> {
+    let captures = log.captures();
+    if captures.is_empty() {
+        return vec![if log.is_verbose() {
+            "No failed requests captured yet".to_string()
+        } else {
+            "Verbose capture is off. Press v to capture headers and bodies of failed requests.".to_string()
+        }];
     }
 
-    // Bottom border
-    execute!(out, cursor::MoveTo(start_x, start_y + modal_height - 1)).unwrap();
-    print!("└");
-    for _ in 0..modal_width - 2 {
-        print!("─");
+    let mut lines = Vec::new();
+    for capture in &captures {
+        lines.extend(format_capture(capture));
+        lines.push(String::new());
     }
-    print!("┘");
+    lines
+}
 
-    execute!(out, ResetColor).unwrap();
+/// Render the full-screen debug view (HTTP log or captured failures) above the status bar
+fn render_debug_screen(out: &mut Screen, debug: &DebugScreen, log: &HttpLog, area: Rect) {
+    let (title, lines) = match debug.tab {
+        DebugTab::Log => {
+            let source = match debug.source_filter {
+                None => "all",
+                Some(LogSource::Google) => "google",
+                Some(LogSource::ICloud) => "icloud",
+                Some(LogSource::Other) => "other",
+            };
+            let (kept, capacity) = log.usage();
+            let title = format!(
+                "HTTP log {}/{} (source: {}, status: {}){}",
+                kept,
+                capacity,
+                source,
+                debug.status_filter.label(),
+                if debug.paused.is_some() { " [paused]" } else { "" }
+            );
+            let lines: Vec<String> = debug.visible_log_entries(log).iter().map(|e| e.to_line()).collect();
+            (title, lines)
+        }
+        DebugTab::Failures => {
+            let title = format!("Failed requests (verbose: {})", if log.is_verbose() { "on" } else { "off" });
+            (title, failure_lines(log))
+        }
+        DebugTab::Metrics => ("Session metrics".to_string(), log.metrics_lines()),
+    };
 
-    // Input field
-    let content_x = start_x + 2;
-    let content_width = (modal_width - 4) as usize;
-    execute!(out, cursor::MoveTo(content_x, start_y + 1)).unwrap();
-    execute!(out, SetForegroundColor(Color::White), SetAttribute(Attribute::Bold)).unwrap();
-    let query_display = truncate_str(&search.query, content_width.saturating_sub(3));
-    print!("> {}_ ", query_display);
-    execute!(out, ResetColor, SetAttribute(Attribute::Reset)).unwrap();
+    let [title_area, separator_area, body] =
+        Layout::vertical([Constraint::Length(1), Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+    let width = area.width as usize;
+    out.render(
+        Paragraph::new(truncate_str(&title, width)).style(fg(colors::HEADER).add_modifier(Modifier::BOLD)),
+        title_area,
+    );
+    out.render(Paragraph::new(separator(area.width)), separator_area);
+
+    let scroll = debug.scroll.min(lines.len().saturating_sub(1));
+    let visible: Vec<Line> = lines
+        .iter()
+        .skip(scroll)
+        .take(body.height as usize)
+        .map(|line| {
+            let style = if line.starts_with('[') { fg(colors::LOG_TEXT) } else { Style::default() };
+            Line::styled(truncate_str(line, width), style)
+        })
+        .collect();
+    out.render(Paragraph::new(visible), body);
+}
 
-    // Separator
-    execute!(out, cursor::MoveTo(content_x, start_y + 2)).unwrap();
-    execute!(out, SetForegroundColor(colors::SEPARATOR)).unwrap();
-    for _ in 0..content_width {
-        print!("─");
-    }
-    execute!(out, ResetColor).unwrap();
+/// Render a centered, scrollable read-only modal
+fn render_info_modal(out: &mut Screen, modal: &InfoModal, area: Rect) {
+    let longest = modal.lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as u16;
+    let modal_width = (longest + 4).max(30).min(area.width.saturating_sub(4));
+    let modal_height = (modal.lines.len() as u16 + 2).max(5).min(area.height.saturating_sub(4));
+
+    let inner = out.modal_box(centered(area, modal_width, modal_height), &modal.title);
+    let lines: Vec<Line> = modal.lines
+        .iter()
+        .skip(modal.scroll)
+        .take(inner.height as usize)
+        .map(|line| Line::raw(truncate_str(line, inner.width as usize)))
+        .collect();
+    out.render(Paragraph::new(lines), inner);
+}
 
-    // Results area
-    let results_start_y = start_y + 3;
-    let results_height = (modal_height - 5) as usize; // 3 top (border+input+sep) + 2 bottom (hint+border)
+/// Render a centered search modal: the query, the results under section headers, and a
+/// hint row
+fn render_search_modal(out: &mut Screen, search: &SearchState, config: &Config, area: Rect) {
+    use crate::cache::EventId;
+
+    let modal_width = 60u16.min(area.width.saturating_sub(4));
+    let modal_height = (area.height * 3 / 4).max(10).min(area.height.saturating_sub(4));
+    let title = if search.include_past { "Search, past included" } else { "Search" };
+    let inner = out.modal_box(centered(area, modal_width, modal_height), title);
+    let [input_area, separator_area, results_area, hint_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Fill(1),
+        Constraint::Length(1),
+    ])
+    .areas(inner);
+    let content_width = inner.width as usize;
+
+    // Input field, with a note while the servers are asked
+    let pending = if search.remote_pending { "searching servers\u{2026}" } else { "" };
+    let pending_width = pending.chars().count();
+    let query_display = truncate_str(&search.query, content_width.saturating_sub(4 + pending_width));
+    let [query_area, pending_area] =
+        Layout::horizontal([Constraint::Fill(1), Constraint::Length(pending_width as u16)]).areas(input_area);
+    out.render(
+        Paragraph::new(format!("> {}_ ", query_display)).style(fg(Color::White).add_modifier(Modifier::BOLD)),
+        query_area,
+    );
+    out.render(Paragraph::new(Span::styled(pending, fg(Color::DarkGrey))), pending_area);
+    out.render(Paragraph::new(Line::styled("\u{2500}".repeat(content_width), fg(colors::SEPARATOR))), separator_area);
 
     if search.query.is_empty() {
-        execute!(out, cursor::MoveTo(content_x, results_start_y)).unwrap();
-        execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-        print!("Type to search events...");
-        execute!(out, ResetColor).unwrap();
+        let hint = truncate_str("Type to search, or filter: from: with: before: after: has:", content_width);
+        out.render(Paragraph::new(Span::styled(hint, fg(Color::DarkGrey))), results_area);
     } else if search.results.is_empty() {
-        execute!(out, cursor::MoveTo(content_x, results_start_y)).unwrap();
-        execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-        print!("No matching events");
-        execute!(out, ResetColor).unwrap();
+        out.render(Paragraph::new(Span::styled("No matching events", fg(Color::DarkGrey))), results_area);
     } else {
-        let num_title_matches = search.results.iter()
-            .filter(|r| r.match_type == MatchType::Title)
-            .count();
-        let has_title_header = num_title_matches > 0;
-        let has_people_header = num_title_matches < search.results.len();
-
-        // Total visual rows = results + header rows
-        let num_headers = has_title_header as usize + has_people_header as usize;
-        let total_visual_rows = search.results.len() + num_headers;
-
-        // Map selected_index to its visual row (accounting for headers above it)
-        let selected_visual_row = {
-            let mut row = search.selected_index;
-            if has_title_header { row += 1; } // title header before first result
-            if has_people_header && search.selected_index >= num_title_matches {
-                row += 1; // people header before participant results
-            }
-            row
-        };
-
-        // Calculate visible window based on visual rows
-        let visible_start = if selected_visual_row >= results_height {
-            selected_visual_row - results_height + 1
-        } else {
-            0
-        };
-
+        // Title matches first, then those found by their people, each under a header
         let today = Local::now().date_naive();
-        let mut visual_row: usize = 0;
-        let mut result_idx: usize = 0;
-        let people_header_row = num_title_matches + has_title_header as usize;
-
-        // Build visual rows: headers interleaved with results
-        while visual_row < total_visual_rows && (visual_row < visible_start + results_height) {
-            // Check if we need a section header at this visual row
-            let is_header = (has_title_header && visual_row == 0)
-                || (has_people_header && visual_row == people_header_row);
-            if is_header {
-                if visual_row >= visible_start {
-                    let screen_row = results_start_y + (visual_row - visible_start) as u16;
-                    let label = if visual_row == 0 { "Titles" } else { "People" };
-                    draw_section_header(out, content_x, screen_row, label, content_width);
-                }
-                visual_row += 1;
-                continue;
+        let mut items = Vec::new();
+        let mut selected_row = 0;
+        for (i, result) in search.results.iter().enumerate() {
+            let first_of_section = i == 0 || (result.match_type == MatchType::Title) != (search.results[i - 1].match_type == MatchType::Title);
+            if first_of_section {
+                let label = if result.match_type == MatchType::Title { "Titles" } else { "People" };
+                items.push(ListItem::new(section_header(label, content_width)));
             }
-
-            // Render a result row
-            if result_idx >= search.results.len() {
-                break;
+            let is_selected = i == search.selected_index;
+            if is_selected {
+                selected_row = items.len();
             }
-            let result = &search.results[result_idx];
-            let is_selected = result_idx == search.selected_index;
-
-            if visual_row >= visible_start {
-                let row = results_start_y + (visual_row - visible_start) as u16;
-                execute!(out, cursor::MoveTo(content_x, row)).unwrap();
-
-                // Selection indicator
-                if is_selected {
-                    execute!(out, SetForegroundColor(colors::SELECTED)).unwrap();
-                    print!("▶ ");
-                } else {
-                    print!("  ");
-                }
 
-                // Smart when column
-                let when = format_smart_when(result.event.date, &result.event.time_str, today);
-                execute!(out, SetForegroundColor(if is_selected { colors::SELECTED } else { Color::DarkGrey })).unwrap();
-                print!("{:>11} ", when);
-
-                // Source color indicator
-                let source_color = match result.source {
-                    EventSource::Google => colors::GOOGLE_ACCENT,
-                    EventSource::ICloud => colors::ICLOUD_ACCENT,
-                };
-                execute!(out, SetForegroundColor(source_color)).unwrap();
-                let source_char = match result.event.id {
-                    EventId::Google { .. } => "G",
-                    EventId::ICloud { .. } => "I",
-                };
-                print!("{} ", source_char);
-
-                // Title
-                let title_space = content_width.saturating_sub(2 + 12 + 2);
-                execute!(out, SetForegroundColor(if is_selected { colors::SELECTED } else { Color::White })).unwrap();
-                if is_selected {
-                    execute!(out, SetAttribute(Attribute::Bold)).unwrap();
-                }
-                print!("{}", truncate_str(&result.event.title, title_space));
-                execute!(out, ResetColor, SetAttribute(Attribute::Reset)).unwrap();
-            }
-
-            result_idx += 1;
-            visual_row += 1;
+            let when = format_smart_when(result.event.date, result.event.start_time, today, &config.time_format);
+            let source_char = match result.event.id {
+                EventId::Google { .. } => "G",
+                EventId::ICloud { .. } => "I",
+                EventId::Feed { .. } => "F",
+            };
+            let title_space = content_width.saturating_sub(2 + 12 + 2);
+            let title_style = if is_selected {
+                fg(colors::SELECTED).add_modifier(Modifier::BOLD)
+            } else {
+                fg(Color::White)
+            };
+            items.push(ListItem::new(Line::from(vec![
+                if is_selected { Span::styled("\u{25B6} ", fg(colors::SELECTED)) } else { Span::raw("  ") },
+                Span::styled(format!("{:>14} ", when), fg(if is_selected { colors::SELECTED } else { Color::DarkGrey })),
+                Span::styled(format!("{} ", source_char), fg(event_accent(config, &result.event))),
+                Span::styled(truncate_str(&result.event.title, title_space), title_style),
+            ])));
         }
+        // The list scrolls to keep the selected result in view
+        let mut list_state = ListState::default().with_selected(Some(selected_row));
+        out.render_stateful(List::new(items), results_area, &mut list_state);
     }
 
-    // Bottom hint
-    let hint_y = start_y + modal_height - 2;
-    execute!(out, cursor::MoveTo(content_x, hint_y)).unwrap();
-    execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-    let count_str = if search.results.is_empty() {
+    let count = if search.results.is_empty() {
         String::new()
     } else {
         format!("{}/{} ", search.selected_index + 1, search.results.len())
     };
-    print!("{}\u{2191}\u{2193}:navigate Enter:select Esc:close", count_str);
-    execute!(out, ResetColor).unwrap();
+    let hint = format!("{}\u{2191}\u{2193}:navigate Enter:select ^P:past Esc:close", count);
+    out.render(Paragraph::new(Span::styled(hint, fg(Color::DarkGrey))), hint_area);
 }
 
 /// Render a centered confirmation modal
-fn render_confirmation_modal(out: &mut impl Write, action: &PendingAction, term_width: u16, term_height: u16) {
+fn render_confirmation_modal(out: &mut Screen, action: &PendingAction, area: Rect) {
     let prompt = match action {
-        PendingAction::AcceptEvent { .. } => "Accept this event?",
-        PendingAction::DeclineEvent { .. } => "Decline this event?",
-        PendingAction::DeleteGoogleEvent { .. } | PendingAction::DeleteICloudEvent { .. } => "Delete this event?",
+        PendingAction::Accept { .. } => "Accept this event?".to_string(),
+        PendingAction::Decline { .. } => "Decline this event?".to_string(),
+        PendingAction::Delete { .. } => "Delete this event?".to_string(),
+        PendingAction::JoinMeeting { title, .. } => format!("Join {} now?", truncate_str(title, 40)),
+        PendingAction::AcceptAndJoin { .. } => "Accept this event and join?".to_string(),
+        PendingAction::Bulk { action, items } => {
+            let skipped = items.iter().filter(|(_, item)| item.is_err()).count();
+            match skipped {
+                0 => format!("{} {} marked event(s)?", action.label(), items.len()),
+                _ => format!("{} {} marked event(s) ({} can't be)?", action.label(), items.len(), skipped),
+            }
+        }
     };
 
-    // Modal dimensions
-    let modal_width = 30u16;
-    let modal_height = 5u16;
-    let start_x = (term_width.saturating_sub(modal_width)) / 2;
-    let start_y = (term_height.saturating_sub(modal_height)) / 2;
-
-    // Draw modal box
-    execute!(out, SetForegroundColor(colors::HEADER)).unwrap();
-
-    // Top border
-    execute!(out, cursor::MoveTo(start_x, start_y)).unwrap();
-    print!("┌");
-    for _ in 0..modal_width - 2 {
-        print!("─");
-    }
-    print!("┐");
-
-    // Middle rows
-    for row in 1..modal_height - 1 {
-        execute!(out, cursor::MoveTo(start_x, start_y + row)).unwrap();
-        print!("│");
-        for _ in 0..modal_width - 2 {
-            print!(" ");
-        }
-        print!("│");
-    }
-
-    // Bottom border
-    execute!(out, cursor::MoveTo(start_x, start_y + modal_height - 1)).unwrap();
-    print!("└");
-    for _ in 0..modal_width - 2 {
-        print!("─");
-    }
-    print!("┘");
-
-    // Title
-    execute!(out, cursor::MoveTo(start_x + 2, start_y + 1)).unwrap();
-    execute!(out, SetForegroundColor(colors::NEXT_EVENT), SetAttribute(Attribute::Bold)).unwrap();
-    print!("{}", prompt);
-    execute!(out, ResetColor, SetAttribute(Attribute::Reset)).unwrap();
-
-    // Options
-    execute!(out, cursor::MoveTo(start_x + 2, start_y + 3)).unwrap();
-    execute!(out, SetForegroundColor(colors::ACTION)).unwrap();
-    print!("[y/Enter]");
-    execute!(out, SetForegroundColor(Color::White)).unwrap();
-    print!(" Yes  ");
-    execute!(out, SetForegroundColor(Color::DarkGrey)).unwrap();
-    print!("[n/Esc]");
-    execute!(out, SetForegroundColor(Color::White)).unwrap();
-    print!(" No");
-    execute!(out, ResetColor).unwrap();
+    let modal_width = (prompt.chars().count() as u16 + 4).max(30).min(area.width.saturating_sub(4));
+    let inner = out.modal_box(centered(area, modal_width, 5), "");
+    let lines = vec![
+        Line::styled(
+            truncate_str(&prompt, inner.width as usize),
+            fg(colors::NEXT_EVENT).add_modifier(Modifier::BOLD),
+        ),
+        Line::default(),
+        Line::from(vec![
+            Span::styled("[y/Enter]", fg(colors::ACTION)),
+            Span::styled(" Yes  ", fg(Color::White)),
+            Span::styled("[n/Esc]", fg(Color::DarkGrey)),
+            Span::styled(" No", fg(Color::White)),
+        ]),
+    ];
+    out.render(Paragraph::new(lines), inner);
 }
 
-fn days_in_month(date: NaiveDate) -> u32 {
-    match date.month() {
-        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
-        4 | 6 | 9 | 11 => 30,
-        2 => {
-            let year = date.year();
-            if (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0) {
-                29
-            } else {
-                28
-            }
-        }
-        _ => 30,
-    }
-}
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Timelike;
 
-    fn make_event(time: &str) -> DisplayEvent {
+    fn countdown_event(title: &str) -> DisplayEvent {
         DisplayEvent {
-            id: EventId::Google { calendar_id: "test".to_string(), event_id: "test-id".to_string(), calendar_name: None },
-            title: "Test".to_string(),
-            time_str: time.to_string(),
-            end_time_str: None,
-            date: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
-            accepted: true,
-            is_organizer: false,
-            is_free: false,
-            meeting_url: None,
-            description: None,
-            location: None,
-            attendees: vec![],
+            id: EventId::Google { calendar_id: "primary".to_string(), event_id: "1".to_string(), calendar_name: None, account: None },
+            title: title.to_string(),
+            start_time: NaiveTime::from_hms_opt(14, 0, 0),
+            end_time: NaiveTime::from_hms_opt(15, 0, 0),
+            date: NaiveDate::from_ymd_opt(2026, 2, 10).unwrap(),
+            ..Default::default()
         }
     }
 
     #[test]
-    fn test_parse_event_time_valid() {
-        let time = parse_event_time("14:30").unwrap();
-        assert_eq!(time.hour(), 14);
-        assert_eq!(time.minute(), 30);
+    fn test_screen_draws_into_buffer() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 3));
+        let mut out = Screen::new(&mut buf);
+        out.move_to(2, 1);
+        out.fg(Color::Yellow);
+        out.print("ab");
+        out.reset_color();
+        out.print("cd");
+        // Text past the right edge and rows past the bottom are dropped
+        out.move_to(7, 2);
+        out.print("overflow");
+        out.move_to(0, 5);
+        out.print("gone");
+
+        assert_eq!(buf[(2, 1)].symbol(), "a");
+        assert_eq!(buf[(2, 1)].fg, Color::Yellow.into());
+        assert_eq!(buf[(4, 1)].symbol(), "c");
+        assert_eq!(buf[(4, 1)].fg, ratatui::style::Color::Reset);
+        assert_eq!(buf[(9, 2)].symbol(), "e");
+
+        let mut out = Screen::new(&mut buf);
+        out.modal_box(Rect::new(0, 0, 10, 3), "Hi");
+        assert_eq!(buf[(0, 0)].symbol(), "\u{250C}");
+        assert_eq!(buf[(4, 1)].symbol(), " ");
+    }
+
+    #[test]
+    fn test_hit_map() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 17).unwrap();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 10));
+        let mut out = Screen::new(&mut buf);
+        out.move_to(0, 3);
+        out.hit(3, Hit::Day(date));
+        hit_event_rows(&mut out, Rect::new(24, 2, 16, 2), date, [(EventSource::ICloud, 0), (EventSource::ICloud, 1)]);
+        out.move_to(24, 8);
+        out.hit(8, Hit::Join);
+        let hits = out.hits;
+
+        assert_eq!(hits.at(2, 3), Some(&Hit::Day(date)));
+        assert_eq!(hits.at(3, 3), None);
+        assert_eq!(hits.at(30, 3), Some(&Hit::Event { date, source: EventSource::ICloud, index: 1 }));
+        assert_eq!(hits.at(30, 4), None);
+        assert_eq!(hits.at(31, 8), Some(&Hit::Join));
+
+        // A modal covers what's under it
+        let mut out = Screen::new(&mut buf);
+        hit_event_rows(&mut out, Rect::new(24, 2, 16, 2), date, [(EventSource::Google, 0), (EventSource::Google, 1)]);
+        out.modal_box(Rect::new(20, 3, 10, 4), "");
+        assert_eq!(out.hits.at(30, 2), Some(&Hit::Event { date, source: EventSource::Google, index: 0 }));
+        assert_eq!(out.hits.at(35, 3), None);
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("#7986cb"), Some(Color::Rgb { r: 0x79, g: 0x86, b: 0xcb }));
+        assert_eq!(parse_color("7986cb"), None);
+        assert_eq!(parse_color("Dark_Cyan"), Some(Color::DarkCyan));
+        assert_eq!(parse_color("#zz86cb"), None);
     }
 
     #[test]
-    fn test_parse_event_time_all_day() {
-        let time = parse_event_time("All day").unwrap();
-        assert_eq!(time.hour(), 0);
-        assert_eq!(time.minute(), 0);
+    fn test_event_accent_prefers_calendar_override() {
+        let mut config = Config::default();
+        config.colors.google = Some("green".to_string());
+        config.colors.calendars.insert("Team".to_string(), "#ff9500".to_string());
+
+        let mut event = countdown_event("Standup");
+        assert_eq!(event_accent(&config, &event), Color::Green);
+        event.id = EventId::Google { calendar_id: "team".to_string(), event_id: "1".to_string(), calendar_name: Some("Team".to_string()), account: None };
+        assert_eq!(event_accent(&config, &event), Color::Rgb { r: 0xff, g: 0x95, b: 0x00 });
+        assert_eq!(source_accent(&config, EventSource::ICloud), colors::ICLOUD_ACCENT);
     }
 
     #[test]
-    fn test_parse_event_time_invalid() {
-        assert!(parse_event_time("invalid").is_none());
-        assert!(parse_event_time("25:00").is_none());
+    fn test_format_countdown_default_templates() {
+        let event = countdown_event("Standup");
+        let config = CountdownConfig::default();
+        let next = NextEventInfo { event: &event, is_current: false, minutes_until: 90 };
+        assert_eq!(format_countdown(&next, &config, &TimeFormatConfig::default(), 30), Some("Next: Standup in 1h 30m".to_string()));
+        let now = NextEventInfo { event: &event, is_current: true, minutes_until: -5 };
+        assert_eq!(format_countdown(&now, &config, &TimeFormatConfig::default(), 30), Some("Now: Standup".to_string()));
+    }
+
+    #[test]
+    fn test_format_countdown_custom_template_and_window() {
+        let event = countdown_event("Standup");
+        let config = CountdownConfig {
+            format: "{title} starts in {rel} ({time}-{end})".to_string(),
+            within_minutes: Some(60),
+            ..CountdownConfig::default()
+        };
+        let soon = NextEventInfo { event: &event, is_current: false, minutes_until: 45 };
+        assert_eq!(format_countdown(&soon, &config, &TimeFormatConfig::default(), 30), Some("Standup starts in 45m (14:00-15:00)".to_string()));
+        let later = NextEventInfo { event: &event, is_current: false, minutes_until: 61 };
+        assert_eq!(format_countdown(&later, &config, &TimeFormatConfig::default(), 30), None);
+
+        let disabled = CountdownConfig { enabled: false, ..CountdownConfig::default() };
+        assert_eq!(format_countdown(&soon, &disabled, &TimeFormatConfig::default(), 30), None);
+    }
+
+    #[test]
+    fn test_leave_hint() {
+        let mut event = countdown_event("Dentist");
+        event.location = Some("Main St 4".to_string());
+        let travel = TravelConfig { minutes: Some(20), ..TravelConfig::default() };
+        let at = |h, m| event.date.and_hms_opt(h, m, 0).unwrap();
+        let next = NextEventInfo { event: &event, is_current: false, minutes_until: 90 };
+        assert_eq!(leave_hint(&next, &travel, &TimeFormatConfig::default(), at(12, 30)), Some((" \u{b7} leave by 13:40".to_string(), false)));
+        assert_eq!(leave_hint(&next, &travel, &TimeFormatConfig::default(), at(13, 30)), Some((" \u{b7} leave by 13:40".to_string(), true)));
+        assert_eq!(leave_hint(&next, &travel, &TimeFormatConfig::default(), at(13, 45)), Some((" \u{b7} leave now".to_string(), true)));
+
+        let current = NextEventInfo { event: &event, is_current: true, minutes_until: 0 };
+        assert_eq!(leave_hint(&current, &travel, &TimeFormatConfig::default(), at(14, 10)), None);
+        event.location = Some("https://zoom.us/j/123".to_string());
+        let online = NextEventInfo { event: &event, is_current: false, minutes_until: 90 };
+        assert_eq!(leave_hint(&online, &travel, &TimeFormatConfig::default(), at(12, 30)), None);
+    }
+
+    #[test]
+    fn test_relative_day_label() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 10).unwrap();
+        assert_eq!(relative_day_label(today, today), Some("Today"));
+        assert_eq!(relative_day_label(today + Duration::days(1), today), Some("Tomorrow"));
+        assert_eq!(relative_day_label(today - Duration::days(1), today), Some("Yesterday"));
+        assert_eq!(relative_day_label(today + Duration::days(2), today), None);
+    }
+
+    #[test]
+    fn test_format_smart_when() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 10).unwrap(); // Tuesday
+        let at = |h, m| NaiveTime::from_hms_opt(h, m, 0);
+        assert_eq!(format_smart_when(today, at(14, 0), today, &TimeFormatConfig::default()), "Today 14:00");
+        assert_eq!(format_smart_when(today + Duration::days(1), None, today, &TimeFormatConfig::default()), "Tomorrow");
+        assert_eq!(format_smart_when(today - Duration::days(1), at(9, 30), today, &TimeFormatConfig::default()), "Yesterday 09:30");
+        assert_eq!(format_smart_when(today + Duration::days(3), at(10, 0), today, &TimeFormatConfig::default()), "Fri 10:00");
+        assert_eq!(format_smart_when(today + Duration::days(10), at(10, 0), today, &TimeFormatConfig::default()), "Feb 20");
+    }
+
+    fn make_event(time: &str) -> DisplayEvent {
+        DisplayEvent {
+            id: EventId::Google { calendar_id: "test".to_string(), event_id: "test-id".to_string(), calendar_name: None, account: None },
+            title: "Test".to_string(),
+            start_time: NaiveTime::parse_from_str(time, "%H:%M").ok(),
+            date: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+            ..Default::default()
+        }
     }
 
     #[test]
@@ -1454,7 +2485,7 @@ mod tests {
     fn test_find_current_and_next_no_events() {
         let events: Vec<DisplayEvent> = vec![];
         let current = NaiveTime::from_hms_opt(10, 0, 0).unwrap();
-        let (current_idx, next_idx) = find_current_and_next_events(&events, current);
+        let (current_idx, next_idx) = find_current_and_next_events(&shared(&events), current);
         assert!(current_idx.is_none());
         assert!(next_idx.is_none());
     }
@@ -1467,7 +2498,7 @@ mod tests {
             make_event("16:00"),
         ];
         let current = NaiveTime::from_hms_opt(10, 0, 0).unwrap();
-        let (current_idx, next_idx) = find_current_and_next_events(&events, current);
+        let (current_idx, next_idx) = find_current_and_next_events(&shared(&events), current);
         assert!(current_idx.is_none());
         assert_eq!(next_idx, Some(0));
     }
@@ -1480,7 +2511,7 @@ mod tests {
             make_event("10:00"),
         ];
         let current = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
-        let (current_idx, next_idx) = find_current_and_next_events(&events, current);
+        let (current_idx, next_idx) = find_current_and_next_events(&shared(&events), current);
         assert_eq!(current_idx, Some(2)); // Last started event
         assert!(next_idx.is_none());
     }
@@ -1494,7 +2525,7 @@ mod tests {
             make_event("16:00"),
         ];
         let current = NaiveTime::from_hms_opt(10, 30, 0).unwrap();
-        let (current_idx, next_idx) = find_current_and_next_events(&events, current);
+        let (current_idx, next_idx) = find_current_and_next_events(&shared(&events), current);
         assert_eq!(current_idx, Some(1));
         assert_eq!(next_idx, Some(2));
     }
@@ -1507,7 +2538,7 @@ mod tests {
             make_event("14:00"),
         ];
         let current = NaiveTime::from_hms_opt(10, 30, 0).unwrap();
-        let (current_idx, next_idx) = find_current_and_next_events(&events, current);
+        let (current_idx, next_idx) = find_current_and_next_events(&shared(&events), current);
         assert_eq!(current_idx, Some(1)); // Skipped all-day
         assert_eq!(next_idx, Some(2));
     }
@@ -1565,33 +2596,31 @@ mod tests {
 
     fn make_event_with_end(time: &str, end: &str) -> DisplayEvent {
         let mut e = make_event(time);
-        e.end_time_str = Some(end.to_string());
+        e.end_time = NaiveTime::parse_from_str(end, "%H:%M").ok();
         e
     }
 
     fn make_icloud_event(time: &str) -> DisplayEvent {
         DisplayEvent {
-            id: EventId::ICloud { calendar_url: "test".to_string(), event_uid: "test-uid".to_string(), etag: None, calendar_name: None },
+            id: EventId::ICloud { calendar_url: "test".to_string(), event_uid: "test-uid".to_string(), etag: None, calendar_name: None, occurrence: None },
             title: "iCloud Test".to_string(),
-            time_str: time.to_string(),
-            end_time_str: None,
+            start_time: NaiveTime::parse_from_str(time, "%H:%M").ok(),
             date: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
-            accepted: true,
-            is_organizer: false,
-            is_free: false,
-            meeting_url: None,
-            description: None,
-            location: None,
-            attendees: vec![],
+            ..Default::default()
         }
     }
 
     fn make_icloud_event_with_end(time: &str, end: &str) -> DisplayEvent {
         let mut e = make_icloud_event(time);
-        e.end_time_str = Some(end.to_string());
+        e.end_time = NaiveTime::parse_from_str(end, "%H:%M").ok();
         e
     }
 
+    /// Events as the cache hands them out
+    fn shared(events: &[DisplayEvent]) -> Vec<Arc<DisplayEvent>> {
+        events.iter().cloned().map(Arc::new).collect()
+    }
+
     #[test]
     fn test_overlap_no_events() {
         let (g, i) = compute_overlapping_events(&[], &[]);
@@ -1603,7 +2632,7 @@ mod tests {
     fn test_overlap_non_overlapping() {
         let google = vec![make_event_with_end("09:00", "10:00")];
         let icloud = vec![make_icloud_event_with_end("10:00", "11:00")];
-        let (g, i) = compute_overlapping_events(&google, &icloud);
+        let (g, i) = compute_overlapping_events(&shared(&google), &shared(&icloud));
         assert!(g.is_empty());
         assert!(i.is_empty());
     }
@@ -1612,7 +2641,7 @@ mod tests {
     fn test_overlap_cross_source() {
         let google = vec![make_event_with_end("09:00", "10:00")];
         let icloud = vec![make_icloud_event_with_end("09:30", "10:30")];
-        let (g, i) = compute_overlapping_events(&google, &icloud);
+        let (g, i) = compute_overlapping_events(&shared(&google), &shared(&icloud));
         assert!(g.contains(&0));
         assert!(i.contains(&0));
     }
@@ -1623,18 +2652,68 @@ mod tests {
             make_event_with_end("09:00", "10:00"),
             make_event_with_end("09:30", "10:30"),
         ];
-        let (g, i) = compute_overlapping_events(&google, &[]);
+        let (g, i) = compute_overlapping_events(&shared(&google), &[]);
         assert!(g.contains(&0));
         assert!(g.contains(&1));
         assert!(i.is_empty());
     }
 
+    #[test]
+    fn test_all_day_strip() {
+        let mut trip = make_event_with_end("18:00", "12:00");
+        let first = trip.date;
+        let last = first + Duration::days(2);
+        trip.end_date = Some(last);
+        assert_eq!(strip_time_label(&trip, first, &TimeFormatConfig::default()), "18:00");
+        assert_eq!(strip_time_label(&trip, first + Duration::days(1), &TimeFormatConfig::default()), "All day");
+        assert_eq!(strip_time_label(&trip, last, &TimeFormatConfig::default()), "\u{2192}12:00");
+
+        // Neither busy nor past, unlike a timed event of the day
+        let evening = NaiveTime::from_hms_opt(20, 0, 0).unwrap();
+        assert_eq!(event_minutes(&trip), None);
+        assert!(!is_event_past(&trip, evening));
+        let meeting = make_event_with_end("09:00", "10:00");
+        assert_eq!(event_minutes(&meeting), Some((9 * 60, 10 * 60)));
+        assert!(is_event_past(&meeting, evening));
+    }
+
+    #[test]
+    fn test_mini_month_rows() {
+        // February 2026 starts on a Sunday: five Monday weeks, four Sunday weeks
+        let february = NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+        assert_eq!(mini_month_rows(february, WeekStart::Monday), 6);
+        assert_eq!(mini_month_rows(february, WeekStart::Sunday), 5);
+        // Six weeks when the 31st spills over
+        let august = NaiveDate::from_ymd_opt(2026, 8, 1).unwrap();
+        assert_eq!(mini_month_rows(august, WeekStart::Monday), 7);
+    }
+
+    #[test]
+    fn test_conflict_groups() {
+        // A chain of overlaps is one conflict; a later separate overlap is another
+        let google = vec![
+            make_event_with_end("09:00", "10:00"),
+            make_event_with_end("10:30", "11:00"),
+            make_event_with_end("14:00", "15:00"),
+            make_event_with_end("16:00", "17:00"),
+        ];
+        let icloud = vec![
+            make_icloud_event_with_end("09:45", "10:45"),
+            make_icloud_event_with_end("14:30", "15:30"),
+        ];
+        let groups = conflict_groups(&shared(&google), &shared(&icloud));
+        assert_eq!(groups, vec![
+            vec![(EventSource::Google, 0), (EventSource::ICloud, 0), (EventSource::Google, 1)],
+            vec![(EventSource::Google, 2), (EventSource::ICloud, 1)],
+        ]);
+    }
+
     #[test]
     fn test_overlap_adjacent_no_overlap() {
         // end == start → strict inequality means no overlap
         let google = vec![make_event_with_end("09:00", "10:00")];
         let icloud = vec![make_icloud_event_with_end("10:00", "11:00")];
-        let (g, i) = compute_overlapping_events(&google, &icloud);
+        let (g, i) = compute_overlapping_events(&shared(&google), &shared(&icloud));
         assert!(g.is_empty());
         assert!(i.is_empty());
     }
@@ -1643,7 +2722,7 @@ mod tests {
     fn test_overlap_skips_all_day() {
         let google = vec![make_event("All day")];
         let icloud = vec![make_icloud_event_with_end("09:00", "10:00")];
-        let (g, i) = compute_overlapping_events(&google, &icloud);
+        let (g, i) = compute_overlapping_events(&shared(&google), &shared(&icloud));
         assert!(g.is_empty());
         assert!(i.is_empty());
     }
@@ -1653,7 +2732,7 @@ mod tests {
         let mut google = vec![make_event_with_end("09:00", "10:00")];
         google[0].is_free = true;
         let icloud = vec![make_icloud_event_with_end("09:00", "10:00")];
-        let (g, i) = compute_overlapping_events(&google, &icloud);
+        let (g, i) = compute_overlapping_events(&shared(&google), &shared(&icloud));
         assert!(g.is_empty());
         assert!(i.is_empty());
     }
@@ -1663,7 +2742,7 @@ mod tests {
         let mut google = vec![make_event_with_end("09:00", "10:00")];
         google[0].accepted = false;
         let icloud = vec![make_icloud_event_with_end("09:00", "10:00")];
-        let (g, i) = compute_overlapping_events(&google, &icloud);
+        let (g, i) = compute_overlapping_events(&shared(&google), &shared(&icloud));
         assert!(g.is_empty());
         assert!(i.is_empty());
     }
@@ -1673,8 +2752,16 @@ mod tests {
         // No end time → defaults to start + 60 min
         let google = vec![make_event("09:00")]; // 09:00-10:00
         let icloud = vec![make_icloud_event("09:30")]; // 09:30-10:30
-        let (g, i) = compute_overlapping_events(&google, &icloud);
+        let (g, i) = compute_overlapping_events(&shared(&google), &shared(&icloud));
         assert!(g.contains(&0));
         assert!(i.contains(&0));
     }
+
+    #[test]
+    fn test_assign_lanes() {
+        // 09:00-10:00 and 09:30-11:00 overlap; 10:00-10:30 reuses the first lane; 12:00 stands alone
+        let ranges = [(600, 630), (540, 600), (570, 660), (720, 780)];
+        assert_eq!(assign_lanes(&ranges), vec![(0, 2), (0, 2), (1, 2), (0, 1)]);
+        assert!(assign_lanes(&[]).is_empty());
+    }
 }